@@ -42,6 +42,7 @@ mod order;
 mod part;
 mod partsupp;
 mod region;
+pub mod ssb;
 mod supplier;
 
 use arrow::array::RecordBatch;
@@ -0,0 +1,496 @@
+//! Generate SSB (Star Schema Benchmark) data as Arrow RecordBatches
+//!
+//! See the [`tpchgen::ssb`] module for details on the tables. Prices are
+//! `Int64` in cents and dates are `Int32` keys like `19920101`, as in the SSB
+//! schema.
+use crate::conversions::string_view_array_from_display_iter;
+use crate::{DEFAULT_BATCH_SIZE, RecordBatchIterator};
+use arrow::array::{Int32Array, Int64Array, RecordBatch, StringViewArray};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use std::sync::{Arc, LazyLock};
+use tpchgen::ssb::generators::{
+    CustomerGenerator, CustomerGeneratorIterator, DateGenerator, DateGeneratorIterator,
+    LineOrderGenerator, LineOrderGeneratorIterator, PartGenerator, PartGeneratorIterator,
+    SupplierGenerator, SupplierGeneratorIterator,
+};
+
+/// Generate SSB [`Customer`]s in [`RecordBatch`] format
+///
+/// [`Customer`]: tpchgen::ssb::generators::Customer
+pub struct CustomerArrow {
+    inner: CustomerGeneratorIterator<'static>,
+    batch_size: usize,
+}
+
+impl CustomerArrow {
+    pub fn new(generator: CustomerGenerator<'static>) -> Self {
+        Self {
+            inner: generator.iter(),
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Set the batch size
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+impl RecordBatchIterator for CustomerArrow {
+    fn schema(&self) -> &SchemaRef {
+        &CUSTOMER_SCHEMA
+    }
+}
+
+impl Iterator for CustomerArrow {
+    type Item = RecordBatch;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Get next rows to convert
+        let rows: Vec<_> = self.inner.by_ref().take(self.batch_size).collect();
+        if rows.is_empty() {
+            return None;
+        }
+
+        let c_custkey = Int64Array::from_iter_values(rows.iter().map(|r| r.c_custkey));
+        let c_name = string_view_array_from_display_iter(rows.iter().map(|r| r.c_name));
+        let c_address = string_view_array_from_display_iter(rows.iter().map(|r| &r.c_address));
+        let c_city = string_view_array_from_display_iter(rows.iter().map(|r| r.c_city));
+        let c_nation = StringViewArray::from_iter_values(rows.iter().map(|r| r.c_nation));
+        let c_region = StringViewArray::from_iter_values(rows.iter().map(|r| r.c_region));
+        let c_phone = string_view_array_from_display_iter(rows.iter().map(|r| &r.c_phone));
+        let c_mktsegment = StringViewArray::from_iter_values(rows.iter().map(|r| r.c_mktsegment));
+
+        let batch = RecordBatch::try_new(
+            Arc::clone(self.schema()),
+            vec![
+                Arc::new(c_custkey),
+                Arc::new(c_name),
+                Arc::new(c_address),
+                Arc::new(c_city),
+                Arc::new(c_nation),
+                Arc::new(c_region),
+                Arc::new(c_phone),
+                Arc::new(c_mktsegment),
+            ],
+        )
+        .unwrap();
+        Some(batch)
+    }
+}
+
+/// Schema for the SSB Customer
+static CUSTOMER_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(make_customer_schema);
+fn make_customer_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("c_custkey", DataType::Int64, false),
+        Field::new("c_name", DataType::Utf8View, false),
+        Field::new("c_address", DataType::Utf8View, false),
+        Field::new("c_city", DataType::Utf8View, false),
+        Field::new("c_nation", DataType::Utf8View, false),
+        Field::new("c_region", DataType::Utf8View, false),
+        Field::new("c_phone", DataType::Utf8View, false),
+        Field::new("c_mktsegment", DataType::Utf8View, false),
+    ]))
+}
+
+/// Generate SSB [`Supplier`]s in [`RecordBatch`] format
+///
+/// [`Supplier`]: tpchgen::ssb::generators::Supplier
+pub struct SupplierArrow {
+    inner: SupplierGeneratorIterator<'static>,
+    batch_size: usize,
+}
+
+impl SupplierArrow {
+    pub fn new(generator: SupplierGenerator<'static>) -> Self {
+        Self {
+            inner: generator.iter(),
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Set the batch size
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+impl RecordBatchIterator for SupplierArrow {
+    fn schema(&self) -> &SchemaRef {
+        &SUPPLIER_SCHEMA
+    }
+}
+
+impl Iterator for SupplierArrow {
+    type Item = RecordBatch;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Get next rows to convert
+        let rows: Vec<_> = self.inner.by_ref().take(self.batch_size).collect();
+        if rows.is_empty() {
+            return None;
+        }
+
+        let s_suppkey = Int64Array::from_iter_values(rows.iter().map(|r| r.s_suppkey));
+        let s_name = string_view_array_from_display_iter(rows.iter().map(|r| r.s_name));
+        let s_address = string_view_array_from_display_iter(rows.iter().map(|r| &r.s_address));
+        let s_city = string_view_array_from_display_iter(rows.iter().map(|r| r.s_city));
+        let s_nation = StringViewArray::from_iter_values(rows.iter().map(|r| r.s_nation));
+        let s_region = StringViewArray::from_iter_values(rows.iter().map(|r| r.s_region));
+        let s_phone = string_view_array_from_display_iter(rows.iter().map(|r| &r.s_phone));
+
+        let batch = RecordBatch::try_new(
+            Arc::clone(self.schema()),
+            vec![
+                Arc::new(s_suppkey),
+                Arc::new(s_name),
+                Arc::new(s_address),
+                Arc::new(s_city),
+                Arc::new(s_nation),
+                Arc::new(s_region),
+                Arc::new(s_phone),
+            ],
+        )
+        .unwrap();
+        Some(batch)
+    }
+}
+
+/// Schema for the SSB Supplier
+static SUPPLIER_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(make_supplier_schema);
+fn make_supplier_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("s_suppkey", DataType::Int64, false),
+        Field::new("s_name", DataType::Utf8View, false),
+        Field::new("s_address", DataType::Utf8View, false),
+        Field::new("s_city", DataType::Utf8View, false),
+        Field::new("s_nation", DataType::Utf8View, false),
+        Field::new("s_region", DataType::Utf8View, false),
+        Field::new("s_phone", DataType::Utf8View, false),
+    ]))
+}
+
+/// Generate SSB [`Part`]s in [`RecordBatch`] format
+///
+/// [`Part`]: tpchgen::ssb::generators::Part
+pub struct PartArrow {
+    inner: PartGeneratorIterator<'static>,
+    batch_size: usize,
+}
+
+impl PartArrow {
+    pub fn new(generator: PartGenerator<'static>) -> Self {
+        Self {
+            inner: generator.iter(),
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Set the batch size
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+impl RecordBatchIterator for PartArrow {
+    fn schema(&self) -> &SchemaRef {
+        &PART_SCHEMA
+    }
+}
+
+impl Iterator for PartArrow {
+    type Item = RecordBatch;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Get next rows to convert
+        let rows: Vec<_> = self.inner.by_ref().take(self.batch_size).collect();
+        if rows.is_empty() {
+            return None;
+        }
+
+        let p_partkey = Int64Array::from_iter_values(rows.iter().map(|r| r.p_partkey));
+        let p_name = string_view_array_from_display_iter(rows.iter().map(|r| &r.p_name));
+        let p_mfgr = string_view_array_from_display_iter(rows.iter().map(|r| r.p_mfgr));
+        let p_category = string_view_array_from_display_iter(rows.iter().map(|r| r.p_category));
+        let p_brand1 = string_view_array_from_display_iter(rows.iter().map(|r| r.p_brand1));
+        let p_color = StringViewArray::from_iter_values(rows.iter().map(|r| r.p_color));
+        let p_type = StringViewArray::from_iter_values(rows.iter().map(|r| r.p_type));
+        let p_size = Int32Array::from_iter_values(rows.iter().map(|r| r.p_size));
+        let p_container = StringViewArray::from_iter_values(rows.iter().map(|r| r.p_container));
+
+        let batch = RecordBatch::try_new(
+            Arc::clone(self.schema()),
+            vec![
+                Arc::new(p_partkey),
+                Arc::new(p_name),
+                Arc::new(p_mfgr),
+                Arc::new(p_category),
+                Arc::new(p_brand1),
+                Arc::new(p_color),
+                Arc::new(p_type),
+                Arc::new(p_size),
+                Arc::new(p_container),
+            ],
+        )
+        .unwrap();
+        Some(batch)
+    }
+}
+
+/// Schema for the SSB Part
+static PART_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(make_part_schema);
+fn make_part_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("p_partkey", DataType::Int64, false),
+        Field::new("p_name", DataType::Utf8View, false),
+        Field::new("p_mfgr", DataType::Utf8View, false),
+        Field::new("p_category", DataType::Utf8View, false),
+        Field::new("p_brand1", DataType::Utf8View, false),
+        Field::new("p_color", DataType::Utf8View, false),
+        Field::new("p_type", DataType::Utf8View, false),
+        Field::new("p_size", DataType::Int32, false),
+        Field::new("p_container", DataType::Utf8View, false),
+    ]))
+}
+
+/// Generate SSB [`Date`]s in [`RecordBatch`] format
+///
+/// [`Date`]: tpchgen::ssb::generators::Date
+pub struct DateArrow {
+    inner: DateGeneratorIterator,
+    batch_size: usize,
+}
+
+impl DateArrow {
+    pub fn new(generator: DateGenerator) -> Self {
+        Self {
+            inner: generator.iter(),
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Set the batch size
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+impl RecordBatchIterator for DateArrow {
+    fn schema(&self) -> &SchemaRef {
+        &DATE_SCHEMA
+    }
+}
+
+impl Iterator for DateArrow {
+    type Item = RecordBatch;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Get next rows to convert
+        let rows: Vec<_> = self.inner.by_ref().take(self.batch_size).collect();
+        if rows.is_empty() {
+            return None;
+        }
+
+        let d_datekey = Int32Array::from_iter_values(rows.iter().map(|r| r.d_datekey));
+        let d_date = StringViewArray::from_iter_values(rows.iter().map(|r| &r.d_date));
+        let d_dayofweek = StringViewArray::from_iter_values(rows.iter().map(|r| r.d_dayofweek));
+        let d_month = StringViewArray::from_iter_values(rows.iter().map(|r| r.d_month));
+        let d_year = Int32Array::from_iter_values(rows.iter().map(|r| r.d_year));
+        let d_yearmonthnum = Int32Array::from_iter_values(rows.iter().map(|r| r.d_yearmonthnum));
+        let d_yearmonth = StringViewArray::from_iter_values(rows.iter().map(|r| &r.d_yearmonth));
+        let d_daynuminweek = Int32Array::from_iter_values(rows.iter().map(|r| r.d_daynuminweek));
+        let d_daynuminmonth = Int32Array::from_iter_values(rows.iter().map(|r| r.d_daynuminmonth));
+        let d_daynuminyear = Int32Array::from_iter_values(rows.iter().map(|r| r.d_daynuminyear));
+        let d_monthnuminyear =
+            Int32Array::from_iter_values(rows.iter().map(|r| r.d_monthnuminyear));
+        let d_weeknuminyear = Int32Array::from_iter_values(rows.iter().map(|r| r.d_weeknuminyear));
+        let d_sellingseason =
+            StringViewArray::from_iter_values(rows.iter().map(|r| r.d_sellingseason));
+        // the flags are 0 or 1, like in the TBL files
+        let d_lastdayinweekfl =
+            Int32Array::from_iter_values(rows.iter().map(|r| i32::from(r.d_lastdayinweekfl)));
+        let d_lastdayinmonthfl =
+            Int32Array::from_iter_values(rows.iter().map(|r| i32::from(r.d_lastdayinmonthfl)));
+        let d_holidayfl =
+            Int32Array::from_iter_values(rows.iter().map(|r| i32::from(r.d_holidayfl)));
+        let d_weekdayfl =
+            Int32Array::from_iter_values(rows.iter().map(|r| i32::from(r.d_weekdayfl)));
+
+        let batch = RecordBatch::try_new(
+            Arc::clone(self.schema()),
+            vec![
+                Arc::new(d_datekey),
+                Arc::new(d_date),
+                Arc::new(d_dayofweek),
+                Arc::new(d_month),
+                Arc::new(d_year),
+                Arc::new(d_yearmonthnum),
+                Arc::new(d_yearmonth),
+                Arc::new(d_daynuminweek),
+                Arc::new(d_daynuminmonth),
+                Arc::new(d_daynuminyear),
+                Arc::new(d_monthnuminyear),
+                Arc::new(d_weeknuminyear),
+                Arc::new(d_sellingseason),
+                Arc::new(d_lastdayinweekfl),
+                Arc::new(d_lastdayinmonthfl),
+                Arc::new(d_holidayfl),
+                Arc::new(d_weekdayfl),
+            ],
+        )
+        .unwrap();
+        Some(batch)
+    }
+}
+
+/// Schema for the SSB Date
+static DATE_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(make_date_schema);
+fn make_date_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("d_datekey", DataType::Int32, false),
+        Field::new("d_date", DataType::Utf8View, false),
+        Field::new("d_dayofweek", DataType::Utf8View, false),
+        Field::new("d_month", DataType::Utf8View, false),
+        Field::new("d_year", DataType::Int32, false),
+        Field::new("d_yearmonthnum", DataType::Int32, false),
+        Field::new("d_yearmonth", DataType::Utf8View, false),
+        Field::new("d_daynuminweek", DataType::Int32, false),
+        Field::new("d_daynuminmonth", DataType::Int32, false),
+        Field::new("d_daynuminyear", DataType::Int32, false),
+        Field::new("d_monthnuminyear", DataType::Int32, false),
+        Field::new("d_weeknuminyear", DataType::Int32, false),
+        Field::new("d_sellingseason", DataType::Utf8View, false),
+        Field::new("d_lastdayinweekfl", DataType::Int32, false),
+        Field::new("d_lastdayinmonthfl", DataType::Int32, false),
+        Field::new("d_holidayfl", DataType::Int32, false),
+        Field::new("d_weekdayfl", DataType::Int32, false),
+    ]))
+}
+
+/// Generate [`LineOrder`]s in [`RecordBatch`] format
+///
+/// [`LineOrder`]: tpchgen::ssb::generators::LineOrder
+///
+/// # Example
+/// ```
+/// # use tpchgen::ssb::generators::LineOrderGenerator;
+/// # use tpchgen_arrow::ssb::LineOrderArrow;
+/// // Create a SF=1.0 generator and wrap it in an Arrow generator
+/// let generator = LineOrderGenerator::new(1.0, 1, 1);
+/// let mut arrow_generator = LineOrderArrow::new(generator).with_batch_size(10);
+/// let batch = arrow_generator.next().unwrap();
+/// assert_eq!(batch.num_rows(), 10);
+/// assert_eq!(batch.schema().field(0).name(), "lo_orderkey");
+/// ```
+pub struct LineOrderArrow {
+    inner: LineOrderGeneratorIterator<'static>,
+    batch_size: usize,
+}
+
+impl LineOrderArrow {
+    pub fn new(generator: LineOrderGenerator<'static>) -> Self {
+        Self {
+            inner: generator.iter(),
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Set the batch size
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+impl RecordBatchIterator for LineOrderArrow {
+    fn schema(&self) -> &SchemaRef {
+        &LINEORDER_SCHEMA
+    }
+}
+
+impl Iterator for LineOrderArrow {
+    type Item = RecordBatch;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Get next rows to convert
+        let rows: Vec<_> = self.inner.by_ref().take(self.batch_size).collect();
+        if rows.is_empty() {
+            return None;
+        }
+
+        let lo_orderkey = Int64Array::from_iter_values(rows.iter().map(|r| r.lo_orderkey));
+        let lo_linenumber = Int32Array::from_iter_values(rows.iter().map(|r| r.lo_linenumber));
+        let lo_custkey = Int64Array::from_iter_values(rows.iter().map(|r| r.lo_custkey));
+        let lo_partkey = Int64Array::from_iter_values(rows.iter().map(|r| r.lo_partkey));
+        let lo_suppkey = Int64Array::from_iter_values(rows.iter().map(|r| r.lo_suppkey));
+        let lo_orderdate = Int32Array::from_iter_values(rows.iter().map(|r| r.lo_orderdate));
+        let lo_orderpriority =
+            StringViewArray::from_iter_values(rows.iter().map(|r| r.lo_orderpriority));
+        let lo_shippriority = Int32Array::from_iter_values(rows.iter().map(|r| r.lo_shippriority));
+        let lo_quantity = Int32Array::from_iter_values(rows.iter().map(|r| r.lo_quantity));
+        let lo_extendedprice =
+            Int64Array::from_iter_values(rows.iter().map(|r| r.lo_extendedprice));
+        let lo_ordtotalprice =
+            Int64Array::from_iter_values(rows.iter().map(|r| r.lo_ordtotalprice));
+        let lo_discount = Int32Array::from_iter_values(rows.iter().map(|r| r.lo_discount));
+        let lo_revenue = Int64Array::from_iter_values(rows.iter().map(|r| r.lo_revenue));
+        let lo_supplycost = Int64Array::from_iter_values(rows.iter().map(|r| r.lo_supplycost));
+        let lo_tax = Int32Array::from_iter_values(rows.iter().map(|r| r.lo_tax));
+        let lo_commitdate = Int32Array::from_iter_values(rows.iter().map(|r| r.lo_commitdate));
+        let lo_shipmode = StringViewArray::from_iter_values(rows.iter().map(|r| r.lo_shipmode));
+
+        let batch = RecordBatch::try_new(
+            Arc::clone(self.schema()),
+            vec![
+                Arc::new(lo_orderkey),
+                Arc::new(lo_linenumber),
+                Arc::new(lo_custkey),
+                Arc::new(lo_partkey),
+                Arc::new(lo_suppkey),
+                Arc::new(lo_orderdate),
+                Arc::new(lo_orderpriority),
+                Arc::new(lo_shippriority),
+                Arc::new(lo_quantity),
+                Arc::new(lo_extendedprice),
+                Arc::new(lo_ordtotalprice),
+                Arc::new(lo_discount),
+                Arc::new(lo_revenue),
+                Arc::new(lo_supplycost),
+                Arc::new(lo_tax),
+                Arc::new(lo_commitdate),
+                Arc::new(lo_shipmode),
+            ],
+        )
+        .unwrap();
+        Some(batch)
+    }
+}
+
+/// Schema for the SSB LineOrder
+static LINEORDER_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(make_lineorder_schema);
+fn make_lineorder_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("lo_orderkey", DataType::Int64, false),
+        Field::new("lo_linenumber", DataType::Int32, false),
+        Field::new("lo_custkey", DataType::Int64, false),
+        Field::new("lo_partkey", DataType::Int64, false),
+        Field::new("lo_suppkey", DataType::Int64, false),
+        Field::new("lo_orderdate", DataType::Int32, false),
+        Field::new("lo_orderpriority", DataType::Utf8View, false),
+        Field::new("lo_shippriority", DataType::Int32, false),
+        Field::new("lo_quantity", DataType::Int32, false),
+        Field::new("lo_extendedprice", DataType::Int64, false),
+        Field::new("lo_ordtotalprice", DataType::Int64, false),
+        Field::new("lo_discount", DataType::Int32, false),
+        Field::new("lo_revenue", DataType::Int64, false),
+        Field::new("lo_supplycost", DataType::Int64, false),
+        Field::new("lo_tax", DataType::Int32, false),
+        Field::new("lo_commitdate", DataType::Int32, false),
+        Field::new("lo_shipmode", DataType::Utf8View, false),
+    ]))
+}
@@ -13,6 +13,8 @@ use tpchgen::generators::{
     OrderGenerator, Part, PartGenerator, PartSupp, PartSuppGenerator, Region, RegionGenerator,
     Supplier, SupplierGenerator,
 };
+use tpchgen::ssb::csv as ssb_csv;
+use tpchgen::ssb::generators as ssb;
 use tpchgen_arrow::{
     CustomerArrow, LineItemArrow, NationArrow, OrderArrow, PartArrow, PartSuppArrow,
     RecordBatchIterator, RegionArrow, SupplierArrow,
@@ -52,6 +54,66 @@ test_row_type!(region_tbl, RegionGenerator, RegionArrow, Test::tbl());
 test_row_type!(region_csv, RegionGenerator, RegionArrow, Test::csv());
 test_row_type!(supplier_tbl, SupplierGenerator, SupplierArrow, Test::tbl());
 test_row_type!(supplier_csv, SupplierGenerator, SupplierArrow, Test::csv());
+test_row_type!(
+    ssb_customer_tbl,
+    ssb::CustomerGenerator,
+    tpchgen_arrow::ssb::CustomerArrow,
+    Test::tbl()
+);
+test_row_type!(
+    ssb_customer_csv,
+    ssb::CustomerGenerator,
+    tpchgen_arrow::ssb::CustomerArrow,
+    Test::csv()
+);
+test_row_type!(
+    ssb_supplier_tbl,
+    ssb::SupplierGenerator,
+    tpchgen_arrow::ssb::SupplierArrow,
+    Test::tbl()
+);
+test_row_type!(
+    ssb_supplier_csv,
+    ssb::SupplierGenerator,
+    tpchgen_arrow::ssb::SupplierArrow,
+    Test::csv()
+);
+test_row_type!(
+    ssb_part_tbl,
+    ssb::PartGenerator,
+    tpchgen_arrow::ssb::PartArrow,
+    Test::tbl()
+);
+test_row_type!(
+    ssb_part_csv,
+    ssb::PartGenerator,
+    tpchgen_arrow::ssb::PartArrow,
+    Test::csv()
+);
+test_row_type!(
+    ssb_date_tbl,
+    ssb::DateGenerator,
+    tpchgen_arrow::ssb::DateArrow,
+    Test::tbl()
+);
+test_row_type!(
+    ssb_date_csv,
+    ssb::DateGenerator,
+    tpchgen_arrow::ssb::DateArrow,
+    Test::csv()
+);
+test_row_type!(
+    ssb_lineorder_tbl,
+    ssb::LineOrderGenerator,
+    tpchgen_arrow::ssb::LineOrderArrow,
+    Test::tbl()
+);
+test_row_type!(
+    ssb_lineorder_csv,
+    ssb::LineOrderGenerator,
+    tpchgen_arrow::ssb::LineOrderArrow,
+    Test::csv()
+);
 
 /// Common trait for writing rows in TBL and CSV format
 trait RowType {
@@ -88,6 +150,11 @@ impl_row_type!(Part<'_>, PartCsv);
 impl_row_type!(PartSupp<'_>, PartSuppCsv);
 impl_row_type!(Region<'_>, RegionCsv);
 impl_row_type!(Supplier, SupplierCsv);
+impl_row_type!(ssb::Customer<'_>, ssb_csv::CustomerCsv);
+impl_row_type!(ssb::Supplier<'_>, ssb_csv::SupplierCsv);
+impl_row_type!(ssb::Part<'_>, ssb_csv::PartCsv);
+impl_row_type!(ssb::Date, ssb_csv::DateCsv);
+impl_row_type!(ssb::LineOrder<'_>, ssb_csv::LineOrderCsv);
 
 #[derive(Debug, Clone, Copy)]
 #[allow(clippy::upper_case_acronyms)]
@@ -0,0 +1,86 @@
+//! * [`Benchmark`]: the benchmark to generate the tables of (`--benchmark`)
+//! * [`BenchmarkTable`]: a table of one of the benchmarks
+
+use clap::ValueEnum;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::str::FromStr;
+use tpchgen::ssb::SsbTable;
+use tpchgen::table::Table;
+
+/// The benchmark to generate the tables of
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, ValueEnum)]
+pub enum Benchmark {
+    /// TPC-H
+    #[default]
+    Tpch,
+    /// Star Schema Benchmark, derived from TPC-H
+    Ssb,
+}
+
+impl Benchmark {
+    /// Returns all the tables of the benchmark
+    pub fn tables(&self) -> Vec<BenchmarkTable> {
+        match self {
+            Benchmark::Tpch => Table::ALL.into_iter().map(BenchmarkTable::Tpch).collect(),
+            Benchmark::Ssb => SsbTable::ALL.into_iter().map(BenchmarkTable::Ssb).collect(),
+        }
+    }
+
+    /// Returns the table of the benchmark named `name` (or its `dbgen`
+    /// abbreviation)
+    pub fn table(&self, name: &str) -> io::Result<BenchmarkTable> {
+        let table = match self {
+            Benchmark::Tpch => Table::from_str(name).map(BenchmarkTable::Tpch),
+            Benchmark::Ssb => SsbTable::from_str(name).map(BenchmarkTable::Ssb),
+        };
+        table.map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    }
+}
+
+/// A table of one of the benchmarks
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BenchmarkTable {
+    Tpch(Table),
+    Ssb(SsbTable),
+}
+
+impl BenchmarkTable {
+    /// Returns the exact number of rows in part `part` of `part_count` of the
+    /// table
+    pub fn row_count(&self, scale_factor: f64, part: i32, part_count: i32) -> i64 {
+        match self {
+            BenchmarkTable::Tpch(table) => table.row_count(scale_factor, part, part_count),
+            BenchmarkTable::Ssb(table) => table.row_count(scale_factor, part, part_count),
+        }
+    }
+
+    /// Returns the total number of rows used to partition the table
+    pub fn partition_row_count(&self, scale_factor: f64) -> i64 {
+        match self {
+            BenchmarkTable::Tpch(table) => table.partition_row_count(scale_factor),
+            BenchmarkTable::Ssb(table) => table.partition_row_count(scale_factor),
+        }
+    }
+}
+
+impl From<Table> for BenchmarkTable {
+    fn from(table: Table) -> Self {
+        BenchmarkTable::Tpch(table)
+    }
+}
+
+impl From<SsbTable> for BenchmarkTable {
+    fn from(table: SsbTable) -> Self {
+        BenchmarkTable::Ssb(table)
+    }
+}
+
+impl Display for BenchmarkTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BenchmarkTable::Tpch(table) => write!(f, "{table}"),
+            BenchmarkTable::Ssb(table) => write!(f, "{table}"),
+        }
+    }
+}
@@ -1,13 +1,15 @@
 //! Implementations of [`Source`] for generating data in TBL format
-use super::generate::Source;
+use super::generate::{PairSource, Source};
 use std::io::Write;
 use tpchgen::csv::{
     CustomerCsv, LineItemCsv, NationCsv, OrderCsv, PartCsv, PartSuppCsv, RegionCsv, SupplierCsv,
 };
 use tpchgen::generators::{
-    CustomerGenerator, LineItemGenerator, NationGenerator, OrderGenerator, PartGenerator,
-    PartSuppGenerator, RegionGenerator, SupplierGenerator,
+    CustomerGenerator, LineItemGenerator, NationGenerator, OrderGenerator, OrderLineItemGenerator,
+    PartGenerator, PartSuppGenerator, RegionGenerator, SupplierGenerator,
 };
+use tpchgen::ssb::csv as ssb_csv;
+use tpchgen::ssb::generators as ssb;
 
 /// Define a Source that writes the table in CSV format
 macro_rules! define_csv_source {
@@ -50,3 +52,59 @@ define_csv_source!(PartSuppCsvSource, PartSuppGenerator<'static>, PartSuppCsv);
 define_csv_source!(CustomerCsvSource, CustomerGenerator<'static>, CustomerCsv);
 define_csv_source!(OrderCsvSource, OrderGenerator<'static>, OrderCsv);
 define_csv_source!(LineItemCsvSource, LineItemGenerator<'static>, LineItemCsv);
+
+// Define .csv sources for the SSB tables
+define_csv_source!(
+    SsbCustomerCsvSource,
+    ssb::CustomerGenerator<'static>,
+    ssb_csv::CustomerCsv
+);
+define_csv_source!(
+    SsbSupplierCsvSource,
+    ssb::SupplierGenerator<'static>,
+    ssb_csv::SupplierCsv
+);
+define_csv_source!(
+    SsbPartCsvSource,
+    ssb::PartGenerator<'static>,
+    ssb_csv::PartCsv
+);
+define_csv_source!(SsbDateCsvSource, ssb::DateGenerator, ssb_csv::DateCsv);
+define_csv_source!(
+    SsbLineOrderCsvSource,
+    ssb::LineOrderGenerator<'static>,
+    ssb_csv::LineOrderCsv
+);
+
+/// A [`PairSource`] that writes the ORDERS and LINEITEM tables in CSV format
+/// in a single pass
+pub struct OrderLineItemCsvSource {
+    inner: OrderLineItemGenerator<'static>,
+}
+
+impl OrderLineItemCsvSource {
+    pub fn new(inner: OrderLineItemGenerator<'static>) -> Self {
+        Self { inner }
+    }
+}
+
+impl PairSource for OrderLineItemCsvSource {
+    fn header(&self, (mut orders, mut line_items): (Vec<u8>, Vec<u8>)) -> (Vec<u8>, Vec<u8>) {
+        writeln!(&mut orders, "{}", OrderCsv::header()).expect("writing to memory is infallible");
+        writeln!(&mut line_items, "{}", LineItemCsv::header())
+            .expect("writing to memory is infallible");
+        (orders, line_items)
+    }
+
+    fn create(self, (mut orders, mut line_items): (Vec<u8>, Vec<u8>)) -> (Vec<u8>, Vec<u8>) {
+        for item in self.inner.iter() {
+            let formatter = OrderCsv::new(item.order);
+            writeln!(&mut orders, "{formatter}").expect("writing to memory is infallible");
+            for line_item in item.line_items {
+                let formatter = LineItemCsv::new(line_item);
+                writeln!(&mut line_items, "{formatter}").expect("writing to memory is infallible");
+            }
+        }
+        (orders, line_items)
+    }
+}
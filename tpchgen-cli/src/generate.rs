@@ -2,6 +2,9 @@
 //!
 //! These traits and function are used to generate data in parallel and write it to a sink
 //! in streaming fashion (chunks). This is useful for generating large datasets that don't fit in memory.
+//!
+//! [`PairSource`] and [`generate_pairs_in_chunks`] do the same for two outputs
+//! generated together, such as the ORDERS and LINEITEM tables.
 
 use futures::StreamExt;
 use log::debug;
@@ -25,6 +28,20 @@ pub trait Source: Send {
     fn header(&self, buffer: Vec<u8>) -> Vec<u8>;
 }
 
+/// Something that knows how to generate data for two outputs into two buffers
+///
+/// For example, this is implemented for the generator of the ORDERS and
+/// LINEITEM tables
+pub trait PairSource: Send {
+    /// generates the data for this generator into the buffers, returning the buffers.
+    fn create(self, buffers: (Vec<u8>, Vec<u8>)) -> (Vec<u8>, Vec<u8>);
+
+    /// Create the first lines for the outputs, into the buffers
+    ///
+    /// See [`Source::header`]
+    fn header(&self, buffers: (Vec<u8>, Vec<u8>)) -> (Vec<u8>, Vec<u8>);
+}
+
 /// Something that can write the contents of a buffer somewhere
 ///
 /// For example, this is implemented for a file writer.
@@ -49,7 +66,7 @@ pub trait Sink: Send {
 /// I: Iterator<Item = G>
 /// S: Sink that writes buffers somewhere
 pub async fn generate_in_chunks<G, I, S>(
-    mut sink: S,
+    sink: S,
     sources: I,
     num_threads: usize,
 ) -> Result<(), io::Error>
@@ -57,6 +74,125 @@ where
     G: Source + 'static,
     I: Iterator<Item = G>,
     S: Sink + 'static,
+{
+    generate_buffers_in_chunks(sink, sources, num_threads).await
+}
+
+/// Generates data in parallel from a series of [`PairSource`] and writes the
+/// first buffers to `first_sink` and the second buffers to `second_sink`
+///
+/// See [`generate_in_chunks`]
+pub async fn generate_pairs_in_chunks<G, I, S1, S2>(
+    first_sink: S1,
+    second_sink: S2,
+    sources: I,
+    num_threads: usize,
+) -> Result<(), io::Error>
+where
+    G: PairSource + 'static,
+    I: Iterator<Item = G>,
+    S1: Sink + 'static,
+    S2: Sink + 'static,
+{
+    generate_buffers_in_chunks((first_sink, second_sink), sources, num_threads).await
+}
+
+/// The buffers a source generates data into: a single buffer for a [`Source`]
+/// and two buffers for a [`PairSource`]
+trait Buffers: Send + 'static {
+    /// return new empty buffers from the recycler
+    fn new_from(recycler: &BufferRecycler) -> Self;
+
+    /// return the buffers to the recycler
+    fn recycle(self, recycler: &BufferRecycler);
+}
+
+impl Buffers for Vec<u8> {
+    fn new_from(recycler: &BufferRecycler) -> Self {
+        recycler.new_buffer(1024 * 1024 * 8)
+    }
+
+    fn recycle(self, recycler: &BufferRecycler) {
+        recycler.return_buffer(self)
+    }
+}
+
+impl Buffers for (Vec<u8>, Vec<u8>) {
+    fn new_from(recycler: &BufferRecycler) -> Self {
+        (Vec::new_from(recycler), Vec::new_from(recycler))
+    }
+
+    fn recycle(self, recycler: &BufferRecycler) {
+        self.0.recycle(recycler);
+        self.1.recycle(recycler);
+    }
+}
+
+/// [`Source`] or [`PairSource`], generating data into `B`
+trait BufferSource<B>: Send {
+    fn create(self, buffers: B) -> B;
+    fn header(&self, buffers: B) -> B;
+}
+
+impl<G: Source> BufferSource<Vec<u8>> for G {
+    fn create(self, buffer: Vec<u8>) -> Vec<u8> {
+        Source::create(self, buffer)
+    }
+
+    fn header(&self, buffer: Vec<u8>) -> Vec<u8> {
+        Source::header(self, buffer)
+    }
+}
+
+impl<G: PairSource> BufferSource<(Vec<u8>, Vec<u8>)> for G {
+    fn create(self, buffers: (Vec<u8>, Vec<u8>)) -> (Vec<u8>, Vec<u8>) {
+        PairSource::create(self, buffers)
+    }
+
+    fn header(&self, buffers: (Vec<u8>, Vec<u8>)) -> (Vec<u8>, Vec<u8>) {
+        PairSource::header(self, buffers)
+    }
+}
+
+/// [`Sink`] or pair of [`Sink`]s, writing data from `B`
+trait BufferSink<B>: Send {
+    fn sink(&mut self, buffers: &B) -> Result<(), io::Error>;
+    fn flush(self) -> Result<(), io::Error>;
+}
+
+impl<S: Sink> BufferSink<Vec<u8>> for S {
+    fn sink(&mut self, buffer: &Vec<u8>) -> Result<(), io::Error> {
+        Sink::sink(self, buffer)
+    }
+
+    fn flush(self) -> Result<(), io::Error> {
+        Sink::flush(self)
+    }
+}
+
+impl<S1: Sink, S2: Sink> BufferSink<(Vec<u8>, Vec<u8>)> for (S1, S2) {
+    fn sink(&mut self, buffers: &(Vec<u8>, Vec<u8>)) -> Result<(), io::Error> {
+        self.0.sink(&buffers.0)?;
+        self.1.sink(&buffers.1)
+    }
+
+    fn flush(self) -> Result<(), io::Error> {
+        self.0.flush()?;
+        self.1.flush()
+    }
+}
+
+/// Implementation of [`generate_in_chunks`] and [`generate_pairs_in_chunks`]
+async fn generate_buffers_in_chunks<B, G, I, S>(
+    mut sink: S,
+    sources: I,
+    num_threads: usize,
+) -> Result<(), io::Error>
+where
+    B: Buffers,
+    G: BufferSource<B> + 'static,
+    I: Iterator<Item = G>,
+    S: BufferSink<B> + 'static,
 {
     let recycler = BufferRecycler::new();
     let mut sources = sources.peekable();
@@ -71,7 +207,7 @@ where
     let Some(first) = sources.peek() else {
         return Ok(()); // no sources
     };
-    let header = first.header(Vec::new());
+    let header = first.header(B::new_from(&recycler));
     tx.send(header)
         .await
         .expect("tx just created, it should not be closed");
@@ -82,7 +218,7 @@ where
     let mut stream = futures::stream::iter(sources_and_recyclers)
         // each generator writes to a buffer
         .map(async |(source, recycler)| {
-            let buffer = recycler.new_buffer(1024 * 1024 * 8);
+            let buffer = B::new_from(&recycler);
             // do the work in a task (on a different thread)
             let mut join_set = JoinSet::new();
             join_set.spawn(async move { source.create(buffer) });
@@ -112,7 +248,7 @@ where
     let writer_task = tokio::task::spawn_blocking(move || {
         while let Some(buffer) = rx.blocking_recv() {
             sink.sink(&buffer)?;
-            captured_recycler.return_buffer(buffer);
+            buffer.recycle(&captured_recycler);
         }
         // No more input, flush the sink and return
         sink.flush()
@@ -5,31 +5,39 @@
 //! and arguments.
 //!
 //! See the documentation on [`Cli`] for more information on the command line
+mod benchmark;
 mod csv;
 mod generate;
 mod output_plan;
 mod parquet;
 mod plan;
+mod qgen;
 mod runner;
 mod statistics;
 mod tbl;
+mod validate_answer;
+mod verify;
 
+use crate::benchmark::{Benchmark, BenchmarkTable};
 use crate::generate::Sink;
 use crate::output_plan::OutputPlanGenerator;
 use crate::parquet::*;
 use crate::plan::{GenerationPlan, DEFAULT_PARQUET_ROW_GROUP_BYTES};
+use crate::qgen::QgenArgs;
 use crate::statistics::WriteStatistics;
+use crate::validate_answer::ValidateAnswerArgs;
+use crate::verify::VerifyArgs;
 use ::parquet::basic::Compression;
 use clap::builder::TypedValueParser;
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 use log::{debug, info, LevelFilter};
-use std::fmt::Display;
 use std::fs::{self, File};
 use std::io::{self, BufWriter, Stdout, Write};
 use std::path::PathBuf;
-use std::str::FromStr;
 use std::time::Instant;
-use tpchgen::distribution::Distributions;
+use tpchgen::dates::DateRange;
+use tpchgen::distribution::{Distribution, Distributions, DistributionsBuilder};
+use tpchgen::skew::Skew;
 use tpchgen::text::TextPool;
 
 #[derive(Parser)]
@@ -58,6 +66,69 @@ tpchgen-cli -s 1 --output-dir=/tmp/tpch
 
 tpchgen-cli -s 100 --tables=lineitem --format=parquet --parts=10 --output-dir=/tmp/tpch
 
+# Generate 4 update streams for the refresh functions at scale factor 1
+
+tpchgen-cli -s 1 --updates=4 --output-dir=/tmp/tpch
+
+# Generate a different dataset at scale factor 1, with the same statistical
+# properties as the standard data
+
+tpchgen-cli -s 1 --seed=7 --output-dir=/tmp/tpch-7
+
+# Generate scale factor 1 where a few customers place most of the orders and
+# a few parts are in most of the line items
+
+tpchgen-cli -s 1 --skew=o_custkey=1,l_partkey=1 --output-dir=/tmp/tpch-skewed
+
+# Generate scale factor 1 with the dates moved to 2019-2025 (and print the
+# queries with the dates moved the same way)
+
+tpchgen-cli -s 1 --date-range=2019-2025 --output-dir=/tmp/tpch-2019
+tpchgen-cli -s 1 --date-range=2019-2025 qgen
+
+# Generate scale factor 1 with the nation names of nations.txt, where each line
+# is a name and the region key adjustment of the distributions file, e.g.
+# `ALGERIEN|0`
+
+tpchgen-cli -s 1 --dist-override nations=nations.txt --output-dir=/tmp/tpch-de
+
+# Generate scale factor 1, keeping the text pool of the comments in a cache file
+# to start faster the next time
+
+tpchgen-cli -s 1 --text-pool-cache=/tmp/tpch-text-pool --output-dir=/tmp/tpch
+
+# Generate scale factor 1 with a 16 MB text pool to use less memory (the
+# comments are NOT TPC-H conformant)
+
+tpchgen-cli -s 1 --text-pool-size=16777216 --output-dir=/tmp/tpch-small
+
+# Generate scale factor 1 with comments in several languages and scripts, with
+# emoji and combining characters (NOT TPC-H conformant)
+
+tpchgen-cli -s 1 --multilingual-text --output-dir=/tmp/tpch-utf8
+
+# Generate the Star Schema Benchmark (SSB) tables at scale factor 1
+
+tpchgen-cli -s 1 --benchmark=ssb --output-dir=/tmp/ssb
+
+# Print the 22 queries with random parameters for scale factor 10 (like qgen)
+
+tpchgen-cli -s 10 qgen --seed 42
+
+# Print the 22 queries for DuckDB, with Q15 as a single statement
+
+tpchgen-cli -s 10 qgen --dialect duckdb --q15-cte
+
+# Check the result of Q3 (with the validation parameters) on scale factor 10
+# data, exported as CSV with a header line
+
+tpchgen-cli -s 10 validate-answer --query 3 q3.csv
+
+# Check that the 10 Parquet files of each table generated at scale factor 100
+# in /tmp/tpch are complete and correct
+
+tpchgen-cli -s 100 --format=parquet --parts=10 --output-dir=/tmp/tpch verify
+
 # Generate scale factor one in current directory, seeing debug output
 
 RUST_LOG=debug tpchgen -s 1
@@ -72,9 +143,16 @@ struct Cli {
     #[arg(short, long, default_value = ".")]
     output_dir: PathBuf,
 
-    /// Which tables to generate (default: all)
+    /// Benchmark to generate the tables of: tpch, ssb
+    ///
+    /// The Star Schema Benchmark (ssb) tables are customer, supplier, part,
+    /// date and lineorder.
+    #[arg(long, default_value = "tpch")]
+    benchmark: Benchmark,
+
+    /// Which tables of the benchmark to generate (default: all)
     #[arg(short = 'T', long = "tables", value_delimiter = ',', value_parser = TableValueParser)]
-    tables: Option<Vec<Table>>,
+    tables: Option<Vec<String>>,
 
     /// Number of part(itions) to generate. If not specified creates a single file per table
     #[arg(short, long)]
@@ -84,6 +162,17 @@ struct Cli {
     #[arg(long)]
     part: Option<i32>,
 
+    /// Split each table into files of about this many bytes instead of a
+    /// number of files (`--parts`)
+    ///
+    /// Each table is written to <output_dir>/<table>/<table>.<n>.<format>,
+    /// and all the files except the last one have the same number of rows.
+    /// The size is estimated from the average row size of each table, so the
+    /// actual file sizes differ, especially for compressed Parquet files.
+    /// The nation, region and SSB date tables are never split.
+    #[arg(long, conflicts_with_all = ["part", "parts", "stdout", "count_only"])]
+    target_file_bytes: Option<i64>,
+
     /// Output format: tbl, csv, parquet
     #[arg(short, long, default_value = "tbl")]
     format: OutputFormat,
@@ -137,23 +226,137 @@ struct Cli {
     /// Path to the distributions file
     #[arg(long)]
     dists_path: PathBuf,
+
+    /// Replace a distribution of the distributions file with the values of
+    /// another file, e.g. `--dist-override nations=nations.txt`
+    ///
+    /// Each line of the file is a `<value>|<weight>` entry, like in the
+    /// distributions file. The distributions are named like in the
+    /// distributions file (e.g. nations, regions, smode, colors). There must
+    /// still be 25 nations in 5 regions. Can be specified multiple times.
+    #[arg(long, value_name = "NAME=FILE", value_parser = parse_dist_override)]
+    dist_override: Vec<(String, PathBuf)>,
+
+    /// Generate comments with multilingual words (NOT TPC-H conformant)
+    ///
+    /// The words of the comments are in several languages and scripts, with
+    /// emoji and combining characters, so the comments are multi-byte UTF-8
+    /// text with the same average length in bytes. `--dist-override` can
+    /// replace some of the words.
+    #[arg(long, default_value_t = false)]
+    multilingual_text: bool,
+
+    /// Load the text pool of the comments from this cache file, or create it
+    ///
+    /// Creating the 300 MB text pool takes a few seconds at the start of each
+    /// run. When the file exists and was created with the same `--seed` and
+    /// distributions, the text pool is memory-mapped from it instead, and
+    /// otherwise it is generated and written to the file. The generated data
+    /// is the same with or without the cache.
+    #[arg(long, value_name = "PATH")]
+    text_pool_cache: Option<PathBuf>,
+
+    /// Check the text of the `--text-pool-cache` file against its checksum
+    ///
+    /// Only the header of the cache file is checked by default, so the text
+    /// is read from the file as needed. With this option the whole file is
+    /// read when it is loaded, and a corrupted cache is created again.
+    #[arg(long, default_value_t = false, requires = "text_pool_cache")]
+    verify_text_pool_cache: bool,
+
+    /// Size in bytes of the text pool of the comments (NOT TPC-H conformant
+    /// if changed)
+    ///
+    /// The comments are taken from a pool of random text, 300 MB by default
+    /// as required by the TPC-H specification. A smaller pool (at least
+    /// 1048576 bytes) uses less memory, but the comments repeat more often and
+    /// differ from the TPC-H data. The other columns are not changed.
+    #[arg(
+        long,
+        value_name = "BYTES",
+        default_value_t = TextPool::DEFAULT_TEXT_POOL_SIZE,
+        value_parser = clap::value_parser!(i32).range(TextPool::MIN_TEXT_POOL_SIZE as i64..)
+    )]
+    text_pool_size: i32,
+
+    /// Generate N update streams for the refresh functions instead of the tables
+    ///
+    /// In the file layout of `dbgen -U N`, each update stream <n> is written to
+    /// <output_dir>/orders.tbl.u<n> and <output_dir>/lineitem.tbl.u<n> (RF1)
+    /// and <output_dir>/delete.<n> (RF2). Only the tbl format is supported.
+    #[arg(short = 'U', long)]
+    updates: Option<i32>,
+
+    /// Seed for generating a different dataset
+    ///
+    /// Each seed generates a different dataset with the same statistical
+    /// properties and number of rows. Seed 0 (the default) generates the
+    /// standard TPC-H data, identical to dbgen. This is unrelated to the
+    /// seed of the query parameters (`qgen --seed`).
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Skew the values of some columns with a Zipf distribution
+    ///
+    /// A comma separated list of `<column>=<factor>` entries, where a factor
+    /// without a column applies to all the columns, e.g.
+    /// `--skew o_custkey=1,l_partkey=0.8`. The factor is the exponent of the
+    /// Zipf distribution: 0 is the uniform TPC-H distribution and 1 is the
+    /// classic Zipf distribution. The smallest keys and the first values of
+    /// each column are the most frequent.
+    ///
+    /// The columns are o_custkey, l_partkey, l_suppkey, p_type, p_container,
+    /// c_mktsegment, o_orderpriority, l_returnflag, l_shipinstruct and
+    /// l_shipmode.
+    #[arg(long, default_value_t = Skew::default())]
+    skew: Skew,
+
+    /// Years of the dates of the dataset, e.g. `2019-2026` (at least 7 years)
+    ///
+    /// The dates of the standard TPC-H data (1992-1998) are moved to the
+    /// first year, and the order dates are spread over the whole range. A
+    /// single year is the first of 7 years, like the standard data. The
+    /// current date of the order and line statuses is June 17 of the middle
+    /// year, and the dates of the queries (`qgen`, `validate-answer`) are
+    /// moved by the same number of years.
+    #[arg(long, default_value_t = DateRange::default())]
+    date_range: DateRange,
+
+    /// Print the exact number of rows of each table (and part) instead of
+    /// generating the data
+    ///
+    /// The output has one `<table>,<part>,<row count>` line for each file
+    /// that would be generated with the same `--tables`, `--parts` and
+    /// `--part` options.
+    #[arg(long, default_value_t = false, conflicts_with = "updates")]
+    count_only: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-enum Table {
-    Nation,
-    Region,
-    Part,
-    Supplier,
-    Partsupp,
-    Customer,
-    Orders,
-    Lineitem,
+/// Commands other than data generation
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the TPC-H queries with substituted parameters (like qgen)
+    Qgen(QgenArgs),
+    /// Check the result of a query against the expected answer for the
+    /// scale factor (and `--seed`, `--skew` and `--date-range`)
+    ValidateAnswer(ValidateAnswerArgs),
+    /// Check that the files generated with the same options (`--output-dir`,
+    /// `--format`, `--tables`, `--parts`, ...) are complete and correct
+    Verify(VerifyArgs),
 }
 
-impl Display for Table {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.name())
+/// Parses a `--dist-override` value: `<name>=<file>`
+fn parse_dist_override(value: &str) -> Result<(String, PathBuf), String> {
+    match value.split_once('=') {
+        Some((name, path)) if !name.is_empty() && !path.is_empty() => {
+            Ok((name.to_string(), PathBuf::from(path)))
+        }
+        _ => Err(format!(
+            "Invalid distribution override '{value}', expected <name>=<file>"
+        )),
     }
 }
 
@@ -161,9 +364,12 @@ impl Display for Table {
 struct TableValueParser;
 
 impl TypedValueParser for TableValueParser {
-    type Value = Table;
+    type Value = String;
 
-    /// Parse the value into a Table enum.
+    /// Parse the value into the name of a table of any benchmark.
+    ///
+    /// The table is looked up in the `--benchmark` tables later, see
+    /// [`Benchmark::table`].
     fn parse_ref(
         &self,
         cmd: &clap::Command,
@@ -173,8 +379,10 @@ impl TypedValueParser for TableValueParser {
         let value = value
             .to_str()
             .ok_or_else(|| clap::Error::new(clap::error::ErrorKind::InvalidValue).with_cmd(cmd))?;
-        Table::from_str(value)
-            .map_err(|_| clap::Error::new(clap::error::ErrorKind::InvalidValue).with_cmd(cmd))
+        if Benchmark::Tpch.table(value).is_err() && Benchmark::Ssb.table(value).is_err() {
+            return Err(clap::Error::new(clap::error::ErrorKind::InvalidValue).with_cmd(cmd));
+        }
+        Ok(value.to_string())
     }
 
     fn possible_values(
@@ -186,55 +394,20 @@ impl TypedValueParser for TableValueParser {
                 clap::builder::PossibleValue::new("nation").help("Nation table (alias: n)"),
                 clap::builder::PossibleValue::new("supplier").help("Supplier table (alias: s)"),
                 clap::builder::PossibleValue::new("customer").help("Customer table (alias: c)"),
-                clap::builder::PossibleValue::new("part").help("Part table (alias: P)"),
+                clap::builder::PossibleValue::new("part")
+                    .help("Part table (alias: P, or p for ssb)"),
                 clap::builder::PossibleValue::new("partsupp").help("PartSupp table (alias: S)"),
                 clap::builder::PossibleValue::new("orders").help("Orders table (alias: O)"),
                 clap::builder::PossibleValue::new("lineitem").help("LineItem table (alias: L)"),
+                clap::builder::PossibleValue::new("date").help("SSB Date table (alias: d)"),
+                clap::builder::PossibleValue::new("lineorder")
+                    .help("SSB LineOrder table (alias: l)"),
             ]
             .into_iter(),
         ))
     }
 }
 
-impl FromStr for Table {
-    type Err = &'static str;
-
-    /// Returns the table enum value from the given string full name or abbreviation
-    ///
-    /// The original dbgen tool allows some abbreviations to mean two different tables
-    /// like 'p' which aliases to both 'part' and 'partsupp'. This implementation does
-    /// not support this since it just adds unnecessary complexity and confusion so we
-    /// only support the exclusive abbreviations.
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "n" | "nation" => Ok(Table::Nation),
-            "r" | "region" => Ok(Table::Region),
-            "s" | "supplier" => Ok(Table::Supplier),
-            "P" | "part" => Ok(Table::Part),
-            "S" | "partsupp" => Ok(Table::Partsupp),
-            "c" | "customer" => Ok(Table::Customer),
-            "O" | "orders" => Ok(Table::Orders),
-            "L" | "lineitem" => Ok(Table::Lineitem),
-            _ => Err("Invalid table name {s}"),
-        }
-    }
-}
-
-impl Table {
-    fn name(&self) -> &'static str {
-        match self {
-            Table::Nation => "nation",
-            Table::Region => "region",
-            Table::Part => "part",
-            Table::Supplier => "supplier",
-            Table::Partsupp => "partsupp",
-            Table::Customer => "customer",
-            Table::Orders => "orders",
-            Table::Lineitem => "lineitem",
-        }
-    }
-}
-
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum OutputFormat {
     Tbl,
@@ -252,7 +425,7 @@ async fn main() -> io::Result<()> {
 impl Cli {
     /// Main function to run the generation
     async fn main(self) -> io::Result<()> {
-        Distributions::init_from_path(&self.dists_path)?;
+        self.init_distributions()?;
 
         if self.verbose {
             // explicitly set logging to info / stdout
@@ -263,27 +436,72 @@ impl Cli {
             debug!("Logging configured from environment variables");
         }
 
-        // Create output directory if it doesn't exist and we are not writing to stdout.
-        if !self.stdout {
-            fs::create_dir_all(&self.output_dir)?;
+        match &self.command {
+            Some(Command::Qgen(args)) => {
+                return args.run(self.scale_factor, self.date_range, &mut io::stdout().lock());
+            }
+            Some(Command::ValidateAnswer(args)) => {
+                self.init_text_pool()?;
+                return args.run(
+                    self.scale_factor,
+                    self.seed,
+                    self.skew,
+                    self.date_range,
+                    &mut io::stdout().lock(),
+                );
+            }
+            Some(Command::Verify(_)) | None => {}
         }
 
         // Determine which tables to generate
-        let tables: Vec<Table> = if let Some(tables) = self.tables.as_ref() {
-            tables.clone()
+        let tables: Vec<BenchmarkTable> = if let Some(tables) = self.tables.as_ref() {
+            tables
+                .iter()
+                .map(|name| self.benchmark.table(name))
+                .collect::<io::Result<_>>()?
         } else {
-            vec![
-                Table::Nation,
-                Table::Region,
-                Table::Part,
-                Table::Supplier,
-                Table::Partsupp,
-                Table::Customer,
-                Table::Orders,
-                Table::Lineitem,
-            ]
+            self.benchmark.tables()
         };
 
+        if self.benchmark != Benchmark::Tpch {
+            if self.updates.is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Update streams can only be generated for the tpch benchmark",
+                ));
+            }
+            if self.skew != Skew::default() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "--skew is only supported for the tpch benchmark",
+                ));
+            }
+            if self.date_range != DateRange::default() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "--date-range is only supported for the tpch benchmark",
+                ));
+            }
+        }
+
+        if let Some(Command::Verify(args)) = &self.command {
+            self.init_text_pool()?;
+            return args.run(&self, &tables, &self.parts()?, &mut io::stdout().lock());
+        }
+
+        if self.count_only {
+            return self.count_rows(&tables, &mut io::stdout().lock());
+        }
+
+        // Create output directory if it doesn't exist and we are not writing to stdout.
+        if !self.stdout {
+            fs::create_dir_all(&self.output_dir)?;
+        }
+
+        if let Some(updates) = self.updates {
+            return self.generate_updates(updates).await;
+        }
+
         // Warn if parquet specific options are set but not generating parquet
         if self.format != OutputFormat::Parquet {
             if self.parquet_compression != Compression::SNAPPY {
@@ -302,6 +520,9 @@ impl Cli {
         let mut output_plan_generator = OutputPlanGenerator::new(
             self.format,
             self.scale_factor,
+            self.seed,
+            self.skew,
+            self.date_range,
             self.parquet_compression,
             self.parquet_row_group_bytes,
             self.stdout,
@@ -309,7 +530,12 @@ impl Cli {
         );
 
         for table in tables {
-            output_plan_generator.generate_plans(table, self.part, self.parts)?;
+            if let Some(target_file_bytes) = self.target_file_bytes {
+                output_plan_generator
+                    .generate_plans_with_target_file_bytes(table, target_file_bytes)?;
+            } else {
+                output_plan_generator.generate_plans(table, self.part, self.parts)?;
+            }
         }
         let output_plans = output_plan_generator.build();
 
@@ -318,7 +544,7 @@ impl Cli {
         let start = Instant::now();
         debug!("Creating distributions and text pool");
         Distributions::static_default();
-        TextPool::get_or_init_default();
+        self.init_text_pool()?;
         let elapsed = start.elapsed();
         info!("Created static distributions and text pools in {elapsed:?}");
 
@@ -328,6 +554,143 @@ impl Cli {
         info!("Generation complete!");
         Ok(())
     }
+
+    /// Print the exact row count of each part of `tables` to `out`
+    fn count_rows(&self, tables: &[BenchmarkTable], out: &mut impl Write) -> io::Result<()> {
+        let parts = self.parts()?;
+        writeln!(out, "table,part,row_count")?;
+        for &table in tables {
+            for &(part, part_count) in &parts {
+                if !GenerationPlan::partitioned_table(table) && part != 1 {
+                    // unpartitioned tables are written to a single file
+                    continue;
+                }
+                let row_count = table.row_count(self.scale_factor, part, part_count);
+                writeln!(out, "{table},{part},{row_count}")?;
+            }
+        }
+        out.flush()
+    }
+
+    /// Returns the `(part, part_count)` of each part selected by the `--part`
+    /// and `--parts` options
+    fn parts(&self) -> io::Result<Vec<(i32, i32)>> {
+        match (self.part, self.parts) {
+            (None, None) => Ok(vec![(1, 1)]),
+            (None, Some(part_count)) => {
+                Ok((1..=part_count).map(|part| (part, part_count)).collect())
+            }
+            (Some(part), Some(part_count)) if (1..=part_count).contains(&part) => {
+                Ok(vec![(part, part_count)])
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Invalid --part {:?} and --parts {:?} options",
+                    self.part, self.parts
+                ),
+            )),
+        }
+    }
+
+    /// Generate the update streams for the refresh functions
+    async fn generate_updates(&self, updates: i32) -> io::Result<()> {
+        if updates < 1 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid number of update streams: {updates}, must be at least 1"),
+            ));
+        }
+        if self.format != OutputFormat::Tbl {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Update streams can only be generated in tbl format, got {:?}",
+                    self.format
+                ),
+            ));
+        }
+        if self.tables.is_some() || self.part.is_some() || self.parts.is_some() {
+            eprintln!("Warning: --tables, --part and --parts are ignored when generating updates");
+        }
+
+        let start = Instant::now();
+        Distributions::static_default();
+        self.init_text_pool()?;
+        info!(
+            "Created static distributions and text pools in {:?}",
+            start.elapsed()
+        );
+
+        runner::write_updates(
+            &self.output_dir,
+            self.scale_factor,
+            self.seed,
+            self.skew,
+            self.date_range,
+            updates,
+            self.stdout,
+        )
+        .await?;
+        info!("Generation complete!");
+        Ok(())
+    }
+
+    /// Load the default distributions from `--dists-path`, with the
+    /// `--multilingual-text` words and the `--dist-override` distributions
+    fn init_distributions(&self) -> io::Result<()> {
+        let mut builder =
+            DistributionsBuilder::new(Distributions::try_from_path(&self.dists_path)?);
+        if self.multilingual_text {
+            eprintln!("Warning: --multilingual-text is set, the comments are not TPC-H conformant");
+            builder = builder.with_multilingual_text();
+        }
+        for (name, path) in &self.dist_override {
+            builder = builder.with_distribution(Distribution::try_from_path(name, path)?);
+        }
+        let distributions = builder
+            .build()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        Distributions::init_default(distributions)
+    }
+
+    /// Create the default text pool, using `--seed`, `--text-pool-size`,
+    /// `--text-pool-cache` and `--verify-text-pool-cache`
+    fn init_text_pool(&self) -> io::Result<()> {
+        let size = self.text_pool_size;
+        let distributions = Distributions::static_default();
+        if size != TextPool::DEFAULT_TEXT_POOL_SIZE {
+            eprintln!(
+                "Warning: the text pool size is not {}, the comments are not TPC-H conformant",
+                TextPool::DEFAULT_TEXT_POOL_SIZE
+            );
+        }
+        if let Some(path) = &self.text_pool_cache {
+            // SAFETY: `write_cache` replaces the cache file with a new file,
+            // so the mapped file is never modified, and the text of a file
+            // that is not verified was written by `write_cache`
+            let verify = self.verify_text_pool_cache;
+            let cached =
+                unsafe { TextPool::map_cache(path, size, distributions, self.seed, verify) };
+            let text_pool = match cached {
+                Ok(text_pool) => {
+                    debug!("Loaded the text pool from {}", path.display());
+                    text_pool
+                }
+                Err(e) => {
+                    info!("Creating the text pool cache {}: {e}", path.display());
+                    let text_pool = TextPool::new_with_seed(size, distributions, self.seed);
+                    text_pool.write_cache(path)?;
+                    text_pool
+                }
+            };
+            TextPool::init_default(text_pool)?;
+        } else if self.seed != 0 || size != TextPool::DEFAULT_TEXT_POOL_SIZE {
+            TextPool::init_default(TextPool::new_with_seed(size, distributions, self.seed))?;
+        }
+        TextPool::get_or_init_default();
+        Ok(())
+    }
 }
 
 impl IntoSize for BufWriter<Stdout> {
@@ -2,14 +2,17 @@
 //! * [`OutputPlan`]: an output file that will be generated
 //! * [`OutputPlanGenerator`]: plans the output files to be generated
 
+use crate::benchmark::BenchmarkTable;
 use crate::plan::GenerationPlan;
-use crate::{OutputFormat, Table};
+use crate::OutputFormat;
 use log::debug;
 use parquet::basic::Compression;
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 use std::io;
 use std::path::PathBuf;
+use tpchgen::dates::DateRange;
+use tpchgen::skew::Skew;
 
 /// Where a partition will be output
 #[derive(Debug, Clone, PartialEq)]
@@ -39,9 +42,15 @@ impl Display for OutputLocation {
 #[derive(Debug, Clone, PartialEq)]
 pub struct OutputPlan {
     /// The table
-    table: Table,
+    table: BenchmarkTable,
     /// The scale factor
     scale_factor: f64,
+    /// The seed of the dataset (0 for the standard TPC-H data)
+    seed: u64,
+    /// The skew of the dataset (uniform for the standard TPC-H data)
+    skew: Skew,
+    /// The calendar of the dataset (1992-1998 for the standard TPC-H data)
+    date_range: DateRange,
     /// The output format (TODO don't depend back on something in main)
     output_format: OutputFormat,
     /// If the output is parquet, what compression level to use
@@ -53,9 +62,13 @@ pub struct OutputPlan {
 }
 
 impl OutputPlan {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        table: Table,
+        table: BenchmarkTable,
         scale_factor: f64,
+        seed: u64,
+        skew: Skew,
+        date_range: DateRange,
         output_format: OutputFormat,
         parquet_compression: Compression,
         output_location: OutputLocation,
@@ -64,6 +77,9 @@ impl OutputPlan {
         Self {
             table,
             scale_factor,
+            seed,
+            skew,
+            date_range,
             output_format,
             parquet_compression,
             output_location,
@@ -72,7 +88,7 @@ impl OutputPlan {
     }
 
     /// Return the table this partition is for
-    pub fn table(&self) -> Table {
+    pub fn table(&self) -> BenchmarkTable {
         self.table
     }
 
@@ -81,6 +97,21 @@ impl OutputPlan {
         self.scale_factor
     }
 
+    /// Return the dataset seed for this partition
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Return the dataset skew for this partition
+    pub fn skew(&self) -> Skew {
+        self.skew
+    }
+
+    /// Return the dataset date range for this partition
+    pub fn date_range(&self) -> DateRange {
+        self.date_range
+    }
+
     /// Return the output format for this partition
     pub fn output_format(&self) -> OutputFormat {
         self.output_format
@@ -125,6 +156,9 @@ impl Display for OutputPlan {
 pub struct OutputPlanGenerator {
     format: OutputFormat,
     scale_factor: f64,
+    seed: u64,
+    skew: Skew,
+    date_range: DateRange,
     parquet_compression: Compression,
     parquet_row_group_bytes: i64,
     stdout: bool,
@@ -137,9 +171,13 @@ pub struct OutputPlanGenerator {
 }
 
 impl OutputPlanGenerator {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         format: OutputFormat,
         scale_factor: f64,
+        seed: u64,
+        skew: Skew,
+        date_range: DateRange,
         parquet_compression: Compression,
         parquet_row_group_bytes: i64,
         stdout: bool,
@@ -148,6 +186,9 @@ impl OutputPlanGenerator {
         Self {
             format,
             scale_factor,
+            seed,
+            skew,
+            date_range,
             parquet_compression,
             parquet_row_group_bytes,
             stdout,
@@ -160,7 +201,7 @@ impl OutputPlanGenerator {
     /// Generate the output plans for the given table and partition options
     pub fn generate_plans(
         &mut self,
-        table: Table,
+        table: BenchmarkTable,
         cli_part: Option<i32>,
         cli_part_count: Option<i32>,
     ) -> io::Result<()> {
@@ -183,9 +224,53 @@ impl OutputPlanGenerator {
         Ok(())
     }
 
+    /// Generate the output plans for the given table, split into files of
+    /// at most `target_file_bytes` (estimated)
+    ///
+    /// The files are named like the files of `--parts`.
+    pub fn generate_plans_with_target_file_bytes(
+        &mut self,
+        table: BenchmarkTable,
+        target_file_bytes: i64,
+    ) -> io::Result<()> {
+        let row_ranges = GenerationPlan::file_row_ranges(
+            table,
+            self.format,
+            self.scale_factor,
+            target_file_bytes,
+        )
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        debug!("Generating {} files for table {table}", row_ranges.len());
+
+        for (file_number, row_range) in (1..).zip(row_ranges) {
+            let generation_plan = GenerationPlan::try_new_with_row_range(
+                table,
+                self.format,
+                self.scale_factor,
+                row_range,
+                self.parquet_row_group_bytes,
+            )
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+            let output_location = self.output_location(table, Some(file_number))?;
+            self.output_plans.push(OutputPlan::new(
+                table,
+                self.scale_factor,
+                self.seed,
+                self.skew,
+                self.date_range,
+                self.format,
+                self.parquet_compression,
+                output_location,
+                generation_plan,
+            ));
+        }
+        Ok(())
+    }
+
     fn generate_plan_inner(
         &mut self,
-        table: Table,
+        table: BenchmarkTable,
         cli_part: Option<i32>,
         cli_part_count: Option<i32>,
     ) -> io::Result<()> {
@@ -204,6 +289,9 @@ impl OutputPlanGenerator {
         let plan = OutputPlan::new(
             table,
             self.scale_factor,
+            self.seed,
+            self.skew,
+            self.date_range,
             self.format,
             self.parquet_compression,
             output_location,
@@ -221,7 +309,11 @@ impl OutputPlanGenerator {
     /// * if part is Some(part), then the output location
     ///   will be `{output_dir}/{table}/{table}table.{part}.{extension}`
     ///   (e.g. orders/orders.1.tbl, orders/orders.2.tbl, etc.)
-    fn output_location(&mut self, table: Table, part: Option<i32>) -> io::Result<OutputLocation> {
+    fn output_location(
+        &mut self,
+        table: BenchmarkTable,
+        part: Option<i32>,
+    ) -> io::Result<OutputLocation> {
         if self.stdout {
             Ok(OutputLocation::Stdout)
         } else {
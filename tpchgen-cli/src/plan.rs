@@ -1,12 +1,18 @@
 //! * [`GenerationPlan`]: how to generate a specific TPC-H dataset.
 
-use crate::{OutputFormat, Table};
+use crate::benchmark::BenchmarkTable;
+use crate::OutputFormat;
 use log::debug;
 use std::fmt::Display;
-use std::ops::RangeInclusive;
+use std::ops::{Range, RangeInclusive};
+use tpchgen::dates::GenerateUtils;
 use tpchgen::generators::{
-    CustomerGenerator, OrderGenerator, PartGenerator, PartSuppGenerator, SupplierGenerator,
+    CustomerGenerator, LineItemGenerator, OrderGenerator, PartGenerator, PartSuppGenerator,
+    SupplierGenerator,
 };
+use tpchgen::ssb::generators as ssb;
+use tpchgen::ssb::SsbTable;
+use tpchgen::table::Table;
 
 /// A list of generator "parts" (data generator chunks, not TPCH parts) for a
 /// single output file.
@@ -56,6 +62,11 @@ pub struct GenerationPlan {
     part_count: i32,
     /// List of parts (1..=part_count)
     part_list: RangeInclusive<i32>,
+    /// Range of the partition rows of the table to generate, split evenly
+    /// between the parts of `part_list`
+    ///
+    /// See [`tpchgen::table::TableGenerator::with_row_range`]
+    row_range: Range<i64>,
 }
 
 pub const DEFAULT_PARQUET_ROW_GROUP_BYTES: i64 = 7 * 1024 * 1024;
@@ -68,7 +79,7 @@ impl GenerationPlan {
     /// * `cli_part_count`: optional total number of parts, `--parts` CLI argument
     /// * `parquet_row_group_size`: optional parquet row group size, `--parquet-row-group-size` CLI argument
     pub fn try_new(
-        table: Table,
+        table: BenchmarkTable,
         format: OutputFormat,
         scale_factor: f64,
         cli_part: Option<i32>,
@@ -103,15 +114,19 @@ impl GenerationPlan {
 
     /// Return true if the tables is unpartitionable (not parameterized by part
     /// count)
-    pub fn partitioned_table(table: Table) -> bool {
-        table != Table::Nation && table != Table::Region
+    pub fn partitioned_table(table: BenchmarkTable) -> bool {
+        !matches!(
+            table,
+            BenchmarkTable::Tpch(Table::Nation | Table::Region)
+                | BenchmarkTable::Ssb(SsbTable::Date)
+        )
     }
 
     /// Returns a new `GenerationPlan` when partitioning
     ///
     /// See [`GenerationPlan::try_new`] for argument documentation.
     fn try_new_with_parts(
-        table: Table,
+        table: BenchmarkTable,
         format: OutputFormat,
         scale_factor: f64,
         cli_part: i32,
@@ -139,6 +154,7 @@ impl GenerationPlan {
             return Ok(Self {
                 part_count: 1,
                 part_list: 1..=1,
+                row_range: 0..table.partition_row_count(scale_factor),
             });
         }
 
@@ -171,12 +187,17 @@ impl GenerationPlan {
         Ok(Self {
             part_count: new_total_parts,
             part_list: new_parts_to_generate,
+            row_range: GenerateUtils::split_range(
+                0..table.partition_row_count(scale_factor),
+                cli_part as i64,
+                cli_part_count as i64,
+            ),
         })
     }
 
     /// Returns a new `GenerationPlan` when no partitioning is specified on the command line
     fn try_new_without_parts(
-        table: Table,
+        table: BenchmarkTable,
         format: OutputFormat,
         scale_factor: f64,
         parquet_row_group_bytes: i64,
@@ -187,23 +208,107 @@ impl GenerationPlan {
         Ok(Self {
             part_count: num_parts,
             part_list: 1..=num_parts,
+            row_range: 0..table.partition_row_count(scale_factor),
+        })
+    }
+
+    /// Returns a new `GenerationPlan` for the partition rows in `row_range`
+    /// of the table
+    ///
+    /// Unlike `--part`, the range can have any size, which allows splitting
+    /// the table into files of a target size.
+    pub fn try_new_with_row_range(
+        table: BenchmarkTable,
+        format: OutputFormat,
+        scale_factor: f64,
+        row_range: Range<i64>,
+        parquet_row_group_bytes: i64,
+    ) -> Result<Self, String> {
+        let partition_row_count = table.partition_row_count(scale_factor);
+        if row_range.start < 0 || row_range.start >= row_range.end {
+            return Err(format!("Invalid row range {row_range:?} for table {table}"));
+        }
+        if row_range.end > partition_row_count {
+            return Err(format!(
+                "Invalid row range {row_range:?}. Table {table} has {partition_row_count} rows"
+            ));
+        }
+
+        let num_parts = OutputSize::new(table, scale_factor, format, parquet_row_group_bytes)
+            .with_row_fraction(row_range.end - row_range.start, partition_row_count)
+            .part_count();
+        debug!(
+            "Generating {num_parts} partitions for rows {row_range:?} of table {table:?} \
+            with scale factor {scale_factor}"
+        );
+
+        Ok(Self {
+            part_count: num_parts,
+            part_list: 1..=num_parts,
+            row_range,
         })
     }
 
+    /// Returns the row ranges of the files of at most `target_file_bytes`
+    /// (estimated) that make up the table
+    ///
+    /// All the files except the last one have the same number of rows.
+    pub fn file_row_ranges(
+        table: BenchmarkTable,
+        format: OutputFormat,
+        scale_factor: f64,
+        target_file_bytes: i64,
+    ) -> Result<Vec<Range<i64>>, String> {
+        if target_file_bytes < 1 {
+            return Err(format!(
+                "Invalid --target-file-bytes. Expected a number greater than zero, got {target_file_bytes}"
+            ));
+        }
+        let partition_row_count = table.partition_row_count(scale_factor);
+        let rows_per_file = if Self::partitioned_table(table) {
+            let table_bytes =
+                OutputSize::new(table, scale_factor, format, DEFAULT_PARQUET_ROW_GROUP_BYTES)
+                    .estimated_table_bytes(table, format);
+            debug!("Estimated size of table {table:?}: {table_bytes} bytes");
+            (partition_row_count as i128 * target_file_bytes as i128 / table_bytes.max(1) as i128)
+                .clamp(1, partition_row_count.max(1) as i128) as i64
+        } else {
+            // nation, region and date are not partitioned
+            partition_row_count
+        };
+        debug!("Splitting table {table:?} into files of {rows_per_file} partition rows");
+
+        Ok((0..partition_row_count)
+            .step_by(rows_per_file as usize)
+            .map(|start| start..(start + rows_per_file).min(partition_row_count))
+            .collect())
+    }
+
+    /// Return the range of the partition rows of the table this plan will
+    /// generate
+    pub fn row_range(&self) -> &Range<i64> {
+        &self.row_range
+    }
+
     /// Return the number of part(ititions) this plan will generate
     pub fn chunk_count(&self) -> usize {
         self.part_list.clone().count()
     }
 }
 
-/// Converts the `GenerationPlan` into an iterator of (part_number, num_parts)
+/// Converts the `GenerationPlan` into an iterator of the row ranges of each
+/// part
+///
+/// The row range of the plan is split evenly between the parts, so
+/// concatenating the parts produces exactly the rows of the plan.
 impl IntoIterator for GenerationPlan {
-    type Item = (i32, i32);
+    type Item = Range<i64>;
     type IntoIter = std::vec::IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.part_list
-            .map(|part_number| (part_number, self.part_count))
+        let chunk_count = self.chunk_count() as i64;
+        (1..=chunk_count)
+            .map(|chunk| GenerateUtils::split_range(self.row_range.clone(), chunk, chunk_count))
             .collect::<Vec<_>>()
             .into_iter()
     }
@@ -230,7 +335,7 @@ struct OutputSize {
 
 impl OutputSize {
     pub fn new(
-        table: Table,
+        table: BenchmarkTable,
         scale_factor: f64,
         format: OutputFormat,
         parquet_row_group_bytes: i64,
@@ -241,29 +346,42 @@ impl OutputSize {
         // this was determined by sampling the data
         let avg_row_size_bytes = match format {
             OutputFormat::Tbl | OutputFormat::Csv => match table {
-                Table::Nation => 88,
-                Table::Region => 77,
-                Table::Part => 115,
-                Table::Supplier => 140,
-                Table::Partsupp => 148,
-                Table::Customer => 160,
-                Table::Orders => 114,
-                Table::Lineitem => 128,
+                BenchmarkTable::Tpch(Table::Nation) => 88,
+                BenchmarkTable::Tpch(Table::Region) => 77,
+                BenchmarkTable::Tpch(Table::Part) => 115,
+                BenchmarkTable::Tpch(Table::Supplier) => 140,
+                BenchmarkTable::Tpch(Table::Partsupp) => 148,
+                BenchmarkTable::Tpch(Table::Customer) => 160,
+                BenchmarkTable::Tpch(Table::Orders) => 114,
+                BenchmarkTable::Tpch(Table::Lineitem) => 128,
+                BenchmarkTable::Ssb(SsbTable::Customer) => 105,
+                BenchmarkTable::Ssb(SsbTable::Supplier) => 93,
+                BenchmarkTable::Ssb(SsbTable::Part) => 85,
+                BenchmarkTable::Ssb(SsbTable::Date) => 89,
+                BenchmarkTable::Ssb(SsbTable::Lineorder) => 100,
             },
             // Average row size in bytes for each table at scale factor 1.0
             // computed using datafusion-cli:
             // ```shell
             // datafusion-cli -c "datafusion-cli -c "select row_group_id, count(*), min(row_group_bytes)::float/min(row_group_num_rows)::float as bytes_per_row from parquet_metadata('lineitem.parquet') GROUP BY 1 ORDER BY 1""
             // ```
+            //
+            // The SSB sizes are estimated from the size of the compressed
+            // files at scale factor 1.0
             OutputFormat::Parquet => match table {
-                Table::Nation => 117,
-                Table::Region => 151,
-                Table::Part => 70,
-                Table::Supplier => 164,
-                Table::Partsupp => 141 * 4, // needed to match observed size
-                Table::Customer => 168,
-                Table::Orders => 75,
-                Table::Lineitem => 64,
+                BenchmarkTable::Tpch(Table::Nation) => 117,
+                BenchmarkTable::Tpch(Table::Region) => 151,
+                BenchmarkTable::Tpch(Table::Part) => 70,
+                BenchmarkTable::Tpch(Table::Supplier) => 164,
+                BenchmarkTable::Tpch(Table::Partsupp) => 141 * 4, // needed to match observed size
+                BenchmarkTable::Tpch(Table::Customer) => 168,
+                BenchmarkTable::Tpch(Table::Orders) => 75,
+                BenchmarkTable::Tpch(Table::Lineitem) => 64,
+                BenchmarkTable::Ssb(SsbTable::Customer) => 90,
+                BenchmarkTable::Ssb(SsbTable::Supplier) => 90,
+                BenchmarkTable::Ssb(SsbTable::Part) => 25,
+                BenchmarkTable::Ssb(SsbTable::Date) => 25,
+                BenchmarkTable::Ssb(SsbTable::Lineorder) => 56,
             },
         };
 
@@ -329,23 +447,84 @@ impl OutputSize {
         }
     }
 
-    fn row_count_for_table(table: Table, scale_factor: f64) -> i64 {
+    /// Scale the row count for the output to `rows` of `total_rows` partition
+    /// rows
+    pub fn with_row_fraction(&self, rows: i64, total_rows: i64) -> OutputSize {
+        let scaled_row_count =
+            (self.row_count as i128 * rows as i128 / total_rows.max(1) as i128) as i64;
+        debug!(
+            "Scaling row count from {} to {scaled_row_count}",
+            self.row_count,
+        );
+        OutputSize {
+            avg_row_size_bytes: self.avg_row_size_bytes,
+            row_count: scaled_row_count,
+            target_chunk_size_bytes: self.target_chunk_size_bytes,
+            max_part_count: self.max_part_count,
+        }
+    }
+
+    /// Returns the estimated size of the whole table in bytes
+    pub fn estimated_table_bytes(&self, table: BenchmarkTable, format: OutputFormat) -> i64 {
+        let bytes = self.row_count * self.avg_row_size_bytes;
+        match (table, format) {
+            // the partsupp row count is the number of parts, but the tbl/csv
+            // average row size is per partsupp row
+            (BenchmarkTable::Tpch(Table::Partsupp), OutputFormat::Tbl | OutputFormat::Csv) => {
+                bytes * PartSuppGenerator::SUPPLIERS_PER_PART as i64
+            }
+            _ => bytes,
+        }
+    }
+
+    /// Largest number of orders for which the exact lineitem row count is
+    /// computed (SF=100, about 2 seconds).
+    ///
+    /// [`LineItemGenerator::calculate_row_count`] replays the line count of
+    /// every order, which is too slow to run before generating larger tables.
+    const EXACT_LINEITEM_COUNT_MAX_ORDERS: i64 = 150_000_000;
+
+    fn row_count_for_table(table: BenchmarkTable, scale_factor: f64) -> i64 {
         //let (avg_row_size_bytes, row_count) = match table {
         match table {
-            Table::Nation => 1,
-            Table::Region => 1,
-            Table::Part => PartGenerator::calculate_row_count(scale_factor, 1, 1),
-            Table::Supplier => SupplierGenerator::calculate_row_count(scale_factor, 1, 1),
-            Table::Partsupp => PartSuppGenerator::calculate_row_count(scale_factor, 1, 1),
-            Table::Customer => CustomerGenerator::calculate_row_count(scale_factor, 1, 1),
-            Table::Orders => OrderGenerator::calculate_row_count(scale_factor, 1, 1),
-            Table::Lineitem => {
-                // there are on average 4 line items per order.
-                // For example, in SF=10,
-                // * orders has 15,000,000 rows
-                // * lineitem has around 60,000,000 rows
-                4 * OrderGenerator::calculate_row_count(scale_factor, 1, 1)
+            BenchmarkTable::Tpch(Table::Nation) => 1,
+            BenchmarkTable::Tpch(Table::Region) => 1,
+            BenchmarkTable::Tpch(Table::Part) => {
+                PartGenerator::calculate_row_count(scale_factor, 1, 1)
+            }
+            BenchmarkTable::Tpch(Table::Supplier) => {
+                SupplierGenerator::calculate_row_count(scale_factor, 1, 1)
+            }
+            BenchmarkTable::Tpch(Table::Partsupp) => {
+                PartSuppGenerator::calculate_row_count(scale_factor, 1, 1)
             }
+            BenchmarkTable::Tpch(Table::Customer) => {
+                CustomerGenerator::calculate_row_count(scale_factor, 1, 1)
+            }
+            BenchmarkTable::Tpch(Table::Orders) => {
+                OrderGenerator::calculate_row_count(scale_factor, 1, 1)
+            }
+            BenchmarkTable::Tpch(Table::Lineitem) => {
+                let order_count = OrderGenerator::calculate_row_count(scale_factor, 1, 1);
+                if order_count <= Self::EXACT_LINEITEM_COUNT_MAX_ORDERS {
+                    LineItemGenerator::calculate_row_count(scale_factor, 1, 1)
+                } else {
+                    // there are on average 4 line items per order, and the
+                    // estimate is within 0.01% of the exact count for such
+                    // large tables.
+                    4 * order_count
+                }
+            }
+            BenchmarkTable::Ssb(SsbTable::Lineorder) => {
+                // LINEORDER has the same rows as LINEITEM
+                let order_count = ssb::LineOrderGenerator::order_count(scale_factor);
+                if order_count <= Self::EXACT_LINEITEM_COUNT_MAX_ORDERS {
+                    ssb::LineOrderGenerator::calculate_row_count(scale_factor, 1, 1)
+                } else {
+                    4 * order_count
+                }
+            }
+            BenchmarkTable::Ssb(table) => table.row_count(scale_factor, 1, 1),
         }
     }
 }
@@ -751,7 +930,7 @@ mod tests {
                     .with_format(OutputFormat::Parquet)
                     .with_scale_factor(10.0)
                     .with_parquet_row_group_bytes(1024 * 1024) // 1MB row groups
-                    .assert(3663, 1..=3663);
+                    .assert(3662, 1..=3662);
             }
 
             #[test]
@@ -777,10 +956,100 @@ mod tests {
         }
     }
 
+    // Row ranges generated for each part and file
+    mod row_ranges {
+        use super::*;
+
+        #[test]
+        fn cli_parts_are_contiguous() {
+            // 150000 orders are not divisible by 7 * the number of chunks
+            let mut next_row = 0;
+            for part in 1..=7 {
+                let plan = GenerationPlan::try_new(
+                    Table::Orders.into(),
+                    OutputFormat::Parquet,
+                    1.0,
+                    Some(part),
+                    Some(7),
+                    1024 * 1024,
+                )
+                .unwrap();
+                assert!(plan.chunk_count() > 1);
+                for row_range in plan {
+                    assert_eq!(row_range.start, next_row);
+                    next_row = row_range.end;
+                }
+            }
+            assert_eq!(next_row, 1_500_000);
+        }
+
+        #[test]
+        fn file_row_ranges() {
+            let ranges = GenerationPlan::file_row_ranges(
+                Table::Orders.into(),
+                OutputFormat::Tbl,
+                1.0,
+                40_000_000,
+            )
+            .unwrap();
+            assert_eq!(
+                ranges,
+                vec![
+                    0..350_877,
+                    350_877..701_754,
+                    701_754..1_052_631,
+                    1_052_631..1_403_508,
+                    1_403_508..1_500_000
+                ]
+            );
+            for row_range in ranges {
+                let plan = GenerationPlan::try_new_with_row_range(
+                    Table::Orders.into(),
+                    OutputFormat::Tbl,
+                    1.0,
+                    row_range.clone(),
+                    DEFAULT_PARQUET_ROW_GROUP_BYTES,
+                )
+                .unwrap();
+                let chunks: Vec<_> = plan.into_iter().collect();
+                assert_eq!(chunks.first().unwrap().start, row_range.start);
+                assert_eq!(chunks.last().unwrap().end, row_range.end);
+            }
+
+            // nation and region are never split
+            assert_eq!(
+                GenerationPlan::file_row_ranges(Table::Nation.into(), OutputFormat::Tbl, 1.0, 100)
+                    .unwrap(),
+                vec![0..25]
+            );
+        }
+
+        #[test]
+        fn invalid_row_ranges() {
+            for row_range in [-1..10, 10..10, 0..1_500_001] {
+                assert!(GenerationPlan::try_new_with_row_range(
+                    Table::Orders.into(),
+                    OutputFormat::Tbl,
+                    1.0,
+                    row_range,
+                    DEFAULT_PARQUET_ROW_GROUP_BYTES,
+                )
+                .is_err());
+            }
+            assert_eq!(
+                GenerationPlan::file_row_ranges(Table::Orders.into(), OutputFormat::Tbl, 1.0, 0),
+                Err(
+                    "Invalid --target-file-bytes. Expected a number greater than zero, got 0"
+                        .to_string()
+                )
+            );
+        }
+    }
+
     /// Test fixture for [`GenerationPlan`].
     #[derive(Debug)]
     struct Test {
-        table: Table,
+        table: BenchmarkTable,
         format: OutputFormat,
         scale_factor: f64,
         cli_part: Option<i32>,
@@ -824,8 +1093,8 @@ mod tests {
         }
 
         /// Set table
-        fn with_table(mut self, table: Table) -> Self {
-            self.table = table;
+        fn with_table(mut self, table: impl Into<BenchmarkTable>) -> Self {
+            self.table = table.into();
             self
         }
 
@@ -863,7 +1132,7 @@ mod tests {
     impl Default for Test {
         fn default() -> Self {
             Self {
-                table: Table::Orders,
+                table: Table::Orders.into(),
                 format: OutputFormat::Tbl,
                 scale_factor: 1.0,
                 cli_part: None,
@@ -0,0 +1,89 @@
+//! Implementation of the `qgen` subcommand: prints the TPC-H queries with
+//! their parameters substituted, similarly to the `qgen` tool of the TPC-H
+//! Tools distribution.
+
+use clap::Args;
+use std::io::{self, Write};
+use tpchgen::dates::DateRange;
+use tpchgen::q_and_a::dialect::{Dialect, Q15Variant, QueryRenderer};
+use tpchgen::q_and_a::metrics::stream_queries;
+use tpchgen::q_and_a::parameters::{QueryParameterGenerator, QueryParameters};
+
+/// Arguments of the `qgen` subcommand
+#[derive(Args, Debug)]
+pub struct QgenArgs {
+    /// Queries to print (1 to 22, default: all)
+    queries: Vec<i32>,
+
+    /// Seed used to generate the random query parameters
+    #[arg(short = 'r', long, default_value_t = 0)]
+    seed: i64,
+
+    /// Use the validation parameters of the TPC-H specification instead of
+    /// random parameters (like `qgen -d`), with the Q11 fraction of the scale
+    /// factor
+    #[arg(short = 'd', long, default_value_t = false)]
+    validation: bool,
+
+    /// Stream number used to name the view created by Q15 (and to order the
+    /// queries with `--permute`)
+    #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(i32).range(0..))]
+    stream: i32,
+
+    /// Print all the queries in the order of the stream of the TPC-H
+    /// performance test (stream 0 is the power test) instead of 1 to 22
+    #[arg(short = 'p', long, default_value_t = false, conflicts_with = "queries")]
+    permute: bool,
+
+    /// SQL dialect of the queries: ansi (the specification text), postgres,
+    /// duckdb, spark or clickhouse
+    #[arg(long, default_value_t = Dialect::default())]
+    dialect: Dialect,
+
+    /// Print Q15 with a common table expression instead of creating and
+    /// dropping a view (an approved variant of the query)
+    #[arg(long, default_value_t = false)]
+    q15_cte: bool,
+}
+
+impl QgenArgs {
+    /// Writes the substituted queries to `out`, with the dates of the
+    /// queries moved to `date_range`
+    pub fn run(
+        &self,
+        scale_factor: f64,
+        date_range: DateRange,
+        out: &mut impl Write,
+    ) -> io::Result<()> {
+        let queries = if self.permute {
+            stream_queries(self.stream as usize).to_vec()
+        } else if self.queries.is_empty() {
+            (1..=22).collect()
+        } else {
+            self.queries.clone()
+        };
+
+        let generator = QueryParameterGenerator::new(scale_factor, self.seed);
+        let renderer = QueryRenderer::new(self.dialect).with_q15_variant(if self.q15_cte {
+            Q15Variant::Cte
+        } else {
+            Q15Variant::View
+        });
+        for query_number in queries {
+            let parameters = if self.validation {
+                QueryParameters::validation_for_scale_factor(query_number, scale_factor)
+            } else {
+                generator.generate(query_number)
+            }
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            let parameters = parameters
+                .with_stream(self.stream)
+                .with_date_range(date_range);
+
+            writeln!(out, "-- TPC-H Query {query_number}")?;
+            writeln!(out, "{}", renderer.render(&parameters).trim())?;
+            writeln!(out)?;
+        }
+        out.flush()
+    }
+}
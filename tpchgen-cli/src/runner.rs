@@ -1,20 +1,29 @@
 //! [`PlanRunner`] for running [`OutputPlan`]s.
 
+use crate::benchmark::BenchmarkTable;
 use crate::csv::*;
-use crate::generate::{generate_in_chunks, Source};
+use crate::generate::{generate_in_chunks, generate_pairs_in_chunks, PairSource, Source};
 use crate::output_plan::{OutputLocation, OutputPlan};
 use crate::parquet::generate_parquet;
 use crate::tbl::*;
 use crate::tbl::{LineItemTblSource, NationTblSource, RegionTblSource};
-use crate::{OutputFormat, Table, WriterSink};
+use crate::{GenerationPlan, OutputFormat, WriterSink};
 use log::{debug, info};
+use std::fmt::{Display, Formatter};
+use std::fs::File;
 use std::io;
 use std::io::BufWriter;
+use std::path::{Path, PathBuf};
 use tokio::task::{JoinError, JoinSet};
+use tpchgen::dates::DateRange;
 use tpchgen::generators::{
-    CustomerGenerator, LineItemGenerator, NationGenerator, OrderGenerator, PartGenerator,
-    PartSuppGenerator, RegionGenerator, SupplierGenerator,
+    CustomerGenerator, LineItemGenerator, NationGenerator, OrderGenerator, OrderLineItemGenerator,
+    PartGenerator, PartSuppGenerator, RefreshGenerator, RegionGenerator, SupplierGenerator,
 };
+use tpchgen::skew::Skew;
+use tpchgen::ssb::generators as ssb;
+use tpchgen::ssb::SsbTable;
+use tpchgen::table::{Table, TableGenerator};
 use tpchgen_arrow::{
     CustomerArrow, LineItemArrow, NationArrow, OrderArrow, PartArrow, PartSuppArrow,
     RecordBatchIterator, RegionArrow, SupplierArrow,
@@ -41,10 +50,10 @@ impl PlanRunner {
             self.plans.len(),
             self.num_threads
         );
-        let Self {
-            mut plans,
-            num_threads,
-        } = self;
+        let Self { plans, num_threads } = self;
+
+        // Generate ORDERS and LINEITEM together when possible
+        let mut plans = pair_order_line_item_plans(plans);
 
         // Sort the plans by the number of parts so the largest are first
         plans.sort_unstable_by(|a, b| {
@@ -62,6 +71,88 @@ impl PlanRunner {
     }
 }
 
+/// A unit of work scheduled by the [`PlanRunner`]
+#[derive(Debug)]
+enum ScheduledPlan {
+    /// Generate a single output
+    Single(OutputPlan),
+    /// Generate the ORDERS and LINEITEM outputs for the same orders in a
+    /// single pass
+    OrdersAndLineItems {
+        orders: OutputPlan,
+        line_items: OutputPlan,
+    },
+}
+
+impl ScheduledPlan {
+    /// Return the number of chunks of the plan
+    fn chunk_count(&self) -> usize {
+        match self {
+            Self::Single(plan) => plan.chunk_count(),
+            // the chunks are those of LINEITEM, the bigger table
+            Self::OrdersAndLineItems { line_items, .. } => line_items.chunk_count(),
+        }
+    }
+}
+
+impl Display for ScheduledPlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Single(plan) => write!(f, "{plan}"),
+            Self::OrdersAndLineItems { orders, line_items } => {
+                write!(f, "{orders} and {line_items}")
+            }
+        }
+    }
+}
+
+/// Pairs each ORDERS plan with the LINEITEM plan for the same orders
+///
+/// Generating the line items of an order requires generating the order, and
+/// generating an order requires simulating its line items, so it is faster to
+/// generate both tables in a single pass with [`OrderLineItemGenerator`].
+///
+/// Only TBL and CSV outputs to new files are paired. Other plans, such as
+/// ORDERS and LINEITEM files with different rows when using
+/// `--target-file-bytes`, are generated separately.
+fn pair_order_line_item_plans(plans: Vec<OutputPlan>) -> Vec<ScheduledPlan> {
+    let pairable = |plan: &OutputPlan| {
+        matches!(plan.output_format(), OutputFormat::Tbl | OutputFormat::Csv)
+            && matches!(plan.output_location(), OutputLocation::File(path) if !path.exists())
+    };
+
+    let (mut orders, plans): (Vec<_>, Vec<_>) = plans
+        .into_iter()
+        .partition(|plan| plan.table() == BenchmarkTable::Tpch(Table::Orders) && pairable(plan));
+
+    let mut scheduled_plans: Vec<_> = plans
+        .into_iter()
+        .map(|plan| {
+            if plan.table() != BenchmarkTable::Tpch(Table::Lineitem) || !pairable(&plan) {
+                return ScheduledPlan::Single(plan);
+            }
+            let matching = orders.iter().position(|orders_plan| {
+                orders_plan.output_format() == plan.output_format()
+                    && orders_plan.scale_factor() == plan.scale_factor()
+                    && orders_plan.seed() == plan.seed()
+                    && orders_plan.skew() == plan.skew()
+                    && orders_plan.date_range() == plan.date_range()
+                    && orders_plan.generation_plan().row_range()
+                        == plan.generation_plan().row_range()
+            });
+            match matching {
+                Some(index) => ScheduledPlan::OrdersAndLineItems {
+                    orders: orders.remove(index),
+                    line_items: plan,
+                },
+                None => ScheduledPlan::Single(plan),
+            }
+        })
+        .collect();
+    scheduled_plans.extend(orders.into_iter().map(ScheduledPlan::Single));
+    scheduled_plans
+}
+
 /// Manages worker tasks, limiting the number of total outstanding threads
 /// to some fixed number
 ///
@@ -102,7 +193,7 @@ impl WorkerQueue {
     /// Note this algorithm does not guarantee that all threads are always busy,
     /// but it should be good enough for most cases. For best thread utilization
     /// spawn the largest plans first.
-    pub async fn schedule_plan(&mut self, plan: OutputPlan) -> io::Result<()> {
+    pub async fn schedule_plan(&mut self, plan: ScheduledPlan) -> io::Result<()> {
         debug!("scheduling plan {plan}");
         loop {
             if self.available_threads == 0 {
@@ -159,28 +250,51 @@ fn task_result<T>(result: Result<io::Result<T>, JoinError>) -> io::Result<T> {
     result.map_err(|e| io::Error::other(format!("Task Panic: {e}")))?
 }
 
+/// Run a single [`ScheduledPlan`]
+async fn run_plan(plan: ScheduledPlan, num_threads: usize) -> io::Result<usize> {
+    match plan {
+        ScheduledPlan::Single(plan) => run_output_plan(plan, num_threads).await,
+        ScheduledPlan::OrdersAndLineItems { orders, line_items } => {
+            run_orders_and_line_items_plan(orders, line_items, num_threads).await
+        }
+    }
+}
+
 /// Run a single [`OutputPlan`]
-async fn run_plan(plan: OutputPlan, num_threads: usize) -> io::Result<usize> {
+async fn run_output_plan(plan: OutputPlan, num_threads: usize) -> io::Result<usize> {
     match plan.table() {
-        Table::Nation => run_nation_plan(plan, num_threads).await,
-        Table::Region => run_region_plan(plan, num_threads).await,
-        Table::Part => run_part_plan(plan, num_threads).await,
-        Table::Supplier => run_supplier_plan(plan, num_threads).await,
-        Table::Partsupp => run_partsupp_plan(plan, num_threads).await,
-        Table::Customer => run_customer_plan(plan, num_threads).await,
-        Table::Orders => run_orders_plan(plan, num_threads).await,
-        Table::Lineitem => run_lineitem_plan(plan, num_threads).await,
+        BenchmarkTable::Tpch(table) => match table {
+            Table::Nation => run_nation_plan(plan, num_threads).await,
+            Table::Region => run_region_plan(plan, num_threads).await,
+            Table::Part => run_part_plan(plan, num_threads).await,
+            Table::Supplier => run_supplier_plan(plan, num_threads).await,
+            Table::Partsupp => run_partsupp_plan(plan, num_threads).await,
+            Table::Customer => run_customer_plan(plan, num_threads).await,
+            Table::Orders => run_orders_plan(plan, num_threads).await,
+            Table::Lineitem => run_lineitem_plan(plan, num_threads).await,
+        },
+        BenchmarkTable::Ssb(table) => match table {
+            SsbTable::Customer => run_ssb_customer_plan(plan, num_threads).await,
+            SsbTable::Supplier => run_ssb_supplier_plan(plan, num_threads).await,
+            SsbTable::Part => run_ssb_part_plan(plan, num_threads).await,
+            SsbTable::Date => run_ssb_date_plan(plan, num_threads).await,
+            SsbTable::Lineorder => run_ssb_lineorder_plan(plan, num_threads).await,
+        },
     }
 }
 
 /// Writes a CSV/TSV output from the sources
-async fn write_file<I>(plan: OutputPlan, num_threads: usize, sources: I) -> Result<(), io::Error>
+async fn write_file<I>(
+    output_location: &OutputLocation,
+    num_threads: usize,
+    sources: I,
+) -> Result<(), io::Error>
 where
     I: Iterator<Item: Source> + 'static,
 {
     // Since generate_in_chunks already buffers, there is no need to buffer
     // again (aka don't use BufWriter here)
-    match plan.output_location() {
+    match output_location {
         OutputLocation::Stdout => {
             let sink = WriterSink::new(io::stdout());
             generate_in_chunks(sink, sources, num_threads).await
@@ -192,21 +306,197 @@ where
                 return Ok(());
             }
             // write to a temp file and then rename to avoid partial files
-            let temp_path = path.with_extension("inprogress");
-            let file = std::fs::File::create(&temp_path).map_err(|err| {
-                io::Error::other(format!("Failed to create {temp_path:?}: {err}"))
-            })?;
+            let (temp_path, file) = create_temp_file(path)?;
             let sink = WriterSink::new(file);
             generate_in_chunks(sink, sources, num_threads).await?;
-            // rename the temp file to the final path
-            std::fs::rename(&temp_path, path).map_err(|e| {
-                io::Error::other(format!(
-                    "Failed to rename {temp_path:?} to {path:?} file: {e}"
-                ))
-            })?;
-            Ok(())
+            rename_temp_file(&temp_path, path)
+        }
+    }
+}
+
+/// Writes two CSV/TSV output files from the sources
+///
+/// Unlike [`write_file`], the outputs are always files, and are generated
+/// even if they already exist.
+async fn write_file_pair<I>(
+    (first_path, second_path): (&Path, &Path),
+    num_threads: usize,
+    sources: I,
+) -> Result<(), io::Error>
+where
+    I: Iterator<Item: PairSource> + 'static,
+{
+    // write to temp files and then rename to avoid partial files
+    let (first_temp_path, first_file) = create_temp_file(first_path)?;
+    let (second_temp_path, second_file) = create_temp_file(second_path)?;
+    let first_sink = WriterSink::new(first_file);
+    let second_sink = WriterSink::new(second_file);
+    generate_pairs_in_chunks(first_sink, second_sink, sources, num_threads).await?;
+    rename_temp_file(&first_temp_path, first_path)?;
+    rename_temp_file(&second_temp_path, second_path)
+}
+
+/// Creates the temp file to write to before renaming it to `path`
+fn create_temp_file(path: &Path) -> io::Result<(PathBuf, File)> {
+    let temp_path = path.with_extension("inprogress");
+    let file = File::create(&temp_path)
+        .map_err(|err| io::Error::other(format!("Failed to create {temp_path:?}: {err}")))?;
+    Ok((temp_path, file))
+}
+
+/// Renames the temp file to the final path
+fn rename_temp_file(temp_path: &Path, path: &Path) -> io::Result<()> {
+    std::fs::rename(temp_path, path).map_err(|e| {
+        io::Error::other(format!(
+            "Failed to rename {temp_path:?} to {path:?} file: {e}"
+        ))
+    })
+}
+
+/// Run an ORDERS and a LINEITEM [`OutputPlan`] for the same orders in a
+/// single pass
+///
+/// See [`pair_order_line_item_plans`]
+async fn run_orders_and_line_items_plan(
+    orders: OutputPlan,
+    line_items: OutputPlan,
+    num_threads: usize,
+) -> io::Result<usize> {
+    info!("Writing {orders} and {line_items} using {num_threads} threads");
+    let (OutputLocation::File(orders_path), OutputLocation::File(line_items_path)) =
+        (orders.output_location(), line_items.output_location())
+    else {
+        return Err(io::Error::other(
+            "Internal Error: ORDERS and LINEITEM can only be generated together to files",
+        ));
+    };
+    let paths = (orders_path.as_path(), line_items_path.as_path());
+
+    /// Interior functions to tell the compiler the lifetime is 'static (see
+    /// `define_run`)
+    fn tbl_sources(
+        generation_plan: &GenerationPlan,
+        scale_factor: f64,
+        seed: u64,
+        skew: Skew,
+        date_range: DateRange,
+    ) -> impl Iterator<Item: PairSource> + 'static {
+        generation_plan
+            .clone()
+            .into_iter()
+            .map(move |row_range| {
+                OrderLineItemGenerator::new(scale_factor, 1, 1)
+                    .with_row_range(row_range)
+                    .with_seed(seed)
+                    .with_skew(skew)
+                    .with_date_range(date_range)
+            })
+            .map(OrderLineItemTblSource::new)
+    }
+
+    fn csv_sources(
+        generation_plan: &GenerationPlan,
+        scale_factor: f64,
+        seed: u64,
+        skew: Skew,
+        date_range: DateRange,
+    ) -> impl Iterator<Item: PairSource> + 'static {
+        generation_plan
+            .clone()
+            .into_iter()
+            .map(move |row_range| {
+                OrderLineItemGenerator::new(scale_factor, 1, 1)
+                    .with_row_range(row_range)
+                    .with_seed(seed)
+                    .with_skew(skew)
+                    .with_date_range(date_range)
+            })
+            .map(OrderLineItemCsvSource::new)
+    }
+
+    // the chunks are those of LINEITEM, the bigger table
+    let scale_factor = line_items.scale_factor();
+    let seed = line_items.seed();
+    let skew = line_items.skew();
+    let date_range = line_items.date_range();
+    match line_items.output_format() {
+        OutputFormat::Tbl => {
+            let sources = tbl_sources(
+                line_items.generation_plan(),
+                scale_factor,
+                seed,
+                skew,
+                date_range,
+            );
+            write_file_pair(paths, num_threads, sources).await?
         }
+        OutputFormat::Csv => {
+            let sources = csv_sources(
+                line_items.generation_plan(),
+                scale_factor,
+                seed,
+                skew,
+                date_range,
+            );
+            write_file_pair(paths, num_threads, sources).await?
+        }
+        OutputFormat::Parquet => {
+            return Err(io::Error::other(
+                "Internal Error: ORDERS and LINEITEM can not be generated together to parquet",
+            ));
+        }
+    };
+    Ok(num_threads)
+}
+
+/// Writes the refresh function files for update streams `1..=update_count`
+///
+/// Each update stream `<n>` is written to three files in `output_dir`, named
+/// like the files created by `dbgen -U`:
+///
+/// * `orders.tbl.u<n>`: orders inserted by RF1
+/// * `lineitem.tbl.u<n>`: line items inserted by RF1
+/// * `delete.<n>`: keys of the orders deleted by RF2
+pub async fn write_updates(
+    output_dir: &Path,
+    scale_factor: f64,
+    seed: u64,
+    skew: Skew,
+    date_range: DateRange,
+    update_count: i32,
+    stdout: bool,
+) -> io::Result<()> {
+    for update_stream in 1..=update_count {
+        let generator = RefreshGenerator::new(scale_factor, update_stream)
+            .with_seed(seed)
+            .with_skew(skew)
+            .with_date_range(date_range);
+        let location = |file_name: String| {
+            if stdout {
+                OutputLocation::Stdout
+            } else {
+                OutputLocation::File(output_dir.join(file_name))
+            }
+        };
+
+        // Each stream is small (0.1% of the orders), so write each file with
+        // a single chunk
+        let orders = location(format!("orders.tbl.u{update_stream}"));
+        info!("Writing update stream {update_stream} orders to {orders}");
+        let sources = std::iter::once(RefreshOrderTblSource::new(generator.clone()));
+        write_file(&orders, 1, sources).await?;
+
+        let line_items = location(format!("lineitem.tbl.u{update_stream}"));
+        info!("Writing update stream {update_stream} line items to {line_items}");
+        let sources = std::iter::once(RefreshLineItemTblSource::new(generator.clone()));
+        write_file(&line_items, 1, sources).await?;
+
+        let deletes = location(format!("delete.{update_stream}"));
+        info!("Writing update stream {update_stream} deletes to {deletes}");
+        let sources = std::iter::once(RefreshDeleteTblSource::new(generator));
+        write_file(&deletes, 1, sources).await?;
     }
+    Ok(())
 }
 
 /// Generates an output parquet file from the sources
@@ -251,11 +541,37 @@ where
 /// $TBL_SOURCE: The [`Source`] type to use for TBL format
 /// $CSV_SOURCE: The [`Source`] type to use for CSV format
 /// $PARQUET_SOURCE: The [`RecordBatchIterator`] type to use for Parquet format
+///
+/// Instead of a generator type, `$GENERATOR` can be an expression creating
+/// the generator of the rows in `$ROW_RANGE`, written like a closure:
+/// `|scale_factor, seed, skew, date_range, row_range| ...`
 macro_rules! define_run {
     ($FUN_NAME:ident, $GENERATOR:ident, $TBL_SOURCE:ty, $CSV_SOURCE:ty, $PARQUET_SOURCE:ty) => {
+        define_run!(
+            $FUN_NAME,
+            |scale_factor, seed, skew, date_range, row_range| $GENERATOR::new(scale_factor, 1, 1)
+                .with_row_range(row_range)
+                .with_seed(seed)
+                .with_skew(skew)
+                .with_date_range(date_range),
+            $TBL_SOURCE,
+            $CSV_SOURCE,
+            $PARQUET_SOURCE
+        );
+    };
+    (
+        $FUN_NAME:ident,
+        |$SCALE_FACTOR:ident, $SEED:ident, $SKEW:ident, $DATE_RANGE:ident, $ROW_RANGE:ident|
+            $GENERATOR:expr,
+        $TBL_SOURCE:ty,
+        $CSV_SOURCE:ty,
+        $PARQUET_SOURCE:ty
+    ) => {
         async fn $FUN_NAME(plan: OutputPlan, num_threads: usize) -> io::Result<usize> {
-            use crate::GenerationPlan;
             let scale_factor = plan.scale_factor();
+            let seed = plan.seed();
+            let skew = plan.skew();
+            let date_range = plan.date_range();
             info!("Writing {plan} using {num_threads} threads");
 
             /// These interior functions are used to tell the compiler that the lifetime is 'static
@@ -268,49 +584,66 @@ macro_rules! define_run {
             ///                 | |______________^ implementation of `FnOnce` is not general enough
             fn tbl_sources(
                 generation_plan: &GenerationPlan,
-                scale_factor: f64,
+                $SCALE_FACTOR: f64,
+                $SEED: u64,
+                $SKEW: Skew,
+                $DATE_RANGE: DateRange,
             ) -> impl Iterator<Item: Source> + 'static {
                 generation_plan
                     .clone()
                     .into_iter()
-                    .map(move |(part, num_parts)| $GENERATOR::new(scale_factor, part, num_parts))
+                    .map(move |$ROW_RANGE| $GENERATOR)
                     .map(<$TBL_SOURCE>::new)
             }
 
             fn csv_sources(
                 generation_plan: &GenerationPlan,
-                scale_factor: f64,
+                $SCALE_FACTOR: f64,
+                $SEED: u64,
+                $SKEW: Skew,
+                $DATE_RANGE: DateRange,
             ) -> impl Iterator<Item: Source> + 'static {
                 generation_plan
                     .clone()
                     .into_iter()
-                    .map(move |(part, num_parts)| $GENERATOR::new(scale_factor, part, num_parts))
+                    .map(move |$ROW_RANGE| $GENERATOR)
                     .map(<$CSV_SOURCE>::new)
             }
 
             fn parquet_sources(
                 generation_plan: &GenerationPlan,
-                scale_factor: f64,
+                $SCALE_FACTOR: f64,
+                $SEED: u64,
+                $SKEW: Skew,
+                $DATE_RANGE: DateRange,
             ) -> impl Iterator<Item: RecordBatchIterator> + 'static {
                 generation_plan
                     .clone()
                     .into_iter()
-                    .map(move |(part, num_parts)| $GENERATOR::new(scale_factor, part, num_parts))
+                    .map(move |$ROW_RANGE| $GENERATOR)
                     .map(<$PARQUET_SOURCE>::new)
             }
 
             // Dispach to the appropriate output format
             match plan.output_format() {
                 OutputFormat::Tbl => {
-                    let gens = tbl_sources(plan.generation_plan(), scale_factor);
-                    write_file(plan, num_threads, gens).await?
+                    let gens =
+                        tbl_sources(plan.generation_plan(), scale_factor, seed, skew, date_range);
+                    write_file(plan.output_location(), num_threads, gens).await?
                 }
                 OutputFormat::Csv => {
-                    let gens = csv_sources(plan.generation_plan(), scale_factor);
-                    write_file(plan, num_threads, gens).await?
+                    let gens =
+                        csv_sources(plan.generation_plan(), scale_factor, seed, skew, date_range);
+                    write_file(plan.output_location(), num_threads, gens).await?
                 }
                 OutputFormat::Parquet => {
-                    let gens = parquet_sources(plan.generation_plan(), scale_factor);
+                    let gens = parquet_sources(
+                        plan.generation_plan(),
+                        scale_factor,
+                        seed,
+                        skew,
+                        date_range,
+                    );
                     write_parquet(plan, num_threads, gens).await?
                 }
             };
@@ -381,3 +714,68 @@ define_run!(
     OrderCsvSource,
     OrderArrow
 );
+
+define_run!(
+    run_ssb_customer_plan,
+    |scale_factor, seed, _skew, _date_range, row_range| ssb::CustomerGenerator::new(
+        scale_factor,
+        1,
+        1
+    )
+    .with_row_range(row_range)
+    .with_seed(seed),
+    SsbCustomerTblSource,
+    SsbCustomerCsvSource,
+    tpchgen_arrow::ssb::CustomerArrow
+);
+
+define_run!(
+    run_ssb_supplier_plan,
+    |scale_factor, seed, _skew, _date_range, row_range| ssb::SupplierGenerator::new(
+        scale_factor,
+        1,
+        1
+    )
+    .with_row_range(row_range)
+    .with_seed(seed),
+    SsbSupplierTblSource,
+    SsbSupplierCsvSource,
+    tpchgen_arrow::ssb::SupplierArrow
+);
+
+define_run!(
+    run_ssb_part_plan,
+    |scale_factor, seed, _skew, _date_range, row_range| ssb::PartGenerator::new(scale_factor, 1, 1)
+        .with_row_range(row_range)
+        .with_seed(seed),
+    SsbPartTblSource,
+    SsbPartCsvSource,
+    tpchgen_arrow::ssb::PartArrow
+);
+
+define_run!(
+    run_ssb_date_plan,
+    |scale_factor, _seed, _skew, _date_range, row_range| ssb::DateGenerator::new(
+        scale_factor,
+        1,
+        1
+    )
+    .with_row_range(row_range),
+    SsbDateTblSource,
+    SsbDateCsvSource,
+    tpchgen_arrow::ssb::DateArrow
+);
+
+define_run!(
+    run_ssb_lineorder_plan,
+    |scale_factor, seed, _skew, _date_range, row_range| ssb::LineOrderGenerator::new(
+        scale_factor,
+        1,
+        1
+    )
+    .with_row_range(row_range)
+    .with_seed(seed),
+    SsbLineOrderTblSource,
+    SsbLineOrderCsvSource,
+    tpchgen_arrow::ssb::LineOrderArrow
+);
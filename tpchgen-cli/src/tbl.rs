@@ -1,15 +1,22 @@
 //! Implementations of [`Source`] for generating data in TBL format
 
-use super::generate::Source;
+use super::generate::{PairSource, Source};
 use std::io::Write;
 use tpchgen::generators::{
-    CustomerGenerator, LineItemGenerator, NationGenerator, OrderGenerator, PartGenerator,
-    PartSuppGenerator, RegionGenerator, SupplierGenerator,
+    CustomerGenerator, LineItemGenerator, NationGenerator, OrderGenerator, OrderLineItemGenerator,
+    PartGenerator, PartSuppGenerator, RefreshGenerator, RegionGenerator, SupplierGenerator,
 };
+use tpchgen::ssb::generators as ssb;
 
 /// Define a Source that writes the table in TBL format
+///
+/// The rows are produced by calling `$ITER_METHOD` (`iter` by default) on the
+/// generator.
 macro_rules! define_tbl_source {
     ($SOURCE_NAME:ident, $GENERATOR_TYPE:ty) => {
+        define_tbl_source!($SOURCE_NAME, $GENERATOR_TYPE, iter);
+    };
+    ($SOURCE_NAME:ident, $GENERATOR_TYPE:ty, $ITER_METHOD:ident) => {
         pub struct $SOURCE_NAME {
             inner: $GENERATOR_TYPE,
         }
@@ -27,7 +34,7 @@ macro_rules! define_tbl_source {
             }
 
             fn create(self, mut buffer: Vec<u8>) -> Vec<u8> {
-                for item in self.inner.iter() {
+                for item in self.inner.$ITER_METHOD() {
                     // The default Display impl writes TBL format
                     writeln!(&mut buffer, "{item}").expect("writing to memory is infallible");
                 }
@@ -46,3 +53,48 @@ define_tbl_source!(PartSuppTblSource, PartSuppGenerator<'static>);
 define_tbl_source!(CustomerTblSource, CustomerGenerator<'static>);
 define_tbl_source!(OrderTblSource, OrderGenerator<'static>);
 define_tbl_source!(LineItemTblSource, LineItemGenerator<'static>);
+
+// Define .tbl sources for the SSB tables
+define_tbl_source!(SsbCustomerTblSource, ssb::CustomerGenerator<'static>);
+define_tbl_source!(SsbSupplierTblSource, ssb::SupplierGenerator<'static>);
+define_tbl_source!(SsbPartTblSource, ssb::PartGenerator<'static>);
+define_tbl_source!(SsbDateTblSource, ssb::DateGenerator);
+define_tbl_source!(SsbLineOrderTblSource, ssb::LineOrderGenerator<'static>);
+
+/// A [`PairSource`] that writes the ORDERS and LINEITEM tables in TBL format
+/// in a single pass
+pub struct OrderLineItemTblSource {
+    inner: OrderLineItemGenerator<'static>,
+}
+
+impl OrderLineItemTblSource {
+    pub fn new(inner: OrderLineItemGenerator<'static>) -> Self {
+        Self { inner }
+    }
+}
+
+impl PairSource for OrderLineItemTblSource {
+    fn header(&self, buffers: (Vec<u8>, Vec<u8>)) -> (Vec<u8>, Vec<u8>) {
+        // TBL source does not have a header
+        buffers
+    }
+
+    fn create(self, (mut orders, mut line_items): (Vec<u8>, Vec<u8>)) -> (Vec<u8>, Vec<u8>) {
+        for item in self.inner.iter() {
+            writeln!(&mut orders, "{}", item.order).expect("writing to memory is infallible");
+            for line_item in item.line_items {
+                writeln!(&mut line_items, "{line_item}").expect("writing to memory is infallible");
+            }
+        }
+        (orders, line_items)
+    }
+}
+
+// Define .tbl sources for the refresh function update streams
+define_tbl_source!(RefreshOrderTblSource, RefreshGenerator<'static>, orders);
+define_tbl_source!(
+    RefreshLineItemTblSource,
+    RefreshGenerator<'static>,
+    line_items
+);
+define_tbl_source!(RefreshDeleteTblSource, RefreshGenerator<'static>, deletes);
@@ -0,0 +1,81 @@
+//! Implementation of the `validate-answer` subcommand: checks the result of a
+//! query against the expected answer for the scale factor, with the precision
+//! rules of the TPC-H specification.
+
+use clap::Args;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use tpchgen::dates::DateRange;
+use tpchgen::q_and_a::evaluator::QueryEvaluator;
+use tpchgen::q_and_a::validation::Answer;
+use tpchgen::skew::Skew;
+
+/// Arguments of the `validate-answer` subcommand
+#[derive(Args, Debug)]
+pub struct ValidateAnswerArgs {
+    /// Query of the result (1 to 22), run with the validation parameters
+    /// (see `qgen --validation`)
+    #[arg(short, long)]
+    query: i32,
+
+    /// Result of the query to validate, with a header line
+    result: PathBuf,
+
+    /// Delimiter of the values in the result file
+    #[arg(long, default_value_t = ',')]
+    delimiter: char,
+
+    /// File with the expected answer, in the same format as the result
+    /// (default: computed from the generated data, which takes about as long
+    /// as generating the tables read by the query)
+    #[arg(long)]
+    expected: Option<PathBuf>,
+}
+
+impl ValidateAnswerArgs {
+    /// Writes the differences between the result and the expected answer to
+    /// `out`, and returns an error if there are any
+    pub fn run(
+        &self,
+        scale_factor: f64,
+        seed: u64,
+        skew: Skew,
+        date_range: DateRange,
+        out: &mut impl Write,
+    ) -> io::Result<()> {
+        let invalid_data = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+        let query_number = self.query;
+
+        let expected = match &self.expected {
+            Some(path) => Answer::parse(query_number, &fs::read_to_string(path)?, self.delimiter),
+            None => QueryEvaluator::new(scale_factor)
+                .with_seed(seed)
+                .with_skew(skew)
+                .with_date_range(date_range)
+                .validation_answer(query_number)
+                .and_then(|answer| Answer::parse(query_number, &answer, '|')),
+        }
+        .map_err(invalid_data)?;
+        let actual = Answer::parse(
+            query_number,
+            &fs::read_to_string(&self.result)?,
+            self.delimiter,
+        )
+        .map_err(|e| invalid_data(format!("{}: {e}", self.result.display())))?;
+
+        let differences = expected.compare(&actual);
+        for difference in &differences {
+            writeln!(out, "{difference}")?;
+        }
+        out.flush()?;
+        if !differences.is_empty() {
+            return Err(invalid_data(format!(
+                "Q{query_number}: the result does not match the expected answer ({} differences)",
+                differences.len()
+            )));
+        }
+        writeln!(out, "Q{query_number}: OK")?;
+        out.flush()
+    }
+}
@@ -0,0 +1,324 @@
+//! Implementation of the `verify` subcommand: checks that the files of a
+//! generated dataset are complete and correct with a [`TableDigest`] of the
+//! rows of each file.
+//!
+//! The digests of the files of each table are added up and compared to the
+//! [known](TableDigest::known) digest of the table. When there is no known
+//! digest (e.g. with `--seed`, `--date-range`, `--text-pool-size` or custom
+//! distributions), or the table does not match, the expected digest of each
+//! file is computed by generating its part of the table again, which reports
+//! the files that do not match.
+
+use crate::benchmark::BenchmarkTable;
+use crate::plan::GenerationPlan;
+use crate::{Cli, OutputFormat};
+use ::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+use clap::Args;
+use std::fmt::{self, Write as _};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use tpchgen::dates::DateRange;
+use tpchgen::digest::TableDigest;
+use tpchgen::distribution::Distributions;
+use tpchgen::owned;
+use tpchgen::parse::{CsvReader, ParseRow};
+use tpchgen::skew::Skew;
+use tpchgen::table::Table;
+use tpchgen::text::TextPool;
+
+/// Arguments of the `verify` subcommand
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    /// Compare each file with its part of the table generated again, even
+    /// when the digest of the whole table is known
+    #[arg(long, default_value_t = false)]
+    regenerate: bool,
+}
+
+/// A file of the dataset, with the part of the table it should contain
+struct DataFile {
+    table: Table,
+    part: i32,
+    part_count: i32,
+    path: PathBuf,
+}
+
+impl VerifyArgs {
+    /// Verifies the files that `cli` would generate for `tables` (in
+    /// `--output-dir`, with the same `--format`, `--parts` and `--part`
+    /// options), writes the results to `out`, and returns an error if any
+    /// file is missing or does not match
+    pub fn run(
+        &self,
+        cli: &Cli,
+        tables: &[BenchmarkTable],
+        parts: &[(i32, i32)],
+        out: &mut impl Write,
+    ) -> io::Result<()> {
+        if cli.target_file_bytes.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "verify does not support --target-file-bytes, the files must be generated with --parts",
+            ));
+        }
+        let tables = tables
+            .iter()
+            .map(|&table| match table {
+                BenchmarkTable::Tpch(table) => Ok(table),
+                BenchmarkTable::Ssb(_) => Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "verify is only supported for the tpch benchmark",
+                )),
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let files: Vec<DataFile> = tables
+            .iter()
+            .flat_map(|&table| data_files(cli, table, parts))
+            .collect();
+        let actual = parallel_map(&files, cli.num_threads, |file| {
+            read_digest(file.table, cli.format, &file.path)
+        });
+
+        // the tables whose files add up to their known digest are correct,
+        // the files of the other tables are compared with the generated data
+        let use_known = !self.regenerate
+            && cli.seed == 0
+            && cli.skew == Skew::default()
+            && cli.date_range == DateRange::default()
+            && cli.part.is_none()
+            && cli.text_pool_size == TextPool::DEFAULT_TEXT_POOL_SIZE
+            && *Distributions::static_default() == Distributions::try_load_default()?;
+        let regenerate: Vec<Table> = tables
+            .iter()
+            .copied()
+            .filter(|&table| {
+                let known = TableDigest::known(table, cli.scale_factor).filter(|_| use_known);
+                let table_digest = files
+                    .iter()
+                    .zip(&actual)
+                    .filter(|(file, _)| file.table == table)
+                    .map(|(_, digest)| digest.as_ref().ok().copied())
+                    .sum::<Option<TableDigest>>();
+                known.is_none() || table_digest != known
+            })
+            .collect();
+        let expected = parallel_map(&files, cli.num_threads, |file| {
+            regenerate.contains(&file.table).then(|| {
+                TableDigest::generate(
+                    file.table,
+                    cli.scale_factor,
+                    file.part,
+                    file.part_count,
+                    cli.seed,
+                    cli.skew,
+                    cli.date_range,
+                )
+            })
+        });
+
+        let mut failed_tables = vec![];
+        for table in tables {
+            let mut table_digest = TableDigest::new();
+            let mut file_count = 0;
+            let mut failed_files = 0;
+            for ((file, actual), expected) in files.iter().zip(&actual).zip(&expected) {
+                if file.table != table {
+                    continue;
+                }
+                file_count += 1;
+                let path = file
+                    .path
+                    .strip_prefix(&cli.output_dir)
+                    .unwrap_or(&file.path);
+                match (actual, expected) {
+                    (Err(e), _) => {
+                        failed_files += 1;
+                        writeln!(out, "{}: {e}", path.display())?;
+                    }
+                    (Ok(actual), Some(expected)) if actual != expected => {
+                        failed_files += 1;
+                        writeln!(
+                            out,
+                            "{}: expected digest {expected}, got {actual}",
+                            path.display()
+                        )?;
+                    }
+                    (Ok(actual), _) => table_digest += *actual,
+                }
+            }
+            if failed_files == 0 {
+                let files = if file_count == 1 { "file" } else { "files" };
+                writeln!(
+                    out,
+                    "{table}: OK ({file_count} {files}, digest {table_digest})"
+                )?;
+            } else {
+                writeln!(
+                    out,
+                    "{table}: FAILED ({failed_files} of {file_count} files do not match)"
+                )?;
+                failed_tables.push(table.to_string());
+            }
+        }
+        out.flush()?;
+        if !failed_tables.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "The files of {} do not match the expected data",
+                    failed_tables.join(", ")
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Returns the files that `cli` generates for `parts` of `table`, named like
+/// the files of the [`OutputPlanGenerator`](crate::output_plan::OutputPlanGenerator)
+fn data_files(cli: &Cli, table: Table, parts: &[(i32, i32)]) -> Vec<DataFile> {
+    let extension = match cli.format {
+        OutputFormat::Tbl => "tbl",
+        OutputFormat::Csv => "csv",
+        OutputFormat::Parquet => "parquet",
+    };
+    let path = |part: Option<i32>| match part {
+        Some(part) => cli
+            .output_dir
+            .join(table.to_string())
+            .join(format!("{table}.{part}.{extension}")),
+        None => cli.output_dir.join(format!("{table}.{extension}")),
+    };
+    let file = |file_part: Option<i32>, part, part_count| DataFile {
+        table,
+        part,
+        part_count,
+        path: path(file_part),
+    };
+
+    if cli.parts.is_none() {
+        vec![file(None, 1, 1)]
+    } else if !GenerationPlan::partitioned_table(table.into()) {
+        // the whole table is written to the file of the part
+        vec![file(Some(cli.part.unwrap_or(1)), 1, 1)]
+    } else {
+        parts
+            .iter()
+            .map(|&(part, part_count)| file(Some(part), part, part_count))
+            .collect()
+    }
+}
+
+/// Computes the digest of the rows of a file of `table` in `format`
+///
+/// TBL lines are hashed as they are, the rows of the other formats are
+/// converted to the TBL format first.
+fn read_digest(table: Table, format: OutputFormat, path: &Path) -> io::Result<TableDigest> {
+    fn read<T: ParseRow + fmt::Display>(
+        format: OutputFormat,
+        file: File,
+    ) -> io::Result<TableDigest> {
+        match format {
+            OutputFormat::Tbl => {
+                let mut digest = TableDigest::new();
+                for line in BufReader::new(file).lines() {
+                    let line = line?;
+                    if !line.is_empty() {
+                        digest.add_line(&line);
+                    }
+                }
+                Ok(digest)
+            }
+            OutputFormat::Csv => {
+                let mut digest = TableDigest::new();
+                for row in CsvReader::<T, _>::new(BufReader::new(file)) {
+                    digest.add_row(&row?);
+                }
+                Ok(digest)
+            }
+            OutputFormat::Parquet => read_parquet::<T>(file),
+        }
+    }
+
+    let file = File::open(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => io::Error::new(e.kind(), "missing"),
+        _ => e,
+    })?;
+    match table {
+        Table::Nation => read::<owned::Nation>(format, file),
+        Table::Region => read::<owned::Region>(format, file),
+        Table::Part => read::<owned::Part>(format, file),
+        Table::Supplier => read::<owned::Supplier>(format, file),
+        Table::Partsupp => read::<owned::PartSupp>(format, file),
+        Table::Customer => read::<owned::Customer>(format, file),
+        Table::Orders => read::<owned::Order>(format, file),
+        Table::Lineitem => read::<owned::LineItem>(format, file),
+    }
+}
+
+/// Computes the digest of the rows of a Parquet file, formatting each value
+/// like the TBL format
+fn read_parquet<T: ParseRow + fmt::Display>(file: File) -> io::Result<TableDigest> {
+    let invalid_data = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+        .and_then(|builder| builder.build())
+        .map_err(|e| invalid_data(e.to_string()))?;
+
+    let mut digest = TableDigest::new();
+    let options = FormatOptions::default();
+    let mut row_number = 0;
+    for batch in reader {
+        let batch = batch.map_err(|e| invalid_data(e.to_string()))?;
+        let formatters = batch
+            .columns()
+            .iter()
+            .map(|column| ArrayFormatter::try_new(column.as_ref(), &options))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| invalid_data(e.to_string()))?;
+        let mut values = vec![String::new(); formatters.len()];
+        for row in 0..batch.num_rows() {
+            row_number += 1;
+            for (value, formatter) in values.iter_mut().zip(&formatters) {
+                value.clear();
+                write!(value, "{}", formatter.value(row))
+                    .map_err(|e| invalid_data(e.to_string()))?;
+            }
+            let fields: Vec<&str> = values.iter().map(String::as_str).collect();
+            let row = T::from_fields(&fields)
+                .map_err(|e| invalid_data(format!("row {row_number}: {e}")))?;
+            digest.add_row(&row);
+        }
+    }
+    Ok(digest)
+}
+
+/// Applies `f` to each item on `num_threads` threads, returning the results
+/// in the order of the items
+fn parallel_map<T: Sync, R: Send>(
+    items: &[T],
+    num_threads: usize,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(items.len()));
+    std::thread::scope(|scope| {
+        for _ in 0..num_threads.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result = f(item);
+                results.lock().unwrap().push((index, result));
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
@@ -1,13 +1,15 @@
 use assert_cmd::Command;
 use parquet::arrow::arrow_reader::{ArrowReaderOptions, ParquetRecordBatchReaderBuilder};
 use parquet::file::metadata::ParquetMetaDataReader;
+use predicates::prelude::PredicateBooleanExt;
 use std::fs;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 use std::sync::Arc;
 use tempfile::tempdir;
-use tpchgen::generators::OrderGenerator;
+use tpchgen::generators::{OrderGenerator, RefreshGenerator};
+use tpchgen::q_and_a::evaluator::QueryEvaluator;
 use tpchgen_arrow::{OrderArrow, RecordBatchIterator};
 
 fn tpchgen_cmd() -> Command {
@@ -243,6 +245,57 @@ fn test_tpchgen_cli_parts_all_tables() {
     verify_table(temp_dir.path(), "region", 1, "0.001");
 }
 
+/// Test that orders and lineitem, which are normally generated together, are
+/// generated separately when only one of the files already exists
+#[test]
+fn test_tpchgen_cli_orders_lineitem_one_file_exists() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let lineitem_file = temp_dir.path().join("lineitem.tbl");
+    fs::write(&lineitem_file, "existing").expect("Failed to write lineitem file");
+
+    tpchgen_cmd()
+        .arg("--scale-factor")
+        .arg("0.001")
+        .arg("--tables")
+        .arg("orders,lineitem")
+        .arg("--output-dir")
+        .arg(temp_dir.path())
+        .assert()
+        .success();
+
+    let orders_contents =
+        fs::read_to_string(temp_dir.path().join("orders.tbl")).expect("Failed to read orders file");
+    assert_eq!(orders_contents, read_reference_file("orders", "0.001"));
+    let lineitem_contents =
+        fs::read_to_string(&lineitem_file).expect("Failed to read lineitem file");
+    assert_eq!(lineitem_contents, "existing");
+}
+
+/// Create tables split into files of a target size with --target-file-bytes
+#[test]
+fn test_tpchgen_cli_target_file_bytes() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    let output_dir = temp_dir.path().to_path_buf();
+    tpchgen_cmd()
+        .arg("--scale-factor")
+        .arg("0.001")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .arg("--target-file-bytes")
+        .arg("100000")
+        .arg("--tables")
+        .arg("orders,lineitem,nation")
+        .assert()
+        .success();
+
+    // the last file is smaller than the others
+    verify_table(temp_dir.path(), "orders", 2, "0.001");
+    verify_table(temp_dir.path(), "lineitem", 8, "0.001");
+    verify_table(temp_dir.path(), "nation", 1, "0.001");
+    assert!(!output_dir.join("lineitem").join("lineitem.9.tbl").exists());
+}
+
 /// Read the N files from `output_dir/table_name/table_name.part.tml` into a
 /// single buffer and compare them to the contents of the reference file
 fn verify_table(output_dir: &Path, table_name: &str, parts: usize, scale_factor: &str) {
@@ -511,6 +564,880 @@ fn test_tpchgen_cli_zero_part_zero_parts() {
         ));
 }
 
+/// Test generating the refresh function update streams
+#[test]
+fn test_tpchgen_cli_updates() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    tpchgen_cmd()
+        .arg("--scale-factor")
+        .arg("0.01")
+        .arg("--output-dir")
+        .arg(temp_dir.path())
+        .arg("--updates")
+        .arg("2")
+        .assert()
+        .success();
+
+    // no tables are generated, only the update streams
+    assert!(!temp_dir.path().join("orders.tbl").exists());
+    for update_stream in 1..=2 {
+        let orders =
+            fs::read_to_string(temp_dir.path().join(format!("orders.tbl.u{update_stream}")))
+                .expect("Failed to read orders update file");
+        let line_items = fs::read_to_string(
+            temp_dir
+                .path()
+                .join(format!("lineitem.tbl.u{update_stream}")),
+        )
+        .expect("Failed to read lineitem update file");
+        let deletes = fs::read_to_string(temp_dir.path().join(format!("delete.{update_stream}")))
+            .expect("Failed to read delete file");
+
+        // 0.1% of the 15,000 orders at SF 0.01
+        assert_eq!(RefreshGenerator::calculate_row_count(0.01), 15);
+        assert_eq!(orders.lines().count(), 15);
+        assert_eq!(deletes.lines().count(), 15);
+        assert!(line_items.lines().count() >= 15);
+    }
+
+    let orders = fs::read_to_string(temp_dir.path().join("orders.tbl.u1")).unwrap();
+    assert!(orders.starts_with("9|370|O|172799.49|1996-01-02|5-LOW|Clerk#000000951|0|"));
+    let deletes = fs::read_to_string(temp_dir.path().join("delete.2")).unwrap();
+    assert!(deletes.starts_with("64|\n65|\n66|\n"));
+}
+
+/// Cross-check the update streams against the dbgen reference tables
+///
+/// The rows inserted by update stream `<n>` are the rows of the initial
+/// population after the rows of the previous streams, with new sparse order
+/// keys. The orders deleted by stream `<n>` are the orders of those rows.
+#[test]
+fn test_tpchgen_cli_updates_match_reference_tables() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    tpchgen_cmd()
+        .arg("--scale-factor")
+        .arg("0.01")
+        .arg("--output-dir")
+        .arg(temp_dir.path())
+        .arg("--updates")
+        .arg("2")
+        .assert()
+        .success();
+
+    let reference_orders = read_reference_file("orders", "0.01");
+    let reference_line_items = read_reference_file("lineitem", "0.01");
+    let reference_keys: Vec<&str> = reference_orders
+        .lines()
+        .map(|line| line.split_once('|').unwrap().0)
+        .collect();
+
+    for update_stream in 1..=2 {
+        let read = |name: String| {
+            fs::read_to_string(temp_dir.path().join(name)).expect("Failed to read update file")
+        };
+        let orders = read(format!("orders.tbl.u{update_stream}"));
+        let line_items = read(format!("lineitem.tbl.u{update_stream}"));
+        let deletes = read(format!("delete.{update_stream}"));
+
+        let row_count = RefreshGenerator::calculate_row_count(0.01) as usize;
+        let start = (update_stream - 1) * row_count;
+        let mut expected_line_items = vec![];
+        for (i, order) in orders.lines().enumerate() {
+            let (key, rest) = order.split_once('|').unwrap();
+            let (reference_key, reference_rest) = reference_orders
+                .lines()
+                .nth(start + i)
+                .unwrap()
+                .split_once('|')
+                .unwrap();
+            assert_eq!(
+                rest, reference_rest,
+                "order {key} of stream {update_stream}"
+            );
+            assert!(!reference_keys.contains(&key), "order {key} already exists");
+            expected_line_items.extend(
+                reference_line_items
+                    .lines()
+                    .filter(|line| line.split_once('|').unwrap().0 == reference_key)
+                    .map(|line| format!("{key}|{}", line.split_once('|').unwrap().1)),
+            );
+        }
+        assert_eq!(line_items.lines().collect::<Vec<_>>(), expected_line_items);
+
+        let expected_deletes: Vec<String> = reference_keys[start..start + row_count]
+            .iter()
+            .map(|key| format!("{key}|"))
+            .collect();
+        assert_eq!(deletes.lines().collect::<Vec<_>>(), expected_deletes);
+    }
+}
+
+#[test]
+fn test_tpchgen_cli_updates_parquet() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    tpchgen_cmd()
+        .arg("--output-dir")
+        .arg(temp_dir.path())
+        .arg("--format")
+        .arg("parquet")
+        .arg("--updates")
+        .arg("1")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Update streams can only be generated in tbl format",
+        ));
+}
+
+/// Test printing the exact row counts without generating any data
+#[test]
+fn test_tpchgen_cli_count_only() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let output_dir = temp_dir.path().join("output");
+
+    tpchgen_cmd()
+        .arg("--scale-factor")
+        .arg("0.01")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .arg("--count-only")
+        .assert()
+        .success()
+        .stdout(
+            "table,part,row_count\n\
+            nation,1,25\n\
+            region,1,5\n\
+            part,1,2000\n\
+            supplier,1,100\n\
+            partsupp,1,8000\n\
+            customer,1,1500\n\
+            orders,1,15000\n\
+            lineitem,1,60175\n",
+        );
+    assert!(!output_dir.exists(), "no output should be generated");
+
+    // The counts of the parts add up to the table count
+    tpchgen_cmd()
+        .arg("--scale-factor")
+        .arg("0.01")
+        .arg("--tables")
+        .arg("lineitem,region")
+        .arg("--parts")
+        .arg("2")
+        .arg("--count-only")
+        .assert()
+        .success()
+        .stdout(
+            "table,part,row_count\n\
+            lineitem,1,30201\n\
+            lineitem,2,29974\n\
+            region,1,5\n",
+        );
+
+    // The update streams are not counted
+    tpchgen_cmd()
+        .arg("--updates")
+        .arg("1")
+        .arg("--count-only")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "the argument '--updates <UPDATES>' cannot be used with '--count-only'",
+        ));
+}
+
+/// Test that `--seed` produces a different dataset with the same row counts,
+/// and that `--seed 0` is the standard dataset
+#[test]
+fn test_tpchgen_cli_seed() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    for seed in ["0", "7"] {
+        tpchgen_cmd()
+            .arg("--scale-factor")
+            .arg("0.001")
+            .arg("--tables")
+            .arg("orders,nation")
+            .arg("--seed")
+            .arg(seed)
+            .arg("--output-dir")
+            .arg(temp_dir.path().join(seed))
+            .assert()
+            .success();
+    }
+
+    for file in ["orders.tbl", "nation.tbl"] {
+        let reference_file = format!("../tpchgen/data/sf-0.001/{file}.gz");
+        let reference_contents = match read_gzipped_file_to_string(&reference_file) {
+            Ok(contents) => contents,
+            Err(e) => panic!("Failed to read reference file {reference_file}: {e}"),
+        };
+        let seed_0 = fs::read_to_string(temp_dir.path().join("0").join(file)).unwrap();
+        let seed_7 = fs::read_to_string(temp_dir.path().join("7").join(file)).unwrap();
+
+        assert_eq!(seed_0, reference_contents, "seed 0 differs for {file}");
+        assert_ne!(seed_7, reference_contents, "seed 7 is the same for {file}");
+        assert_eq!(seed_7.lines().count(), seed_0.lines().count());
+    }
+}
+
+/// Test that `--skew` makes the first customer place many orders
+#[test]
+fn test_tpchgen_cli_skew() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    tpchgen_cmd()
+        .arg("--scale-factor")
+        .arg("0.001")
+        .arg("--tables")
+        .arg("orders")
+        .arg("--skew")
+        .arg("o_custkey=1")
+        .arg("--output-dir")
+        .arg(temp_dir.path())
+        .assert()
+        .success();
+
+    let orders = fs::read_to_string(temp_dir.path().join("orders.tbl")).unwrap();
+    let reference_contents = read_gzipped_file_to_string("../tpchgen/data/sf-0.001/orders.tbl.gz")
+        .expect("Failed to read reference file");
+    assert_ne!(orders, reference_contents);
+    assert_eq!(orders.lines().count(), reference_contents.lines().count());
+    let first_customer_orders = orders
+        .lines()
+        .filter(|line| line.split('|').nth(1) == Some("1"))
+        .count();
+    assert!(first_customer_orders > 100, "{first_customer_orders}");
+
+    tpchgen_cmd()
+        .arg("--skew")
+        .arg("o_custkey=-1")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Invalid skew factor"));
+}
+
+/// Test that `--date-range` moves the dates of the data and the queries
+#[test]
+fn test_tpchgen_cli_date_range() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let cmd = || {
+        let mut cmd = tpchgen_cmd();
+        cmd.arg("--scale-factor")
+            .arg("0.001")
+            .arg("--tables")
+            .arg("orders,lineitem")
+            .arg("--date-range")
+            .arg("2019-2025")
+            .arg("--output-dir")
+            .arg(temp_dir.path());
+        cmd
+    };
+    cmd().assert().success();
+
+    let orders = fs::read_to_string(temp_dir.path().join("orders.tbl")).unwrap();
+    assert!(
+        orders.starts_with("1|37|O|131251.81|2023-01-02|"),
+        "{orders}"
+    );
+    for line in orders.lines() {
+        let year: i32 = line.split('|').nth(4).unwrap()[..4].parse().unwrap();
+        assert!((2019..=2025).contains(&year), "{line}");
+    }
+    cmd().arg("verify").assert().success();
+
+    tpchgen_cmd()
+        .arg("--date-range")
+        .arg("2019-2025")
+        .arg("qgen")
+        .arg("--validation")
+        .arg("6")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("l_shipdate >= date '2021-01-01'"));
+
+    tpchgen_cmd()
+        .arg("--date-range")
+        .arg("2019-2022")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("at least 7 years"));
+    tpchgen_cmd()
+        .arg("--benchmark")
+        .arg("ssb")
+        .arg("--date-range")
+        .arg("2019")
+        .arg("--output-dir")
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "--date-range is only supported for the tpch benchmark",
+        ));
+}
+
+/// Test generating the Star Schema Benchmark tables with `--benchmark ssb`
+#[test]
+fn test_tpchgen_cli_ssb() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+
+    tpchgen_cmd()
+        .arg("--scale-factor")
+        .arg("0.01")
+        .arg("--benchmark")
+        .arg("ssb")
+        .arg("--output-dir")
+        .arg(temp_dir.path())
+        .assert()
+        .success();
+
+    for (table, row_count) in [
+        ("customer", 300),
+        ("supplier", 20),
+        ("part", 2000),
+        ("date", 2557),
+        ("lineorder", 60175),
+    ] {
+        let contents = fs::read_to_string(temp_dir.path().join(format!("{table}.tbl")))
+            .expect("Failed to read table");
+        assert_eq!(contents.lines().count(), row_count, "{table}");
+    }
+    // LINEORDER uses the random streams of the TPC-H orders and line items
+    let line_orders = fs::read_to_string(temp_dir.path().join("lineorder.tbl")).unwrap();
+    assert_eq!(
+        line_orders.lines().next().unwrap(),
+        "1|1|74|1552|9|19960102|5-LOW|0|17|2471035|17279949|4|2372193|87213|2|19960212|TRUCK|"
+    );
+
+    // generating the parts produces the same rows
+    tpchgen_cmd()
+        .arg("--scale-factor")
+        .arg("0.01")
+        .arg("--benchmark")
+        .arg("ssb")
+        .arg("--tables")
+        .arg("lineorder,date")
+        .arg("--parts")
+        .arg("3")
+        .arg("--output-dir")
+        .arg(temp_dir.path())
+        .assert()
+        .success();
+    let parts: String = (1..=3)
+        .map(|part| {
+            let path = temp_dir
+                .path()
+                .join(format!("lineorder/lineorder.{part}.tbl"));
+            fs::read_to_string(path).unwrap()
+        })
+        .collect();
+    assert_eq!(parts, line_orders);
+    assert!(temp_dir.path().join("date/date.1.tbl").exists());
+    assert!(!temp_dir.path().join("date/date.2.tbl").exists());
+
+    tpchgen_cmd()
+        .arg("--benchmark")
+        .arg("ssb")
+        .arg("--tables")
+        .arg("orders")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Invalid SSB table name orders"));
+}
+
+/// Test printing the queries with the validation parameters
+#[test]
+fn test_tpchgen_cli_qgen_validation() {
+    tpchgen_cmd()
+        .arg("qgen")
+        .arg("--validation")
+        .arg("13")
+        .arg("16")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("-- TPC-H Query 13"))
+        .stdout(predicates::str::contains(
+            "o_comment not like '%special%requests%'",
+        ))
+        .stdout(predicates::str::contains(
+            "p_size in (49, 14, 23, 45, 19, 3, 36, 9)",
+        ))
+        .stdout(predicates::str::contains("-- TPC-H Query 1\n").not());
+}
+
+/// Test that the random query parameters are deterministic for a seed
+#[test]
+fn test_tpchgen_cli_qgen_seed() {
+    let run_qgen = |seed: &str| {
+        let output = tpchgen_cmd()
+            .arg("--scale-factor")
+            .arg("10")
+            .arg("qgen")
+            .arg("--seed")
+            .arg(seed)
+            .output()
+            .expect("Failed to run qgen");
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).expect("qgen output is not utf8")
+    };
+
+    let queries = run_qgen("1");
+    assert_eq!(queries.matches("-- TPC-H Query").count(), 22);
+    assert!(!queries.contains(":1"));
+    // Q11 fraction is 0.0001 / SF
+    assert!(queries.contains("* 0.00001"));
+    assert_eq!(queries, run_qgen("1"));
+    assert_ne!(queries, run_qgen("2"));
+}
+
+/// Test printing the queries for a SQL dialect
+#[test]
+fn test_tpchgen_cli_qgen_dialect() {
+    tpchgen_cmd()
+        .arg("qgen")
+        .arg("--validation")
+        .arg("--dialect")
+        .arg("clickhouse")
+        .arg("--q15-cte")
+        .arg("3")
+        .arg("15")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "o_orderdate < toDate('1995-03-15')",
+        ))
+        .stdout(predicates::str::contains("o_orderdate\nlimit 10;"))
+        .stdout(predicates::str::contains("with revenue0 as ("))
+        .stdout(predicates::str::contains("create view").not());
+
+    tpchgen_cmd()
+        .arg("qgen")
+        .arg("--dialect")
+        .arg("oracle")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Unknown SQL dialect 'oracle'"));
+}
+
+/// Test printing the queries in the order of a stream
+#[test]
+fn test_tpchgen_cli_qgen_permute() {
+    let output = tpchgen_cmd()
+        .arg("qgen")
+        .arg("--stream")
+        .arg("1")
+        .arg("--permute")
+        .output()
+        .expect("Failed to run qgen");
+    assert!(output.status.success());
+    let queries = String::from_utf8(output.stdout).expect("qgen output is not utf8");
+    let order: Vec<_> = queries
+        .lines()
+        .filter_map(|line| line.strip_prefix("-- TPC-H Query "))
+        .collect();
+    assert_eq!(
+        order,
+        [
+            "21", "3", "18", "5", "11", "7", "6", "20", "17", "12", "16", "15", "13", "10", "2",
+            "8", "14", "19", "9", "22", "1", "4"
+        ]
+    );
+    assert!(queries.contains("create view revenue1 "));
+
+    tpchgen_cmd()
+        .arg("qgen")
+        .arg("--permute")
+        .arg("3")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_tpchgen_cli_qgen_invalid_query() {
+    tpchgen_cmd()
+        .arg("qgen")
+        .arg("23")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Invalid TPC-H query number: 23"));
+}
+
+/// Test validating a query result against the computed answer
+#[test]
+fn test_tpchgen_cli_validate_answer() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let answer = QueryEvaluator::new(0.01).validation_answer(4).unwrap();
+    let csv = answer.trim().replace('|', ",");
+    let result_path = temp_dir.path().join("q4.csv");
+    let validate = |result: &str| {
+        fs::write(&result_path, result).expect("Failed to write result");
+        let mut cmd = tpchgen_cmd();
+        cmd.arg("-s")
+            .arg("0.01")
+            .arg("validate-answer")
+            .arg("--query")
+            .arg("4")
+            .arg(&result_path);
+        cmd
+    };
+
+    validate(&csv)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Q4: OK"));
+
+    // change the order count of the last priority
+    let (rows, last) = csv.rsplit_once('\n').unwrap();
+    let (priority, _) = last.split_once(',').unwrap();
+    validate(&format!("{rows}\n{priority},0\n"))
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains("order_count"))
+        .stderr(predicates::str::contains(
+            "does not match the expected answer",
+        ));
+}
+
+#[test]
+fn test_tpchgen_cli_verify() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let cmd = |args: &[&str]| {
+        let mut cmd = tpchgen_cmd();
+        cmd.arg("-s")
+            .arg("0.01")
+            .arg("--output-dir")
+            .arg(temp_dir.path())
+            .arg("--parts")
+            .arg("3")
+            .args(args);
+        cmd
+    };
+    cmd(&[]).assert().success();
+
+    cmd(&["verify"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "nation: OK (1 file, digest 25:444704f174554371)",
+        ))
+        .stdout(predicates::str::contains(
+            "lineitem: OK (3 files, digest 60175:fdffc408c1fb8f45)",
+        ));
+
+    // modify a row of lineitem and remove a file of orders
+    let lineitem_path = temp_dir.path().join("lineitem/lineitem.2.tbl");
+    let lineitem = fs::read_to_string(&lineitem_path).unwrap();
+    fs::write(&lineitem_path, lineitem.replacen("|N|O|", "|R|F|", 1)).unwrap();
+    fs::remove_file(temp_dir.path().join("orders/orders.3.tbl")).unwrap();
+    cmd(&["verify"])
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains(
+            "lineitem/lineitem.2.tbl: expected digest",
+        ))
+        .stdout(predicates::str::contains("orders/orders.3.tbl: missing"))
+        .stdout(predicates::str::contains("part: OK"))
+        .stderr(predicates::str::contains(
+            "The files of orders, lineitem do not match the expected data",
+        ));
+    // only the selected part is checked
+    cmd(&["--tables", "lineitem,orders", "--part", "1", "verify"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("lineitem: OK (1 file, digest"));
+}
+
+#[test]
+fn test_tpchgen_cli_verify_formats() {
+    for format in ["csv", "parquet"] {
+        let temp_dir = tempdir().expect("Failed to create temporary directory");
+        let cmd = || {
+            let mut cmd = tpchgen_cmd();
+            cmd.arg("-s")
+                .arg("0.01")
+                .arg("--seed")
+                .arg("7")
+                .arg("--format")
+                .arg(format)
+                .arg("--output-dir")
+                .arg(temp_dir.path());
+            cmd
+        };
+        cmd().assert().success();
+        cmd()
+            .arg("verify")
+            .assert()
+            .success()
+            .stdout(predicates::str::contains("lineitem: OK (1 file"));
+        // the files do not match the standard data
+        tpchgen_cmd()
+            .arg("-s")
+            .arg("0.01")
+            .arg("--format")
+            .arg(format)
+            .arg("--output-dir")
+            .arg(temp_dir.path())
+            .arg("--tables")
+            .arg("orders")
+            .arg("verify")
+            .assert()
+            .failure()
+            .stdout(predicates::str::contains(format!(
+                "orders.{format}: expected digest 15000:ef86a6dfde6e96c5"
+            )));
+    }
+}
+
+#[test]
+fn test_tpchgen_cli_invalid_dists() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let dists =
+        fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("../tpchgen/src/dists.dss"))
+            .unwrap();
+    let dists_path = temp_dir.path().join("my.dss");
+    fs::write(&dists_path, dists.replacen("REG AIR|1", "REG AIR|x", 1)).unwrap();
+
+    Command::cargo_bin("tpchgen-cli")
+        .expect("Binary not found")
+        .arg("--dists-path")
+        .arg(&dists_path)
+        .arg("--count-only")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(format!(
+            "{}:250: distribution smode: invalid weight 'x'",
+            dists_path.display()
+        )));
+}
+
+#[test]
+fn test_tpchgen_cli_text_pool_cache() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let cache_path = temp_dir.path().join("text-pool");
+    let generate = |name: &str, args: &[&str]| {
+        let output_dir = temp_dir.path().join(name);
+        tpchgen_cmd()
+            .arg("--scale-factor")
+            .arg("0.01")
+            .arg("--tables")
+            .arg("orders")
+            .arg("--output-dir")
+            .arg(&output_dir)
+            .args(args)
+            .assert()
+            .success();
+        fs::read(output_dir.join("orders.tbl")).unwrap()
+    };
+    let cache = format!("--text-pool-cache={}", cache_path.display());
+
+    // the first run creates the cache, the second one loads it
+    let expected = generate("uncached", &[]);
+    assert_eq!(generate("create", &[&cache]), expected);
+    let cache_size = fs::metadata(&cache_path).unwrap().len();
+    assert!(cache_size > 300 * 1024 * 1024, "{cache_size}");
+    assert_eq!(generate("load", &[&cache]), expected);
+
+    // a cache created with another seed is replaced
+    assert_eq!(
+        generate("seed", &[&cache, "--seed=7"]),
+        generate("seed-uncached", &["--seed=7"])
+    );
+    assert_eq!(generate("load-again", &[&cache]), expected);
+
+    // a corrupted cache is replaced when it is verified
+    let mut bytes = fs::read(&cache_path).unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 1;
+    fs::write(&cache_path, &bytes).unwrap();
+    assert_eq!(
+        generate("verify", &[&cache, "--verify-text-pool-cache"]),
+        expected
+    );
+    assert_ne!(fs::read(&cache_path).unwrap(), bytes);
+    assert_eq!(generate("load-verified", &[&cache]), expected);
+}
+
+#[test]
+fn test_tpchgen_cli_text_pool_size() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let cmd = |output_dir: &str, text_pool_size: &str| {
+        let mut cmd = tpchgen_cmd();
+        cmd.arg("--scale-factor")
+            .arg("0.01")
+            .arg("--tables")
+            .arg("orders")
+            .arg("--output-dir")
+            .arg(temp_dir.path().join(output_dir))
+            .arg("--text-pool-size")
+            .arg(text_pool_size);
+        cmd
+    };
+
+    cmd("small", "1048576")
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "the comments are not TPC-H conformant",
+        ));
+    cmd("default", "314572800")
+        .assert()
+        .success()
+        .stderr(predicates::str::is_empty());
+    let orders = |output_dir: &str| {
+        fs::read_to_string(temp_dir.path().join(output_dir).join("orders.tbl")).unwrap()
+    };
+    let (small, default) = (orders("small"), orders("default"));
+    assert_ne!(small, default);
+    // only the comments are different
+    for (small, default) in small.lines().zip(default.lines()) {
+        assert_eq!(small.rsplitn(3, '|').nth(2), default.rsplitn(3, '|').nth(2));
+    }
+    // the files are compared with the data generated with the same size
+    cmd("small", "1048576")
+        .arg("verify")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("orders: OK (1 file"));
+    cmd("small", "314572800").arg("verify").assert().failure();
+
+    cmd("small", "1000")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("--text-pool-size"));
+}
+
+#[test]
+fn test_tpchgen_cli_multilingual_text() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let cmd = |format: &str| {
+        let mut cmd = tpchgen_cmd();
+        cmd.arg("--scale-factor")
+            .arg("0.01")
+            .arg("--tables")
+            .arg("orders,supplier")
+            .arg("--format")
+            .arg(format)
+            .arg("--output-dir")
+            .arg(temp_dir.path().join(format))
+            .arg("--text-pool-size")
+            .arg("1048576")
+            .arg("--multilingual-text");
+        cmd
+    };
+
+    cmd("tbl")
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "--multilingual-text is set, the comments are not TPC-H conformant",
+        ));
+    let orders = fs::read_to_string(temp_dir.path().join("tbl").join("orders.tbl")).unwrap();
+    assert!(!orders.is_ascii());
+    // the other columns are not changed
+    assert!(orders.starts_with("1|370|O|172799.49|1996-01-02|5-LOW|Clerk#000000951|0|"));
+
+    // the multi-byte text can be read back from the other formats
+    for format in ["csv", "parquet"] {
+        cmd(format).assert().success();
+        cmd(format)
+            .arg("verify")
+            .assert()
+            .success()
+            .stdout(predicates::str::contains("orders: OK (1 file"))
+            .stdout(predicates::str::contains("supplier: OK (1 file"));
+    }
+}
+
+#[test]
+fn test_tpchgen_cli_dist_override() {
+    let temp_dir = tempdir().expect("Failed to create temporary directory");
+    let regions_path = temp_dir.path().join("regions.txt");
+    fs::write(
+        &regions_path,
+        "# regions in German\nAFRIKA|1\nAMERIKA|1\nASIEN|1\nEUROPA|1\nNAHER OSTEN|1\n",
+    )
+    .unwrap();
+    let cmd = |regions_path: &Path| {
+        let mut cmd = tpchgen_cmd();
+        cmd.arg("--output-dir")
+            .arg(temp_dir.path())
+            .arg("--tables")
+            .arg("region")
+            .arg("--dist-override")
+            .arg(format!("regions={}", regions_path.display()));
+        cmd
+    };
+
+    cmd(&regions_path).assert().success();
+    let region = fs::read_to_string(temp_dir.path().join("region.tbl")).unwrap();
+    assert!(region.starts_with("0|AFRIKA|"), "{region}");
+    assert!(region.contains("\n4|NAHER OSTEN|"), "{region}");
+    // the files are compared with the data generated with the same distributions
+    cmd(&regions_path)
+        .arg("verify")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("region: OK (1 file"));
+
+    fs::write(&regions_path, "AFRIKA|1\n").unwrap();
+    cmd(&regions_path)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "distribution regions has 1 values, expected 5",
+        ));
+    cmd(&temp_dir.path().join("missing.txt")).assert().failure();
+
+    // there must be 25 nations, in the 5 regions
+    let nations_path = temp_dir.path().join("nations.txt");
+    let nation_override = |nations: &str| {
+        fs::write(&nations_path, nations).unwrap();
+        let mut cmd = tpchgen_cmd();
+        cmd.arg("--output-dir")
+            .arg(temp_dir.path())
+            .arg("--tables")
+            .arg("nation")
+            .arg("--dist-override")
+            .arg(format!("nations={}", nations_path.display()));
+        cmd
+    };
+    nation_override("ALGERIEN|0\nARGENTINIEN|1\n")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "distribution nations has 2 values, expected 25",
+        ));
+    // the weights are the differences of the region keys of the nations
+    let dists = fs::read_to_string("../tpchgen/src/dists.dss").unwrap();
+    let nations: Vec<&str> = dists
+        .lines()
+        .skip_while(|line| *line != "begin nations")
+        .skip(2)
+        .take(25)
+        .collect();
+    let mut out_of_range = nations.clone();
+    out_of_range[1] = "ARGENTINIEN|9";
+    nation_override(&out_of_range.join("\n"))
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "distribution nations: nation ARGENTINIEN is in region 9, which does not exist",
+        ));
+    nation_override(&nations.join("\n")).assert().success();
+
+    tpchgen_cmd()
+        .arg("--dist-override")
+        .arg("regions")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Invalid distribution override 'regions', expected <name>=<file>",
+        ));
+}
+
 /// Test specifying parquet options even when writing tbl output
 #[tokio::test]
 async fn test_incompatible_options_warnings() {
@@ -0,0 +1,483 @@
+//! Struct-of-arrays (columnar) output for the generated rows.
+//!
+//! The generator iterators produce one row struct at a time. For engines that
+//! ingest columnar data, the `fill_columns` methods of the iterators replace
+//! the contents of reusable primitive column buffers such as
+//! [`LineItemColumns`] with the next rows. The fields are written to the
+//! columns as they are drawn from the random streams, without creating the row
+//! structs, and the buffers are reused across calls.
+//!
+//! Columns are represented as follows:
+//!
+//! * Integers: `Vec<i64>` or `Vec<i32>`, matching the row struct field
+//! * Decimals: `Vec<i64>` of the value in hundredths (e.g. `123.45` is `12345`),
+//!   see [`TPCHDecimal`]
+//! * Dates: `Vec<i32>` of days since the Unix epoch, see
+//!   [`TPCHDate::to_unix_epoch`]
+//! * Strings: [`StringColumn`], UTF-8 bytes and offsets
+//!
+//! # Example
+//! ```
+//! # use tpchgen::generators::LineItemGenerator;
+//! # use tpchgen::columns::LineItemColumns;
+//! let mut iter = LineItemGenerator::new(1.0, 1, 1).iter();
+//! let mut columns = LineItemColumns::new();
+//!
+//! // generate the first 1000 line items
+//! assert_eq!(iter.fill_columns(&mut columns, 1000), 1000);
+//! assert_eq!(columns.len(), 1000);
+//! assert_eq!(&columns.l_orderkey[..7], &[1, 1, 1, 1, 1, 1, 2]);
+//! assert_eq!(columns.l_extendedprice[0], 2116823); // 21168.23
+//! assert_eq!(columns.l_shipdate[0], 9568); // 1996-03-13
+//! assert_eq!(columns.l_shipmode.get(0), "TRUCK");
+//!
+//! // generate the next 1000 line items, reusing the buffers
+//! assert_eq!(iter.fill_columns(&mut columns, 1000), 1000);
+//! assert_eq!(columns.l_orderkey[0], 999);
+//! ```
+//!
+//! [`TPCHDecimal`]: crate::decimal::TPCHDecimal
+//! [`TPCHDate::to_unix_epoch`]: crate::dates::TPCHDate::to_unix_epoch
+
+use crate::dates::TPCHDate;
+use crate::decimal::TPCHDecimal;
+use crate::generators::{
+    ClerkName, Customer, CustomerGeneratorIterator, CustomerName, LineItem,
+    LineItemGeneratorIterator, Nation, NationGeneratorIterator, Order, OrderGeneratorIterator,
+    OrderStatus, Part, PartBrandName, PartGeneratorIterator, PartManufacturerName, PartSupp,
+    PartSuppGeneratorIterator, Region, RegionGeneratorIterator, Supplier,
+    SupplierGeneratorIterator, SupplierName,
+};
+use crate::random::{PhoneNumberInstance, RandomAlphaNumericInstance, StringSequenceInstance};
+use std::fmt::Display;
+use std::io::Write;
+
+/// A column of strings, stored as UTF-8 bytes and offsets into them
+///
+/// The value at index `i` is `bytes[offsets[i]..offsets[i + 1]]`, so there is
+/// one more offset than values. This is the same layout as the Arrow `Utf8`
+/// type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StringColumn {
+    offsets: Vec<i32>,
+    bytes: Vec<u8>,
+}
+
+impl Default for StringColumn {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StringColumn {
+    /// Creates a new empty column
+    pub fn new() -> Self {
+        Self {
+            offsets: vec![0],
+            bytes: Vec::new(),
+        }
+    }
+
+    /// Returns the number of values in the column
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns true if the column has no values
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all values, keeping the allocated buffers
+    pub fn clear(&mut self) {
+        self.offsets.truncate(1);
+        self.bytes.clear();
+    }
+
+    /// Appends a value
+    pub fn push_str(&mut self, value: &str) {
+        self.bytes.extend_from_slice(value.as_bytes());
+        self.push_offset();
+    }
+
+    /// Appends the formatted value, without an intermediate `String`
+    pub fn push_display(&mut self, value: &impl Display) {
+        write!(&mut self.bytes, "{value}").expect("writing to memory is infallible");
+        self.push_offset();
+    }
+
+    /// Returns the value at `index`
+    ///
+    /// # Panics
+    /// If `index` is out of bounds
+    pub fn get(&self, index: usize) -> &str {
+        let start = self.offsets[index] as usize;
+        let end = self.offsets[index + 1] as usize;
+        std::str::from_utf8(&self.bytes[start..end]).expect("values are valid UTF-8")
+    }
+
+    /// Returns an iterator over the values
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        (0..self.len()).map(|index| self.get(index))
+    }
+
+    /// Returns the offsets of the values in [`Self::bytes`], starting with 0
+    pub fn offsets(&self) -> &[i32] {
+        &self.offsets
+    }
+
+    /// Returns the UTF-8 bytes of all values
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn push_offset(&mut self) {
+        let offset = i32::try_from(self.bytes.len())
+            .expect("string column exceeds 2GB, fill fewer rows at a time");
+        self.offsets.push(offset);
+    }
+}
+
+/// The type of a column buffer of the given kind
+macro_rules! column_type {
+    (i64) => { Vec<i64> };
+    (i32) => { Vec<i32> };
+    (decimal) => { Vec<i64> };
+    (date) => { Vec<i32> };
+    (str) => { StringColumn };
+    (display) => { StringColumn };
+}
+
+/// Appends a field value to a column buffer of the given kind
+macro_rules! push_value {
+    (i64, $COLUMN:expr, $VALUE:expr) => {
+        $COLUMN.push($VALUE)
+    };
+    (i32, $COLUMN:expr, $VALUE:expr) => {
+        $COLUMN.push($VALUE)
+    };
+    (decimal, $COLUMN:expr, $VALUE:expr) => {
+        $COLUMN.push($VALUE.0)
+    };
+    (date, $COLUMN:expr, $VALUE:expr) => {
+        $COLUMN.push($VALUE.to_unix_epoch())
+    };
+    (str, $COLUMN:expr, $VALUE:expr) => {
+        $COLUMN.push_str(&$VALUE)
+    };
+    (display, $COLUMN:expr, $VALUE:expr) => {
+        $COLUMN.push_display(&$VALUE)
+    };
+}
+
+/// Builds the row structs of the generator iterators from the generated
+/// fields
+#[derive(Debug)]
+pub(crate) struct RowStructs;
+
+/// Defines the columns struct of a table, the sink trait receiving the fields
+/// of the generated rows, and the `fill_columns` method of the generator
+/// iterator of the table
+///
+/// Each field is the name and type of the row struct field, and the kind of
+/// column: `i64`, `i32`, `decimal`, `date`, `str` or `display` (for values
+/// that are formatted to strings).
+///
+/// The generator iterator draws the fields of each row from the random streams
+/// and passes them to the sink: [`RowStructs`] builds the row struct returned
+/// by `next`, and the columns struct appends them to its columns.
+macro_rules! define_columns {
+    (
+        $(#[$META:meta])*
+        $COLUMNS:ident, $SINK:ident<$LT:lifetime>, $ROW:ident$(<$ROW_LT:lifetime>)?,
+        $ITERATOR:ident {
+            $FIRST:ident: $FIRST_TYPE:ty => $FIRST_KIND:ident,
+            $($FIELD:ident: $TYPE:ty => $KIND:ident,)*
+        }
+    ) => {
+        $(#[$META])*
+        #[derive(Debug, Clone, Default, PartialEq)]
+        pub struct $COLUMNS {
+            pub $FIRST: column_type!($FIRST_KIND),
+            $(pub $FIELD: column_type!($KIND),)*
+        }
+
+        impl $COLUMNS {
+            /// Creates new empty columns
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Returns the number of rows
+            pub fn len(&self) -> usize {
+                self.$FIRST.len()
+            }
+
+            /// Returns true if there are no rows
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            /// Removes all rows, keeping the allocated buffers
+            pub fn clear(&mut self) {
+                self.$FIRST.clear();
+                $(self.$FIELD.clear();)*
+            }
+        }
+
+        impl<$LT> $COLUMNS {
+            /// Appends a row
+            pub fn push(&mut self, row: &$ROW$(<$ROW_LT>)?) {
+                push_value!($FIRST_KIND, self.$FIRST, row.$FIRST);
+                $(push_value!($KIND, self.$FIELD, row.$FIELD);)*
+            }
+        }
+
+        #[doc = concat!("Receives the fields of each generated [`", stringify!($ROW), "`]")]
+        pub(crate) trait $SINK<$LT> {
+            type Output;
+
+            #[allow(clippy::too_many_arguments)]
+            fn row(&mut self, $FIRST: $FIRST_TYPE, $($FIELD: $TYPE,)*) -> Self::Output;
+        }
+
+        impl<$LT> $SINK<$LT> for RowStructs {
+            type Output = $ROW$(<$ROW_LT>)?;
+
+            fn row(&mut self, $FIRST: $FIRST_TYPE, $($FIELD: $TYPE,)*) -> Self::Output {
+                $ROW { $FIRST, $($FIELD,)* }
+            }
+        }
+
+        impl<$LT> $SINK<$LT> for $COLUMNS {
+            type Output = ();
+
+            fn row(&mut self, $FIRST: $FIRST_TYPE, $($FIELD: $TYPE,)*) {
+                push_value!($FIRST_KIND, self.$FIRST, $FIRST);
+                $(push_value!($KIND, self.$FIELD, $FIELD);)*
+            }
+        }
+
+        impl $ITERATOR<'_> {
+            #[doc = concat!(
+                "Replaces the contents of `columns` with the next `n` rows (or ",
+                "fewer, if the iterator ends), returning the number of rows\n\n",
+                "The fields are written to the columns as they are generated, ",
+                "without creating the row structs. See [`", stringify!($COLUMNS), "`]"
+            )]
+            pub fn fill_columns(&mut self, columns: &mut $COLUMNS, n: usize) -> usize {
+                columns.clear();
+                while columns.len() < n && self.next_row(columns).is_some() {}
+                columns.len()
+            }
+        }
+    };
+}
+
+define_columns!(
+    /// Columns of the NATION table, see [`Nation`]
+    NationColumns, NationSink<'a>, Nation<'a>, NationGeneratorIterator {
+        n_nationkey: i64 => i64,
+        n_name: &'a str => str,
+        n_regionkey: i64 => i64,
+        n_comment: &'a str => str,
+    }
+);
+
+define_columns!(
+    /// Columns of the REGION table, see [`Region`]
+    RegionColumns, RegionSink<'a>, Region<'a>, RegionGeneratorIterator {
+        r_regionkey: i64 => i64,
+        r_name: &'a str => str,
+        r_comment: &'a str => str,
+    }
+);
+
+define_columns!(
+    /// Columns of the PART table, see [`Part`]
+    PartColumns, PartSink<'a>, Part<'a>, PartGeneratorIterator {
+        p_partkey: i64 => i64,
+        p_name: StringSequenceInstance<'a> => display,
+        p_mfgr: PartManufacturerName => display,
+        p_brand: PartBrandName => display,
+        p_type: &'a str => str,
+        p_size: i32 => i32,
+        p_container: &'a str => str,
+        p_retailprice: TPCHDecimal => decimal,
+        p_comment: &'a str => str,
+    }
+);
+
+define_columns!(
+    /// Columns of the SUPPLIER table, see [`Supplier`]
+    SupplierColumns, SupplierSink<'a>, Supplier, SupplierGeneratorIterator {
+        s_suppkey: i64 => i64,
+        s_name: SupplierName => display,
+        s_address: RandomAlphaNumericInstance => display,
+        s_nationkey: i64 => i64,
+        s_phone: PhoneNumberInstance => display,
+        s_acctbal: TPCHDecimal => decimal,
+        s_comment: String => str,
+    }
+);
+
+define_columns!(
+    /// Columns of the PARTSUPP table, see [`PartSupp`]
+    PartSuppColumns, PartSuppSink<'a>, PartSupp<'a>, PartSuppGeneratorIterator {
+        ps_partkey: i64 => i64,
+        ps_suppkey: i64 => i64,
+        ps_availqty: i32 => i32,
+        ps_supplycost: TPCHDecimal => decimal,
+        ps_comment: &'a str => str,
+    }
+);
+
+define_columns!(
+    /// Columns of the CUSTOMER table, see [`Customer`]
+    CustomerColumns, CustomerSink<'a>, Customer<'a>, CustomerGeneratorIterator {
+        c_custkey: i64 => i64,
+        c_name: CustomerName => display,
+        c_address: RandomAlphaNumericInstance => display,
+        c_nationkey: i64 => i64,
+        c_phone: PhoneNumberInstance => display,
+        c_acctbal: TPCHDecimal => decimal,
+        c_mktsegment: &'a str => str,
+        c_comment: &'a str => str,
+    }
+);
+
+define_columns!(
+    /// Columns of the ORDERS table, see [`Order`]
+    OrderColumns, OrderSink<'a>, Order<'a>, OrderGeneratorIterator {
+        o_orderkey: i64 => i64,
+        o_custkey: i64 => i64,
+        o_orderstatus: OrderStatus => display,
+        o_totalprice: TPCHDecimal => decimal,
+        o_orderdate: TPCHDate => date,
+        o_orderpriority: &'a str => str,
+        o_clerk: ClerkName => display,
+        o_shippriority: i32 => i32,
+        o_comment: &'a str => str,
+    }
+);
+
+define_columns!(
+    /// Columns of the LINEITEM table, see [`LineItem`]
+    ///
+    /// Note `l_quantity` is an integer, not a decimal.
+    LineItemColumns, LineItemSink<'a>, LineItem<'a>, LineItemGeneratorIterator {
+        l_orderkey: i64 => i64,
+        l_partkey: i64 => i64,
+        l_suppkey: i64 => i64,
+        l_linenumber: i32 => i32,
+        l_quantity: i64 => i64,
+        l_extendedprice: TPCHDecimal => decimal,
+        l_discount: TPCHDecimal => decimal,
+        l_tax: TPCHDecimal => decimal,
+        l_returnflag: &'a str => str,
+        l_linestatus: &'static str => str,
+        l_shipdate: TPCHDate => date,
+        l_commitdate: TPCHDate => date,
+        l_receiptdate: TPCHDate => date,
+        l_shipinstruct: &'a str => str,
+        l_shipmode: &'a str => str,
+        l_comment: &'a str => str,
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::{
+        CustomerGenerator, LineItemGenerator, NationGenerator, OrderGenerator, PartGenerator,
+        PartSuppGenerator, RegionGenerator, SupplierGenerator,
+    };
+
+    #[test]
+    fn test_string_column() {
+        let mut column = StringColumn::new();
+        assert!(column.is_empty());
+        column.push_str("abc");
+        column.push_str("");
+        column.push_display(&42);
+        assert_eq!(column.len(), 3);
+        assert_eq!(column.offsets(), &[0, 3, 3, 5]);
+        assert_eq!(column.bytes(), b"abc42");
+        assert_eq!(column.iter().collect::<Vec<_>>(), vec!["abc", "", "42"]);
+
+        column.clear();
+        assert!(column.is_empty());
+        assert_eq!(column.offsets(), &[0]);
+    }
+
+    #[test]
+    fn test_fill_line_item_columns() {
+        let rows: Vec<_> = LineItemGenerator::new(0.01, 1, 1).iter().collect();
+        let mut iter = LineItemGenerator::new(0.01, 1, 1).iter();
+        let mut columns = LineItemColumns::new();
+
+        let mut offset = 0;
+        loop {
+            let count = iter.fill_columns(&mut columns, 1000);
+            assert_eq!(count, columns.len());
+            if count == 0 {
+                break;
+            }
+            for (i, row) in rows[offset..offset + count].iter().enumerate() {
+                assert_eq!(columns.l_orderkey[i], row.l_orderkey);
+                assert_eq!(columns.l_quantity[i], row.l_quantity);
+                assert_eq!(columns.l_tax[i], row.l_tax.0);
+                assert_eq!(columns.l_receiptdate[i], row.l_receiptdate.to_unix_epoch());
+                assert_eq!(columns.l_returnflag.get(i), row.l_returnflag);
+                assert_eq!(columns.l_comment.get(i), row.l_comment);
+            }
+            offset += count;
+        }
+        assert_eq!(offset, rows.len());
+    }
+
+    #[test]
+    fn test_fill_customer_columns() {
+        let rows: Vec<_> = CustomerGenerator::new(0.01, 1, 1).iter().collect();
+        let mut columns = CustomerColumns::new();
+        let count = CustomerGenerator::new(0.01, 1, 1)
+            .iter()
+            .fill_columns(&mut columns, usize::MAX);
+        assert_eq!(count, rows.len());
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(columns.c_custkey[i], row.c_custkey);
+            assert_eq!(columns.c_name.get(i), row.c_name.to_string());
+            assert_eq!(columns.c_phone.get(i), row.c_phone.to_string());
+            assert_eq!(columns.c_acctbal[i], row.c_acctbal.0);
+        }
+    }
+
+    #[test]
+    fn test_fill_columns_matches_rows() {
+        /// Checks `fill_columns` generates the same columns as pushing the rows
+        macro_rules! check {
+            ($GENERATOR:expr, $COLUMNS:ident) => {
+                let mut expected = $COLUMNS::new();
+                for row in $GENERATOR.iter() {
+                    expected.push(&row);
+                }
+
+                // the previous contents are replaced
+                let mut columns = $COLUMNS::new();
+                columns.push(&$GENERATOR.iter().next().unwrap());
+                let count = $GENERATOR.iter().fill_columns(&mut columns, usize::MAX);
+                assert_eq!(count, expected.len());
+                assert_eq!(columns, expected);
+            };
+        }
+
+        check!(NationGenerator::default(), NationColumns);
+        check!(RegionGenerator::default(), RegionColumns);
+        check!(PartGenerator::new(0.01, 1, 1), PartColumns);
+        check!(SupplierGenerator::new(0.01, 1, 1), SupplierColumns);
+        check!(PartSuppGenerator::new(0.01, 1, 1), PartSuppColumns);
+        check!(CustomerGenerator::new(0.01, 1, 1), CustomerColumns);
+        check!(OrderGenerator::new(0.01, 1, 1), OrderColumns);
+        check!(LineItemGenerator::new(0.01, 2, 3), LineItemColumns);
+    }
+}
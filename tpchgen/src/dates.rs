@@ -1,6 +1,8 @@
 //! [`TPCHDate`] and date handling
 use std::{
     fmt::{Display, Formatter},
+    ops::Range,
+    str::FromStr,
     sync::LazyLock,
 };
 
@@ -10,30 +12,19 @@ pub const GENERATED_DATE_EPOCH_OFFSET: i32 = 83966;
 /// corresponding to `1992-01-01`.
 ///
 /// The maximum date that can be generated by the data generator is this value
-/// plus [`TOTAL_DATE_RANGE`].
+/// plus [`TOTAL_DATE_RANGE`], or the end of the [`DateRange`] of the
+/// generators.
 pub const MIN_GENERATE_DATE: i32 = 92001;
-const CURRENT_DATE: i32 = 95168;
-/// The total number of days that can be generated by the data generator
+/// The total number of days that can be generated by the data generator with
+/// the default [`DateRange`]
 pub const TOTAL_DATE_RANGE: i32 = 2557;
 
-/// Month boundaries for a standard (non-leap) year
-const MONTH_YEAR_DAY_START: [i32; 13] =
-    [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334, 365];
-
 /// Lookup table for converting dates from the data generator to strings
 ///
 /// index: dates generated by the data generator
 /// value: strings like 1992-01-01
 static DATE_TO_STRING: LazyLock<Vec<String>> = LazyLock::new(make_date_string_index);
 
-/// Lookup table for julian date format used to check if a given date is in
-/// the past as part of the lineitem generation.
-static JULIAN_DATE: LazyLock<Vec<i32>> = LazyLock::new(|| {
-    (0..TOTAL_DATE_RANGE)
-        .map(|date| julian(date + MIN_GENERATE_DATE))
-        .collect()
-});
-
 pub struct GenerateUtils;
 
 impl GenerateUtils {
@@ -66,6 +57,42 @@ impl GenerateUtils {
         let rows_per_part = total_row_count / part_count as i64;
         rows_per_part * (part as i64 - 1)
     }
+
+    /// Calculates the range of row indexes of a specific part of the data
+    pub fn calculate_range(
+        scale_base: i32,
+        scale_factor: f64,
+        part: i32,
+        part_count: i32,
+    ) -> Range<i64> {
+        let start_index = Self::calculate_start_index(scale_base, scale_factor, part, part_count);
+        start_index
+            ..start_index + Self::calculate_row_count(scale_base, scale_factor, part, part_count)
+    }
+
+    /// Splits a range of row indexes in `part_count` parts and returns the
+    /// range of part `part` (1-based)
+    ///
+    /// Like [`Self::calculate_row_count`], the remainder rows are added to the
+    /// last part, so the ranges of the parts of `0..N` are the same as the
+    /// ranges of the parts of a table with `N` rows.
+    pub fn split_range(range: Range<i64>, part: i64, part_count: i64) -> Range<i64> {
+        let rows_per_part = (range.end - range.start) / part_count;
+        let start = range.start + rows_per_part * (part - 1);
+        if part == part_count {
+            start..range.end
+        } else {
+            start..start + rows_per_part
+        }
+    }
+
+    /// Clamps a range of row indexes to the rows of a table with
+    /// `row_count` rows, returning an empty range if they do not overlap
+    pub fn clamp_range(range: Range<i64>, row_count: i64) -> Range<i64> {
+        let start = range.start.clamp(0, row_count);
+        let end = range.end.clamp(start, row_count);
+        start..end
+    }
 }
 
 /// Represents a date (day/year)
@@ -91,8 +118,16 @@ pub struct TPCHDate {
 
 impl Display for TPCHDate {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        // uses a pre-computed table to avoid recalculating the date
-        write!(f, "{}", &DATE_TO_STRING[self.date_index as usize])
+        // uses a pre-computed table to avoid recalculating the dates of the
+        // default range
+        match DATE_TO_STRING.get(self.date_index as usize) {
+            Some(date) => write!(f, "{date}"),
+            // dates of other date ranges are written without allocating
+            None => {
+                let (y, m, dy) = civil_from_days(self.to_unix_epoch());
+                write!(f, "{y:04}-{m:02}-{dy:02}")
+            }
+        }
     }
 }
 
@@ -121,7 +156,8 @@ impl TPCHDate {
     /// * month: `2`
     /// * day: `3`
     pub fn to_ymd(&self) -> (i32, i32, i32) {
-        to_ymd(self.date_index + 1)
+        let (year, month, day) = civil_from_days(self.to_unix_epoch());
+        (year - 1900, month, day)
     }
 
     /// Return the inner date index
@@ -129,14 +165,10 @@ impl TPCHDate {
         self.date_index
     }
 
-    /// Checks if a date is in the past
+    /// Checks if a generated date is not after the current date of the
+    /// default [`DateRange`] (1995-06-17), see [`DateRange::is_in_past`]
     pub fn is_in_past(date: i32) -> bool {
-        Self::to_julian(date) <= CURRENT_DATE
-    }
-
-    /// Lookup if a date is in the past.
-    fn to_julian(date: i32) -> i32 {
-        JULIAN_DATE[(date - MIN_GENERATE_DATE) as usize]
+        DateRange::DEFAULT.is_in_past(date)
     }
 
     /// Returns the number of days since the Unix epoch this date
@@ -145,76 +177,318 @@ impl TPCHDate {
     pub fn to_unix_epoch(&self) -> i32 {
         self.date_index + Self::UNIX_EPOCH_OFFSET
     }
+
+    /// Returns the same day `years` years later, or February 28 for February
+    /// 29 in a year that is not a leap year
+    pub(crate) fn add_years(self, years: i32) -> Self {
+        let (year, month, day) = civil_from_days(self.to_unix_epoch());
+        let year = year + years;
+        let day = day.min(days_in_month(year, month));
+        Self {
+            date_index: date_index(year, month, day),
+        }
+    }
 }
 
-/// Creates a index table of formatted strings
-///
-/// index: dates generated by the data generator
-/// value: strings like 1992-01-01
-fn make_date_string_index() -> Vec<String> {
-    (0..TOTAL_DATE_RANGE)
-        .map(|i| {
-            let (y, m, dy) = to_ymd(i + 1);
-            format_ymd(y, m, dy)
+impl FromStr for TPCHDate {
+    type Err = String;
+
+    /// Parses a `yyyy-mm-dd` date between `1992-01-01` and `9999-12-31`, the
+    /// dates of the data generator with any [`DateRange`]
+    ///
+    /// # Example
+    /// ```
+    /// # use tpchgen::dates::{TPCHDate, MIN_GENERATE_DATE};
+    /// let date: TPCHDate = "1992-02-11".parse().unwrap();
+    /// assert_eq!(date, TPCHDate::new(MIN_GENERATE_DATE + 41));
+    /// assert!("1991-12-31".parse::<TPCHDate>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid date '{s}', expected yyyy-mm-dd");
+        let fields: Vec<_> = s.split('-').collect();
+        let [year, month, day] = fields[..] else {
+            return Err(invalid());
+        };
+        let number = |field: &str, len: usize| {
+            if field.len() == len && field.bytes().all(|b| b.is_ascii_digit()) {
+                field.parse::<i32>().map_err(|_| invalid())
+            } else {
+                Err(invalid())
+            }
+        };
+        let (year, month, day) = (number(year, 4)?, number(month, 2)?, number(day, 2)?);
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return Err(invalid());
+        }
+
+        if year < DateRange::MIN_YEAR {
+            return Err(format!(
+                "Date '{s}' is outside the range of TPC-H dates (1992-01-01 to 9999-12-31)"
+            ));
+        }
+        Ok(Self {
+            date_index: date_index(year, month, day),
         })
-        .collect()
+    }
 }
 
-/// Returns the (year, month, day) for the specified date index
+/// The years of the generated dates
+///
+/// The TPC-H data has dates from 1992-01-01 to 1998-12-31, and the status of
+/// the orders and line items depends on whether they were shipped and
+/// received before the current date, 1995-06-17. A `DateRange` moves these
+/// dates to other years, e.g. 2019 to 2025, and can extend them by whole
+/// years:
+///
+/// * the order dates are spread over all the days of the range, except the
+///   last [`ITEM_SHIP_DAYS`] so that all the line items are received in the
+///   range
+/// * the ship, commit and receipt dates are the same number of days after
+///   the order date as with the default range
+/// * the current date is June 17 of the middle year of the range (the fourth
+///   of 7 years)
+///
+/// Only the order dates and the current date change, so a range of 7 years
+/// has the same rows as the default range, with dates in other years. The
+/// dates of the leap years of the range exist, and the other February 29
+/// do not.
+///
+/// A `DateRange` can be parsed from `first-last`, e.g. `2019-2026`, or from a
+/// single year, the first of 7 years.
+///
+/// # Example
+/// ```
+/// # use tpchgen::dates::DateRange;
+/// let date_range: DateRange = "2019-2026".parse().unwrap();
+/// assert_eq!(date_range.start_date().to_string(), "2019-01-01");
+/// assert_eq!(date_range.end_date().to_string(), "2026-12-31");
+/// assert_eq!(date_range.current_date().to_string(), "2022-06-17");
 ///
-/// See [`TPCHDate::to_ymd`] for more information
-fn to_ymd(index: i32) -> (i32, i32, i32) {
-    let y = julian(index + MIN_GENERATE_DATE - 1) / 1000;
-    let d = julian(index + MIN_GENERATE_DATE - 1) % 1000;
+/// assert_eq!(DateRange::default().to_string(), "1992-1998");
+/// assert_eq!("2019".parse(), DateRange::new(2019, 2025));
+/// assert!("2019-2024".parse::<DateRange>().is_err());
+/// ```
+///
+/// [`ITEM_SHIP_DAYS`]: crate::generators::LineItemGenerator::ITEM_SHIP_DAYS
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateRange {
+    first_year: i32,
+    last_year: i32,
+    /// index of the first date, the `date_index` of [`TPCHDate`]
+    start_index: i32,
+    /// index of the day after the last date
+    end_index: i32,
+    /// index of the current date
+    current_index: i32,
+}
 
-    let mut m = 0;
-    while d > MONTH_YEAR_DAY_START[m as usize] + leap_year_adjustment(y, m) {
-        m += 1;
+impl DateRange {
+    /// The range of the TPC-H dates, 1992 to 1998
+    pub const DEFAULT: DateRange = DateRange::from_years(1992, 1998);
+    /// The first possible year
+    pub const MIN_YEAR: i32 = 1992;
+    /// The last possible year
+    pub const MAX_YEAR: i32 = 9999;
+    /// The minimum number of years of a range, the years of the TPC-H dates
+    pub const MIN_YEAR_COUNT: i32 = 7;
+
+    /// Creates the range from January 1 of `first_year` to December 31 of
+    /// `last_year`
+    ///
+    /// Returns an error if the years are not from [`Self::MIN_YEAR`] to
+    /// [`Self::MAX_YEAR`], or if there are less than [`Self::MIN_YEAR_COUNT`]
+    /// years.
+    pub fn new(first_year: i32, last_year: i32) -> Result<Self, String> {
+        if first_year < Self::MIN_YEAR || last_year > Self::MAX_YEAR {
+            return Err(format!(
+                "Invalid date range {first_year}-{last_year}, the years must be from {} to {}",
+                Self::MIN_YEAR,
+                Self::MAX_YEAR
+            ));
+        }
+        if last_year - first_year + 1 < Self::MIN_YEAR_COUNT {
+            return Err(format!(
+                "Invalid date range {first_year}-{last_year}, the range must have at least {} years",
+                Self::MIN_YEAR_COUNT
+            ));
+        }
+        Ok(Self::from_years(first_year, last_year))
     }
 
-    let dy =
-        d - MONTH_YEAR_DAY_START[(m - 1) as usize] - if is_leap_year(y) && m > 2 { 1 } else { 0 };
+    const fn from_years(first_year: i32, last_year: i32) -> Self {
+        let current_year = first_year + (last_year - first_year) / 2;
+        Self {
+            first_year,
+            last_year,
+            start_index: date_index(first_year, 1, 1),
+            end_index: date_index(last_year + 1, 1, 1),
+            current_index: date_index(current_year, 6, 17),
+        }
+    }
 
-    (y, m, dy)
-}
+    /// Returns the first year of the range
+    pub fn first_year(&self) -> i32 {
+        self.first_year
+    }
 
-/// Formants the specified y, m, d as `yyyy-mm-dd`
-fn format_ymd(y: i32, m: i32, dy: i32) -> String {
-    format!("{:04}-{:02}-{:02}", 1900 + y, m, dy)
-}
+    /// Returns the last year of the range
+    pub fn last_year(&self) -> i32 {
+        self.last_year
+    }
 
-/// Helpers duplicated to avoid circular references
-const fn julian(date: i32) -> i32 {
-    let mut offset = date - MIN_GENERATE_DATE;
-    let mut result = MIN_GENERATE_DATE;
+    /// Returns the first date of the range, January 1 of the first year
+    pub fn start_date(&self) -> TPCHDate {
+        TPCHDate {
+            date_index: self.start_index,
+        }
+    }
 
-    loop {
-        let year = result / 1000;
-        let year_end = year * 1000 + 365 + if is_leap_year(year) { 1 } else { 0 };
+    /// Returns the last date of the range, December 31 of the last year
+    pub fn end_date(&self) -> TPCHDate {
+        TPCHDate {
+            date_index: self.end_index - 1,
+        }
+    }
 
-        if result + offset <= year_end {
-            break;
+    /// Returns the current date, June 17 of the middle year
+    ///
+    /// The line items shipped (received) on or before this date have the
+    /// `l_linestatus` `F` (an `l_returnflag` other than `N`).
+    pub fn current_date(&self) -> TPCHDate {
+        TPCHDate {
+            date_index: self.current_index,
         }
+    }
+
+    /// Returns the number of days of the range
+    pub fn day_count(&self) -> i32 {
+        self.end_index - self.start_index
+    }
+
+    /// Returns the number of years between the current date of this range
+    /// and the current date of the default range
+    ///
+    /// The current date is in the middle year of the range (1995 for the
+    /// default range 1992-1998), so the shift is the difference of the
+    /// middle years. For a range that is longer than 7 years, the dates that
+    /// are anchored at the end of the default range (e.g. `1998-12-01` in Q1)
+    /// land before the end of the range: with 2000-2009 the shift is 9 years
+    /// and the Q1 cutoff is `2007-12-01`.
+    ///
+    /// The dates of the queries are moved by this number of years, see
+    /// [`QueryParameters::with_date_range`].
+    ///
+    /// [`QueryParameters::with_date_range`]: crate::q_and_a::parameters::QueryParameters::with_date_range
+    pub fn year_shift(&self) -> i32 {
+        let middle_year =
+            |range: &Self| range.first_year + (range.last_year - range.first_year) / 2;
+        middle_year(self) - middle_year(&Self::DEFAULT)
+    }
 
-        offset -= year_end - result + 1;
-        result += 1000;
+    /// Returns the first generated date of the range, see
+    /// [`MIN_GENERATE_DATE`]
+    pub fn min_generate_date(&self) -> i32 {
+        MIN_GENERATE_DATE + self.start_index
     }
 
-    result + offset
+    /// Checks if a generated date is not after the current date
+    #[inline(always)]
+    pub fn is_in_past(&self, date: i32) -> bool {
+        date - MIN_GENERATE_DATE <= self.current_index
+    }
 }
 
-const fn is_leap_year(year: i32) -> bool {
-    year % 4 == 0 && year % 100 != 0
+impl Default for DateRange {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Display for DateRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.first_year, self.last_year)
+    }
 }
 
-const fn leap_year_adjustment(year: i32, month: i32) -> i32 {
-    if is_leap_year(year) && month >= 2 {
-        1
-    } else {
-        0
+impl FromStr for DateRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = |year: &str| {
+            year.trim()
+                .parse::<i32>()
+                .map_err(|_| format!("Invalid date range '{s}', expected a year or first-last"))
+        };
+        match s.split_once('-') {
+            Some((first_year, last_year)) => Self::new(year(first_year)?, year(last_year)?),
+            None => {
+                let first_year = year(s)?;
+                Self::new(first_year, first_year + Self::MIN_YEAR_COUNT - 1)
+            }
+        }
     }
 }
 
+/// Returns the number of days of `month` (1 to 12) of the Gregorian `year`
+fn days_in_month(year: i32, month: i32) -> i32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days between 1970-01-01 and the Gregorian date
+const fn days_since_unix_epoch(year: i32, month: i32, day: i32) -> i32 {
+    // years starting in March, so that the leap day is the last day
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Returns the `date_index` of [`TPCHDate`] of the Gregorian date
+const fn date_index(year: i32, month: i32, day: i32) -> i32 {
+    days_since_unix_epoch(year, month, day) - TPCHDate::UNIX_EPOCH_OFFSET
+}
+
+/// Returns the Gregorian (year, month, day) of the date `days` days after
+/// 1970-01-01, the inverse of [`days_since_unix_epoch`]
+fn civil_from_days(days: i32) -> (i32, i32, i32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Creates a index table of formatted strings
+///
+/// index: dates generated by the data generator
+/// value: strings like 1992-01-01
+fn make_date_string_index() -> Vec<String> {
+    (0..TOTAL_DATE_RANGE)
+        .map(|i| {
+            let (y, m, dy) = TPCHDate { date_index: i }.to_ymd();
+            format_ymd(y, m, dy)
+        })
+        .collect()
+}
+
+/// Formants the specified y, m, d as `yyyy-mm-dd`
+fn format_ymd(y: i32, m: i32, dy: i32) -> String {
+    format!("{:04}-{:02}-{:02}", 1900 + y, m, dy)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -237,6 +511,12 @@ mod test {
             let (y, m, dy) = date.to_ymd();
             assert_eq!(format_ymd(y, m, dy), date.to_string());
         }
+
+        // dates after the default range are not in the pre-computed table
+        let date = TPCHDate::new(MIN_GENERATE_DATE + TOTAL_DATE_RANGE);
+        assert_eq!(date.to_string(), "1999-01-01");
+        let date: TPCHDate = "2024-02-29".parse().unwrap();
+        assert_eq!(date.to_string(), "2024-02-29");
     }
 
     #[test]
@@ -250,4 +530,95 @@ mod test {
         assert_eq!(date.to_string(), "1995-05-19");
         assert_eq!(date.to_unix_epoch(), 9269);
     }
+
+    #[test]
+    fn test_parse_dates() {
+        for index in 0..TOTAL_DATE_RANGE {
+            let date = TPCHDate::new(MIN_GENERATE_DATE + index);
+            assert_eq!(date.to_string().parse(), Ok(date));
+        }
+        for input in [
+            "1995-02-29",
+            "1995-13-01",
+            "1995-00-10",
+            "1995-1-10",
+            "95-01-10",
+            "1995/01/10",
+            "1995-01-10-1",
+            "+995-01-10",
+        ] {
+            assert_eq!(
+                input.parse::<TPCHDate>(),
+                Err(format!("Invalid date '{input}', expected yyyy-mm-dd"))
+            );
+        }
+        assert_eq!(
+            "1991-12-31".parse::<TPCHDate>(),
+            Err(
+                "Date '1991-12-31' is outside the range of TPC-H dates (1992-01-01 to 9999-12-31)"
+                    .to_string()
+            )
+        );
+        // the dates of other date ranges
+        let date: TPCHDate = "2024-02-29".parse().unwrap();
+        assert_eq!(date.to_string(), "2024-02-29");
+        assert_eq!(date.to_ymd(), (124, 2, 29));
+        assert_eq!(date.add_years(1).to_string(), "2025-02-28");
+        assert_eq!(date.add_years(-4).to_string(), "2020-02-29");
+        assert_eq!(
+            "2100-02-29".parse::<TPCHDate>(),
+            Err("Invalid date '2100-02-29', expected yyyy-mm-dd".to_string())
+        );
+    }
+
+    #[test]
+    fn test_date_range() {
+        let default = DateRange::default();
+        assert_eq!(default, DateRange::new(1992, 1998).unwrap());
+        assert_eq!(default.start_date(), TPCHDate::new(MIN_GENERATE_DATE));
+        assert_eq!(
+            default.end_date(),
+            TPCHDate::new(MIN_GENERATE_DATE + TOTAL_DATE_RANGE - 1)
+        );
+        assert_eq!(default.day_count(), TOTAL_DATE_RANGE);
+        assert_eq!(default.min_generate_date(), MIN_GENERATE_DATE);
+        assert_eq!(default.current_date().to_string(), "1995-06-17");
+        assert_eq!(default.year_shift(), 0);
+        // the dates up to 1995-06-17 are in the past
+        for index in 0..TOTAL_DATE_RANGE {
+            let date = TPCHDate::new(MIN_GENERATE_DATE + index);
+            assert_eq!(
+                TPCHDate::is_in_past(MIN_GENERATE_DATE + index),
+                date.to_string().as_str() <= "1995-06-17"
+            );
+        }
+
+        let shifted: DateRange = "2019".parse().unwrap();
+        assert_eq!(shifted.to_string(), "2019-2025");
+        assert_eq!(shifted.start_date().to_string(), "2019-01-01");
+        assert_eq!(shifted.end_date().to_string(), "2025-12-31");
+        assert_eq!(shifted.current_date().to_string(), "2022-06-17");
+        // 2020 and 2024 are leap years, like 1992 and 1996
+        assert_eq!(shifted.day_count(), TOTAL_DATE_RANGE);
+        assert_eq!(shifted.year_shift(), 27);
+
+        let extended: DateRange = "2000-2009".parse().unwrap();
+        assert_eq!(extended.day_count(), 3653);
+        assert_eq!(extended.current_date().to_string(), "2004-06-17");
+        assert!(extended.is_in_past(extended.min_generate_date() + 1629));
+        assert!(!extended.is_in_past(extended.min_generate_date() + 1630));
+
+        assert_eq!(
+            "1991-1998".parse::<DateRange>(),
+            Err("Invalid date range 1991-1998, the years must be from 1992 to 9999".to_string())
+        );
+        assert_eq!(
+            "2019-2024".parse::<DateRange>(),
+            Err("Invalid date range 2019-2024, the range must have at least 7 years".to_string())
+        );
+        assert_eq!(
+            "2019-".parse::<DateRange>(),
+            Err("Invalid date range '2019-', expected a year or first-last".to_string())
+        );
+    }
 }
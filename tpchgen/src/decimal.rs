@@ -1,6 +1,7 @@
 //! [`TPCHDecimal`] and decimal handling
 
 use std::fmt;
+use std::str::FromStr;
 
 /// Represents a decimal with a scale of 2.
 ///
@@ -66,6 +67,46 @@ impl fmt::Display for TPCHDecimal {
     }
 }
 
+impl FromStr for TPCHDecimal {
+    type Err = String;
+
+    /// Parses a decimal with at most 2 digits after the decimal point
+    ///
+    /// # Example
+    /// ```
+    /// use tpchgen::decimal::TPCHDecimal;
+    /// assert_eq!("-12.34".parse(), Ok(TPCHDecimal::new(-1234)));
+    /// assert_eq!("0.5".parse(), Ok(TPCHDecimal::new(50)));
+    /// assert_eq!("7".parse(), Ok(TPCHDecimal::new(700)));
+    /// assert!("1.234".parse::<TPCHDecimal>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid decimal '{s}'");
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        let (int, fraction) = match digits.split_once('.') {
+            Some((int, fraction)) if !fraction.is_empty() && fraction.len() <= 2 => (int, fraction),
+            Some(_) => return Err(invalid()),
+            None => (digits, ""),
+        };
+        if int.is_empty() || !(int.bytes().chain(fraction.bytes())).all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let cents = format!("{fraction:0<2}")
+            .parse::<i64>()
+            .map_err(|_| invalid())?;
+        let value = int
+            .parse::<i64>()
+            .ok()
+            .and_then(|int| int.checked_mul(100))
+            .and_then(|value| value.checked_add(cents))
+            .ok_or_else(invalid)?;
+        Ok(TPCHDecimal(if negative { -value } else { value }))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,6 +178,28 @@ mod tests {
                 "input decimal: {:?}",
                 test_case.decimal,
             );
+            assert_eq!(formatted.parse(), Ok(test_case.decimal));
+        }
+    }
+
+    #[test]
+    fn decimal_parse_errors() {
+        for input in [
+            "",
+            "-",
+            ".5",
+            "1.",
+            "1.234",
+            "1,5",
+            "+1",
+            "1e3",
+            "--1",
+            "99999999999999999999",
+        ] {
+            assert_eq!(
+                input.parse::<TPCHDecimal>(),
+                Err(format!("Invalid decimal '{input}'"))
+            );
         }
     }
 }
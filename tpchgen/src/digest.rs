@@ -0,0 +1,474 @@
+//! [`TableDigest`]: order independent digests of the rows of a table.
+//!
+//! Comparing the checksums of whole files (e.g. with `shasum`) only works
+//! for a table written to a single file in a given format. A [`TableDigest`]
+//! is computed from the TBL text of each row instead, and combines the rows
+//! in any order, so:
+//!
+//! * the digests of the parts of a table (e.g. the files of `--parts`) add
+//!   up to the digest of the whole table, however it is split
+//! * the digest is the same for TBL, CSV and Parquet files, once their rows
+//!   are converted to TBL (e.g. with the [`owned`] rows)
+//!
+//! [`TableDigest::known`] returns the digests of the tables of the standard
+//! TPC-H data for common scale factors ([`KNOWN_SCALE_FACTORS`]), and
+//! [`TableDigest::generate`] computes the digest of any part of a table.
+//!
+//! The digest is the number of rows and the sum (modulo 2^64) of a 64-bit
+//! hash of each row. It detects missing, extra and modified rows, but it is
+//! not a cryptographic hash.
+//!
+//! # Example
+//! ```
+//! # use tpchgen::digest::TableDigest;
+//! # use tpchgen::generators::NationGenerator;
+//! # use tpchgen::table::Table;
+//! let generator = NationGenerator::default();
+//! let digest = TableDigest::from_rows(generator.iter());
+//! assert_eq!(digest.row_count(), 25);
+//! assert_eq!(Some(digest), TableDigest::known(Table::Nation, 1.0));
+//!
+//! // the rows in any order, split in any number of parts
+//! let mut rows: Vec<_> = generator.iter().map(|row| row.to_string()).collect();
+//! rows.reverse();
+//! let (first, second) = rows.split_at(10);
+//! let mut first_digest = TableDigest::new();
+//! first.iter().for_each(|line| first_digest.add_line(line));
+//! let mut second_digest = TableDigest::new();
+//! second.iter().for_each(|line| second_digest.add_line(line));
+//! assert_eq!(first_digest + second_digest, digest);
+//! ```
+//!
+//! [`owned`]: crate::owned
+
+use crate::dates::DateRange;
+use crate::generators::{
+    CustomerGenerator, LineItemGenerator, NationGenerator, OrderGenerator, PartGenerator,
+    PartSuppGenerator, RegionGenerator, SupplierGenerator,
+};
+use crate::skew::Skew;
+use crate::table::{Table, TableGenerator};
+use std::fmt::{self, Write};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+use std::str::FromStr;
+
+/// The scale factors with [known](TableDigest::known) digests
+pub const KNOWN_SCALE_FACTORS: [f64; 4] = [0.01, 0.1, 1.0, 10.0];
+
+/// Order independent digest of the rows of a table, see the
+/// [module docs](self)
+///
+/// The `Display` and `FromStr` impls use the format
+/// `<row count>:<hash as 16 hexadecimal digits>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TableDigest {
+    row_count: u64,
+    hash: u64,
+}
+
+impl TableDigest {
+    /// Creates the digest of no rows
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the digest of `rows`, whose `Display` impl writes the TBL
+    /// format
+    pub fn from_rows<T: fmt::Display>(rows: impl IntoIterator<Item = T>) -> Self {
+        let mut digest = Self::new();
+        for row in rows {
+            digest.add_row(&row);
+        }
+        digest
+    }
+
+    /// Adds a row whose `Display` impl writes the TBL format, such as the rows
+    /// of the generators and the [`owned`](crate::owned) rows
+    pub fn add_row(&mut self, row: &impl fmt::Display) {
+        let mut hasher = RowHasher::new();
+        write!(hasher, "{row}").expect("hashing can not fail");
+        self.add_hash(hasher.finish());
+    }
+
+    /// Adds a row in TBL format, without the line terminator
+    pub fn add_line(&mut self, line: &str) {
+        let mut hasher = RowHasher::new();
+        hasher.write_bytes(line.as_bytes());
+        self.add_hash(hasher.finish());
+    }
+
+    fn add_hash(&mut self, hash: u64) {
+        self.row_count += 1;
+        self.hash = self.hash.wrapping_add(hash);
+    }
+
+    /// Returns the number of rows
+    pub fn row_count(&self) -> u64 {
+        self.row_count
+    }
+
+    /// Computes the digest of part `part` of `part_count` of `table`, generated
+    /// with `seed`, `skew` and `date_range` (see [`TableGenerator`])
+    pub fn generate(
+        table: Table,
+        scale_factor: f64,
+        part: i32,
+        part_count: i32,
+        seed: u64,
+        skew: Skew,
+        date_range: DateRange,
+    ) -> Self {
+        fn generate<'a, G: TableGenerator<'a>>(
+            scale_factor: f64,
+            part: i32,
+            part_count: i32,
+            seed: u64,
+            skew: Skew,
+            date_range: DateRange,
+        ) -> TableDigest {
+            let generator = G::new(scale_factor, part, part_count)
+                .with_seed(seed)
+                .with_skew(skew)
+                .with_date_range(date_range);
+            TableDigest::from_rows(generator)
+        }
+
+        match table {
+            Table::Nation => {
+                generate::<NationGenerator>(scale_factor, part, part_count, seed, skew, date_range)
+            }
+            Table::Region => {
+                generate::<RegionGenerator>(scale_factor, part, part_count, seed, skew, date_range)
+            }
+            Table::Part => {
+                generate::<PartGenerator>(scale_factor, part, part_count, seed, skew, date_range)
+            }
+            Table::Supplier => generate::<SupplierGenerator>(
+                scale_factor,
+                part,
+                part_count,
+                seed,
+                skew,
+                date_range,
+            ),
+            Table::Partsupp => generate::<PartSuppGenerator>(
+                scale_factor,
+                part,
+                part_count,
+                seed,
+                skew,
+                date_range,
+            ),
+            Table::Customer => generate::<CustomerGenerator>(
+                scale_factor,
+                part,
+                part_count,
+                seed,
+                skew,
+                date_range,
+            ),
+            Table::Orders => {
+                generate::<OrderGenerator>(scale_factor, part, part_count, seed, skew, date_range)
+            }
+            Table::Lineitem => generate::<LineItemGenerator>(
+                scale_factor,
+                part,
+                part_count,
+                seed,
+                skew,
+                date_range,
+            ),
+        }
+    }
+
+    /// Returns the digest of `table` in the standard TPC-H data (seed 0, no
+    /// skew) at `scale_factor`, if it is one of the [`KNOWN_SCALE_FACTORS`]
+    ///
+    /// The NATION and REGION tables are the same for all scale factors.
+    pub fn known(table: Table, scale_factor: f64) -> Option<Self> {
+        let index = KNOWN_SCALE_FACTORS
+            .iter()
+            .position(|&known| known == scale_factor)?;
+        let digest = match table {
+            Table::Nation => NATION_DIGEST,
+            Table::Region => REGION_DIGEST,
+            _ => {
+                let position = Table::ALL.iter().position(|&t| t == table)?;
+                KNOWN_DIGESTS[index][position - 2]
+            }
+        };
+        Some(digest)
+    }
+}
+
+impl Add for TableDigest {
+    type Output = TableDigest;
+
+    fn add(mut self, other: TableDigest) -> TableDigest {
+        self += other;
+        self
+    }
+}
+
+impl AddAssign for TableDigest {
+    /// Combines the digests of two sets of rows
+    fn add_assign(&mut self, other: TableDigest) {
+        self.row_count += other.row_count;
+        self.hash = self.hash.wrapping_add(other.hash);
+    }
+}
+
+impl Sum for TableDigest {
+    fn sum<I: Iterator<Item = TableDigest>>(iter: I) -> Self {
+        iter.fold(TableDigest::new(), Add::add)
+    }
+}
+
+impl fmt::Display for TableDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{:016x}", self.row_count, self.hash)
+    }
+}
+
+impl FromStr for TableDigest {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid table digest '{s}', expected <row count>:<hash>");
+        let (row_count, hash) = s.split_once(':').ok_or_else(invalid)?;
+        if hash.len() != 16 {
+            return Err(invalid());
+        }
+        Ok(Self {
+            row_count: row_count.parse().map_err(|_| invalid())?,
+            hash: u64::from_str_radix(hash, 16).map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Hash of the text of a row: 64-bit FNV-1a, with the finalizer of
+/// MurmurHash3 so that the bits of the sum of the hashes are well mixed
+struct RowHasher(u64);
+
+impl RowHasher {
+    fn new() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn finish(self) -> u64 {
+        let mut hash = self.0;
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+        hash ^ (hash >> 33)
+    }
+}
+
+impl Write for RowHasher {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_bytes(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Shorthand for the digests in [`KNOWN_DIGESTS`]
+const fn digest(row_count: u64, hash: u64) -> TableDigest {
+    TableDigest { row_count, hash }
+}
+
+const NATION_DIGEST: TableDigest = digest(25, 0x444704f174554371);
+const REGION_DIGEST: TableDigest = digest(5, 0x65356f73047d1a40);
+
+/// The digests of the PART, SUPPLIER, PARTSUPP, CUSTOMER, ORDERS and LINEITEM
+/// tables (in the order of [`Table::ALL`]) for each of the
+/// [`KNOWN_SCALE_FACTORS`]
+///
+/// The digests were computed with [`TableDigest::generate`] from the
+/// generated tables, whose TBL files have the `dbgen` checksums listed in
+/// `TESTING.md`. `test_known_digests` checks the SF 0.01 digests, and
+/// `test_known_digests_large` (ignored, run it in release mode) regenerates
+/// the SF 0.1 and SF 1 digests:
+///
+/// ```shell
+/// cargo test --release -p tpchgen --lib -- --ignored test_known_digests_large
+/// ```
+///
+/// The SF 10 digests were checked the same way, which takes a few minutes.
+const KNOWN_DIGESTS: [[TableDigest; 6]; 4] = [
+    // SF 0.01
+    [
+        digest(2000, 0xd3309df9fc9e5d60),
+        digest(100, 0x912eabe7195147a9),
+        digest(8000, 0x1a958f9d99c7fb62),
+        digest(1500, 0x512826205112c6d0),
+        digest(15000, 0xef86a6dfde6e96c5),
+        digest(60175, 0xfdffc408c1fb8f45),
+    ],
+    // SF 0.1
+    [
+        digest(20000, 0xb6de0314de8ddbed),
+        digest(1000, 0x41571d0cdd7d7f87),
+        digest(80000, 0x0a276745a7c8027a),
+        digest(15000, 0xf49fba2c8df2db74),
+        digest(150000, 0x3509c3a183bf0b38),
+        digest(600572, 0x6c6d4a63d14e6507),
+    ],
+    // SF 1
+    [
+        digest(200000, 0xbb5c8061d144c278),
+        digest(10000, 0xb17b6d1e127bdaa4),
+        digest(800000, 0xdd494bb5a50f0d47),
+        digest(150000, 0xb07e7969b0593bbe),
+        digest(1500000, 0x9c7b8e5f22272fb7),
+        digest(6001215, 0x077d53de183c2f24),
+    ],
+    // SF 10
+    [
+        digest(2000000, 0x3fd6f7701c4be614),
+        digest(100000, 0x5d6e2d515feed869),
+        digest(8000000, 0xefaa1b31c3bbf794),
+        digest(1500000, 0x56b306f44a4cd378),
+        digest(15000000, 0xc5aa63dccff0d93a),
+        digest(59986052, 0x71f98e10681abe39),
+    ],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_digests() {
+        for table in Table::ALL {
+            let digest =
+                TableDigest::generate(table, 0.01, 1, 1, 0, Skew::default(), DateRange::default());
+            assert_eq!(TableDigest::known(table, 0.01), Some(digest), "{table}");
+            if matches!(table, Table::Nation | Table::Region) {
+                // not partitioned
+                continue;
+            }
+            // the parts add up to the whole table
+            let parts: TableDigest = (1..=3)
+                .map(|part| {
+                    TableDigest::generate(
+                        table,
+                        0.01,
+                        part,
+                        3,
+                        0,
+                        Skew::default(),
+                        DateRange::default(),
+                    )
+                })
+                .sum();
+            assert_eq!(parts, digest, "{table}");
+        }
+        assert_eq!(
+            TableDigest::known(Table::Lineitem, 1.0).map(|digest| digest.row_count()),
+            Some(6001215)
+        );
+        assert_eq!(TableDigest::known(Table::Lineitem, 2.0), None);
+        // other datasets have other digests
+        assert_ne!(
+            TableDigest::generate(
+                Table::Orders,
+                0.01,
+                1,
+                1,
+                7,
+                Skew::default(),
+                DateRange::default()
+            ),
+            TableDigest::known(Table::Orders, 0.01).unwrap()
+        );
+        let date_range = DateRange::new(2019, 2025).unwrap();
+        assert_ne!(
+            TableDigest::generate(Table::Orders, 0.01, 1, 1, 0, Skew::default(), date_range),
+            TableDigest::known(Table::Orders, 0.01).unwrap()
+        );
+        assert_eq!(
+            TableDigest::generate(Table::Part, 0.01, 1, 1, 0, Skew::default(), date_range),
+            TableDigest::known(Table::Part, 0.01).unwrap()
+        );
+    }
+
+    /// Regenerates the SF 0.1 and SF 1 digests, too slow for debug builds
+    #[test]
+    #[ignore]
+    fn test_known_digests_large() {
+        for scale_factor in [0.1, 1.0] {
+            for table in Table::ALL {
+                // generate the parts in parallel, NATION and REGION are not
+                // partitioned
+                let part_count = match table {
+                    Table::Nation | Table::Region => 1,
+                    _ => 8,
+                };
+                let digest = std::thread::scope(|scope| {
+                    let parts: Vec<_> = (1..=part_count)
+                        .map(|part| {
+                            scope.spawn(move || {
+                                TableDigest::generate(
+                                    table,
+                                    scale_factor,
+                                    part,
+                                    part_count,
+                                    0,
+                                    Skew::default(),
+                                    DateRange::default(),
+                                )
+                            })
+                        })
+                        .collect();
+                    parts
+                        .into_iter()
+                        .map(|part| part.join().unwrap())
+                        .sum::<TableDigest>()
+                });
+                assert_eq!(
+                    TableDigest::known(table, scale_factor),
+                    Some(digest),
+                    "{table} at SF {scale_factor}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_digest_changes() {
+        let lines = ["0|AFRICA|lar deposits|", "1|AMERICA|hs use ironic|"];
+        let mut digest = TableDigest::new();
+        lines.iter().for_each(|line| digest.add_line(line));
+
+        let mut modified = TableDigest::new();
+        modified.add_line(lines[0]);
+        modified.add_line("1|AMERICA|hs use ironic.|");
+        assert_ne!(modified, digest);
+
+        let mut duplicated = digest;
+        duplicated.add_line(lines[1]);
+        assert_eq!(duplicated.row_count(), 3);
+        assert_ne!(duplicated, digest);
+    }
+
+    #[test]
+    fn test_display_and_parse() {
+        let digest = TableDigest::known(Table::Region, 1.0).unwrap();
+        assert_eq!(digest.to_string(), "5:65356f73047d1a40");
+        assert_eq!("5:65356f73047d1a40".parse(), Ok(digest));
+        assert_eq!(
+            "5:65356f73".parse::<TableDigest>(),
+            Err("Invalid table digest '5:65356f73', expected <row count>:<hash>".to_string())
+        );
+        assert!("x:65356f73047d1a40".parse::<TableDigest>().is_err());
+    }
+}
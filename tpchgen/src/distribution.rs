@@ -1,5 +1,7 @@
+use crate::generators::PartGenerator;
 use crate::random::RowRandomInt;
 use std::{
+    collections::HashMap,
     fs,
     io::{self},
     path::Path,
@@ -9,25 +11,34 @@ use std::{
 /// TPC-H distributions seed file.
 pub(crate) const DISTS_SEED: &str = include_str!("dists.dss");
 
+/// Multilingual words of the text, see
+/// [`DistributionsBuilder::with_multilingual_text`]
+const MULTILINGUAL_WORDS: &str = include_str!("multilingual.dss");
+
+/// Number of nations (and rows of the NATION table)
+const NATION_COUNT: usize = 25;
+/// Number of regions (and rows of the REGION table)
+const REGION_COUNT: usize = 5;
+
 /// Distribution represents a weighted collection of string values from the TPC-H specification.
 /// It provides methods to access values by index or randomly based on their weights.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Distribution {
-    name: &'static str,
-    values: Vec<&'static str>,
+    name: String,
+    values: Vec<String>,
     weights: Vec<i32>,
-    /// Weighted text values generated from the distribution.
+    /// Index in `values` of the value for each unit of weight.
     ///
     /// If the table this distribution is for isn't actually a valid
     /// distribution (e.g. 'nation'), then this vec will be empty, and
     /// `max_weight` set to -1.
-    distribution: Vec<&'static str>,
+    distribution: Vec<u32>,
     max_weight: i32,
 }
 
 impl Distribution {
     /// Creates a new Distribution with the given name and weighted values.
-    pub fn new(name: &'static str, distribution: Vec<(&'static str, i32)>) -> Self {
+    pub fn new<S: Into<String>>(name: impl Into<String>, distribution: Vec<(S, i32)>) -> Self {
         let mut weights = vec![0; distribution.len()];
 
         let mut running_weight = 0;
@@ -44,27 +55,24 @@ impl Distribution {
 
         // Only create the full distribution array for valid distributions
         // "nations" is a special case that's not a valid distribution
-        let (distribution_array, max_weight) = if is_valid_distribution {
+        let (distribution_array, max_weight) = if is_valid_distribution && !weights.is_empty() {
             let max = weights[weights.len() - 1];
-            let mut dist = vec![""; max as usize];
-
-            let mut index = 0;
-            for (value, weight) in &distribution {
-                for _ in 0..*weight {
-                    dist[index] = value;
-                    index += 1;
-                }
+            let mut dist = Vec::with_capacity(max as usize);
+            for (index, (_, weight)) in distribution.iter().enumerate() {
+                dist.extend(std::iter::repeat_n(index as u32, *weight as usize));
             }
-
             (dist, max)
         } else {
             (Vec::new(), -1)
         };
 
-        let values = distribution.into_iter().map(|(tok, _)| tok).collect();
+        let values = distribution
+            .into_iter()
+            .map(|(value, _)| value.into())
+            .collect();
 
         Distribution {
-            name,
+            name: name.into(),
             values,
             weights,
             distribution: distribution_array,
@@ -74,16 +82,16 @@ impl Distribution {
 
     /// Returns the distribution name.
     pub fn name(&self) -> &str {
-        self.name
+        &self.name
     }
 
     /// Gets a value at the specified index.
     pub fn get_value(&self, index: usize) -> &str {
-        self.values[index]
+        &self.values[index]
     }
 
     /// Gets all values in this distribution.
-    pub fn get_values(&self) -> &[&'static str] {
+    pub fn get_values(&self) -> &[String] {
         &self.values
     }
 
@@ -97,67 +105,130 @@ impl Distribution {
         self.values.len()
     }
 
+    /// Returns true if all the weights are positive, so that
+    /// [`random_value`](Self::random_value) can be used
+    pub fn is_weighted(&self) -> bool {
+        !self.distribution.is_empty()
+    }
+
     /// Gets a random value from this distribution using the provided random number.
     pub fn random_value(&self, random: &mut RowRandomInt) -> &str {
         debug_assert!(
-            !self.distribution.is_empty(),
+            self.is_weighted(),
             "Not a valid distribution, cannot get a random value"
         );
         let random_value = random.next_int(0, self.max_weight - 1);
-        self.distribution[random_value as usize]
+        &self.values[self.distribution[random_value as usize] as usize]
+    }
+
+    /// Gets a random value from this distribution, ignoring the weights and
+    /// choosing the values with a Zipf distribution with exponent `factor`
+    ///
+    /// The first value of the distribution is the most frequent, the second
+    /// value the second most frequent and so on.
+    pub fn random_skewed_value(&self, random: &mut RowRandomInt, factor: f64) -> &str {
+        let random_value = random.next_int_skewed(0, self.values.len() as i32 - 1, factor);
+        &self.values[random_value as usize]
+    }
+
+    /// Loads the values of the distribution named `name` from a file at
+    /// `path`
+    ///
+    /// Each line of the file is a `value|weight` entry of the distribution,
+    /// like between the `BEGIN` and `END` markers of a distribution in
+    /// `dists.dss`. Empty lines and lines starting with `#` are ignored. The
+    /// errors report the file and line of invalid data.
+    pub fn try_from_path(name: &str, path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        let source = path.display().to_string();
+        let mut lines = data_lines(&contents);
+        Self::load_distribution(&mut lines, name, None, &source)
+    }
+
+    /// Returns the weight of each value, rather than the cumulative weights
+    fn value_weights(&self) -> impl Iterator<Item = i32> + '_ {
+        let previous = std::iter::once(0).chain(self.weights.iter().copied());
+        self.weights
+            .iter()
+            .zip(previous)
+            .map(|(weight, previous)| weight - previous)
     }
 
     /// Loads a single distribution until its END marker.
-    fn load_distribution<I>(
-        lines: &mut std::iter::Peekable<I>,
-        name: &'static str,
+    ///
+    /// `begin_line` is the line number of the BEGIN marker, or `None` if the
+    /// distribution ends at the end of `lines` instead.
+    fn load_distribution<'a, I>(
+        lines: &mut I,
+        name: &str,
+        begin_line: Option<usize>,
+        source: &str,
     ) -> io::Result<Self>
     where
-        I: Iterator<Item = &'static str>,
+        I: Iterator<Item = (usize, &'a str)>,
     {
         // (Token, Weight) pairs within a distribution.
-        let mut members: Vec<(&'static str, i32)> = Vec::new();
-        let mut _count = -1;
+        let mut members: Vec<(&str, i32)> = Vec::new();
+        let mut count = None;
+        let mut last_line = 0;
+
+        let finish = |members: Vec<(&str, i32)>, count: Option<(usize, i32)>| match count {
+            Some((line_number, count)) if count as usize != members.len() => Err(parse_error(
+                source,
+                line_number,
+                format!(
+                    "distribution {name}: COUNT is {count}, but there are {} values",
+                    members.len()
+                ),
+            )),
+            _ => Ok(Distribution::new(name, members)),
+        };
 
-        for line in lines.by_ref() {
+        for (line_number, line) in lines.by_ref() {
+            last_line = line_number;
+            let error = |message: String| {
+                parse_error(
+                    source,
+                    line_number,
+                    format!("distribution {name}: {message}"),
+                )
+            };
             if Self::is_end(line) {
-                let distribution = Distribution::new(name, members);
-                return Ok(distribution);
+                return finish(members, count);
             }
 
-            let parts: Vec<&str> = line.split("|").collect::<Vec<_>>();
-            if parts.len() < 2 {
-                return Err(io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    format!("Invalid distribution line format: {}", line),
-                ));
-            }
-
-            let value = parts[0];
-            let weight = match parts[1].trim().parse::<i32>() {
-                Ok(w) => w,
-                Err(_) => {
-                    return Err(io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        format!(
-                            "Invalid distribution {}: invalid weight on line {}",
-                            name, line
-                        ),
-                    ));
-                }
+            let Some((value, weight)) = line.split_once('|') else {
+                return Err(error(format!(
+                    "invalid line '{line}', expected <value>|<weight>"
+                )));
+            };
+            // anything after a second '|' is ignored, like in dbgen
+            let weight = weight.split('|').next().unwrap_or_default().trim();
+            let Ok(weight) = weight.parse::<i32>() else {
+                return Err(error(format!("invalid weight '{weight}'")));
             };
 
             if value.eq_ignore_ascii_case("count") {
-                _count = weight;
+                count = Some((line_number, weight));
             } else {
                 members.push((value, weight));
             }
         }
 
-        Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("Invalid distribution {}: no end statement", name),
-        ))
+        match begin_line {
+            Some(begin_line) => Err(parse_error(
+                source,
+                begin_line,
+                format!("distribution {name}: no END statement"),
+            )),
+            None if members.is_empty() => Err(parse_error(
+                source,
+                last_line,
+                format!("distribution {name}: no values"),
+            )),
+            None => finish(members, count),
+        }
     }
 
     /// Checks if a line is an END marker.
@@ -167,13 +238,39 @@ impl Distribution {
     }
 }
 
+/// Returns the lines of `data` with their line number, without the empty
+/// lines and comments
+fn data_lines(data: &str) -> impl Iterator<Item = (usize, &str)> {
+    data.split('\n').enumerate().filter_map(|(index, line)| {
+        let trimmed = line.trim();
+        if !trimmed.is_empty() && !trimmed.starts_with('#') {
+            Some((index + 1, trimmed))
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns an `InvalidData` error for line `line_number` of `source`
+fn parse_error(source: &str, line_number: usize, message: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{source}:{line_number}: {message}"),
+    )
+}
+
 /// Static global instance of the default distributions.
 ///
 /// Initialized once on first access.
 static DEFAULT_DISTRIBUTIONS: OnceLock<Distributions> = OnceLock::new();
 
 /// Distributions wraps all TPC-H distributions and provides methods to access them.
-#[derive(Debug, Clone, Default)]
+///
+/// The distributions own their values, so any number of `Distributions` can
+/// be loaded (e.g. from different files) and dropped. They are loaded from
+/// files in the format of the `dists.dss` file of `dbgen`, which must define
+/// all the distributions used by the generators.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Distributions {
     articles: Distribution,
     adjectives: Distribution,
@@ -190,6 +287,8 @@ pub struct Distributions {
     part_containers: Distribution,
     part_types: Distribution,
     prepositions: Distribution,
+    q13a: Distribution,
+    q13b: Distribution,
     regions: Distribution,
     return_flags: Distribution,
     ship_instructions: Distribution,
@@ -199,64 +298,215 @@ pub struct Distributions {
     verbs: Distribution,
 }
 
+/// Defines [`Distributions::NAMES`] and the accessors of the distributions
+/// by name, from the field and the name in `dists.dss` of each distribution
+macro_rules! distribution_names {
+    ($($FIELD:ident: $NAME:literal,)*) => {
+        impl Distributions {
+            /// The names of the distributions used by the generators, as in
+            /// `dists.dss`
+            pub const NAMES: &'static [&'static str] = &[$($NAME),*];
+
+            /// Returns the distribution named `name` in `dists.dss`, see
+            /// [`Self::NAMES`]
+            pub fn get(&self, name: &str) -> Option<&Distribution> {
+                match name {
+                    $($NAME => Some(&self.$FIELD),)*
+                    _ => None,
+                }
+            }
+
+            fn get_mut(&mut self, name: &str) -> Option<&mut Distribution> {
+                match name {
+                    $($NAME => Some(&mut self.$FIELD),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+distribution_names!(
+    articles: "articles",
+    adjectives: "adjectives",
+    adverbs: "adverbs",
+    // P.S: The correct spelling is `auxiliaries` which is what we use.
+    auxiliaries: "auxillaries",
+    grammar: "grammar",
+    category: "category",
+    market_segments: "msegmnt",
+    nations: "nations",
+    noun_phrase: "np",
+    nouns: "nouns",
+    order_priority: "o_oprio",
+    part_colors: "colors",
+    part_containers: "p_cntr",
+    part_types: "p_types",
+    prepositions: "prepositions",
+    q13a: "Q13a",
+    q13b: "Q13b",
+    regions: "regions",
+    return_flags: "rflag",
+    ship_instructions: "instruct",
+    ship_modes: "smode",
+    terminators: "terminators",
+    verb_phrase: "vp",
+    verbs: "verbs",
+);
+
 impl Distributions {
-    fn try_from_str(data: &'static str) -> io::Result<Self> {
-        let lines = data.split('\n');
+    /// Distributions of `dists.dss` that are not used by the generators
+    const UNUSED: [&'static str; 2] = ["nations2", "p_names"];
 
+    /// Parses the distributions in `data`, named `source` in the errors
+    fn try_from_str(data: &str, source: &str) -> io::Result<Self> {
         let mut new_self = Self::default();
-        for (name, distribution) in Self::load_distributions(lines)? {
-            match name {
-                "articles" => new_self.articles = distribution,
-                "adjectives" => new_self.adjectives = distribution,
-                "adverbs" => new_self.adverbs = distribution,
-                // P.S: The correct spelling is `auxiliaries` which is what we use.
-                "auxillaries" => new_self.auxiliaries = distribution,
-                "grammar" => new_self.grammar = distribution,
-                "category" => new_self.category = distribution,
-                "msegmnt" => new_self.market_segments = distribution,
-                "nations" => new_self.nations = distribution,
-                "np" => new_self.noun_phrase = distribution,
-                "nouns" => new_self.nouns = distribution,
-                "o_oprio" => new_self.order_priority = distribution,
-                "colors" => new_self.part_colors = distribution,
-                "p_cntr" => new_self.part_containers = distribution,
-                "p_types" => new_self.part_types = distribution,
-                "prepositions" => new_self.prepositions = distribution,
-                "regions" => new_self.regions = distribution,
-                "rflag" => new_self.return_flags = distribution,
-                "instruct" => new_self.ship_instructions = distribution,
-                "smode" => new_self.ship_modes = distribution,
-                "terminators" => new_self.terminators = distribution,
-                "vp" => new_self.verb_phrase = distribution,
-                "verbs" => new_self.verbs = distribution,
-
-                // currently unused distributions
-                "nations2" | "Q13a" | "Q13b" | "p_names" => {}
-                _ => {
-                    return Err(io::Error::new(
-                        io::ErrorKind::InvalidInput,
-                        format!("Internal Error: Unknown distribution: {name}"),
+        // the line of the BEGIN marker of each distribution
+        let mut begin_lines: HashMap<String, usize> = HashMap::new();
+        for (line_number, distribution) in Self::load_distributions(data, source)? {
+            let name = distribution.name().to_string();
+            if let Some(first_line) = begin_lines.get(&name) {
+                return Err(parse_error(
+                    source,
+                    line_number,
+                    format!("distribution {name} is already defined on line {first_line}"),
+                ));
+            }
+            begin_lines.insert(name.clone(), line_number);
+            if Self::UNUSED.contains(&name.as_str()) {
+                continue;
+            }
+            let Some(target) = new_self.get_mut(&name) else {
+                return Err(parse_error(
+                    source,
+                    line_number,
+                    format!("unknown distribution {name}"),
+                ));
+            };
+            *target = distribution;
+        }
+
+        if let Some(name) = Self::NAMES
+            .iter()
+            .find(|name| !begin_lines.contains_key(**name))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{source}: missing distribution {name}"),
+            ));
+        }
+        new_self
+            .validate()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{source}: {e}")))?;
+        Ok(new_self)
+    }
+
+    /// Checks that the distributions can be used by the generators
+    fn validate(&self) -> Result<(), String> {
+        for &name in Self::NAMES {
+            let distribution = self.get(name).expect("known distribution");
+            if distribution.size() == 0 {
+                return Err(format!("distribution {name} is empty"));
+            }
+            if name != "nations" && !distribution.is_weighted() {
+                return Err(format!(
+                    "distribution {name} has weights that are not positive"
+                ));
+            }
+            // the values are written to TBL files, one row per line
+            let invalid = |value: &&String| value.contains(['|', '\n', '\r']);
+            if let Some(value) = distribution.get_values().iter().find(invalid) {
+                return Err(format!(
+                    "distribution {name}: invalid value {value:?}, values cannot contain '|' or line breaks"
+                ));
+            }
+        }
+
+        // the tables and the queries have 25 nations and 5 regions
+        let sizes = [("nations", NATION_COUNT), ("regions", REGION_COUNT)];
+        for (name, size) in sizes {
+            let distribution = self.get(name).expect("known distribution");
+            if distribution.size() != size {
+                return Err(format!(
+                    "distribution {name} has {} values, expected {size}",
+                    distribution.size()
+                ));
+            }
+        }
+        // the weights of the nations are adjustments to the region key of
+        // the previous nation
+        for (index, nation) in self.nations.get_values().iter().enumerate() {
+            let region_key = self.nations.get_weight(index);
+            if !(0..self.regions.size() as i32).contains(&region_key) {
+                return Err(format!(
+                    "distribution nations: nation {nation} is in region {region_key}, which does not exist"
+                ));
+            }
+        }
+        // the part names are made of distinct colors
+        if self.part_colors.size() < PartGenerator::NAME_WORDS as usize {
+            return Err(format!(
+                "distribution colors has {} values, expected at least {}",
+                self.part_colors.size(),
+                PartGenerator::NAME_WORDS
+            ));
+        }
+
+        // the text pool is generated with the grammar
+        for (name, tokens) in [("grammar", "NVPT"), ("vp", "DVX")] {
+            for value in self.get(name).expect("known distribution").get_values() {
+                if !value.chars().step_by(2).all(|c| tokens.contains(c)) {
+                    return Err(format!(
+                        "distribution {name}: invalid value '{value}', expected a space separated list of {tokens}"
                     ));
                 }
             }
         }
+        for value in self.noun_phrase.get_values() {
+            if !value.chars().all(|c| "AJDN, ".contains(c)) {
+                return Err(format!(
+                    "distribution np: invalid value '{value}', expected a list of A, J, D, N and ','"
+                ));
+            }
+        }
+        Ok(())
+    }
 
-        Ok(new_self)
+    /// Returns a builder that replaces some of the default distributions
+    pub fn builder() -> DistributionsBuilder {
+        DistributionsBuilder::new(Self::static_default().clone())
     }
 
+    /// Loads the default distributions, from the `dists.dss` file of the
+    /// TPC-H specification
     pub fn try_load_default() -> io::Result<Self> {
-        Self::try_from_str(DISTS_SEED)
+        Self::try_from_str(DISTS_SEED, "dists.dss")
     }
 
+    /// Loads the distributions of the file at `path`
+    ///
+    /// The errors report the file, line and distribution of invalid data, and
+    /// all the distributions used by the generators must be defined.
     pub fn try_from_path(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
         let contents = fs::read_to_string(path)?;
-        let leaked: &'static str = Box::leak(contents.into_boxed_str());
-        Self::try_from_str(leaked)
+        Self::try_from_str(&contents, &path.display().to_string())
     }
 
+    /// Loads the distributions of the file at `path` as the
+    /// [default](Self::static_default) distributions
+    ///
+    /// Returns an error if the default distributions are already initialized.
     pub fn init_from_path(path: impl AsRef<Path>) -> io::Result<()> {
-        let dist = Self::try_from_path(path)?;
-        DEFAULT_DISTRIBUTIONS.set(dist).map_err(|_| {
+        Self::init_default(Self::try_from_path(path)?)
+    }
+
+    /// Sets the [default](Self::static_default) distributions, e.g. to
+    /// distributions created with [`Self::builder`]
+    ///
+    /// Returns an error if the default distributions are already initialized.
+    pub fn init_default(distributions: Distributions) -> io::Result<()> {
+        DEFAULT_DISTRIBUTIONS.set(distributions).map_err(|_| {
             io::Error::new(
                 io::ErrorKind::AlreadyExists,
                 "Distributions already initialized",
@@ -264,31 +514,19 @@ impl Distributions {
         })
     }
 
-    /// Loads distributions from a stream of lines.
+    /// Loads the distributions of `data`, with the line number of the BEGIN
+    /// marker of each distribution.
     ///
     /// The format is expected to follow the TPC-H specification format where:
     /// - Lines starting with `"#"` are comments
     /// - Distributions start with `"BEGIN <name>"`
     /// - Distribution entries are formatted as `"value|weight"`
     /// - Distributions end with `"END"`
-    fn load_distributions<I>(lines: I) -> io::Result<Vec<(&'static str, Distribution)>>
-    where
-        I: Iterator<Item = &'static str>,
-    {
-        let mut filtered_lines = lines
-            .filter_map(|line| {
-                let trimmed = line.trim();
-                if !trimmed.is_empty() && !trimmed.starts_with('#') {
-                    Some(trimmed)
-                } else {
-                    None
-                }
-            })
-            .peekable();
-
+    fn load_distributions(data: &str, source: &str) -> io::Result<Vec<(usize, Distribution)>> {
+        let mut filtered_lines = data_lines(data);
         let mut distributions = Vec::new();
 
-        while let Some(line) = filtered_lines.next() {
+        while let Some((line_number, line)) = filtered_lines.next() {
             // This checks if the line has exactly two parts and the first part is "BEGIN"
             let mut part_iter = line.split_whitespace();
             let Some(part0) = part_iter.next() else {
@@ -303,8 +541,13 @@ impl Distributions {
             }
             if part0.eq_ignore_ascii_case("BEGIN") {
                 let name = part1;
-                let distribution = Distribution::load_distribution(&mut filtered_lines, name)?;
-                distributions.push((name, distribution));
+                let distribution = Distribution::load_distribution(
+                    &mut filtered_lines,
+                    name,
+                    Some(line_number),
+                    source,
+                )?;
+                distributions.push((line_number, distribution));
             }
         }
 
@@ -395,6 +638,16 @@ impl Distributions {
         &self.prepositions
     }
 
+    /// Returns the `Q13a` distribution (the first word substituted in Q13).
+    pub fn q13a(&self) -> &Distribution {
+        &self.q13a
+    }
+
+    /// Returns the `Q13b` distribution (the second word substituted in Q13).
+    pub fn q13b(&self) -> &Distribution {
+        &self.q13b
+    }
+
     /// Returns the `regions` distribution.
     pub fn regions(&self) -> &Distribution {
         &self.regions
@@ -431,16 +684,174 @@ impl Distributions {
     }
 }
 
+/// Builder of [`Distributions`] that replaces some of the distributions, for
+/// example to generate data with localized names
+///
+/// The builder starts from the default distributions ([`Distributions::builder`])
+/// or any other distributions ([`DistributionsBuilder::new`]). The
+/// distributions are named like in `dists.dss` (see [`Distributions::NAMES`]),
+/// and [`build`](Self::build) checks that they can be used by the
+/// generators, e.g. that there are 25 nations in existing regions.
+///
+/// # Example
+/// ```
+/// # use tpchgen::distribution::{Distribution, Distributions};
+/// # use tpchgen::generators::{LineItemGenerator, RegionGenerator};
+/// # use tpchgen::text::TextPool;
+/// let ship_modes = vec![("LUFT", 1), ("SCHIFF", 1), ("BAHN", 1), ("LKW", 1)];
+/// let distributions = Distributions::builder()
+///     .with_distribution(Distribution::new("smode", ship_modes))
+///     .with_values("regions", ["AFRIKA", "AMERIKA", "ASIEN", "EUROPA", "NAHER OSTEN"])
+///     .build()
+///     .unwrap();
+///
+/// let text_pool = TextPool::new(1024 * 1024, &distributions);
+/// let region = RegionGenerator::new_with_distributions_and_text_pool(&distributions, &text_pool)
+///     .iter()
+///     .next()
+///     .unwrap();
+/// assert_eq!(region.r_name, "AFRIKA");
+/// let line_item = LineItemGenerator::new_with_distributions_and_text_pool(
+///     1.0, 1, 1, &distributions, &text_pool,
+/// )
+/// .iter()
+/// .next()
+/// .unwrap();
+/// assert!(["LUFT", "SCHIFF", "BAHN", "LKW"].contains(&line_item.l_shipmode));
+///
+/// // there must be 5 regions
+/// let error = Distributions::builder()
+///     .with_distribution(Distribution::new("regions", vec![("WORLD", 1)]))
+///     .build()
+///     .unwrap_err();
+/// assert_eq!(error, "distribution regions has 1 values, expected 5");
+/// ```
+#[derive(Debug, Clone)]
+pub struct DistributionsBuilder {
+    distributions: Distributions,
+    /// The first error of the `with_` methods, returned by `build`
+    error: Option<String>,
+}
+
+impl DistributionsBuilder {
+    /// Creates a builder that replaces some of `distributions`
+    pub fn new(distributions: Distributions) -> Self {
+        Self {
+            distributions,
+            error: None,
+        }
+    }
+
+    /// Replaces the distribution with the same name, see
+    /// [`Distributions::NAMES`]
+    pub fn with_distribution(mut self, distribution: Distribution) -> Self {
+        match self.distributions.get_mut(distribution.name()) {
+            Some(target) => *target = distribution,
+            None => self.set_error(format!("unknown distribution {}", distribution.name())),
+        }
+        self
+    }
+
+    /// Replaces the values of the distribution `name`, keeping their weights
+    ///
+    /// There must be as many values as in the replaced distribution, e.g. to
+    /// translate the names of the nations in the same order. The values are
+    /// written to TBL files, so they cannot contain `|` or line breaks.
+    pub fn with_values<S: Into<String>>(
+        mut self,
+        name: &str,
+        values: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let Some(target) = self.distributions.get_mut(name) else {
+            self.set_error(format!("unknown distribution {name}"));
+            return self;
+        };
+        let values: Vec<String> = values.into_iter().map(Into::into).collect();
+        if values.len() != target.size() {
+            let message = format!(
+                "distribution {name} has {} values, got {} values",
+                target.size(),
+                values.len()
+            );
+            self.set_error(message);
+            return self;
+        }
+        let weighted = values.into_iter().zip(target.value_weights()).collect();
+        *target = Distribution::new(name, weighted);
+        self
+    }
+
+    /// Replaces the words of the text of the comments with multilingual
+    /// words (NOT TPC-H conformant)
+    ///
+    /// The words are in several languages and scripts, with emoji and
+    /// combining characters, so the comments are multi-byte UTF-8 text with
+    /// the same average length in bytes, see the [multilingual
+    /// text](crate::text::TextPool#multilingual-text) of the text pool. The
+    /// grammar of the sentences is not changed.
+    ///
+    /// # Example
+    /// ```
+    /// # use tpchgen::distribution::Distributions;
+    /// # use tpchgen::generators::OrderGenerator;
+    /// # use tpchgen::text::TextPool;
+    /// let distributions = Distributions::builder()
+    ///     .with_multilingual_text()
+    ///     .build()
+    ///     .unwrap();
+    /// let text_pool = TextPool::new(TextPool::MIN_TEXT_POOL_SIZE, &distributions);
+    /// let generator =
+    ///     OrderGenerator::new_with_distributions_and_text_pool(0.01, 1, 1, &distributions, &text_pool);
+    /// assert!(generator.iter().any(|order| !order.o_comment.is_ascii()));
+    /// ```
+    pub fn with_multilingual_text(self) -> Self {
+        Distributions::load_distributions(MULTILINGUAL_WORDS, "multilingual.dss")
+            .expect("valid multilingual words")
+            .into_iter()
+            .fold(self, |builder, (_, distribution)| {
+                builder.with_distribution(distribution)
+            })
+    }
+
+    fn set_error(&mut self, message: String) {
+        self.error.get_or_insert(message);
+    }
+
+    /// Returns the distributions, or an error if a distribution is unknown or
+    /// can not be used by the generators
+    pub fn build(self) -> Result<Distributions, String> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        self.distributions.validate()?;
+        Ok(self.distributions)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+
+    /// Loads the distributions of `input` by name
+    fn load(input: &str) -> io::Result<HashMap<String, Distribution>> {
+        let distributions = Distributions::load_distributions(input, "test.dss")?;
+        Ok(distributions
+            .into_iter()
+            .map(|(_, distribution)| (distribution.name().to_string(), distribution))
+            .collect())
+    }
+
+    /// Returns the error message of loading `input` as `Distributions`
+    fn load_error(input: &str) -> String {
+        Distributions::try_from_str(input, "test.dss")
+            .unwrap_err()
+            .to_string()
+    }
 
     #[test]
     fn test_load_empty() {
         let input = "";
-        let lines = input.split('\n');
-        let distributions = Distributions::load_distributions(lines).unwrap();
+        let distributions = load(input).unwrap();
         assert!(distributions.is_empty());
     }
 
@@ -453,11 +864,7 @@ mod tests {
             value2|20
             END
         ";
-        let lines = input.split('\n');
-        let distributions: HashMap<_, _> = Distributions::load_distributions(lines)
-            .unwrap()
-            .into_iter()
-            .collect();
+        let distributions = load(input).unwrap();
 
         assert_eq!(distributions.len(), 1);
         assert!(distributions.contains_key("test"));
@@ -484,11 +891,7 @@ mod tests {
             z|4
             END
         ";
-        let lines = input.split('\n');
-        let distributions: HashMap<_, _> = Distributions::load_distributions(lines)
-            .unwrap()
-            .into_iter()
-            .collect();
+        let distributions = load(input).unwrap();
 
         assert_eq!(distributions.len(), 2);
         assert!(distributions.contains_key("first"));
@@ -508,9 +911,11 @@ mod tests {
             value|invalid
             END
         ";
-        let lines = input.split('\n');
-        let result = Distributions::load_distributions(lines);
-        assert!(result.is_err());
+        let result = load(input);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "test.dss:3: distribution test: invalid weight 'invalid'"
+        );
     }
 
     #[test]
@@ -519,9 +924,11 @@ mod tests {
             BEGIN test
             value|10
         ";
-        let lines = input.split('\n');
-        let result = Distributions::load_distributions(lines);
-        assert!(result.is_err());
+        let result = load(input);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "test.dss:2: distribution test: no END statement"
+        );
     }
 
     #[test]
@@ -556,11 +963,7 @@ mod tests {
             "Q13b",
         ];
 
-        let lines = DISTS_SEED.split('\n');
-        let distributions: HashMap<_, _> = Distributions::load_distributions(lines)
-            .unwrap()
-            .into_iter()
-            .collect();
+        let distributions = load(DISTS_SEED).unwrap();
         assert_eq!(distributions.len(), 26);
 
         for name in expected_distributions {
@@ -571,4 +974,167 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_error_on_count_mismatch() {
+        let input = "BEGIN test\nCOUNT|3\na|1\nb|1\nEND\n";
+        assert_eq!(
+            load(input).unwrap_err().to_string(),
+            "test.dss:2: distribution test: COUNT is 3, but there are 2 values"
+        );
+    }
+
+    #[test]
+    fn test_load_errors() {
+        // the default distributions with one change
+        let replace = |from: &str, to: &str| {
+            assert!(DISTS_SEED.contains(from), "{from}");
+            load_error(&DISTS_SEED.replacen(from, to, 1))
+        };
+        let line_of = |text: &str| DISTS_SEED[..DISTS_SEED.find(text).unwrap()].lines().count() + 1;
+
+        let grammar = &DISTS_SEED[DISTS_SEED.find("BEGIN grammar").unwrap()..];
+        let grammar = &grammar[..grammar.find("END grammar").unwrap()];
+        assert_eq!(
+            replace(grammar, ""),
+            "test.dss: missing distribution grammar"
+        );
+        assert_eq!(
+            replace("BEGIN grammar", "BEGIN grammer"),
+            format!(
+                "test.dss:{}: unknown distribution grammer",
+                line_of("BEGIN grammar")
+            )
+        );
+        assert_eq!(
+            replace("begin p_names", "begin p_namez"),
+            format!(
+                "test.dss:{}: unknown distribution p_namez",
+                line_of("begin p_names")
+            )
+        );
+        assert_eq!(
+            replace("BEGIN Q13b", "BEGIN Q13a"),
+            format!(
+                "test.dss:{}: distribution Q13a is already defined on line {}",
+                line_of("BEGIN Q13b"),
+                line_of("BEGIN Q13a")
+            )
+        );
+        assert_eq!(
+            replace("REG AIR|1", "REG AIR|0"),
+            "test.dss: distribution smode has weights that are not positive"
+        );
+        assert_eq!(
+            replace("ETHIOPIA|-4", "ETHIOPIA|4"),
+            "test.dss: distribution nations: nation ETHIOPIA is in region 8, which does not exist"
+        );
+    }
+
+    #[test]
+    fn test_owned_distributions() {
+        let distributions = Distributions::try_load_default().unwrap();
+        let other = distributions.clone();
+        drop(distributions);
+        assert_eq!(other.nations().get_value(7), "GERMANY");
+        assert_eq!(other.nations().name(), "nations");
+    }
+
+    #[test]
+    fn test_builder() {
+        let nations: Vec<String> = Distributions::static_default()
+            .nations()
+            .get_values()
+            .iter()
+            .map(|nation| nation.to_lowercase())
+            .collect();
+        let distributions = Distributions::builder()
+            .with_values("nations", nations)
+            .with_distribution(Distribution::new("rflag", vec![("X", 1), ("Y", 3)]))
+            .build()
+            .unwrap();
+        // the weights (the region keys) are kept
+        assert_eq!(distributions.nations().get_value(7), "germany");
+        assert_eq!(distributions.nations().get_weight(7), 3);
+        assert_eq!(distributions.return_flags().get_weight(1), 4);
+        assert_eq!(
+            distributions.ship_modes().get_values(),
+            Distributions::static_default().ship_modes().get_values()
+        );
+
+        let error = |builder: DistributionsBuilder| builder.build().unwrap_err();
+        assert_eq!(
+            error(Distributions::builder().with_values("nations", ["ALGERIA"])),
+            "distribution nations has 25 values, got 1 values"
+        );
+        assert_eq!(
+            error(Distributions::builder().with_values("smodes", ["AIR"])),
+            "unknown distribution smodes"
+        );
+        assert_eq!(
+            error(
+                Distributions::builder()
+                    .with_distribution(Distribution::new("smode", vec![("AIR", 1), ("RAIL", 0)]))
+            ),
+            "distribution smode has weights that are not positive"
+        );
+        assert_eq!(
+            error(
+                Distributions::builder()
+                    .with_distribution(Distribution::new("colors", vec![("red", 1), ("green", 1)]))
+            ),
+            "distribution colors has 2 values, expected at least 5"
+        );
+        assert_eq!(
+            error(
+                Distributions::builder()
+                    .with_distribution(Distribution::new("grammar", vec![("N X T", 1)]))
+            ),
+            "distribution grammar: invalid value 'N X T', expected a space separated list of NVPT"
+        );
+        assert_eq!(
+            error(Distributions::builder().with_values(
+                "regions",
+                ["AFRICA", "AMERICA", "ASIA", "EUROPE", "MIDDLE|EAST"]
+            )),
+            "distribution regions: invalid value \"MIDDLE|EAST\", values cannot contain '|' or line breaks"
+        );
+        assert_eq!(
+            error(
+                Distributions::builder()
+                    .with_distribution(Distribution::new("smode", vec![("AIR\nMAIL", 1)]))
+            ),
+            "distribution smode: invalid value \"AIR\\nMAIL\", values cannot contain '|' or line breaks"
+        );
+        // values that are not in the text can be any UTF-8 text
+        let regions = ["アフリカ", "アメリカ", "アジア", "ヨーロッパ", "中東"];
+        assert!(Distributions::builder()
+            .with_values("regions", regions)
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_distribution_from_path() {
+        let dir = std::env::temp_dir().join(format!("tpchgen-dists-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("smode.txt");
+        fs::write(&path, "# ship modes\nCOUNT|2\nAIR|3\n\nRAIL|1\n").unwrap();
+        let distribution = Distribution::try_from_path("smode", &path).unwrap();
+        assert_eq!(distribution.name(), "smode");
+        assert_eq!(distribution.get_values(), ["AIR", "RAIL"]);
+        assert_eq!(distribution.get_weight(1), 4);
+
+        fs::write(&path, "AIR|3\nRAIL\n").unwrap();
+        assert_eq!(
+            Distribution::try_from_path("smode", &path)
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "{}:2: distribution smode: invalid line 'RAIL', expected <value>|<weight>",
+                path.display()
+            )
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -1,5 +1,4 @@
 //! Generators for each TPC-H Tables
-use crate::dates;
 use crate::decimal::TPCHDecimal;
 use crate::distribution::Distribution;
 use crate::distribution::Distributions;
@@ -7,18 +6,29 @@ use crate::random::RandomPhoneNumber;
 use crate::random::RowRandomInt;
 use crate::random::{PhoneNumberInstance, RandomBoundedLong, StringSequenceInstance};
 use crate::random::{RandomAlphaNumeric, RandomAlphaNumericInstance};
-use crate::text::TextPool;
+use crate::skew::{Skew, SkewedColumn};
+use crate::text::{ceil_char_boundary, TextPool};
 use core::fmt;
 use std::fmt::Display;
-
-use crate::dates::{GenerateUtils, TPCHDate};
-use crate::random::{RandomBoundedInt, RandomString, RandomStringSequence, RandomText};
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::columns::{
+    CustomerSink, LineItemSink, NationSink, OrderSink, PartSink, PartSuppSink, RegionSink,
+    RowStructs, SupplierSink,
+};
+use crate::dates::{DateRange, GenerateUtils, TPCHDate};
+use crate::random::{
+    stream_seed, RandomBoundedInt, RandomString, RandomStringSequence, RandomText,
+};
 
 /// Generator for Nation table data
 #[derive(Debug, Clone)]
 pub struct NationGenerator<'a> {
     distributions: &'a Distributions,
     text_pool: &'a TextPool,
+    row_range: Range<i64>,
+    seed: u64,
 }
 
 impl Default for NationGenerator<'_> {
@@ -54,12 +64,48 @@ impl<'a> NationGenerator<'a> {
         NationGenerator {
             distributions,
             text_pool,
+            row_range: 0..distributions.nations().size() as i64,
+            seed: 0,
         }
     }
 
+    /// Restricts the generator to the rows with indexes in `range` (the
+    /// nation keys)
+    pub fn with_row_range(mut self, range: Range<i64>) -> Self {
+        self.row_range =
+            GenerateUtils::clamp_range(range, self.distributions.nations().size() as i64);
+        self
+    }
+
+    /// Generates a different, statistically equivalent, dataset by using
+    /// `seed` for the random number generators
+    ///
+    /// Seed `0` (the default) generates the standard TPC-H data, identical to
+    /// `dbgen`. The number of line items of each order does not depend on the
+    /// seed, so all seeds produce the same number of rows in every table. The
+    /// text of the comments also depends on the [`TextPool`], see
+    /// [`TextPool::new_with_seed`].
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
     /// Returns an iterator over the nation rows
     pub fn iter(&self) -> NationGeneratorIterator<'a> {
-        NationGeneratorIterator::new(self.distributions.nations(), self.text_pool)
+        self.generate_range(self.row_range.clone())
+    }
+
+    /// Returns the nation with the given row index (the nation key), or
+    /// `None` if there is no such nation
+    pub fn generate_row(&self, index: i64) -> Option<Nation<'a>> {
+        self.generate_range(index..index + 1).next()
+    }
+
+    /// Returns an iterator over the nation rows with indexes in `range`
+    pub fn generate_range(&self, range: Range<i64>) -> NationGeneratorIterator<'a> {
+        let nations = self.distributions.nations();
+        let range = GenerateUtils::clamp_range(range, nations.size() as i64);
+        NationGeneratorIterator::new(nations, self.text_pool, range, self.seed)
     }
 }
 
@@ -82,6 +128,7 @@ impl<'a> IntoIterator for NationGenerator<'a> {
 /// 1|ARGENTINA|1|al foxes promise slyly according to the regular accounts. bold requests alon|
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Nation<'a> {
     /// Primary key (0-24)
     pub n_nationkey: i64,
@@ -121,42 +168,49 @@ pub struct NationGeneratorIterator<'a> {
     nations: &'a Distribution,
     comment_random: RandomText<'a>,
     index: usize,
+    end: usize,
 }
 
 impl<'a> NationGeneratorIterator<'a> {
     const COMMENT_AVERAGE_LENGTH: i32 = 72;
 
-    fn new(nations: &'a Distribution, text_pool: &'a TextPool) -> Self {
+    fn new(
+        nations: &'a Distribution,
+        text_pool: &'a TextPool,
+        range: Range<i64>,
+        seed: u64,
+    ) -> Self {
+        let mut comment_random = RandomText::new(
+            stream_seed(606179079, seed),
+            text_pool,
+            Self::COMMENT_AVERAGE_LENGTH as f64,
+        );
+        comment_random.advance_rows(range.start);
+
         NationGeneratorIterator {
             nations,
-            comment_random: RandomText::new(
-                606179079,
-                text_pool,
-                Self::COMMENT_AVERAGE_LENGTH as f64,
-            ),
-            index: 0,
+            comment_random,
+            index: range.start as usize,
+            end: range.end as usize,
         }
     }
-}
-
-impl<'a> Iterator for NationGeneratorIterator<'a> {
-    type Item = Nation<'a>;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.index >= self.nations.size() {
+    /// Generates the next row into `sink`, see [`crate::columns`]
+    pub(crate) fn next_row<S: NationSink<'a>>(&mut self, sink: &mut S) -> Option<S::Output> {
+        if self.index >= self.end {
             return None;
         }
 
-        let nation = Nation {
+        let nation = sink.row(
             // n_nationkey
-            n_nationkey: self.index as i64,
+            self.index as i64,
             // n_name
-            n_name: self.nations.get_value(self.index),
+            self.nations.get_value(self.index),
             // n_regionkey
-            n_regionkey: self.nations.get_weight(self.index) as i64,
+            self.nations.get_weight(self.index) as i64,
             // n_comment
-            n_comment: self.comment_random.next_value(),
-        };
+            self.comment_random.next_value(),
+        );
 
         self.comment_random.row_finished();
         self.index += 1;
@@ -165,6 +219,14 @@ impl<'a> Iterator for NationGeneratorIterator<'a> {
     }
 }
 
+impl<'a> Iterator for NationGeneratorIterator<'a> {
+    type Item = Nation<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_row(&mut RowStructs)
+    }
+}
+
 /// The REGION table
 ///
 /// The Display trait is implemented to format the line item data as a string
@@ -175,6 +237,7 @@ impl<'a> Iterator for NationGeneratorIterator<'a> {
 /// 1|AMERICA|hs use ironic, even requests. s|
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Region<'a> {
     /// Primary key (0-4)
     pub r_regionkey: i64,
@@ -210,6 +273,8 @@ impl<'a> Region<'a> {
 pub struct RegionGenerator<'a> {
     distributions: &'a Distributions,
     text_pool: &'a TextPool,
+    row_range: Range<i64>,
+    seed: u64,
 }
 
 impl Default for RegionGenerator<'_> {
@@ -245,12 +310,42 @@ impl<'a> RegionGenerator<'a> {
         RegionGenerator {
             distributions,
             text_pool,
+            row_range: 0..distributions.regions().size() as i64,
+            seed: 0,
         }
     }
 
+    /// Restricts the generator to the rows with indexes in `range` (the
+    /// region keys)
+    pub fn with_row_range(mut self, range: Range<i64>) -> Self {
+        self.row_range =
+            GenerateUtils::clamp_range(range, self.distributions.regions().size() as i64);
+        self
+    }
+
+    /// Generates a different dataset by using `seed` for the random number
+    /// generators. See [`NationGenerator::with_seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
     /// Returns an iterator over the region rows
     pub fn iter(&self) -> RegionGeneratorIterator<'a> {
-        RegionGeneratorIterator::new(self.distributions.regions(), self.text_pool)
+        self.generate_range(self.row_range.clone())
+    }
+
+    /// Returns the region with the given row index (the region key), or
+    /// `None` if there is no such region
+    pub fn generate_row(&self, index: i64) -> Option<Region<'a>> {
+        self.generate_range(index..index + 1).next()
+    }
+
+    /// Returns an iterator over the region rows with indexes in `range`
+    pub fn generate_range(&self, range: Range<i64>) -> RegionGeneratorIterator<'a> {
+        let regions = self.distributions.regions();
+        let range = GenerateUtils::clamp_range(range, regions.size() as i64);
+        RegionGeneratorIterator::new(regions, self.text_pool, range, self.seed)
     }
 }
 
@@ -269,20 +364,30 @@ pub struct RegionGeneratorIterator<'a> {
     regions: &'a Distribution,
     comment_random: RandomText<'a>,
     index: usize,
+    end: usize,
 }
 
 impl<'a> RegionGeneratorIterator<'a> {
     const COMMENT_AVERAGE_LENGTH: i32 = 72;
 
-    fn new(regions: &'a Distribution, text_pool: &'a TextPool) -> Self {
+    fn new(
+        regions: &'a Distribution,
+        text_pool: &'a TextPool,
+        range: Range<i64>,
+        seed: u64,
+    ) -> Self {
+        let mut comment_random = RandomText::new(
+            stream_seed(1500869201, seed),
+            text_pool,
+            Self::COMMENT_AVERAGE_LENGTH as f64,
+        );
+        comment_random.advance_rows(range.start);
+
         RegionGeneratorIterator {
             regions,
-            comment_random: RandomText::new(
-                1500869201,
-                text_pool,
-                Self::COMMENT_AVERAGE_LENGTH as f64,
-            ),
-            index: 0,
+            comment_random,
+            index: range.start as usize,
+            end: range.end as usize,
         }
     }
 }
@@ -291,15 +396,25 @@ impl<'a> Iterator for RegionGeneratorIterator<'a> {
     type Item = Region<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index >= self.regions.size() {
+        self.next_row(&mut RowStructs)
+    }
+}
+
+impl<'a> RegionGeneratorIterator<'a> {
+    /// Generates the next row into `sink`, see [`crate::columns`]
+    pub(crate) fn next_row<S: RegionSink<'a>>(&mut self, sink: &mut S) -> Option<S::Output> {
+        if self.index >= self.end {
             return None;
         }
 
-        let region = Region {
-            r_regionkey: self.index as i64,
-            r_name: self.regions.get_value(self.index),
-            r_comment: self.comment_random.next_value(),
-        };
+        let region = sink.row(
+            // r_regionkey
+            self.index as i64,
+            // r_name
+            self.regions.get_value(self.index),
+            // r_comment
+            self.comment_random.next_value(),
+        );
 
         self.comment_random.row_finished();
         self.index += 1;
@@ -316,6 +431,11 @@ impl PartManufacturerName {
     pub fn new(value: i32) -> Self {
         PartManufacturerName(value)
     }
+
+    /// Returns the manufacturer number
+    pub fn value(&self) -> i32 {
+        self.0
+    }
 }
 
 impl fmt::Display for PartManufacturerName {
@@ -332,6 +452,11 @@ impl PartBrandName {
     pub fn new(value: i32) -> Self {
         PartBrandName(value)
     }
+
+    /// Returns the brand number, the manufacturer number followed by a digit
+    pub fn value(&self) -> i32 {
+        self.0
+    }
 }
 
 impl fmt::Display for PartBrandName {
@@ -350,6 +475,7 @@ impl fmt::Display for PartBrandName {
 /// 2|blush thistle blue yellow saddle|Manufacturer#1|Brand#13|LARGE BRUSHED BRASS|1|LG CASE|902.00|lar accounts amo|
 /// ```
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Part<'a> {
     /// Primary key
     pub p_partkey: i64,
@@ -393,10 +519,11 @@ impl fmt::Display for Part<'_> {
 #[derive(Debug, Clone)]
 pub struct PartGenerator<'a> {
     scale_factor: f64,
-    part: i32,
-    part_count: i32,
+    row_range: Range<i64>,
     distributions: &'a Distributions,
     text_pool: &'a TextPool,
+    seed: u64,
+    skew: Skew,
 }
 
 impl<'a> PartGenerator<'a> {
@@ -404,7 +531,7 @@ impl<'a> PartGenerator<'a> {
     const SCALE_BASE: i32 = 200_000;
 
     // Constants for part generation
-    const NAME_WORDS: i32 = 5;
+    pub(crate) const NAME_WORDS: i32 = 5;
     const MANUFACTURER_MIN: i32 = 1;
     const MANUFACTURER_MAX: i32 = 5;
     const BRAND_MIN: i32 = 1;
@@ -438,10 +565,16 @@ impl<'a> PartGenerator<'a> {
     ) -> PartGenerator<'b> {
         PartGenerator {
             scale_factor,
-            part,
-            part_count,
+            row_range: GenerateUtils::calculate_range(
+                Self::SCALE_BASE,
+                scale_factor,
+                part,
+                part_count,
+            ),
             distributions,
             text_pool,
+            seed: 0,
+            skew: Skew::default(),
         }
     }
 
@@ -450,18 +583,70 @@ impl<'a> PartGenerator<'a> {
         GenerateUtils::calculate_row_count(Self::SCALE_BASE, scale_factor, part, part_count)
     }
 
+    /// Restricts the generator to the rows with indexes in `range`, instead
+    /// of its part
+    ///
+    /// Rows are numbered from `0` across the whole table, so generators for
+    /// adjacent ranges produce the same part rows as a single generator for
+    /// the whole table. This allows splitting the table into pieces of any
+    /// size, e.g. to balance the work between distributed workers.
+    pub fn with_row_range(mut self, range: Range<i64>) -> Self {
+        self.row_range =
+            GenerateUtils::clamp_range(range, Self::calculate_row_count(self.scale_factor, 1, 1));
+        self
+    }
+
+    /// Generates a different dataset by using `seed` for the random number
+    /// generators. See [`NationGenerator::with_seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Skews `p_type` and `p_container`
+    /// with `skew`. See the [`skew`](crate::skew) module for details.
+    pub fn with_skew(mut self, skew: Skew) -> Self {
+        self.skew = skew;
+        self
+    }
+
     /// Returns an iterator over the part rows
     pub fn iter(&self) -> PartGeneratorIterator<'a> {
         PartGeneratorIterator::new(
             self.distributions,
             self.text_pool,
-            GenerateUtils::calculate_start_index(
-                Self::SCALE_BASE,
-                self.scale_factor,
-                self.part,
-                self.part_count,
-            ),
-            Self::calculate_row_count(self.scale_factor, self.part, self.part_count),
+            self.row_range.start,
+            self.row_range.end - self.row_range.start,
+            self.seed,
+            self.skew,
+        )
+    }
+
+    /// Returns the part with the given row index, or `None` if there is no
+    /// such part
+    ///
+    /// Rows are numbered from `0` across the whole table, independently of
+    /// the part of this generator. See [`Self::generate_range`].
+    pub fn generate_row(&self, index: i64) -> Option<Part<'a>> {
+        self.generate_range(index..index + 1).next()
+    }
+
+    /// Returns an iterator over the part rows with indexes in `range`
+    ///
+    /// Rows are numbered from `0` across the whole table, independently of
+    /// the part of this generator, and indexes past the end of the table are
+    /// ignored. The random number generators jump directly to the first row,
+    /// so this is much faster than skipping rows of [`Self::iter`].
+    pub fn generate_range(&self, range: Range<i64>) -> PartGeneratorIterator<'a> {
+        let range =
+            GenerateUtils::clamp_range(range, Self::calculate_row_count(self.scale_factor, 1, 1));
+        PartGeneratorIterator::new(
+            self.distributions,
+            self.text_pool,
+            range.start,
+            range.end - range.start,
+            self.seed,
+            self.skew,
         )
     }
 }
@@ -497,25 +682,39 @@ impl<'a> PartGeneratorIterator<'a> {
         text_pool: &'a TextPool,
         start_index: i64,
         row_count: i64,
+        seed: u64,
+        skew: Skew,
     ) -> Self {
         let mut name_random = RandomStringSequence::new(
-            709314158,
+            stream_seed(709314158, seed),
             PartGenerator::NAME_WORDS,
             distributions.part_colors(),
         );
         let mut manufacturer_random = RandomBoundedInt::new(
-            1,
+            stream_seed(1, seed),
             PartGenerator::MANUFACTURER_MIN,
             PartGenerator::MANUFACTURER_MAX,
         );
-        let mut brand_random =
-            RandomBoundedInt::new(46831694, PartGenerator::BRAND_MIN, PartGenerator::BRAND_MAX);
-        let mut type_random = RandomString::new(1841581359, distributions.part_types());
-        let mut size_random =
-            RandomBoundedInt::new(1193163244, PartGenerator::SIZE_MIN, PartGenerator::SIZE_MAX);
-        let mut container_random = RandomString::new(727633698, distributions.part_containers());
+        let mut brand_random = RandomBoundedInt::new(
+            stream_seed(46831694, seed),
+            PartGenerator::BRAND_MIN,
+            PartGenerator::BRAND_MAX,
+        );
+        let mut type_random =
+            RandomString::new(stream_seed(1841581359, seed), distributions.part_types())
+                .with_skew(skew.factor(SkewedColumn::PType));
+        let mut size_random = RandomBoundedInt::new(
+            stream_seed(1193163244, seed),
+            PartGenerator::SIZE_MIN,
+            PartGenerator::SIZE_MAX,
+        );
+        let mut container_random = RandomString::new(
+            stream_seed(727633698, seed),
+            distributions.part_containers(),
+        )
+        .with_skew(skew.factor(SkewedColumn::PContainer));
         let mut comment_random = RandomText::new(
-            804159733,
+            stream_seed(804159733, seed),
             text_pool,
             PartGenerator::COMMENT_AVERAGE_LENGTH as f64,
         );
@@ -544,23 +743,32 @@ impl<'a> PartGeneratorIterator<'a> {
     }
 
     /// Creates a part with the given key
-    fn make_part(&mut self, part_key: i64) -> Part<'a> {
+    fn make_part<S: PartSink<'a>>(&mut self, part_key: i64, sink: &mut S) -> S::Output {
         let name = self.name_random.next_value();
 
         let manufacturer = self.manufacturer_random.next_value();
         let brand = manufacturer * 10 + self.brand_random.next_value();
 
-        Part {
-            p_partkey: part_key,
-            p_name: name,
-            p_mfgr: PartManufacturerName::new(manufacturer),
-            p_brand: PartBrandName::new(brand),
-            p_type: self.type_random.next_value(),
-            p_size: self.size_random.next_value(),
-            p_container: self.container_random.next_value(),
-            p_retailprice: TPCHDecimal(Self::calculate_part_price(part_key)),
-            p_comment: self.comment_random.next_value(),
-        }
+        sink.row(
+            // p_partkey
+            part_key,
+            // p_name
+            name,
+            // p_mfgr
+            PartManufacturerName::new(manufacturer),
+            // p_brand
+            PartBrandName::new(brand),
+            // p_type
+            self.type_random.next_value(),
+            // p_size
+            self.size_random.next_value(),
+            // p_container
+            self.container_random.next_value(),
+            // p_retailprice
+            TPCHDecimal(Self::calculate_part_price(part_key)),
+            // p_comment
+            self.comment_random.next_value(),
+        )
     }
 
     /// Calculates the price for a part
@@ -579,11 +787,18 @@ impl<'a> Iterator for PartGeneratorIterator<'a> {
     type Item = Part<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.next_row(&mut RowStructs)
+    }
+}
+
+impl<'a> PartGeneratorIterator<'a> {
+    /// Generates the next row into `sink`, see [`crate::columns`]
+    pub(crate) fn next_row<S: PartSink<'a>>(&mut self, sink: &mut S) -> Option<S::Output> {
         if self.index >= self.row_count {
             return None;
         }
 
-        let part = self.make_part(self.start_index + self.index + 1);
+        let part = self.make_part(self.start_index + self.index + 1, sink);
 
         self.name_random.row_finished();
         self.manufacturer_random.row_finished();
@@ -626,6 +841,7 @@ impl fmt::Display for SupplierName {
 /// 2|Supplier#000000002|89eJ5ksX3ImxJQBvxObC,|5|15-679-861-2259|4032.68| slyly bold instructions. idle dependen|
 /// ```
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Supplier {
     /// Primary key
     pub s_suppkey: i64,
@@ -663,10 +879,10 @@ impl fmt::Display for Supplier {
 #[derive(Debug, Clone)]
 pub struct SupplierGenerator<'a> {
     scale_factor: f64,
-    part: i32,
-    part_count: i32,
+    row_range: Range<i64>,
     distributions: &'a Distributions,
     text_pool: &'a TextPool,
+    seed: u64,
 }
 
 impl<'a> SupplierGenerator<'a> {
@@ -713,10 +929,15 @@ impl<'a> SupplierGenerator<'a> {
     ) -> SupplierGenerator<'b> {
         SupplierGenerator {
             scale_factor,
-            part,
-            part_count,
+            row_range: GenerateUtils::calculate_range(
+                Self::SCALE_BASE,
+                scale_factor,
+                part,
+                part_count,
+            ),
             distributions,
             text_pool,
+            seed: 0,
         }
     }
 
@@ -725,18 +946,61 @@ impl<'a> SupplierGenerator<'a> {
         GenerateUtils::calculate_row_count(Self::SCALE_BASE, scale_factor, part, part_count)
     }
 
+    /// Restricts the generator to the rows with indexes in `range`, instead
+    /// of its part
+    ///
+    /// Rows are numbered from `0` across the whole table, so generators for
+    /// adjacent ranges produce the same supplier rows as a single generator for
+    /// the whole table. This allows splitting the table into pieces of any
+    /// size, e.g. to balance the work between distributed workers.
+    pub fn with_row_range(mut self, range: Range<i64>) -> Self {
+        self.row_range =
+            GenerateUtils::clamp_range(range, Self::calculate_row_count(self.scale_factor, 1, 1));
+        self
+    }
+
+    /// Generates a different dataset by using `seed` for the random number
+    /// generators. See [`NationGenerator::with_seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
     /// Returns an iterator over the supplier rows
     pub fn iter(&self) -> SupplierGeneratorIterator<'a> {
         SupplierGeneratorIterator::new(
             self.distributions,
             self.text_pool,
-            GenerateUtils::calculate_start_index(
-                Self::SCALE_BASE,
-                self.scale_factor,
-                self.part,
-                self.part_count,
-            ),
-            Self::calculate_row_count(self.scale_factor, self.part, self.part_count),
+            self.row_range.start,
+            self.row_range.end - self.row_range.start,
+            self.seed,
+        )
+    }
+
+    /// Returns the supplier with the given row index, or `None` if there is no
+    /// such supplier
+    ///
+    /// Rows are numbered from `0` across the whole table, independently of
+    /// the part of this generator. See [`Self::generate_range`].
+    pub fn generate_row(&self, index: i64) -> Option<Supplier> {
+        self.generate_range(index..index + 1).next()
+    }
+
+    /// Returns an iterator over the supplier rows with indexes in `range`
+    ///
+    /// Rows are numbered from `0` across the whole table, independently of
+    /// the part of this generator, and indexes past the end of the table are
+    /// ignored. The random number generators jump directly to the first row,
+    /// so this is much faster than skipping rows of [`Self::iter`].
+    pub fn generate_range(&self, range: Range<i64>) -> SupplierGeneratorIterator<'a> {
+        let range =
+            GenerateUtils::clamp_range(range, Self::calculate_row_count(self.scale_factor, 1, 1));
+        SupplierGeneratorIterator::new(
+            self.distributions,
+            self.text_pool,
+            range.start,
+            range.end - range.start,
+            self.seed,
         )
     }
 }
@@ -774,27 +1038,36 @@ impl<'a> SupplierGeneratorIterator<'a> {
         text_pool: &'a TextPool,
         start_index: i64,
         row_count: i64,
+        seed: u64,
     ) -> Self {
-        let mut address_random =
-            RandomAlphaNumeric::new(706178559, SupplierGenerator::ADDRESS_AVERAGE_LENGTH);
-        let mut nation_key_random =
-            RandomBoundedInt::new(110356601, 0, (distributions.nations().size() - 1) as i32);
-        let mut phone_random = RandomPhoneNumber::new(884434366);
+        let mut address_random = RandomAlphaNumeric::new(
+            stream_seed(706178559, seed),
+            SupplierGenerator::ADDRESS_AVERAGE_LENGTH,
+        );
+        let mut nation_key_random = RandomBoundedInt::new(
+            stream_seed(110356601, seed),
+            0,
+            (distributions.nations().size() - 1) as i32,
+        );
+        let mut phone_random = RandomPhoneNumber::new(stream_seed(884434366, seed));
         let mut account_balance_random = RandomBoundedInt::new(
-            962338209,
+            stream_seed(962338209, seed),
             SupplierGenerator::ACCOUNT_BALANCE_MIN,
             SupplierGenerator::ACCOUNT_BALANCE_MAX,
         );
         let mut comment_random = RandomText::new(
-            1341315363,
+            stream_seed(1341315363, seed),
             text_pool,
             SupplierGenerator::COMMENT_AVERAGE_LENGTH as f64,
         );
-        let mut bbb_comment_random =
-            RandomBoundedInt::new(202794285, 1, SupplierGenerator::SCALE_BASE);
-        let mut bbb_junk_random = RowRandomInt::new(263032577, 1);
-        let mut bbb_offset_random = RowRandomInt::new(715851524, 1);
-        let mut bbb_type_random = RandomBoundedInt::new(753643799, 0, 100);
+        let mut bbb_comment_random = RandomBoundedInt::new(
+            stream_seed(202794285, seed),
+            1,
+            SupplierGenerator::SCALE_BASE,
+        );
+        let mut bbb_junk_random = RowRandomInt::new(stream_seed(263032577, seed), 1);
+        let mut bbb_offset_random = RowRandomInt::new(stream_seed(715851524, seed), 1);
+        let mut bbb_type_random = RandomBoundedInt::new(stream_seed(753643799, seed), 0, 100);
 
         // Advance all generators to the starting position
         address_random.advance_rows(start_index);
@@ -824,7 +1097,7 @@ impl<'a> SupplierGeneratorIterator<'a> {
     }
 
     /// Creates a supplier with the given key
-    fn make_supplier(&mut self, supplier_key: i64) -> Supplier {
+    fn make_supplier<S: SupplierSink<'a>>(&mut self, supplier_key: i64, sink: &mut S) -> S::Output {
         let mut comment = self.comment_random.next_value().to_string();
 
         // Add supplier complaints or commendation to the comment
@@ -850,34 +1123,42 @@ impl<'a> SupplierGeneratorIterator<'a> {
                     SupplierGenerator::BBB_RECOMMEND_TEXT
                 };
 
+            // The replaced ranges of the comment, moved to character
+            // boundaries for multilingual text (a no-op for ASCII)
+            let boundary = |index: usize| ceil_char_boundary(comment.as_bytes(), index);
+            let base_end = offset + SupplierGenerator::BBB_BASE_TEXT.len();
+            let type_start = base_end + noise;
+            let type_end = type_start + type_text.len();
+
             // Create a mutable string that we can modify in chunks
             let mut modified_comment = String::with_capacity(comment.len());
-            modified_comment.push_str(&comment[..offset]);
+            modified_comment.push_str(&comment[..boundary(offset)]);
             modified_comment.push_str(SupplierGenerator::BBB_BASE_TEXT);
-            modified_comment.push_str(
-                &comment[offset + SupplierGenerator::BBB_BASE_TEXT.len()
-                    ..offset + SupplierGenerator::BBB_BASE_TEXT.len() + noise],
-            );
+            modified_comment.push_str(&comment[boundary(base_end)..boundary(type_start)]);
             modified_comment.push_str(type_text);
-            modified_comment.push_str(
-                &comment
-                    [offset + SupplierGenerator::BBB_BASE_TEXT.len() + noise + type_text.len()..],
-            );
+            modified_comment.push_str(&comment[boundary(type_end)..]);
 
             comment = modified_comment;
         }
 
         let nation_key = self.nation_key_random.next_value() as i64;
 
-        Supplier {
-            s_suppkey: supplier_key,
-            s_name: SupplierName::new(supplier_key),
-            s_address: self.address_random.next_value(),
-            s_nationkey: nation_key,
-            s_phone: self.phone_random.next_value(nation_key),
-            s_acctbal: TPCHDecimal(self.account_balance_random.next_value() as i64),
-            s_comment: comment,
-        }
+        sink.row(
+            // s_suppkey
+            supplier_key,
+            // s_name
+            SupplierName::new(supplier_key),
+            // s_address
+            self.address_random.next_value(),
+            // s_nationkey
+            nation_key,
+            // s_phone
+            self.phone_random.next_value(nation_key),
+            // s_acctbal
+            TPCHDecimal(self.account_balance_random.next_value() as i64),
+            // s_comment
+            comment,
+        )
     }
 }
 
@@ -885,11 +1166,18 @@ impl Iterator for SupplierGeneratorIterator<'_> {
     type Item = Supplier;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.next_row(&mut RowStructs)
+    }
+}
+
+impl<'a> SupplierGeneratorIterator<'a> {
+    /// Generates the next row into `sink`, see [`crate::columns`]
+    pub(crate) fn next_row<S: SupplierSink<'a>>(&mut self, sink: &mut S) -> Option<S::Output> {
         if self.index >= self.row_count {
             return None;
         }
 
-        let supplier = self.make_supplier(self.start_index + self.index + 1);
+        let supplier = self.make_supplier(self.start_index + self.index + 1, sink);
 
         self.address_random.row_finished();
         self.nation_key_random.row_finished();
@@ -934,6 +1222,7 @@ impl fmt::Display for CustomerName {
 /// 2|Customer#000000002|XSTf4,NCwDVaWNe6tEgvwfmRchLXak|13|23-768-687-3665|121.65|AUTOMOBILE|l accounts. blithely ironic theodolites integrate boldly: caref|
 /// ```
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Customer<'a> {
     /// Primary key
     pub c_custkey: i64,
@@ -974,10 +1263,11 @@ impl fmt::Display for Customer<'_> {
 #[derive(Debug, Clone)]
 pub struct CustomerGenerator<'a> {
     scale_factor: f64,
-    part: i32,
-    part_count: i32,
+    row_range: Range<i64>,
     distributions: &'a Distributions,
     text_pool: &'a TextPool,
+    seed: u64,
+    skew: Skew,
 }
 
 impl<'a> CustomerGenerator<'a> {
@@ -1015,10 +1305,16 @@ impl<'a> CustomerGenerator<'a> {
     ) -> CustomerGenerator<'b> {
         CustomerGenerator {
             scale_factor,
-            part,
-            part_count,
+            row_range: GenerateUtils::calculate_range(
+                Self::SCALE_BASE,
+                scale_factor,
+                part,
+                part_count,
+            ),
             distributions,
             text_pool,
+            seed: 0,
+            skew: Skew::default(),
         }
     }
 
@@ -1027,18 +1323,70 @@ impl<'a> CustomerGenerator<'a> {
         GenerateUtils::calculate_row_count(Self::SCALE_BASE, scale_factor, part, part_count)
     }
 
+    /// Restricts the generator to the rows with indexes in `range`, instead
+    /// of its part
+    ///
+    /// Rows are numbered from `0` across the whole table, so generators for
+    /// adjacent ranges produce the same customer rows as a single generator for
+    /// the whole table. This allows splitting the table into pieces of any
+    /// size, e.g. to balance the work between distributed workers.
+    pub fn with_row_range(mut self, range: Range<i64>) -> Self {
+        self.row_range =
+            GenerateUtils::clamp_range(range, Self::calculate_row_count(self.scale_factor, 1, 1));
+        self
+    }
+
+    /// Generates a different dataset by using `seed` for the random number
+    /// generators. See [`NationGenerator::with_seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Skews `c_mktsegment`
+    /// with `skew`. See the [`skew`](crate::skew) module for details.
+    pub fn with_skew(mut self, skew: Skew) -> Self {
+        self.skew = skew;
+        self
+    }
+
     /// Returns an iterator over the customer rows
     pub fn iter(&self) -> CustomerGeneratorIterator<'a> {
         CustomerGeneratorIterator::new(
             self.distributions,
             self.text_pool,
-            GenerateUtils::calculate_start_index(
-                Self::SCALE_BASE,
-                self.scale_factor,
-                self.part,
-                self.part_count,
-            ),
-            Self::calculate_row_count(self.scale_factor, self.part, self.part_count),
+            self.row_range.start,
+            self.row_range.end - self.row_range.start,
+            self.seed,
+            self.skew,
+        )
+    }
+
+    /// Returns the customer with the given row index, or `None` if there is no
+    /// such customer
+    ///
+    /// Rows are numbered from `0` across the whole table, independently of
+    /// the part of this generator. See [`Self::generate_range`].
+    pub fn generate_row(&self, index: i64) -> Option<Customer<'a>> {
+        self.generate_range(index..index + 1).next()
+    }
+
+    /// Returns an iterator over the customer rows with indexes in `range`
+    ///
+    /// Rows are numbered from `0` across the whole table, independently of
+    /// the part of this generator, and indexes past the end of the table are
+    /// ignored. The random number generators jump directly to the first row,
+    /// so this is much faster than skipping rows of [`Self::iter`].
+    pub fn generate_range(&self, range: Range<i64>) -> CustomerGeneratorIterator<'a> {
+        let range =
+            GenerateUtils::clamp_range(range, Self::calculate_row_count(self.scale_factor, 1, 1));
+        CustomerGeneratorIterator::new(
+            self.distributions,
+            self.text_pool,
+            range.start,
+            range.end - range.start,
+            self.seed,
+            self.skew,
         )
     }
 }
@@ -1073,21 +1421,31 @@ impl<'a> CustomerGeneratorIterator<'a> {
         text_pool: &'a TextPool,
         start_index: i64,
         row_count: i64,
+        seed: u64,
+        skew: Skew,
     ) -> Self {
-        let mut address_random =
-            RandomAlphaNumeric::new(881155353, CustomerGenerator::ADDRESS_AVERAGE_LENGTH);
-        let mut nation_key_random =
-            RandomBoundedInt::new(1489529863, 0, (distributions.nations().size() - 1) as i32);
-        let mut phone_random = RandomPhoneNumber::new(1521138112);
+        let mut address_random = RandomAlphaNumeric::new(
+            stream_seed(881155353, seed),
+            CustomerGenerator::ADDRESS_AVERAGE_LENGTH,
+        );
+        let mut nation_key_random = RandomBoundedInt::new(
+            stream_seed(1489529863, seed),
+            0,
+            (distributions.nations().size() - 1) as i32,
+        );
+        let mut phone_random = RandomPhoneNumber::new(stream_seed(1521138112, seed));
         let mut account_balance_random = RandomBoundedInt::new(
-            298370230,
+            stream_seed(298370230, seed),
             CustomerGenerator::ACCOUNT_BALANCE_MIN,
             CustomerGenerator::ACCOUNT_BALANCE_MAX,
         );
-        let mut market_segment_random =
-            RandomString::new(1140279430, distributions.market_segments());
+        let mut market_segment_random = RandomString::new(
+            stream_seed(1140279430, seed),
+            distributions.market_segments(),
+        )
+        .with_skew(skew.factor(SkewedColumn::CMktsegment));
         let mut comment_random = RandomText::new(
-            1335826707,
+            stream_seed(1335826707, seed),
             text_pool,
             CustomerGenerator::COMMENT_AVERAGE_LENGTH as f64,
         );
@@ -1114,19 +1472,27 @@ impl<'a> CustomerGeneratorIterator<'a> {
     }
 
     /// Creates a customer with the given key
-    fn make_customer(&mut self, customer_key: i64) -> Customer<'a> {
+    fn make_customer<S: CustomerSink<'a>>(&mut self, customer_key: i64, sink: &mut S) -> S::Output {
         let nation_key = self.nation_key_random.next_value() as i64;
 
-        Customer {
-            c_custkey: customer_key,
-            c_name: CustomerName::new(customer_key),
-            c_address: self.address_random.next_value(),
-            c_nationkey: nation_key,
-            c_phone: self.phone_random.next_value(nation_key),
-            c_acctbal: TPCHDecimal(self.account_balance_random.next_value() as i64),
-            c_mktsegment: self.market_segment_random.next_value(),
-            c_comment: self.comment_random.next_value(),
-        }
+        sink.row(
+            // c_custkey
+            customer_key,
+            // c_name
+            CustomerName::new(customer_key),
+            // c_address
+            self.address_random.next_value(),
+            // c_nationkey
+            nation_key,
+            // c_phone
+            self.phone_random.next_value(nation_key),
+            // c_acctbal
+            TPCHDecimal(self.account_balance_random.next_value() as i64),
+            // c_mktsegment
+            self.market_segment_random.next_value(),
+            // c_comment
+            self.comment_random.next_value(),
+        )
     }
 }
 
@@ -1134,11 +1500,18 @@ impl<'a> Iterator for CustomerGeneratorIterator<'a> {
     type Item = Customer<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.next_row(&mut RowStructs)
+    }
+}
+
+impl<'a> CustomerGeneratorIterator<'a> {
+    /// Generates the next row into `sink`, see [`crate::columns`]
+    pub(crate) fn next_row<S: CustomerSink<'a>>(&mut self, sink: &mut S) -> Option<S::Output> {
         if self.index >= self.row_count {
             return None;
         }
 
-        let customer = self.make_customer(self.start_index + self.index + 1);
+        let customer = self.make_customer(self.start_index + self.index + 1, sink);
 
         self.address_random.row_finished();
         self.nation_key_random.row_finished();
@@ -1163,6 +1536,7 @@ impl<'a> Iterator for CustomerGeneratorIterator<'a> {
 /// 1|4|8076|993.49|ven ideas. quickly even packages print. pending multipliers must have to are fluff|
 /// ```
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PartSupp<'a> {
     /// Primary key, foreign key to PART
     pub ps_partkey: i64,
@@ -1190,14 +1564,14 @@ impl fmt::Display for PartSupp<'_> {
 #[derive(Debug, Clone)]
 pub struct PartSuppGenerator<'a> {
     scale_factor: f64,
-    part: i32,
-    part_count: i32,
+    row_range: Range<i64>,
     text_pool: &'a TextPool,
+    seed: u64,
 }
 
 impl<'a> PartSuppGenerator<'a> {
     /// Base scale for part-supplier generation
-    const SUPPLIERS_PER_PART: i32 = 4;
+    pub const SUPPLIERS_PER_PART: i32 = 4;
 
     // Constants for part-supplier generation
     const AVAILABLE_QUANTITY_MIN: i32 = 1;
@@ -1229,9 +1603,14 @@ impl<'a> PartSuppGenerator<'a> {
     ) -> PartSuppGenerator<'_> {
         PartSuppGenerator {
             scale_factor,
-            part,
-            part_count,
+            row_range: GenerateUtils::calculate_range(
+                PartGenerator::SCALE_BASE,
+                scale_factor,
+                part,
+                part_count,
+            ),
             text_pool,
+            seed: 0,
         }
     }
 
@@ -1246,20 +1625,60 @@ impl<'a> PartSuppGenerator<'a> {
         )
     }
 
+    /// Restricts the generator to the rows with indexes in `range`, instead
+    /// of its part
+    ///
+    /// Rows are numbered from `0` across the whole table, so generators for
+    /// adjacent ranges produce the same part supplier rows as a single generator for
+    /// the whole table. This allows splitting the table into pieces of any
+    /// size, e.g. to balance the work between distributed workers.
+    ///
+    /// The rows of the range are parts (see [`PartGenerator`]), each of which
+    /// has [`Self::SUPPLIERS_PER_PART`] part supplier rows.
+    pub fn with_row_range(mut self, range: Range<i64>) -> Self {
+        self.row_range =
+            GenerateUtils::clamp_range(range, Self::calculate_row_count(self.scale_factor, 1, 1));
+        self
+    }
+
+    /// Generates a different dataset by using `seed` for the random number
+    /// generators. See [`NationGenerator::with_seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
     /// Returns an iterator over the part supplier rows
     pub fn iter(&self) -> PartSuppGeneratorIterator<'a> {
-        let scale_base = PartGenerator::SCALE_BASE;
+        self.generate_range(self.row_range.clone())
+    }
+
+    /// Returns the [`Self::SUPPLIERS_PER_PART`] part supplier rows of the
+    /// part with the given row index, or an empty iterator if there is no
+    /// such part
+    ///
+    /// Like in [`Self::with_row_range`], rows are numbered by part from `0`
+    /// across the whole table, independently of the part of this generator.
+    pub fn generate_row(&self, index: i64) -> PartSuppGeneratorIterator<'a> {
+        self.generate_range(index..index + 1)
+    }
 
+    /// Returns an iterator over the part supplier rows of the parts with
+    /// indexes in `range`
+    ///
+    /// Like in [`Self::with_row_range`], rows are numbered by part from `0`
+    /// across the whole table, independently of the part of this generator,
+    /// and indexes past the end of the table are ignored. The random number
+    /// generators jump directly to the first part.
+    pub fn generate_range(&self, range: Range<i64>) -> PartSuppGeneratorIterator<'a> {
+        let range =
+            GenerateUtils::clamp_range(range, Self::calculate_row_count(self.scale_factor, 1, 1));
         PartSuppGeneratorIterator::new(
             self.text_pool,
             self.scale_factor,
-            GenerateUtils::calculate_start_index(
-                scale_base,
-                self.scale_factor,
-                self.part,
-                self.part_count,
-            ),
-            Self::calculate_row_count(self.scale_factor, self.part, self.part_count),
+            range.start,
+            range.end - range.start,
+            self.seed,
         )
     }
 }
@@ -1289,21 +1708,27 @@ pub struct PartSuppGeneratorIterator<'a> {
 }
 
 impl<'a> PartSuppGeneratorIterator<'a> {
-    fn new(text_pool: &'a TextPool, scale_factor: f64, start_index: i64, row_count: i64) -> Self {
+    fn new(
+        text_pool: &'a TextPool,
+        scale_factor: f64,
+        start_index: i64,
+        row_count: i64,
+        seed: u64,
+    ) -> Self {
         let mut available_quantity_random = RandomBoundedInt::new_with_seeds_per_row(
-            1671059989,
+            stream_seed(1671059989, seed),
             PartSuppGenerator::AVAILABLE_QUANTITY_MIN,
             PartSuppGenerator::AVAILABLE_QUANTITY_MAX,
             PartSuppGenerator::SUPPLIERS_PER_PART,
         );
         let mut supply_cost_random = RandomBoundedInt::new_with_seeds_per_row(
-            1051288424,
+            stream_seed(1051288424, seed),
             PartSuppGenerator::SUPPLY_COST_MIN,
             PartSuppGenerator::SUPPLY_COST_MAX,
             PartSuppGenerator::SUPPLIERS_PER_PART,
         );
         let mut comment_random = RandomText::new_with_expected_row_count(
-            1961692154,
+            stream_seed(1961692154, seed),
             text_pool,
             PartSuppGenerator::COMMENT_AVERAGE_LENGTH as f64,
             PartSuppGenerator::SUPPLIERS_PER_PART,
@@ -1327,7 +1752,11 @@ impl<'a> PartSuppGeneratorIterator<'a> {
     }
 
     /// Creates a part-supplier entry with the given part key
-    fn make_part_supplier(&mut self, part_key: i64) -> PartSupp<'a> {
+    fn make_part_supplier<S: PartSuppSink<'a>>(
+        &mut self,
+        part_key: i64,
+        sink: &mut S,
+    ) -> S::Output {
         let supplier_key = Self::select_part_supplier(
             part_key,
             self.part_supplier_number as i64,
@@ -1338,13 +1767,13 @@ impl<'a> PartSuppGeneratorIterator<'a> {
         let ps_supplycost = TPCHDecimal(self.supply_cost_random.next_value() as i64);
         let ps_comment = self.comment_random.next_value();
 
-        PartSupp {
-            ps_partkey: part_key,
-            ps_suppkey: supplier_key,
+        sink.row(
+            part_key,
+            supplier_key,
             ps_availqty,
             ps_supplycost,
             ps_comment,
-        }
+        )
     }
 
     /// Selects a supplier for a given part and supplier number
@@ -1365,12 +1794,19 @@ impl<'a> Iterator for PartSuppGeneratorIterator<'a> {
     type Item = PartSupp<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.next_row(&mut RowStructs)
+    }
+}
+
+impl<'a> PartSuppGeneratorIterator<'a> {
+    /// Generates the next row into `sink`, see [`crate::columns`]
+    pub(crate) fn next_row<S: PartSuppSink<'a>>(&mut self, sink: &mut S) -> Option<S::Output> {
         if self.index >= self.row_count {
             return None;
         }
 
         let part_key = self.start_index + self.index + 1;
-        let part_supplier = self.make_part_supplier(part_key);
+        let part_supplier = self.make_part_supplier(part_key, sink);
         self.part_supplier_number += 1;
 
         // advance next row only when all suppliers for the part have been produced
@@ -1431,6 +1867,19 @@ impl Display for OrderStatus {
     }
 }
 
+impl FromStr for OrderStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "F" => Ok(OrderStatus::Fulfilled),
+            "O" => Ok(OrderStatus::Open),
+            "P" => Ok(OrderStatus::Pending),
+            _ => Err(format!("Invalid order status '{s}', expected F, O or P")),
+        }
+    }
+}
+
 /// The ORDERS table
 ///
 /// The Display trait is implemented to format the line item data as a string
@@ -1441,6 +1890,7 @@ impl Display for OrderStatus {
 ///  2|79|O|40183.29|1996-12-01|1-URGENT|Clerk#000000880|0| foxes. pending accounts at the pending, silent asymptot|
 /// ```
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Order<'a> {
     /// Primary key
     pub o_orderkey: i64,
@@ -1484,10 +1934,12 @@ impl fmt::Display for Order<'_> {
 #[derive(Debug, Clone)]
 pub struct OrderGenerator<'a> {
     scale_factor: f64,
-    part: i32,
-    part_count: i32,
+    row_range: Range<i64>,
     distributions: &'a Distributions,
     text_pool: &'a TextPool,
+    seed: u64,
+    skew: Skew,
+    date_range: DateRange,
 }
 
 impl<'a> OrderGenerator<'a> {
@@ -1496,9 +1948,6 @@ impl<'a> OrderGenerator<'a> {
 
     // Constants for order generation
     const CUSTOMER_MORTALITY: i32 = 3; // portion with no orders
-    const ORDER_DATE_MIN: i32 = dates::MIN_GENERATE_DATE;
-    const ORDER_DATE_MAX: i32 =
-        Self::ORDER_DATE_MIN + (dates::TOTAL_DATE_RANGE - LineItemGenerator::ITEM_SHIP_DAYS - 1);
     const CLERK_SCALE_BASE: i32 = 1000;
 
     const LINE_COUNT_MIN: i32 = 1;
@@ -1533,10 +1982,17 @@ impl<'a> OrderGenerator<'a> {
     ) -> OrderGenerator<'b> {
         OrderGenerator {
             scale_factor,
-            part,
-            part_count,
+            row_range: GenerateUtils::calculate_range(
+                Self::SCALE_BASE,
+                scale_factor,
+                part,
+                part_count,
+            ),
             distributions,
             text_pool,
+            seed: 0,
+            skew: Skew::default(),
+            date_range: DateRange::default(),
         }
     }
 
@@ -1545,25 +2001,103 @@ impl<'a> OrderGenerator<'a> {
         GenerateUtils::calculate_row_count(Self::SCALE_BASE, scale_factor, part, part_count)
     }
 
+    /// Restricts the generator to the rows with indexes in `range`, instead
+    /// of its part
+    ///
+    /// Rows are numbered from `0` across the whole table, so generators for
+    /// adjacent ranges produce the same order rows as a single generator for
+    /// the whole table. This allows splitting the table into pieces of any
+    /// size, e.g. to balance the work between distributed workers.
+    pub fn with_row_range(mut self, range: Range<i64>) -> Self {
+        self.row_range =
+            GenerateUtils::clamp_range(range, Self::calculate_row_count(self.scale_factor, 1, 1));
+        self
+    }
+
+    /// Generates a different dataset by using `seed` for the random number
+    /// generators. See [`NationGenerator::with_seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Skews `o_custkey` and `o_orderpriority`, and `l_partkey` of the
+    /// line items used to compute `o_totalprice`
+    /// with `skew`. See the [`skew`](crate::skew) module for details.
+    pub fn with_skew(mut self, skew: Skew) -> Self {
+        self.skew = skew;
+        self
+    }
+
+    /// Generates the order dates in `date_range` instead of 1992 to 1998,
+    /// see [`DateRange`]
+    ///
+    /// The `o_orderstatus` of the orders depends on the current date of the
+    /// range.
+    pub fn with_date_range(mut self, date_range: DateRange) -> Self {
+        self.date_range = date_range;
+        self
+    }
+
     /// Returns an iterator over the order rows
     pub fn iter(&self) -> OrderGeneratorIterator<'a> {
         OrderGeneratorIterator::new(
             self.distributions,
             self.text_pool,
             self.scale_factor,
-            GenerateUtils::calculate_start_index(
-                Self::SCALE_BASE,
-                self.scale_factor,
-                self.part,
-                self.part_count,
-            ),
-            Self::calculate_row_count(self.scale_factor, self.part, self.part_count),
+            self.row_range.start,
+            self.row_range.end - self.row_range.start,
+            0,
+            self.seed,
+            self.skew,
+            self.date_range,
+        )
+    }
+
+    /// Returns the order with the given row index, or `None` if there is no
+    /// such order
+    ///
+    /// Rows are numbered from `0` across the whole table, independently of
+    /// the part of this generator. See [`Self::generate_range`].
+    pub fn generate_row(&self, index: i64) -> Option<Order<'a>> {
+        self.generate_range(index..index + 1).next()
+    }
+
+    /// Returns an iterator over the order rows with indexes in `range`
+    ///
+    /// Rows are numbered from `0` across the whole table, independently of
+    /// the part of this generator, and indexes past the end of the table are
+    /// ignored. The random number generators jump directly to the first row,
+    /// so this is much faster than skipping rows of [`Self::iter`].
+    pub fn generate_range(&self, range: Range<i64>) -> OrderGeneratorIterator<'a> {
+        let range =
+            GenerateUtils::clamp_range(range, Self::calculate_row_count(self.scale_factor, 1, 1));
+        OrderGeneratorIterator::new(
+            self.distributions,
+            self.text_pool,
+            self.scale_factor,
+            range.start,
+            range.end - range.start,
+            0,
+            self.seed,
+            self.skew,
+            self.date_range,
         )
     }
 
     /// Creates the order date random generator
-    pub fn create_order_date_random() -> RandomBoundedInt {
-        RandomBoundedInt::new(1066728069, Self::ORDER_DATE_MIN, Self::ORDER_DATE_MAX)
+    ///
+    /// The order dates are in `date_range`, at least
+    /// [`LineItemGenerator::ITEM_SHIP_DAYS`] days before its end.
+    pub fn create_order_date_random(seed: u64, date_range: DateRange) -> RandomBoundedInt {
+        let order_date_min = date_range.min_generate_date();
+        let order_date_max =
+            order_date_min + (date_range.day_count() - LineItemGenerator::ITEM_SHIP_DAYS - 1);
+        RandomBoundedInt::new(
+            stream_seed(1066728069, seed),
+            order_date_min,
+            order_date_max,
+        )
     }
 
     /// Creates the line count random generator
@@ -1573,11 +2107,48 @@ impl<'a> OrderGenerator<'a> {
 
     /// Creates an order key from an index
     pub fn make_order_key(order_index: i64) -> i64 {
+        Self::make_sparse_order_key(order_index, 0)
+    }
+
+    /// Returns the order index of an order key of the initial population, the
+    /// inverse of [`Self::make_order_key`]
+    ///
+    /// Returns `None` if the key was not created by [`Self::make_order_key`],
+    /// for example the keys of the orders inserted by the refresh functions.
+    ///
+    /// ```
+    /// # use tpchgen::generators::OrderGenerator;
+    /// assert_eq!(OrderGenerator::make_order_key(9), 33);
+    /// assert_eq!(OrderGenerator::order_index_from_key(33), Some(9));
+    /// assert_eq!(OrderGenerator::order_index_from_key(9), None);
+    /// ```
+    pub fn order_index_from_key(order_key: i64) -> Option<i64> {
+        if order_key <= 0 {
+            return None;
+        }
+        let low_bits = order_key & ((1 << Self::ORDER_KEY_SPARSE_KEEP) - 1);
+        let high_bits = order_key >> Self::ORDER_KEY_SPARSE_KEEP;
+        if high_bits & ((1 << Self::ORDER_KEY_SPARSE_BITS) - 1) != 0 {
+            return None;
+        }
+
+        let order_index =
+            ((high_bits >> Self::ORDER_KEY_SPARSE_BITS) << Self::ORDER_KEY_SPARSE_KEEP) + low_bits;
+        (order_index > 0).then_some(order_index)
+    }
+
+    /// Creates an order key from an index and a sparse key sequence number.
+    ///
+    /// The initial population uses sequence `0`, leaving the keys of
+    /// sequences `1..=3` unused. The refresh functions insert new orders using
+    /// these keys (see [`RefreshGenerator`]).
+    pub fn make_sparse_order_key(order_index: i64, sequence: i64) -> i64 {
         let low_bits = order_index & ((1 << Self::ORDER_KEY_SPARSE_KEEP) - 1);
 
         let mut ok = order_index;
         ok >>= Self::ORDER_KEY_SPARSE_KEEP;
         ok <<= Self::ORDER_KEY_SPARSE_BITS;
+        ok += sequence;
         ok <<= Self::ORDER_KEY_SPARSE_KEEP;
         ok += low_bits;
 
@@ -1614,44 +2185,58 @@ pub struct OrderGeneratorIterator<'a> {
     start_index: i64,
     row_count: i64,
     max_customer_key: i64,
+    order_key_sequence: i64,
+    date_range: DateRange,
 
     index: i64,
 }
 impl<'a> OrderGeneratorIterator<'a> {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         distributions: &'a Distributions,
         text_pool: &'a TextPool,
         scale_factor: f64,
         start_index: i64,
         row_count: i64,
+        order_key_sequence: i64,
+        seed: u64,
+        skew: Skew,
+        date_range: DateRange,
     ) -> Self {
-        let mut order_date_random = OrderGenerator::create_order_date_random();
+        let mut order_date_random = OrderGenerator::create_order_date_random(seed, date_range);
         let mut line_count_random = OrderGenerator::create_line_count_random();
 
         let max_customer_key = (CustomerGenerator::SCALE_BASE as f64 * scale_factor) as i64;
 
-        let mut customer_key_random =
-            RandomBoundedLong::new(851767375, scale_factor >= 30000.0, 1, max_customer_key);
+        let mut customer_key_random = RandomBoundedLong::new(
+            stream_seed(851767375, seed),
+            scale_factor >= 30000.0,
+            1,
+            max_customer_key,
+        )
+        .with_skew(skew.factor(SkewedColumn::OCustkey));
 
         let mut order_priority_random =
-            RandomString::new(591449447, distributions.order_priority());
+            RandomString::new(stream_seed(591449447, seed), distributions.order_priority())
+                .with_skew(skew.factor(SkewedColumn::OOrderpriority));
 
         let max_clerk = (scale_factor * OrderGenerator::CLERK_SCALE_BASE as f64)
             .max(OrderGenerator::CLERK_SCALE_BASE as f64) as i32;
-        let mut clerk_random = RandomBoundedInt::new(1171034773, 1, max_clerk);
+        let mut clerk_random = RandomBoundedInt::new(stream_seed(1171034773, seed), 1, max_clerk);
 
         let mut comment_random = RandomText::new(
-            276090261,
+            stream_seed(276090261, seed),
             text_pool,
             OrderGenerator::COMMENT_AVERAGE_LENGTH as f64,
         );
 
         // For line item simulation
-        let mut line_quantity_random = LineItemGenerator::create_quantity_random();
-        let mut line_discount_random = LineItemGenerator::create_discount_random();
-        let mut line_tax_random = LineItemGenerator::create_tax_random();
-        let mut line_part_key_random = LineItemGenerator::create_part_key_random(scale_factor);
-        let mut line_ship_date_random = LineItemGenerator::create_ship_date_random();
+        let mut line_quantity_random = LineItemGenerator::create_quantity_random(seed);
+        let mut line_discount_random = LineItemGenerator::create_discount_random(seed);
+        let mut line_tax_random = LineItemGenerator::create_tax_random(seed);
+        let mut line_part_key_random =
+            LineItemGenerator::create_part_key_random(scale_factor, seed, &skew);
+        let mut line_ship_date_random = LineItemGenerator::create_ship_date_random(seed);
 
         // Advance all generators to the starting position
         order_date_random.advance_rows(start_index);
@@ -1682,28 +2267,20 @@ impl<'a> OrderGeneratorIterator<'a> {
             start_index,
             row_count,
             max_customer_key,
+            order_key_sequence,
+            date_range,
             index: 0,
         }
     }
 
     /// Creates an order with the given index
-    fn make_order(&mut self, index: i64) -> Order<'a> {
-        let order_key = OrderGenerator::make_order_key(index);
-
+    fn make_order<S: OrderSink<'a>>(&mut self, index: i64, sink: &mut S) -> S::Output {
         let order_date = self.order_date_random.next_value();
 
-        // generate customer key, taking into account customer mortality rate
-        let mut customer_key = self.customer_key_random.next_value();
-        let mut delta = 1;
-        while customer_key % OrderGenerator::CUSTOMER_MORTALITY as i64 == 0 {
-            customer_key += delta;
-            customer_key = customer_key.min(self.max_customer_key);
-            delta *= -1;
-        }
-
         let mut total_price = 0;
         let mut shipped_count = 0;
 
+        // simulate the line items of the order
         let line_count = self.line_count_random.next_value();
         for _ in 0..line_count {
             let quantity = self.line_quantity_random.next_value();
@@ -1718,11 +2295,46 @@ impl<'a> OrderGeneratorIterator<'a> {
             total_price += ((discounted_price / 100) * (100 + tax as i64)) / 100;
 
             let ship_date = self.line_ship_date_random.next_value() + order_date;
-            if TPCHDate::is_in_past(ship_date) {
+            if self.date_range.is_in_past(ship_date) {
                 shipped_count += 1;
             }
         }
 
+        self.complete_order(
+            index,
+            order_date,
+            line_count,
+            total_price,
+            shipped_count,
+            sink,
+        )
+    }
+
+    /// Creates an order with the given index from the summary of its line
+    /// items
+    ///
+    /// Uses only the random streams of the order columns, not the ones
+    /// simulating the line items.
+    fn complete_order<S: OrderSink<'a>>(
+        &mut self,
+        index: i64,
+        order_date: i32,
+        line_count: i32,
+        total_price: i64,
+        shipped_count: i32,
+        sink: &mut S,
+    ) -> S::Output {
+        let order_key = OrderGenerator::make_sparse_order_key(index, self.order_key_sequence);
+
+        // generate customer key, taking into account customer mortality rate
+        let mut customer_key = self.customer_key_random.next_value();
+        let mut delta = 1;
+        while customer_key % OrderGenerator::CUSTOMER_MORTALITY as i64 == 0 {
+            customer_key += delta;
+            customer_key = customer_key.min(self.max_customer_key);
+            delta *= -1;
+        }
+
         let order_status = if shipped_count == line_count {
             OrderStatus::Fulfilled
         } else if shipped_count > 0 {
@@ -1734,17 +2346,26 @@ impl<'a> OrderGeneratorIterator<'a> {
         let clerk_id = self.clerk_random.next_value();
         let clerk_name = ClerkName::new(clerk_id);
 
-        Order {
-            o_orderkey: order_key,
-            o_custkey: customer_key,
-            o_orderstatus: order_status,
-            o_totalprice: TPCHDecimal(total_price),
-            o_orderdate: TPCHDate::new(order_date),
-            o_orderpriority: self.order_priority_random.next_value(),
-            o_clerk: clerk_name,
-            o_shippriority: 0, // Fixed value per TPC-H spec
-            o_comment: self.comment_random.next_value(),
-        }
+        sink.row(
+            // o_orderkey
+            order_key,
+            // o_custkey
+            customer_key,
+            // o_orderstatus
+            order_status,
+            // o_totalprice
+            TPCHDecimal(total_price),
+            // o_orderdate
+            TPCHDate::new(order_date),
+            // o_orderpriority
+            self.order_priority_random.next_value(),
+            // o_clerk
+            clerk_name,
+            // o_shippriority
+            0, // Fixed value per TPC-H spec
+            // o_comment
+            self.comment_random.next_value(),
+        )
     }
 }
 
@@ -1752,12 +2373,25 @@ impl<'a> Iterator for OrderGeneratorIterator<'a> {
     type Item = Order<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.next_row(&mut RowStructs)
+    }
+}
+
+impl<'a> OrderGeneratorIterator<'a> {
+    /// Generates the next row into `sink`, see [`crate::columns`]
+    pub(crate) fn next_row<S: OrderSink<'a>>(&mut self, sink: &mut S) -> Option<S::Output> {
         if self.index >= self.row_count {
             return None;
         }
 
-        let order = self.make_order(self.start_index + self.index + 1);
+        let order = self.make_order(self.start_index + self.index + 1, sink);
+        self.finish_row();
 
+        Some(order)
+    }
+
+    /// Advances all the random streams to the next order
+    fn finish_row(&mut self) {
         self.order_date_random.row_finished();
         self.line_count_random.row_finished();
         self.customer_key_random.row_finished();
@@ -1772,8 +2406,6 @@ impl<'a> Iterator for OrderGeneratorIterator<'a> {
         self.line_ship_date_random.row_finished();
 
         self.index += 1;
-
-        Some(order)
     }
 }
 
@@ -1788,6 +2420,7 @@ impl<'a> Iterator for OrderGeneratorIterator<'a> {
 /// 1|68|9|2|36|34850.16|0.09|0.06|N|O|1996-04-12|1996-02-28|1996-04-20|TAKE BACK RETURN|MAIL|ly final dependencies: slyly bold |
 /// ```
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct LineItem<'a> {
     /// Foreign key to ORDERS
     pub l_orderkey: i64,
@@ -1853,10 +2486,12 @@ impl fmt::Display for LineItem<'_> {
 #[derive(Debug, Clone)]
 pub struct LineItemGenerator<'a> {
     scale_factor: f64,
-    part: i32,
-    part_count: i32,
+    row_range: Range<i64>,
     distributions: &'a Distributions,
     text_pool: &'a TextPool,
+    seed: u64,
+    skew: Skew,
+    date_range: DateRange,
 }
 
 impl<'a> LineItemGenerator<'a> {
@@ -1904,38 +2539,167 @@ impl<'a> LineItemGenerator<'a> {
     ) -> LineItemGenerator<'b> {
         LineItemGenerator {
             scale_factor,
-            part,
-            part_count,
+            row_range: GenerateUtils::calculate_range(
+                OrderGenerator::SCALE_BASE,
+                scale_factor,
+                part,
+                part_count,
+            ),
             distributions,
             text_pool,
+            seed: 0,
+            skew: Skew::default(),
+            date_range: DateRange::default(),
         }
     }
 
+    /// Restricts the generator to the rows with indexes in `range`, instead
+    /// of its part
+    ///
+    /// Rows are numbered from `0` across the whole table, so generators for
+    /// adjacent ranges produce the same line item rows as a single generator for
+    /// the whole table. This allows splitting the table into pieces of any
+    /// size, e.g. to balance the work between distributed workers.
+    ///
+    /// The rows of the range are orders (see [`OrderGenerator`]), since the
+    /// line items of each order are generated together.
+    pub fn with_row_range(mut self, range: Range<i64>) -> Self {
+        self.row_range = GenerateUtils::clamp_range(range, Self::order_count(self.scale_factor));
+        self
+    }
+
+    /// Generates a different dataset by using `seed` for the random number
+    /// generators. See [`NationGenerator::with_seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Skews `l_partkey`, `l_suppkey`, `l_returnflag`, `l_shipinstruct`
+    /// and `l_shipmode`
+    /// with `skew`. See the [`skew`](crate::skew) module for details.
+    pub fn with_skew(mut self, skew: Skew) -> Self {
+        self.skew = skew;
+        self
+    }
+
+    /// Generates the dates of the line items of orders in `date_range`
+    /// instead of 1992 to 1998, see [`DateRange`]
+    ///
+    /// The `l_returnflag` and `l_linestatus` of the line items depend on the
+    /// current date of the range.
+    pub fn with_date_range(mut self, date_range: DateRange) -> Self {
+        self.date_range = date_range;
+        self
+    }
+
     /// Returns an iterator over the line item rows
     pub fn iter(&self) -> LineItemGeneratorIterator<'a> {
+        self.generate_range(self.row_range.clone())
+    }
+
+    /// Returns an iterator over the line items of the order with the given
+    /// row index, or an empty iterator if there is no such order
+    ///
+    /// Like in [`Self::with_row_range`], rows are numbered by order from `0`
+    /// across the whole table, independently of the part of this generator.
+    /// The random number generators jump directly to the order.
+    pub fn generate_row(&self, index: i64) -> LineItemGeneratorIterator<'a> {
+        self.generate_range(index..index + 1)
+    }
+
+    /// Returns an iterator over the line items of the orders with indexes in
+    /// `range`
+    ///
+    /// Like in [`Self::with_row_range`], rows are numbered by order from `0`
+    /// across the whole table, independently of the part of this generator,
+    /// and indexes past the end of the table are ignored. The random number
+    /// generators jump directly to the first order.
+    pub fn generate_range(&self, range: Range<i64>) -> LineItemGeneratorIterator<'a> {
+        let range = GenerateUtils::clamp_range(range, Self::order_count(self.scale_factor));
         LineItemGeneratorIterator::new(
             self.distributions,
             self.text_pool,
             self.scale_factor,
-            GenerateUtils::calculate_start_index(
-                OrderGenerator::SCALE_BASE,
-                self.scale_factor,
-                self.part,
-                self.part_count,
-            ),
-            GenerateUtils::calculate_row_count(
-                OrderGenerator::SCALE_BASE,
-                self.scale_factor,
-                self.part,
-                self.part_count,
-            ),
+            range.start,
+            range.end - range.start,
+            0,
+            self.seed,
+            self.skew,
+            self.date_range,
         )
     }
 
+    /// Returns an iterator over the line items of the order with the given
+    /// order key
+    ///
+    /// The iterator is empty if there is no order with this key.
+    ///
+    /// ```
+    /// # use tpchgen::generators::LineItemGenerator;
+    /// let generator = LineItemGenerator::new(1.0, 1, 1);
+    /// let line_items: Vec<_> = generator.line_items_for_order_key(3).collect();
+    /// assert_eq!(line_items.len(), 6);
+    /// assert!(line_items.iter().all(|line_item| line_item.l_orderkey == 3));
+    /// ```
+    pub fn line_items_for_order_key(&self, order_key: i64) -> LineItemGeneratorIterator<'a> {
+        match OrderGenerator::order_index_from_key(order_key) {
+            Some(order_index) => self.generate_row(order_index - 1),
+            None => self.generate_range(0..0),
+        }
+    }
+
+    /// Returns the total number of orders at the given scale factor
+    fn order_count(scale_factor: f64) -> i64 {
+        GenerateUtils::calculate_row_count(OrderGenerator::SCALE_BASE, scale_factor, 1, 1)
+    }
+
+    /// Return the exact row count for the given scale factor and generator part count
+    ///
+    /// The number of line items of each order is random, so unlike the other
+    /// tables the count can not be computed from the scale factor alone.
+    /// Instead, this function replays only the random stream that chooses the
+    /// number of line items per order, which is much faster than generating
+    /// the rows.
+    ///
+    /// ```
+    /// # use tpchgen::generators::LineItemGenerator;
+    /// assert_eq!(LineItemGenerator::calculate_row_count(0.01, 1, 1), 60175);
+    /// assert_eq!(
+    ///     LineItemGenerator::calculate_row_count(0.01, 1, 2)
+    ///         + LineItemGenerator::calculate_row_count(0.01, 2, 2),
+    ///     60175
+    /// );
+    /// ```
+    pub fn calculate_row_count(scale_factor: f64, part: i32, part_count: i32) -> i64 {
+        let start_index = GenerateUtils::calculate_start_index(
+            OrderGenerator::SCALE_BASE,
+            scale_factor,
+            part,
+            part_count,
+        );
+        let order_count = GenerateUtils::calculate_row_count(
+            OrderGenerator::SCALE_BASE,
+            scale_factor,
+            part,
+            part_count,
+        );
+
+        let mut line_count_random = OrderGenerator::create_line_count_random();
+        line_count_random.advance_rows(start_index);
+
+        let mut row_count = 0;
+        for _ in 0..order_count {
+            row_count += line_count_random.next_value() as i64;
+            line_count_random.row_finished();
+        }
+        row_count
+    }
+
     /// Creates a quantity random generator
-    pub fn create_quantity_random() -> RandomBoundedInt {
+    pub fn create_quantity_random(seed: u64) -> RandomBoundedInt {
         RandomBoundedInt::new_with_seeds_per_row(
-            209208115,
+            stream_seed(209208115, seed),
             Self::QUANTITY_MIN,
             Self::QUANTITY_MAX,
             OrderGenerator::LINE_COUNT_MAX,
@@ -1943,9 +2707,9 @@ impl<'a> LineItemGenerator<'a> {
     }
 
     /// Creates a discount random generator
-    pub fn create_discount_random() -> RandomBoundedInt {
+    pub fn create_discount_random(seed: u64) -> RandomBoundedInt {
         RandomBoundedInt::new_with_seeds_per_row(
-            554590007,
+            stream_seed(554590007, seed),
             Self::DISCOUNT_MIN.0 as i32,
             Self::DISCOUNT_MAX.0 as i32,
             OrderGenerator::LINE_COUNT_MAX,
@@ -1953,9 +2717,9 @@ impl<'a> LineItemGenerator<'a> {
     }
 
     /// Creates a tax random generator
-    pub fn create_tax_random() -> RandomBoundedInt {
+    pub fn create_tax_random(seed: u64) -> RandomBoundedInt {
         RandomBoundedInt::new_with_seeds_per_row(
-            721958466,
+            stream_seed(721958466, seed),
             Self::TAX_MIN.0 as i32,
             Self::TAX_MAX.0 as i32,
             OrderGenerator::LINE_COUNT_MAX,
@@ -1963,22 +2727,23 @@ impl<'a> LineItemGenerator<'a> {
     }
 
     /// Creates a part key random generator
-    pub fn create_part_key_random(scale_factor: f64) -> RandomBoundedLong {
+    pub fn create_part_key_random(scale_factor: f64, seed: u64, skew: &Skew) -> RandomBoundedLong {
         // If scale_factor >= 30000, use long `RandomBoundedLong` otherwise
         // use `RandomBoundedInt` to avoid overflow.
         RandomBoundedLong::new_with_seeds_per_row(
-            1808217256,
+            stream_seed(1808217256, seed),
             scale_factor >= 30000.0,
             Self::PART_KEY_MIN as i64,
             (PartGenerator::SCALE_BASE as f64 * scale_factor) as i64,
             OrderGenerator::LINE_COUNT_MAX,
         )
+        .with_skew(skew.factor(SkewedColumn::LPartkey))
     }
 
     /// Creates a ship date random generator
-    pub fn create_ship_date_random() -> RandomBoundedInt {
+    pub fn create_ship_date_random(seed: u64) -> RandomBoundedInt {
         RandomBoundedInt::new_with_seeds_per_row(
-            1769349045,
+            stream_seed(1769349045, seed),
             Self::SHIP_DATE_MIN,
             Self::SHIP_DATE_MAX,
             OrderGenerator::LINE_COUNT_MAX,
@@ -2022,6 +2787,8 @@ pub struct LineItemGeneratorIterator<'a> {
     scale_factor: f64,
     start_index: i64,
     row_count: i64,
+    order_key_sequence: i64,
+    date_range: DateRange,
 
     index: i64,
     order_date: i32,
@@ -2030,60 +2797,70 @@ pub struct LineItemGeneratorIterator<'a> {
 }
 
 impl<'a> LineItemGeneratorIterator<'a> {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         distributions: &'a Distributions,
         text_pool: &'a TextPool,
         scale_factor: f64,
         start_index: i64,
         row_count: i64,
+        order_key_sequence: i64,
+        seed: u64,
+        skew: Skew,
+        date_range: DateRange,
     ) -> Self {
-        let mut order_date_random = OrderGenerator::create_order_date_random();
+        let mut order_date_random = OrderGenerator::create_order_date_random(seed, date_range);
         let mut line_count_random = OrderGenerator::create_line_count_random();
 
-        let mut quantity_random = LineItemGenerator::create_quantity_random();
-        let mut discount_random = LineItemGenerator::create_discount_random();
-        let mut tax_random = LineItemGenerator::create_tax_random();
+        let mut quantity_random = LineItemGenerator::create_quantity_random(seed);
+        let mut discount_random = LineItemGenerator::create_discount_random(seed);
+        let mut tax_random = LineItemGenerator::create_tax_random(seed);
 
-        let mut line_part_key_random = LineItemGenerator::create_part_key_random(scale_factor);
+        let mut line_part_key_random =
+            LineItemGenerator::create_part_key_random(scale_factor, seed, &skew);
 
         let mut supplier_number_random = RandomBoundedInt::new_with_seeds_per_row(
-            2095021727,
+            stream_seed(2095021727, seed),
             0,
             3,
             OrderGenerator::LINE_COUNT_MAX,
-        );
+        )
+        .with_skew(skew.factor(SkewedColumn::LSuppkey));
 
-        let mut ship_date_random = LineItemGenerator::create_ship_date_random();
+        let mut ship_date_random = LineItemGenerator::create_ship_date_random(seed);
         let mut commit_date_random = RandomBoundedInt::new_with_seeds_per_row(
-            904914315,
+            stream_seed(904914315, seed),
             LineItemGenerator::COMMIT_DATE_MIN,
             LineItemGenerator::COMMIT_DATE_MAX,
             OrderGenerator::LINE_COUNT_MAX,
         );
         let mut receipt_date_random = RandomBoundedInt::new_with_seeds_per_row(
-            373135028,
+            stream_seed(373135028, seed),
             LineItemGenerator::RECEIPT_DATE_MIN,
             LineItemGenerator::RECEIPT_DATE_MAX,
             OrderGenerator::LINE_COUNT_MAX,
         );
 
         let mut returned_flag_random = RandomString::new_with_expected_row_count(
-            717419739,
+            stream_seed(717419739, seed),
             distributions.return_flags(),
             OrderGenerator::LINE_COUNT_MAX,
-        );
+        )
+        .with_skew(skew.factor(SkewedColumn::LReturnflag));
         let mut ship_instructions_random = RandomString::new_with_expected_row_count(
-            1371272478,
+            stream_seed(1371272478, seed),
             distributions.ship_instructions(),
             OrderGenerator::LINE_COUNT_MAX,
-        );
+        )
+        .with_skew(skew.factor(SkewedColumn::LShipinstruct));
         let mut ship_mode_random = RandomString::new_with_expected_row_count(
-            675466456,
+            stream_seed(675466456, seed),
             distributions.ship_modes(),
             OrderGenerator::LINE_COUNT_MAX,
-        );
+        )
+        .with_skew(skew.factor(SkewedColumn::LShipmode));
         let mut comment_random = RandomText::new_with_expected_row_count(
-            1095462486,
+            stream_seed(1095462486, seed),
             text_pool,
             LineItemGenerator::COMMENT_AVERAGE_LENGTH as f64,
             OrderGenerator::LINE_COUNT_MAX,
@@ -2111,11 +2888,7 @@ impl<'a> LineItemGeneratorIterator<'a> {
 
         comment_random.advance_rows(start_index);
 
-        // generate information for initial order
-        let order_date = order_date_random.next_value();
-        let line_count = line_count_random.next_value() - 1;
-
-        LineItemGeneratorIterator {
+        let mut iterator = LineItemGeneratorIterator {
             order_date_random,
             line_count_random,
             quantity_random,
@@ -2133,16 +2906,71 @@ impl<'a> LineItemGeneratorIterator<'a> {
             scale_factor,
             start_index,
             row_count,
+            order_key_sequence,
+            date_range,
             index: 0,
-            order_date,
-            line_count,
+            order_date: 0,
+            line_count: 0,
             line_number: 0,
+        };
+        iterator.start_order();
+        iterator
+    }
+
+    /// Generates the order date and line count of the current order
+    fn start_order(&mut self) {
+        self.order_date = self.order_date_random.next_value();
+        self.line_count = self.line_count_random.next_value();
+        self.line_number = 0;
+    }
+
+    /// Generates the remaining line items of the current order and advances
+    /// to the next order
+    ///
+    /// Returns the order date of the order with its line items. This is how
+    /// [`OrderLineItemGeneratorIterator`] generates all the line items of an
+    /// order at once.
+    fn complete_order(&mut self) -> (i32, Vec<LineItem<'a>>) {
+        let order_date = self.order_date;
+        let order_index = self.start_index + self.index + 1;
+        let mut line_items = Vec::with_capacity((self.line_count - self.line_number) as usize);
+        while self.line_number < self.line_count {
+            line_items.push(self.make_line_item(order_index, &mut RowStructs));
+            self.line_number += 1;
         }
+        self.finish_row();
+        (order_date, line_items)
+    }
+
+    /// Advances all the random streams to the next order
+    fn finish_row(&mut self) {
+        self.order_date_random.row_finished();
+        self.line_count_random.row_finished();
+
+        self.quantity_random.row_finished();
+        self.discount_random.row_finished();
+        self.tax_random.row_finished();
+
+        self.line_part_key_random.row_finished();
+        self.supplier_number_random.row_finished();
+
+        self.ship_date_random.row_finished();
+        self.commit_date_random.row_finished();
+        self.receipt_date_random.row_finished();
+
+        self.returned_flag_random.row_finished();
+        self.ship_instructions_random.row_finished();
+        self.ship_mode_random.row_finished();
+
+        self.comment_random.row_finished();
+
+        self.index += 1;
+        self.start_order();
     }
 
     /// Creates a line item with the given order index
-    fn make_line_item(&mut self, order_index: i64) -> LineItem<'a> {
-        let order_key = OrderGenerator::make_order_key(order_index);
+    fn make_line_item<S: LineItemSink<'a>>(&mut self, order_index: i64, sink: &mut S) -> S::Output {
+        let order_key = OrderGenerator::make_sparse_order_key(order_index, self.order_key_sequence);
 
         let quantity = self.quantity_random.next_value();
         let discount = self.discount_random.next_value();
@@ -2167,13 +2995,13 @@ impl<'a> LineItemGeneratorIterator<'a> {
         let mut receipt_date = self.receipt_date_random.next_value();
         receipt_date += ship_date;
 
-        let returned_flag = if TPCHDate::is_in_past(receipt_date) {
+        let returned_flag = if self.date_range.is_in_past(receipt_date) {
             self.returned_flag_random.next_value()
         } else {
             "N"
         };
 
-        let status = if TPCHDate::is_in_past(ship_date) {
+        let status = if self.date_range.is_in_past(ship_date) {
             "F" // Fulfilled
         } else {
             "O" // Open
@@ -2183,24 +3011,40 @@ impl<'a> LineItemGeneratorIterator<'a> {
         let ship_mode = self.ship_mode_random.next_value();
         let comment = self.comment_random.next_value();
 
-        LineItem {
-            l_orderkey: order_key,
-            l_partkey: part_key,
-            l_suppkey: supplier_key,
-            l_linenumber: (self.line_number + 1),
-            l_quantity: quantity as i64,
-            l_extendedprice: TPCHDecimal(extended_price),
-            l_discount: TPCHDecimal(discount as i64),
-            l_tax: TPCHDecimal(tax as i64),
-            l_returnflag: returned_flag,
-            l_linestatus: status,
-            l_shipdate: TPCHDate::new(ship_date),
-            l_commitdate: TPCHDate::new(commit_date),
-            l_receiptdate: TPCHDate::new(receipt_date),
-            l_shipinstruct: ship_instructions,
-            l_shipmode: ship_mode,
-            l_comment: comment,
-        }
+        sink.row(
+            // l_orderkey
+            order_key,
+            // l_partkey
+            part_key,
+            // l_suppkey
+            supplier_key,
+            // l_linenumber
+            self.line_number + 1,
+            // l_quantity
+            quantity as i64,
+            // l_extendedprice
+            TPCHDecimal(extended_price),
+            // l_discount
+            TPCHDecimal(discount as i64),
+            // l_tax
+            TPCHDecimal(tax as i64),
+            // l_returnflag
+            returned_flag,
+            // l_linestatus
+            status,
+            // l_shipdate
+            TPCHDate::new(ship_date),
+            // l_commitdate
+            TPCHDate::new(commit_date),
+            // l_receiptdate
+            TPCHDate::new(receipt_date),
+            // l_shipinstruct
+            ship_instructions,
+            // l_shipmode
+            ship_mode,
+            // l_comment
+            comment,
+        )
     }
 }
 
@@ -2208,44 +3052,447 @@ impl<'a> Iterator for LineItemGeneratorIterator<'a> {
     type Item = LineItem<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.next_row(&mut RowStructs)
+    }
+}
+
+impl<'a> LineItemGeneratorIterator<'a> {
+    /// Generates the next row into `sink`, see [`crate::columns`]
+    pub(crate) fn next_row<S: LineItemSink<'a>>(&mut self, sink: &mut S) -> Option<S::Output> {
         if self.index >= self.row_count {
             return None;
         }
 
-        let line_item = self.make_line_item(self.start_index + self.index + 1);
+        let line_item = self.make_line_item(self.start_index + self.index + 1, sink);
         self.line_number += 1;
 
         // advance next row only when all lines for the order have been produced
-        if self.line_number > self.line_count {
-            self.order_date_random.row_finished();
-            self.line_count_random.row_finished();
+        if self.line_number == self.line_count {
+            self.finish_row();
+        }
 
-            self.quantity_random.row_finished();
-            self.discount_random.row_finished();
-            self.tax_random.row_finished();
+        Some(line_item)
+    }
+}
 
-            self.line_part_key_random.row_finished();
-            self.supplier_number_random.row_finished();
+/// An [`Order`] together with its [`LineItem`]s
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct OrderLineItems<'a> {
+    /// The order
+    pub order: Order<'a>,
+    /// The line items of the order
+    pub line_items: Vec<LineItem<'a>>,
+}
 
-            self.ship_date_random.row_finished();
-            self.commit_date_random.row_finished();
-            self.receipt_date_random.row_finished();
+/// Generator for the ORDERS and LINEITEM tables in a single pass
+///
+/// [`OrderGenerator`] simulates the line items of each order to calculate the
+/// order total price and status, and [`LineItemGenerator`] then generates the
+/// same line items again. This generator produces each order together with
+/// its line items, generating each line item only once, so it is faster than
+/// using both generators when both tables are needed.
+///
+/// The orders and line items are the same as the ones of [`OrderGenerator`]
+/// and [`LineItemGenerator`] for the same part.
+///
+/// # Example
+/// ```
+/// # use tpchgen::generators::{LineItemGenerator, OrderGenerator, OrderLineItemGenerator};
+/// let generator = OrderLineItemGenerator::new(1.0, 1, 1);
+/// let first = generator.iter().next().unwrap();
+///
+/// assert_eq!(first.order, OrderGenerator::new(1.0, 1, 1).iter().next().unwrap());
+/// assert_eq!(first.line_items.len(), 6);
+/// assert_eq!(
+///     first.line_items,
+///     LineItemGenerator::new(1.0, 1, 1).iter().take(6).collect::<Vec<_>>()
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrderLineItemGenerator<'a> {
+    scale_factor: f64,
+    row_range: Range<i64>,
+    distributions: &'a Distributions,
+    text_pool: &'a TextPool,
+    seed: u64,
+    skew: Skew,
+    date_range: DateRange,
+}
 
-            self.returned_flag_random.row_finished();
-            self.ship_instructions_random.row_finished();
-            self.ship_mode_random.row_finished();
+impl<'a> OrderLineItemGenerator<'a> {
+    /// Creates a new OrderLineItemGenerator with the given scale factor
+    ///
+    /// Note the generator's lifetime is `&'static`. See [`NationGenerator`] for
+    /// more details.
+    pub fn new(scale_factor: f64, part: i32, part_count: i32) -> OrderLineItemGenerator<'static> {
+        // Note: use explicit lifetime to ensure this remains `&'static`
+        Self::new_with_distributions_and_text_pool(
+            scale_factor,
+            part,
+            part_count,
+            Distributions::static_default(),
+            TextPool::get_or_init_default(),
+        )
+    }
 
-            self.comment_random.row_finished();
+    /// Creates a OrderLineItemGenerator with specified distributions and text pool
+    pub fn new_with_distributions_and_text_pool<'b>(
+        scale_factor: f64,
+        part: i32,
+        part_count: i32,
+        distributions: &'b Distributions,
+        text_pool: &'b TextPool,
+    ) -> OrderLineItemGenerator<'b> {
+        OrderLineItemGenerator {
+            scale_factor,
+            row_range: GenerateUtils::calculate_range(
+                OrderGenerator::SCALE_BASE,
+                scale_factor,
+                part,
+                part_count,
+            ),
+            distributions,
+            text_pool,
+            seed: 0,
+            skew: Skew::default(),
+            date_range: DateRange::default(),
+        }
+    }
 
-            self.index += 1;
+    /// Restricts the generator to the orders with indexes in `range`, instead
+    /// of its part
+    ///
+    /// See [`OrderGenerator::with_row_range`].
+    pub fn with_row_range(mut self, range: Range<i64>) -> Self {
+        self.row_range = GenerateUtils::clamp_range(
+            range,
+            OrderGenerator::calculate_row_count(self.scale_factor, 1, 1),
+        );
+        self
+    }
+
+    /// Generates a different dataset by using `seed` for the random number
+    /// generators. See [`NationGenerator::with_seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
 
-            // generate information for next order
-            self.line_count = self.line_count_random.next_value() - 1;
-            self.order_date = self.order_date_random.next_value();
-            self.line_number = 0;
+    /// Skews the columns of the orders and line items, see
+    /// [`OrderGenerator::with_skew`] and [`LineItemGenerator::with_skew`]
+    /// with `skew`. See the [`skew`](crate::skew) module for details.
+    pub fn with_skew(mut self, skew: Skew) -> Self {
+        self.skew = skew;
+        self
+    }
+
+    /// Generates the dates of the orders and line items in `date_range`, see
+    /// [`OrderGenerator::with_date_range`]
+    pub fn with_date_range(mut self, date_range: DateRange) -> Self {
+        self.date_range = date_range;
+        self
+    }
+
+    /// Returns an iterator over the orders and their line items
+    pub fn iter(&self) -> OrderLineItemGeneratorIterator<'a> {
+        let start_index = self.row_range.start;
+        let row_count = self.row_range.end - self.row_range.start;
+        OrderLineItemGeneratorIterator {
+            orders: OrderGeneratorIterator::new(
+                self.distributions,
+                self.text_pool,
+                self.scale_factor,
+                start_index,
+                row_count,
+                0,
+                self.seed,
+                self.skew,
+                self.date_range,
+            ),
+            line_items: LineItemGeneratorIterator::new(
+                self.distributions,
+                self.text_pool,
+                self.scale_factor,
+                start_index,
+                row_count,
+                0,
+                self.seed,
+                self.skew,
+                self.date_range,
+            ),
         }
+    }
+}
 
-        Some(line_item)
+impl<'a> IntoIterator for OrderLineItemGenerator<'a> {
+    type Item = OrderLineItems<'a>;
+    type IntoIter = OrderLineItemGeneratorIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator that generates Order rows together with their LineItem rows
+#[derive(Debug)]
+pub struct OrderLineItemGeneratorIterator<'a> {
+    /// Only the random streams of the order columns are used
+    orders: OrderGeneratorIterator<'a>,
+    line_items: LineItemGeneratorIterator<'a>,
+}
+
+impl<'a> Iterator for OrderLineItemGeneratorIterator<'a> {
+    type Item = OrderLineItems<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let orders = &mut self.orders;
+        if orders.index >= orders.row_count {
+            return None;
+        }
+
+        // the line items iterator is positioned at the first line of the order
+        let (order_date, line_items) = self.line_items.complete_order();
+
+        // same calculation as `OrderGeneratorIterator::make_order`
+        let mut total_price = 0;
+        let mut shipped_count = 0;
+        for line_item in &line_items {
+            let discounted_price = line_item.l_extendedprice.0 * (100 - line_item.l_discount.0);
+            total_price += ((discounted_price / 100) * (100 + line_item.l_tax.0)) / 100;
+            if line_item.l_linestatus == "F" {
+                shipped_count += 1;
+            }
+        }
+
+        let order = orders.complete_order(
+            orders.start_index + orders.index + 1,
+            order_date,
+            line_items.len() as i32,
+            total_price,
+            shipped_count,
+            &mut RowStructs,
+        );
+        orders.finish_row();
+
+        Some(OrderLineItems { order, line_items })
+    }
+}
+
+/// An order deleted by the RF2 refresh function
+///
+/// The Display trait is implemented to format the order key in the format of
+/// the `delete.<n>` files written by `dbgen`.
+///
+/// ```text
+/// 1|
+/// 2|
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrderDelete {
+    /// Key of the order (and its line items) to delete
+    pub o_orderkey: i64,
+}
+
+impl fmt::Display for OrderDelete {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}|", self.o_orderkey)
+    }
+}
+
+/// Generator for the refresh function update streams
+///
+/// The TPC-H refresh functions modify the ORDERS and LINEITEM tables:
+///
+/// * RF1 inserts new orders and their line items. The new orders use the
+///   sparse order keys left unused by the initial population (see
+///   [`OrderGenerator::make_sparse_order_key`]).
+/// * RF2 deletes existing orders and their line items by order key.
+///
+/// Each update stream inserts and deletes 0.1% of the orders of the initial
+/// population, in the format of the `orders.tbl.u<n>`, `lineitem.tbl.u<n>`
+/// and `delete.<n>` files created by `dbgen -U <n>`. The inserted rows are
+/// the rows of the initial population following those of the previous
+/// streams, with new order keys, and the deleted orders are the orders of
+/// these rows.
+///
+/// # Example
+/// ```
+/// # use tpchgen::generators::RefreshGenerator;
+/// // Create the first update stream at Scale Factor 1 (SF 1)
+/// let generator = RefreshGenerator::new(1.0, 1);
+///
+/// // RF1: new orders (and their line items with `line_items()`)
+/// let order = generator.orders().next().unwrap();
+/// assert_eq!(order.o_orderkey, 9);
+///
+/// // RF2: the keys of the orders to delete
+/// let deletes: Vec<_> = generator.deletes().take(3).map(|d| d.to_string()).collect();
+/// assert_eq!(deletes, vec!["1|", "2|", "3|"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RefreshGenerator<'a> {
+    scale_factor: f64,
+    update_stream: i32,
+    distributions: &'a Distributions,
+    text_pool: &'a TextPool,
+    seed: u64,
+    skew: Skew,
+    date_range: DateRange,
+}
+
+impl<'a> RefreshGenerator<'a> {
+    /// Percentage of orders modified by each update stream, in units of 0.01%
+    const UPDATE_PERCENTAGE: i64 = 10;
+    /// Number of update streams that share a sparse order key sequence
+    const STREAMS_PER_KEY_SEQUENCE: i64 = 10_000 / Self::UPDATE_PERCENTAGE;
+
+    /// Creates a new RefreshGenerator for the given scale factor and update
+    /// stream (1-based)
+    ///
+    /// Note the generator's lifetime is `&'static`. See [`NationGenerator`] for
+    /// more details.
+    pub fn new(scale_factor: f64, update_stream: i32) -> RefreshGenerator<'static> {
+        // Note: use explicit lifetime to ensure this remains `&'static`
+        Self::new_with_distributions_and_text_pool(
+            scale_factor,
+            update_stream,
+            Distributions::static_default(),
+            TextPool::get_or_init_default(),
+        )
+    }
+
+    /// Creates a RefreshGenerator with specified distributions and text pool
+    pub fn new_with_distributions_and_text_pool<'b>(
+        scale_factor: f64,
+        update_stream: i32,
+        distributions: &'b Distributions,
+        text_pool: &'b TextPool,
+    ) -> RefreshGenerator<'b> {
+        assert!(
+            update_stream >= 1,
+            "update streams are numbered from 1, got {update_stream}"
+        );
+        RefreshGenerator {
+            scale_factor,
+            update_stream,
+            distributions,
+            text_pool,
+            seed: 0,
+            skew: Skew::default(),
+            date_range: DateRange::default(),
+        }
+    }
+
+    /// Generates a different dataset by using `seed` for the random number
+    /// generators. See [`NationGenerator::with_seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Skews the columns of the inserted orders and line items, see
+    /// [`OrderGenerator::with_skew`] and [`LineItemGenerator::with_skew`]
+    /// with `skew`. See the [`skew`](crate::skew) module for details.
+    pub fn with_skew(mut self, skew: Skew) -> Self {
+        self.skew = skew;
+        self
+    }
+
+    /// Generates the dates of the inserted orders and line items in
+    /// `date_range`, see [`OrderGenerator::with_date_range`]
+    pub fn with_date_range(mut self, date_range: DateRange) -> Self {
+        self.date_range = date_range;
+        self
+    }
+
+    /// Return the number of orders inserted (and deleted) by each update
+    /// stream for the given scale factor
+    pub fn calculate_row_count(scale_factor: f64) -> i64 {
+        // like dbgen, truncates the count only once
+        ((OrderGenerator::SCALE_BASE / 10_000) as f64
+            * scale_factor
+            * Self::UPDATE_PERCENTAGE as f64) as i64
+    }
+
+    /// Returns an iterator over the orders inserted by RF1
+    pub fn orders(&self) -> OrderGeneratorIterator<'a> {
+        OrderGeneratorIterator::new(
+            self.distributions,
+            self.text_pool,
+            self.scale_factor,
+            self.start_index(),
+            Self::calculate_row_count(self.scale_factor),
+            self.insert_key_sequence(),
+            self.seed,
+            self.skew,
+            self.date_range,
+        )
+    }
+
+    /// Returns an iterator over the line items inserted by RF1
+    pub fn line_items(&self) -> LineItemGeneratorIterator<'a> {
+        LineItemGeneratorIterator::new(
+            self.distributions,
+            self.text_pool,
+            self.scale_factor,
+            self.start_index(),
+            Self::calculate_row_count(self.scale_factor),
+            self.insert_key_sequence(),
+            self.seed,
+            self.skew,
+            self.date_range,
+        )
+    }
+
+    /// Returns an iterator over the orders deleted by RF2
+    pub fn deletes(&self) -> OrderDeleteIterator {
+        OrderDeleteIterator {
+            start_index: self.start_index(),
+            row_count: Self::calculate_row_count(self.scale_factor),
+            order_key_sequence: (self.update_stream as i64 - 1) / Self::STREAMS_PER_KEY_SEQUENCE,
+            index: 0,
+        }
+    }
+
+    /// Index of the first order modified by this update stream
+    fn start_index(&self) -> i64 {
+        (self.update_stream as i64 - 1) * Self::calculate_row_count(self.scale_factor)
+    }
+
+    /// Sparse key sequence of the orders inserted by this update stream
+    fn insert_key_sequence(&self) -> i64 {
+        1 + self.update_stream as i64 / Self::STREAMS_PER_KEY_SEQUENCE
+    }
+}
+
+/// Iterator that generates the orders deleted by RF2
+#[derive(Debug)]
+pub struct OrderDeleteIterator {
+    start_index: i64,
+    row_count: i64,
+    order_key_sequence: i64,
+
+    index: i64,
+}
+
+impl Iterator for OrderDeleteIterator {
+    type Item = OrderDelete;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.row_count {
+            return None;
+        }
+
+        let order_key = OrderGenerator::make_sparse_order_key(
+            self.start_index + self.index + 1,
+            self.order_key_sequence,
+        );
+        self.index += 1;
+
+        Some(OrderDelete {
+            o_orderkey: order_key,
+        })
     }
 }
 
@@ -2508,6 +3755,352 @@ mod tests {
         assert!(!line_statuses.is_empty());
     }
 
+    #[test]
+    fn test_line_item_row_count() {
+        for part in 1..=3 {
+            let generator = LineItemGenerator::new(0.01, part, 3);
+            assert_eq!(
+                LineItemGenerator::calculate_row_count(0.01, part, 3),
+                generator.iter().count() as i64
+            );
+        }
+    }
+
+    #[test]
+    fn test_make_sparse_order_key() {
+        // Sequence 0 is used by the initial population
+        assert_eq!(OrderGenerator::make_sparse_order_key(9, 0), 33);
+        // Other sequences fill the gaps between the initial keys
+        assert_eq!(OrderGenerator::make_sparse_order_key(1, 1), 9);
+        assert_eq!(OrderGenerator::make_sparse_order_key(9, 1), 32 + 8 + 1);
+        assert_eq!(OrderGenerator::make_sparse_order_key(1, 3), 25);
+    }
+
+    #[test]
+    fn test_refresh_generation() {
+        // 0.1% of the 150,000 orders at SF 0.1
+        assert_eq!(RefreshGenerator::calculate_row_count(0.1), 150);
+        // 1.5 orders at SF 0.001, truncated once like dbgen
+        assert_eq!(RefreshGenerator::calculate_row_count(0.01), 15);
+        assert_eq!(RefreshGenerator::calculate_row_count(0.001), 1);
+        assert_eq!(RefreshGenerator::calculate_row_count(0.0001), 0);
+
+        let base_orders: Vec<_> = OrderGenerator::new(0.1, 1, 1).iter().take(300).collect();
+        let generator = RefreshGenerator::new(0.1, 2);
+
+        // RF1 orders match the initial population except for the sparse key
+        let orders: Vec<_> = generator.orders().collect();
+        assert_eq!(orders.len(), 150);
+        for (order, base) in orders.iter().zip(&base_orders[150..]) {
+            assert_ne!(order.o_orderkey, base.o_orderkey);
+            assert_eq!(order.o_custkey, base.o_custkey);
+            assert_eq!(order.o_totalprice, base.o_totalprice);
+            assert_eq!(order.o_orderdate, base.o_orderdate);
+            assert_eq!(order.o_comment, base.o_comment);
+        }
+        assert_eq!(
+            orders[0].o_orderkey,
+            OrderGenerator::make_sparse_order_key(151, 1)
+        );
+
+        // RF1 line items belong to the RF1 orders
+        let order_keys: std::collections::HashSet<_> =
+            orders.iter().map(|o| o.o_orderkey).collect();
+        assert!(generator
+            .line_items()
+            .all(|l| order_keys.contains(&l.l_orderkey)));
+
+        // RF2 deletes orders of the initial population
+        let deletes: Vec<_> = generator.deletes().map(|d| d.o_orderkey).collect();
+        let base_keys: Vec<_> = base_orders[150..].iter().map(|o| o.o_orderkey).collect();
+        assert_eq!(deletes, base_keys);
+    }
+
+    #[test]
+    fn test_generate_range() {
+        /// Checks generating `range` matches the same rows of the whole table
+        fn check<T: Display>(all: Vec<T>, range: Range<i64>, rows: impl Iterator<Item = T>) {
+            let all: Vec<_> = all.iter().map(|row| row.to_string()).collect();
+            let start = (range.start as usize).min(all.len());
+            let end = (range.end as usize).min(all.len());
+            let rows: Vec<_> = rows.map(|row| row.to_string()).collect();
+            assert_eq!(rows, all[start..end], "range {range:?}");
+        }
+
+        let scale_factor = 0.01;
+        for range in [0..3, 7..8, 5..5, 1495..1510, 9_999_999..10_000_000] {
+            let g = NationGenerator::default();
+            check(
+                g.iter().collect(),
+                range.clone(),
+                g.generate_range(range.clone()),
+            );
+            let g = RegionGenerator::default();
+            check(
+                g.iter().collect(),
+                range.clone(),
+                g.generate_range(range.clone()),
+            );
+            let g = PartGenerator::new(scale_factor, 1, 1);
+            check(
+                g.iter().collect(),
+                range.clone(),
+                g.generate_range(range.clone()),
+            );
+            let g = SupplierGenerator::new(scale_factor, 1, 1);
+            check(
+                g.iter().collect(),
+                range.clone(),
+                g.generate_range(range.clone()),
+            );
+            let g = CustomerGenerator::new(scale_factor, 1, 1);
+            check(
+                g.iter().collect(),
+                range.clone(),
+                g.generate_range(range.clone()),
+            );
+            let g = OrderGenerator::new(scale_factor, 1, 1);
+            check(
+                g.iter().collect(),
+                range.clone(),
+                g.generate_range(range.clone()),
+            );
+
+            // PARTSUPP and LINEITEM are indexed by part and by order
+            let g = PartSuppGenerator::new(scale_factor, 1, 1);
+            assert!(g
+                .generate_range(range.clone())
+                .eq(g.clone().with_row_range(range.clone()).iter()));
+            let g = LineItemGenerator::new(scale_factor, 1, 1);
+            assert!(g
+                .generate_range(range.clone())
+                .eq(g.clone().with_row_range(range.clone()).iter()));
+        }
+
+        // rows are numbered across the whole table, independently of the part
+        let part = OrderGenerator::new(scale_factor, 2, 3);
+        assert_eq!(
+            part.generate_row(0),
+            OrderGenerator::new(scale_factor, 1, 1).iter().next()
+        );
+        assert_eq!(part.generate_row(-1), None);
+        assert_eq!(part.generate_row(15_000), None);
+        let part_supps: Vec<_> = PartSuppGenerator::new(scale_factor, 1, 1)
+            .generate_row(1999)
+            .collect();
+        assert_eq!(part_supps.len(), 4);
+        assert!(part_supps.iter().all(|ps| ps.ps_partkey == 2000));
+        let line_items = LineItemGenerator::new(scale_factor, 1, 1);
+        assert!(line_items
+            .generate_row(14_999)
+            .eq(line_items.line_items_for_order_key(60_000)));
+        assert_eq!(
+            line_items.generate_row(14_999).last(),
+            line_items.iter().last()
+        );
+        assert_eq!(line_items.generate_row(15_000).count(), 0);
+    }
+
+    #[test]
+    fn test_order_index_from_key() {
+        for order_index in [1, 7, 8, 9, 1_000_000, 6_000_000_000] {
+            let order_key = OrderGenerator::make_order_key(order_index);
+            assert_eq!(
+                OrderGenerator::order_index_from_key(order_key),
+                Some(order_index)
+            );
+            for sequence in 1..=3 {
+                let order_key = OrderGenerator::make_sparse_order_key(order_index, sequence);
+                assert_eq!(OrderGenerator::order_index_from_key(order_key), None);
+            }
+        }
+        assert_eq!(OrderGenerator::order_index_from_key(0), None);
+        assert_eq!(OrderGenerator::order_index_from_key(-5), None);
+    }
+
+    #[test]
+    fn test_line_items_for_order_key() {
+        let generator = LineItemGenerator::new(0.01, 1, 1);
+        let all: Vec<_> = generator.iter().collect();
+        for order in OrderGenerator::new(0.01, 1, 1).iter().step_by(997) {
+            let expected: Vec<_> = all
+                .iter()
+                .filter(|line_item| line_item.l_orderkey == order.o_orderkey)
+                .cloned()
+                .collect();
+            let line_items: Vec<_> = generator
+                .line_items_for_order_key(order.o_orderkey)
+                .collect();
+            assert!(!line_items.is_empty());
+            assert_eq!(line_items, expected);
+        }
+
+        // keys without orders
+        assert_eq!(generator.line_items_for_order_key(9).count(), 0);
+        assert_eq!(
+            generator
+                .line_items_for_order_key(OrderGenerator::make_order_key(15_001))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_order_line_item_generator() {
+        // the joint generator must match the separate generators, per part and
+        // for row ranges
+        for (part, part_count) in [(1, 1), (2, 3)] {
+            let generator = OrderLineItemGenerator::new(0.01, part, part_count);
+            let orders: Vec<_> = OrderGenerator::new(0.01, part, part_count).iter().collect();
+            let line_items: Vec<_> = LineItemGenerator::new(0.01, part, part_count)
+                .iter()
+                .collect();
+
+            let joint: Vec<_> = generator.iter().collect();
+            assert_eq!(joint.len(), orders.len());
+            for (rows, order) in joint.iter().zip(&orders) {
+                assert_eq!(&rows.order, order);
+                assert!(!rows.line_items.is_empty());
+                for line_item in &rows.line_items {
+                    assert_eq!(line_item.l_orderkey, order.o_orderkey);
+                }
+            }
+            let joint_line_items: Vec<_> =
+                joint.into_iter().flat_map(|rows| rows.line_items).collect();
+            assert_eq!(joint_line_items, line_items);
+        }
+
+        let joint: Vec<_> = OrderLineItemGenerator::new(0.01, 1, 1)
+            .with_row_range(100..200)
+            .iter()
+            .map(|rows| rows.order)
+            .collect();
+        let orders: Vec<_> = OrderGenerator::new(0.01, 1, 1)
+            .with_row_range(100..200)
+            .iter()
+            .collect();
+        assert_eq!(joint, orders);
+    }
+
+    #[test]
+    fn test_with_seed() {
+        // seed 0 is the standard data
+        let orders: Vec<_> = OrderGenerator::new(0.01, 1, 1).iter().collect();
+        let seed_0: Vec<_> = OrderGenerator::new(0.01, 1, 1)
+            .with_seed(0)
+            .iter()
+            .collect();
+        assert_eq!(orders, seed_0);
+
+        // other seeds generate different data with the same number of rows
+        let seed_1: Vec<_> = OrderGenerator::new(0.01, 1, 1)
+            .with_seed(1)
+            .iter()
+            .collect();
+        let seed_2: Vec<_> = OrderGenerator::new(0.01, 1, 1)
+            .with_seed(2)
+            .iter()
+            .collect();
+        assert_eq!(seed_1.len(), orders.len());
+        assert_ne!(seed_1, orders);
+        assert_ne!(seed_1, seed_2);
+        assert_ne!(seed_1[0].o_custkey, orders[0].o_custkey);
+        let line_item_count = LineItemGenerator::new(0.01, 1, 1)
+            .with_seed(1)
+            .iter()
+            .count();
+        assert_eq!(
+            line_item_count as i64,
+            LineItemGenerator::calculate_row_count(0.01, 1, 1)
+        );
+
+        // parts and row ranges generate the same rows as the whole table
+        let part_2: Vec<_> = OrderGenerator::new(0.01, 2, 3)
+            .with_seed(1)
+            .iter()
+            .collect();
+        let range = GenerateUtils::calculate_range(OrderGenerator::SCALE_BASE, 0.01, 2, 3);
+        assert_eq!(part_2, seed_1[range.start as usize..range.end as usize]);
+        assert_eq!(
+            OrderGenerator::new(0.01, 1, 1)
+                .with_seed(1)
+                .generate_row(100),
+            Some(seed_1[100].clone())
+        );
+
+        // the orders are consistent with their line items
+        let joint: Vec<_> = OrderLineItemGenerator::new(0.01, 1, 1)
+            .with_seed(1)
+            .iter()
+            .map(|rows| rows.order)
+            .collect();
+        assert_eq!(joint, seed_1);
+    }
+
+    #[test]
+    fn test_with_skew() {
+        let line_items: Vec<_> = LineItemGenerator::new(0.01, 1, 1).iter().collect();
+        let uniform: Vec<_> = LineItemGenerator::new(0.01, 1, 1)
+            .with_skew(Skew::default())
+            .iter()
+            .collect();
+        assert_eq!(line_items, uniform);
+
+        // only the skewed columns change
+        let skew = Skew::default()
+            .with_factor(SkewedColumn::LPartkey, 1.0)
+            .with_factor(SkewedColumn::LShipmode, 2.0);
+        let skewed: Vec<_> = LineItemGenerator::new(0.01, 1, 1)
+            .with_skew(skew)
+            .iter()
+            .collect();
+        assert_eq!(skewed.len(), line_items.len());
+        for (skewed, line_item) in skewed.iter().zip(&line_items) {
+            assert_eq!(skewed.l_orderkey, line_item.l_orderkey);
+            assert_eq!(skewed.l_quantity, line_item.l_quantity);
+            assert_eq!(skewed.l_shipdate, line_item.l_shipdate);
+            assert_eq!(skewed.l_shipinstruct, line_item.l_shipinstruct);
+        }
+        let count = |rows: &[LineItem], f: &dyn Fn(&LineItem) -> bool| {
+            rows.iter().filter(|row| f(row)).count()
+        };
+        // with 2,000 parts, the hottest part is in about 1/10 of the line items
+        let hot_part = count(&skewed, &|l| l.l_partkey == 1);
+        assert!(hot_part > 3000, "{hot_part}");
+        assert!(count(&line_items, &|l| l.l_partkey == 1) < 100);
+        let first_mode = Distributions::static_default().ship_modes().get_value(0);
+        let hot_mode = count(&skewed, &|l| l.l_shipmode == first_mode);
+        assert!(hot_mode > skewed.len() / 2, "{hot_mode}");
+
+        // parts, row ranges and the orders are consistent with the whole table
+        let part_2: Vec<_> = LineItemGenerator::new(0.01, 2, 3)
+            .with_skew(skew)
+            .iter()
+            .collect();
+        let first = part_2[0].l_orderkey;
+        let start = skewed.iter().position(|l| l.l_orderkey == first).unwrap();
+        assert_eq!(part_2, skewed[start..start + part_2.len()]);
+        let orders: Vec<_> = OrderGenerator::new(0.01, 1, 1)
+            .with_skew(skew)
+            .iter()
+            .collect();
+        let joint: Vec<_> = OrderLineItemGenerator::new(0.01, 1, 1)
+            .with_skew(skew)
+            .iter()
+            .collect();
+        assert!(joint
+            .iter()
+            .zip(&orders)
+            .all(|(rows, order)| rows.order == *order));
+        assert_eq!(
+            joint
+                .into_iter()
+                .flat_map(|rows| rows.line_items)
+                .collect::<Vec<_>>(),
+            skewed
+        );
+    }
+
     #[test]
     fn check_iter_static_lifetimes() {
         // Lifetimes of iterators should be independent of the generator that
@@ -39,23 +39,52 @@
 //!
 //! - TBL: The `Display` impl of the row structs produces the TPCH TBL format.
 //! - CSV: the [`csv`] module has formatters for CSV output (e.g. [`LineItemCsv`]).
+//! - Columnar: the [`columns`] module has column buffers (e.g. [`LineItemColumns`])
+//!   filled directly by the generator iterators.
+//!
+//! The [`parse`] module reads TBL and CSV files back into the [`owned`] row
+//! types (e.g. [`owned::LineItem`]), which own their values and can also be
+//! created from the rows of the generators with `From`.
+//!
+//! The [`validate`] module checks the rows of the tables against the rules of
+//! the TPC-H data, such as `o_totalprice` being the total of the line items.
+//!
+//! The [`digest`] module computes a [`TableDigest`](digest::TableDigest) of the
+//! rows of a table, to check that files (or the parts of a table in several
+//! files) have the expected content.
+//!
+//! With the optional `serde` feature, the rows of the generators implement
+//! `Serialize`, and the owned rows implement `Serialize` and `Deserialize`.
+//! With the optional `mmap` feature, `TextPool::map_cache` memory-maps a
+//! text pool cache file.
 //!
 //! [`LineItem`]: generators::LineItem
 //! [`LineItemCsv`]: csv::LineItemCsv
+//! [`LineItemColumns`]: columns::LineItemColumns
 //!
 //! The library was designed to be easily integrated in existing Rust projects as
 //! such it avoids exposing a malleable API and purposely does not have any dependencies
-//! on other Rust crates. It is focused entirely on the core
+//! on other Rust crates (other than `serde` and `memmap2` with the optional `serde` and `mmap` features). It is focused entirely on the core
 //! generation logic.
 //!
 //! If you want an easy way to generate the TPC-H dataset for usage with external
 //! see the [`tpchgen-cli`](https://github.com/alamb/tpchgen-rs/tree/main/tpchgen-cli)
 //! tool instead.
+pub mod columns;
 pub mod csv;
 pub mod dates;
 pub mod decimal;
+pub mod digest;
 pub mod distribution;
 pub mod generators;
+pub mod owned;
+pub mod parse;
 pub mod q_and_a;
 pub mod random;
+#[cfg(feature = "serde")]
+mod serde_impls;
+pub mod skew;
+pub mod ssb;
+pub mod table;
 pub mod text;
+pub mod validate;
@@ -0,0 +1,381 @@
+//! Owned row types of the TPC-H tables.
+//!
+//! The row structs of the [`generators`] borrow their text from the
+//! [`TextPool`] and [`Distributions`], and format some values (such as
+//! addresses and phone numbers) lazily. The rows in this module own all their
+//! values, with a `String` for each text column, so they can be stored, sent
+//! to other threads or created from existing data, for example with the
+//! parsers in [`parse`]. Each owned row can be created from the row of the
+//! generator with `From`.
+//!
+//! The `Display` impl of each row produces the TPC-H TBL format, like the
+//! row structs of the [`generators`].
+//!
+//! # Example
+//! ```
+//! # use tpchgen::owned::Nation;
+//! # use tpchgen::parse::ParseRow;
+//! let line = "7|GERMANY|3|l platelets. regular accounts x-ray: unusual, regular acco|";
+//! let nation = Nation::from_tbl(line).unwrap();
+//! assert_eq!(nation.n_name, "GERMANY");
+//! assert_eq!(nation.to_string(), line);
+//! ```
+//!
+//! Converting the rows of a generator:
+//! ```
+//! # use tpchgen::generators::LineItemGenerator;
+//! # use tpchgen::owned::LineItem;
+//! let line_items: Vec<LineItem> = LineItemGenerator::new(0.01, 1, 1)
+//!     .iter()
+//!     .take(3)
+//!     .map(LineItem::from)
+//!     .collect();
+//! assert_eq!(line_items[2].l_shipmode, "REG AIR");
+//! ```
+//!
+//! [`generators`]: crate::generators
+//! [`TextPool`]: crate::text::TextPool
+//! [`Distributions`]: crate::distribution::Distributions
+//! [`parse`]: crate::parse
+
+use crate::dates::TPCHDate;
+use crate::decimal::TPCHDecimal;
+use crate::generators::{self, OrderStatus};
+use crate::parse::ParseRow;
+use std::fmt;
+
+/// The type of a row field of the given kind
+macro_rules! field_type {
+    (i64) => {
+        i64
+    };
+    (i32) => {
+        i32
+    };
+    (quantity) => {
+        i64
+    };
+    (decimal) => {
+        TPCHDecimal
+    };
+    (date) => {
+        TPCHDate
+    };
+    (status) => {
+        OrderStatus
+    };
+    (string) => {
+        String
+    };
+}
+
+/// Converts a field of the given kind of a generator row to the owned value
+macro_rules! own_field {
+    (string, $VALUE:expr) => {
+        $VALUE.to_string()
+    };
+    ($KIND:ident, $VALUE:expr) => {
+        $VALUE
+    };
+}
+
+/// Parses the text of a field of the given kind
+macro_rules! parse_field {
+    (string, $VALUE:expr) => {
+        Ok::<_, String>($VALUE.to_string())
+    };
+    (i64, $VALUE:expr) => {
+        $VALUE
+            .parse::<i64>()
+            .map_err(|_| format!("Invalid integer '{}'", $VALUE))
+    };
+    (i32, $VALUE:expr) => {
+        $VALUE
+            .parse::<i32>()
+            .map_err(|_| format!("Invalid integer '{}'", $VALUE))
+    };
+    // an integer, also written as a decimal like in the schema of the
+    // specification, e.g. `17` or `17.00`
+    (quantity, $VALUE:expr) => {
+        $VALUE
+            .parse::<i64>()
+            .ok()
+            .or_else(|| {
+                let decimal = $VALUE.parse::<TPCHDecimal>().ok()?;
+                (decimal.0 % 100 == 0).then_some(decimal.0 / 100)
+            })
+            .ok_or_else(|| format!("Invalid quantity '{}'", $VALUE))
+    };
+    ($KIND:ident, $VALUE:expr) => {
+        $VALUE.parse::<field_type!($KIND)>()
+    };
+}
+
+/// Defines an owned row struct, its TBL `Display` impl, its [`ParseRow`]
+/// impl and its conversions from the row of the generator
+///
+/// Each field is the name of the column, and its kind: `i64`, `i32`,
+/// `quantity` (an `i64` that can also be written as a decimal), `decimal`,
+/// `date`, `status` (an [`OrderStatus`]) or `string`.
+macro_rules! define_row {
+    (
+        $(#[$META:meta])*
+        $ROW:ident from $SOURCE:ty {
+            $($(#[$FIELD_META:meta])* $FIELD:ident: $KIND:ident,)*
+        }
+    ) => {
+        $(#[$META])*
+        #[derive(Debug, Clone, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub struct $ROW {
+            $($(#[$FIELD_META])* pub $FIELD: field_type!($KIND),)*
+        }
+
+        impl fmt::Display for $ROW {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                $(write!(f, "{}|", self.$FIELD)?;)*
+                Ok(())
+            }
+        }
+
+        impl ParseRow for $ROW {
+            const COLUMNS: &'static [&'static str] = &[$(stringify!($FIELD)),*];
+
+            fn from_fields(fields: &[&str]) -> Result<Self, String> {
+                if fields.len() != Self::COLUMNS.len() {
+                    return Err(format!(
+                        "Expected {} fields, got {}",
+                        Self::COLUMNS.len(),
+                        fields.len()
+                    ));
+                }
+                let mut fields = fields.iter();
+                Ok(Self {
+                    $($FIELD: {
+                        let value = fields.next().expect("number of fields is checked");
+                        parse_field!($KIND, value)
+                            .map_err(|e| format!("{}: {e}", stringify!($FIELD)))?
+                    },)*
+                })
+            }
+        }
+
+        impl From<&$SOURCE> for $ROW {
+            fn from(row: &$SOURCE) -> Self {
+                Self {
+                    $($FIELD: own_field!($KIND, row.$FIELD),)*
+                }
+            }
+        }
+
+        impl From<$SOURCE> for $ROW {
+            fn from(row: $SOURCE) -> Self {
+                Self::from(&row)
+            }
+        }
+    };
+}
+
+define_row!(
+    /// An owned row of the NATION table, see [`Nation`](crate::generators::Nation)
+    Nation from generators::Nation<'_> {
+        /// Primary key (0-24)
+        n_nationkey: i64,
+        /// Nation name
+        n_name: string,
+        /// Foreign key to REGION
+        n_regionkey: i64,
+        /// Variable length comment
+        n_comment: string,
+    }
+);
+
+define_row!(
+    /// An owned row of the REGION table, see [`Region`](crate::generators::Region)
+    Region from generators::Region<'_> {
+        /// Primary key (0-4)
+        r_regionkey: i64,
+        /// Region name
+        r_name: string,
+        /// Variable length comment
+        r_comment: string,
+    }
+);
+
+define_row!(
+    /// An owned row of the PART table, see [`Part`](crate::generators::Part)
+    Part from generators::Part<'_> {
+        /// Primary key
+        p_partkey: i64,
+        /// Part name
+        p_name: string,
+        /// Part manufacturer, e.g. `Manufacturer#1`
+        p_mfgr: string,
+        /// Part brand, e.g. `Brand#13`
+        p_brand: string,
+        /// Part type
+        p_type: string,
+        /// Part size
+        p_size: i32,
+        /// Part container
+        p_container: string,
+        /// Part retail price
+        p_retailprice: decimal,
+        /// Variable length comment
+        p_comment: string,
+    }
+);
+
+define_row!(
+    /// An owned row of the SUPPLIER table, see [`Supplier`](crate::generators::Supplier)
+    Supplier from generators::Supplier {
+        /// Primary key
+        s_suppkey: i64,
+        /// Supplier name, e.g. `Supplier#000000001`
+        s_name: string,
+        /// Supplier address
+        s_address: string,
+        /// Foreign key to NATION
+        s_nationkey: i64,
+        /// Supplier phone number
+        s_phone: string,
+        /// Supplier account balance
+        s_acctbal: decimal,
+        /// Variable length comment
+        s_comment: string,
+    }
+);
+
+define_row!(
+    /// An owned row of the PARTSUPP table, see [`PartSupp`](crate::generators::PartSupp)
+    PartSupp from generators::PartSupp<'_> {
+        /// Primary key, foreign key to PART
+        ps_partkey: i64,
+        /// Primary key, foreign key to SUPPLIER
+        ps_suppkey: i64,
+        /// Available quantity
+        ps_availqty: i32,
+        /// Supplier cost
+        ps_supplycost: decimal,
+        /// Variable length comment
+        ps_comment: string,
+    }
+);
+
+define_row!(
+    /// An owned row of the CUSTOMER table, see [`Customer`](crate::generators::Customer)
+    Customer from generators::Customer<'_> {
+        /// Primary key
+        c_custkey: i64,
+        /// Customer name, e.g. `Customer#000000001`
+        c_name: string,
+        /// Customer address
+        c_address: string,
+        /// Foreign key to NATION
+        c_nationkey: i64,
+        /// Customer phone number
+        c_phone: string,
+        /// Customer account balance
+        c_acctbal: decimal,
+        /// Customer market segment
+        c_mktsegment: string,
+        /// Variable length comment
+        c_comment: string,
+    }
+);
+
+define_row!(
+    /// An owned row of the ORDERS table, see [`Order`](crate::generators::Order)
+    Order from generators::Order<'_> {
+        /// Primary key
+        o_orderkey: i64,
+        /// Foreign key to CUSTOMER
+        o_custkey: i64,
+        /// Order status (F=final, O=open, P=pending)
+        o_orderstatus: status,
+        /// Order total price
+        o_totalprice: decimal,
+        /// Order date
+        o_orderdate: date,
+        /// Order priority
+        o_orderpriority: string,
+        /// Clerk who processed the order, e.g. `Clerk#000000951`
+        o_clerk: string,
+        /// Order shipping priority
+        o_shippriority: i32,
+        /// Variable length comment
+        o_comment: string,
+    }
+);
+
+define_row!(
+    /// An owned row of the LINEITEM table, see [`LineItem`](crate::generators::LineItem)
+    LineItem from generators::LineItem<'_> {
+        /// Foreign key to ORDERS
+        l_orderkey: i64,
+        /// Foreign key to PART
+        l_partkey: i64,
+        /// Foreign key to SUPPLIER
+        l_suppkey: i64,
+        /// Line item number within order
+        l_linenumber: i32,
+        /// Quantity ordered, parsed from an integer or a decimal such as `17.00`
+        l_quantity: quantity,
+        /// Extended price (l_quantity * p_retailprice)
+        l_extendedprice: decimal,
+        /// Discount percentage
+        l_discount: decimal,
+        /// Tax percentage
+        l_tax: decimal,
+        /// Return flag (R=returned, A=accepted, N=pending)
+        l_returnflag: string,
+        /// Line status (O=ordered, F=fulfilled)
+        l_linestatus: string,
+        /// Date shipped
+        l_shipdate: date,
+        /// Date committed to ship
+        l_commitdate: date,
+        /// Date received
+        l_receiptdate: date,
+        /// Shipping instructions
+        l_shipinstruct: string,
+        /// Shipping mode
+        l_shipmode: string,
+        /// Variable length comment
+        l_comment: string,
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::{
+        CustomerGenerator, LineItemGenerator, NationGenerator, OrderGenerator, PartGenerator,
+        PartSuppGenerator, RegionGenerator, SupplierGenerator,
+    };
+
+    /// Checks that the owned rows are formatted like the rows they are created from
+    fn check_rows<T, O>(rows: impl Iterator<Item = T>)
+    where
+        T: fmt::Display,
+        O: for<'a> From<&'a T> + fmt::Display + ParseRow + PartialEq + fmt::Debug,
+    {
+        for row in rows {
+            let owned = O::from(&row);
+            assert_eq!(owned.to_string(), row.to_string());
+            assert_eq!(O::from_tbl(&owned.to_string()), Ok(owned));
+        }
+    }
+
+    #[test]
+    fn test_from_generator_rows() {
+        check_rows::<_, Nation>(NationGenerator::default().iter());
+        check_rows::<_, Region>(RegionGenerator::default().iter());
+        check_rows::<_, Part>(PartGenerator::new(0.01, 1, 1).iter().take(100));
+        check_rows::<_, Supplier>(SupplierGenerator::new(0.01, 1, 1).iter().take(100));
+        check_rows::<_, PartSupp>(PartSuppGenerator::new(0.01, 1, 1).iter().take(100));
+        check_rows::<_, Customer>(CustomerGenerator::new(0.01, 1, 1).iter().take(100));
+        check_rows::<_, Order>(OrderGenerator::new(0.01, 1, 1).iter().take(100));
+        check_rows::<_, LineItem>(LineItemGenerator::new(0.01, 1, 1).iter().take(100));
+    }
+}
@@ -0,0 +1,292 @@
+//! Parsers for the TBL and CSV formats of the TPC-H tables.
+//!
+//! The parsers read the files written by `dbgen` and `tpchgen` (with the
+//! `Display` impls of the rows and the [`csv`] formatters) back into the
+//! [`owned`] row types, for example to load existing data or to compare two
+//! files row by row:
+//!
+//! * [`TblReader`] reads TBL files: one row per line, with the values
+//!   separated (and terminated) by `|`
+//! * [`CsvReader`] reads CSV files: a header line with the column names, then
+//!   one row per line with the values separated by `,` and optionally quoted
+//!   with `"` (a quote in a quoted value is written `""`)
+//!
+//! # Example
+//! ```
+//! # use tpchgen::generators::OrderGenerator;
+//! # use tpchgen::csv::OrderCsv;
+//! # use tpchgen::owned::Order;
+//! # use tpchgen::parse::{CsvReader, TblReader};
+//! let generator = OrderGenerator::new(0.01, 1, 1);
+//! let tbl: String = generator.iter().take(10).map(|order| format!("{order}\n")).collect();
+//! let mut csv = format!("{}\n", OrderCsv::header());
+//! for order in generator.iter().take(10) {
+//!     csv.push_str(&format!("{}\n", OrderCsv::new(order)));
+//! }
+//!
+//! let orders = TblReader::<Order, _>::new(tbl.as_bytes())
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//! assert_eq!(orders.len(), 10);
+//! assert_eq!(orders[0].o_orderdate.to_string(), "1996-01-02");
+//!
+//! // both formats have the same rows
+//! let csv_orders = CsvReader::<Order, _>::new(csv.as_bytes())
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//! assert_eq!(orders, csv_orders);
+//! ```
+//!
+//! [`csv`]: crate::csv
+//! [`owned`]: crate::owned
+
+use std::io::{self, BufRead};
+use std::marker::PhantomData;
+
+/// A row of a TPC-H table that can be parsed from the text of its values
+///
+/// Implemented by the [`owned`](crate::owned) row types.
+pub trait ParseRow: Sized {
+    /// The names of the columns of the table, in order
+    const COLUMNS: &'static [&'static str];
+
+    /// Creates a row from the text of the value of each column
+    fn from_fields(fields: &[&str]) -> Result<Self, String>;
+
+    /// Parses a line of a TBL file
+    ///
+    /// The terminating `|` of the line is optional.
+    fn from_tbl(line: &str) -> Result<Self, String> {
+        let line = line.strip_suffix('|').unwrap_or(line);
+        Self::from_fields(&line.split('|').collect::<Vec<_>>())
+    }
+
+    /// Parses a line of a CSV file
+    fn from_csv(line: &str) -> Result<Self, String> {
+        let fields = split_csv(line)?;
+        Self::from_fields(&fields.iter().map(String::as_str).collect::<Vec<_>>())
+    }
+}
+
+/// Splits a CSV line into its values, removing the quotes of quoted values
+fn split_csv(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("Unterminated quoted value".to_string());
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+/// Returns an `InvalidData` error for line `line_number` (1 based)
+fn line_error(line_number: usize, message: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {line_number}: {message}"),
+    )
+}
+
+/// Iterator over the rows of a TBL file, see the [module docs](self)
+///
+/// Empty lines are skipped. Errors report the line number.
+#[derive(Debug)]
+pub struct TblReader<T, R> {
+    lines: io::Lines<R>,
+    line_number: usize,
+    row: PhantomData<T>,
+}
+
+impl<T: ParseRow, R: BufRead> TblReader<T, R> {
+    /// Creates a reader of the rows of `reader`
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            line_number: 0,
+            row: PhantomData,
+        }
+    }
+}
+
+impl<T: ParseRow, R: BufRead> Iterator for TblReader<T, R> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            self.line_number += 1;
+            let line = line.strip_suffix('\r').unwrap_or(&line);
+            if !line.is_empty() {
+                return Some(T::from_tbl(line).map_err(|e| line_error(self.line_number, e)));
+            }
+        }
+    }
+}
+
+/// Iterator over the rows of a CSV file, see the [module docs](self)
+///
+/// The first line must be the header with the column names of the table, as
+/// written by the [`csv`](crate::csv) formatters. Empty lines are skipped.
+/// Errors report the line number.
+#[derive(Debug)]
+pub struct CsvReader<T, R> {
+    lines: io::Lines<R>,
+    line_number: usize,
+    header_read: bool,
+    row: PhantomData<T>,
+}
+
+impl<T: ParseRow, R: BufRead> CsvReader<T, R> {
+    /// Creates a reader of the rows of `reader`
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            line_number: 0,
+            header_read: false,
+            row: PhantomData,
+        }
+    }
+}
+
+impl<T: ParseRow, R: BufRead> Iterator for CsvReader<T, R> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            self.line_number += 1;
+            let line = line.strip_suffix('\r').unwrap_or(&line);
+            if line.is_empty() {
+                continue;
+            }
+            if !self.header_read {
+                self.header_read = true;
+                let header = T::COLUMNS.join(",");
+                if line != header {
+                    return Some(Err(line_error(
+                        self.line_number,
+                        format!("Expected the header '{header}', got '{line}'"),
+                    )));
+                }
+                continue;
+            }
+            return Some(T::from_csv(line).map_err(|e| line_error(self.line_number, e)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dates::TPCHDate;
+    use crate::decimal::TPCHDecimal;
+    use crate::generators::OrderStatus;
+    use crate::owned::{LineItem, Order, Region};
+
+    #[test]
+    fn test_parse_tbl() {
+        let line = "1|155190|7706|1|17|21168.23|0.04|0.02|N|O|1996-03-13|1996-02-12|1996-03-22|DELIVER IN PERSON|TRUCK|egular courts above the|";
+        let line_item = LineItem::from_tbl(line).unwrap();
+        assert_eq!(line_item.l_quantity, 17);
+        assert_eq!(line_item.l_extendedprice, TPCHDecimal(2116823));
+        assert_eq!(
+            line_item.l_shipdate,
+            "1996-03-13".parse::<TPCHDate>().unwrap()
+        );
+        assert_eq!(line_item.l_comment, "egular courts above the");
+        assert_eq!(line_item.to_string(), line);
+        // the terminating '|' is optional
+        assert_eq!(
+            LineItem::from_tbl(line.strip_suffix('|').unwrap()),
+            Ok(line_item)
+        );
+        // l_quantity can be written as a decimal
+        let decimal_line = line.replacen("|17|", "|17.00|", 1);
+        assert_eq!(LineItem::from_tbl(&decimal_line), LineItem::from_tbl(line));
+        assert_eq!(
+            LineItem::from_tbl(&line.replacen("|17|", "|17.50|", 1)),
+            Err("l_quantity: Invalid quantity '17.50'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let line = "2,79,O,40183.29,1996-12-01,1-URGENT,Clerk#000000880,0,\" foxes. pending accounts at the pending, silent \"\"asymptot\"\"\"";
+        let order = Order::from_csv(line).unwrap();
+        assert_eq!(order.o_orderstatus, OrderStatus::Open);
+        assert_eq!(order.o_clerk, "Clerk#000000880");
+        assert_eq!(
+            order.o_comment,
+            " foxes. pending accounts at the pending, silent \"asymptot\""
+        );
+        assert_eq!(
+            Order::from_csv("2,79,O,40183.29,1996-12-01,1-URGENT,Clerk#000000880,0,\"foxes"),
+            Err("Unterminated quoted value".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Region::from_tbl("0|AFRICA|"),
+            Err("Expected 3 fields, got 2".to_string())
+        );
+        assert_eq!(
+            Region::from_tbl("x|AFRICA|comment|"),
+            Err("r_regionkey: Invalid integer 'x'".to_string())
+        );
+        assert_eq!(
+            Order::from_tbl("1|37|X|131251.81|1996-01-02|5-LOW|Clerk#000000951|0|comment|"),
+            Err("o_orderstatus: Invalid order status 'X', expected F, O or P".to_string())
+        );
+        assert_eq!(
+            Order::from_tbl("1|37|O|131251.81|1996-02-30|5-LOW|Clerk#000000951|0|comment|"),
+            Err("o_orderdate: Invalid date '1996-02-30', expected yyyy-mm-dd".to_string())
+        );
+    }
+
+    #[test]
+    fn test_readers() {
+        let tbl = "0|AFRICA|lar deposits|\n\n1|AMERICA|hs use ironic, even requests. s|\n2|ASIA\n";
+        let regions: Vec<_> = TblReader::<Region, _>::new(tbl.as_bytes()).collect();
+        assert_eq!(regions.len(), 3);
+        assert_eq!(regions[1].as_ref().unwrap().r_name, "AMERICA");
+        assert_eq!(
+            regions[2].as_ref().unwrap_err().to_string(),
+            "line 4: Expected 3 fields, got 2"
+        );
+
+        let csv = "r_regionkey,r_name,r_comment\r\n0,AFRICA,\"lar deposits\"\r\n1,AMERICA,\"hs use ironic, even requests. s\"\r\n";
+        let regions = CsvReader::<Region, _>::new(csv.as_bytes())
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[1].r_comment, "hs use ironic, even requests. s");
+
+        let mut reader = CsvReader::<Region, _>::new("r_regionkey,r_name\n".as_bytes());
+        assert_eq!(
+            reader.next().unwrap().unwrap_err().to_string(),
+            "line 1: Expected the header 'r_regionkey,r_name,r_comment', got 'r_regionkey,r_name'"
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! Rendering of the TPC-H queries for the SQL dialects of popular engines.
+//!
+//! The queries in [`queries`] are the templates of the TPC-H specification,
+//! which no engine accepts verbatim: Q1 uses an interval with a precision
+//! (`interval '90' day (3)`), the maximum number of rows of Q2, Q3, Q10, Q18
+//! and Q21 is a directive of `qgen` that is not in the query text, and Q15
+//! creates and drops a view. [`QueryRenderer`] substitutes the parameters of a
+//! query and rewrites it for a [`Dialect`]:
+//!
+//! * [`Dialect::Ansi`]: the specification text, with `fetch first n rows only`
+//! * [`Dialect::PostgreSql`] and [`Dialect::DuckDb`]: `interval '90' day` and
+//!   `limit n`
+//! * [`Dialect::SparkSql`]: as PostgreSQL, with a temporary view for Q15
+//! * [`Dialect::ClickHouse`]: `toDate('1998-12-01')` and `interval 90 day`
+//!   literals, column aliases instead of column lists (Q13 and Q15), and the
+//!   settings needed for the correct results of Q13 (`join_use_nulls`) and
+//!   the queries with correlated subqueries (Q2, Q4, Q17, Q20, Q21 and Q22,
+//!   which require ClickHouse 25.4 or later)
+//!
+//! Q15 can also be rendered with the approved variant that uses a common
+//! table expression instead of a view (see [`Q15Variant`]), which is a single
+//! statement.
+//!
+//! # Example
+//! ```
+//! # use tpchgen::q_and_a::dialect::{Dialect, Q15Variant, QueryRenderer};
+//! # use tpchgen::q_and_a::parameters::QueryParameters;
+//! let renderer = QueryRenderer::new(Dialect::PostgreSql);
+//! let query = renderer.render(&QueryParameters::validation(1).unwrap());
+//! assert!(query.contains("date '1998-12-01' - interval '90' day\n"));
+//!
+//! let query = renderer.render(&QueryParameters::validation(3).unwrap());
+//! assert!(query.ends_with("o_orderdate\nlimit 10;"));
+//!
+//! let renderer = QueryRenderer::new(Dialect::DuckDb).with_q15_variant(Q15Variant::Cte);
+//! let query = renderer.render(&QueryParameters::validation(15).unwrap());
+//! assert!(query.contains("with revenue0 (supplier_no, total_revenue) as ("));
+//! assert!(!query.contains("create view"));
+//! ```
+//!
+//! [`queries`]: crate::q_and_a::queries
+
+use crate::q_and_a::parameters::{rewrite_literals, QueryParameters};
+use crate::q_and_a::queries;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The SQL dialect of an engine
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Dialect {
+    /// ANSI SQL, the text of the TPC-H specification
+    #[default]
+    Ansi,
+    /// PostgreSQL
+    PostgreSql,
+    /// DuckDB
+    DuckDb,
+    /// Apache Spark SQL
+    SparkSql,
+    /// ClickHouse
+    ClickHouse,
+}
+
+impl Dialect {
+    /// All dialects
+    pub const ALL: [Dialect; 5] = [
+        Dialect::Ansi,
+        Dialect::PostgreSql,
+        Dialect::DuckDb,
+        Dialect::SparkSql,
+        Dialect::ClickHouse,
+    ];
+
+    /// Returns the dialect name, e.g. `postgres`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dialect::Ansi => "ansi",
+            Dialect::PostgreSql => "postgres",
+            Dialect::DuckDb => "duckdb",
+            Dialect::SparkSql => "spark",
+            Dialect::ClickHouse => "clickhouse",
+        }
+    }
+}
+
+impl Display for Dialect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Dialect {
+    type Err = String;
+
+    /// Returns the dialect from its name, `postgresql` and `sparksql` are
+    /// also accepted
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "postgresql" => return Ok(Dialect::PostgreSql),
+            "sparksql" => return Ok(Dialect::SparkSql),
+            _ => {}
+        }
+        Dialect::ALL
+            .into_iter()
+            .find(|dialect| dialect.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<_> = Dialect::ALL.iter().map(|d| d.name()).collect();
+                format!(
+                    "Unknown SQL dialect '{s}', expected one of: {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// How Q15 creates the `revenue` relation it reads twice
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Q15Variant {
+    /// A view created before and dropped after the query (three statements),
+    /// as in the specification
+    #[default]
+    View,
+    /// A common table expression (a single statement), an approved variant
+    Cte,
+}
+
+/// Renders the TPC-H queries for a [`Dialect`], see the [module docs](self)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryRenderer {
+    dialect: Dialect,
+    q15_variant: Q15Variant,
+}
+
+impl QueryRenderer {
+    /// Creates a renderer for `dialect`
+    pub fn new(dialect: Dialect) -> Self {
+        Self {
+            dialect,
+            q15_variant: Q15Variant::default(),
+        }
+    }
+
+    /// Sets the variant of Q15 (default: [`Q15Variant::View`])
+    pub fn with_q15_variant(mut self, q15_variant: Q15Variant) -> Self {
+        self.q15_variant = q15_variant;
+        self
+    }
+
+    /// Returns the dialect of the queries
+    pub fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    /// Returns the query text with the substituted `parameters`, rewritten for
+    /// the dialect
+    pub fn render(&self, parameters: &QueryParameters) -> String {
+        let query_number = parameters.query_number();
+        let template = match (query_number, self.q15_variant) {
+            (15, Q15Variant::Cte) => queries::Q15_CTE,
+            _ => queries::query(query_number).expect("query number is validated"),
+        };
+        let mut query = parameters.substitute_template(template);
+
+        if self.dialect != Dialect::Ansi {
+            // only ANSI SQL allows a precision for a day interval
+            query = query.replace(" day (3)", " day");
+        }
+        match self.dialect {
+            Dialect::SparkSql => query = query.replace("create view", "create temporary view"),
+            Dialect::ClickHouse => query = clickhouse(query),
+            _ => {}
+        }
+
+        let mut clauses = vec![];
+        if let Some(limit) = queries::row_limit(query_number) {
+            clauses.push(match self.dialect {
+                Dialect::Ansi => format!("fetch first {limit} rows only"),
+                _ => format!("limit {limit}"),
+            });
+        }
+        if self.dialect == Dialect::ClickHouse {
+            match query_number {
+                2 | 4 | 17 | 20 | 21 | 22 => {
+                    clauses.push("settings allow_experimental_correlated_subqueries = 1".into())
+                }
+                // unmatched orders must be NULL, not 0, to not be counted
+                13 => clauses.push("settings join_use_nulls = 1".into()),
+                _ => {}
+            }
+        }
+        if !clauses.is_empty() {
+            // none of the queries with clauses create a view, so the select
+            // is the last statement
+            let select = query.strip_suffix(';').unwrap_or(&query);
+            query = format!("{select}\n{};", clauses.join("\n"));
+        }
+        query
+    }
+}
+
+/// Rewrites a query for ClickHouse
+fn clickhouse(query: String) -> String {
+    let query = rewrite_literals(&query, "date '", |date| format!("toDate('{date}')"));
+    let query = rewrite_literals(&query, "interval '", |count| format!("interval {count}"));
+    query
+        .replace(
+            "substring(c_phone from 1 for 2)",
+            "substring(c_phone, 1, 2)",
+        )
+        // no column lists for derived tables, views and CTEs
+        .replace("count(o_orderkey)", "count(o_orderkey) as c_count")
+        .replace(" as c_orders (c_custkey, c_count)", " as c_orders")
+        .replace(" (supplier_no, total_revenue) as", " as")
+        .replace(
+            "\t\tl_suppkey,\n\t\tsum(l_extendedprice * (1 - l_discount))\n",
+            "\t\tl_suppkey as supplier_no,\n\t\tsum(l_extendedprice * (1 - l_discount)) as total_revenue\n",
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(dialect: Dialect, query_number: i32) -> String {
+        QueryRenderer::new(dialect).render(&QueryParameters::validation(query_number).unwrap())
+    }
+
+    #[test]
+    fn test_dialect_names() {
+        for dialect in Dialect::ALL {
+            assert_eq!(dialect.to_string().parse::<Dialect>(), Ok(dialect));
+        }
+        assert_eq!("PostgreSQL".parse::<Dialect>(), Ok(Dialect::PostgreSql));
+        assert_eq!("SparkSQL".parse::<Dialect>(), Ok(Dialect::SparkSql));
+        assert_eq!(
+            "oracle".parse::<Dialect>(),
+            Err("Unknown SQL dialect 'oracle', expected one of: ansi, postgres, duckdb, spark, clickhouse".to_string())
+        );
+    }
+
+    #[test]
+    fn test_ansi_is_specification_text() {
+        for query_number in 1..=22 {
+            let parameters = QueryParameters::validation(query_number).unwrap();
+            let query = render(Dialect::Ansi, query_number);
+            match queries::row_limit(query_number) {
+                Some(limit) => assert_eq!(
+                    query,
+                    parameters
+                        .query()
+                        .replace(";", &format!("\nfetch first {limit} rows only;"))
+                ),
+                None => assert_eq!(query, parameters.query()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_dialects() {
+        for dialect in Dialect::ALL {
+            for query_number in 1..=22 {
+                let query = render(dialect, query_number);
+                assert!(!query.contains(":1"), "{dialect} Q{query_number}: {query}");
+                assert_eq!(
+                    query.contains(" day (3)"),
+                    dialect == Dialect::Ansi && query_number == 1,
+                    "{dialect} Q{query_number}: {query}"
+                );
+                if query_number != 15 {
+                    assert_eq!(query.matches(';').count(), 1, "{dialect} Q{query_number}");
+                    assert!(query.ends_with(';'), "{dialect} Q{query_number}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_row_limits() {
+        assert!(render(Dialect::Ansi, 2).ends_with("p_partkey\nfetch first 100 rows only;"));
+        assert!(render(Dialect::PostgreSql, 10).ends_with("revenue desc\nlimit 20;"));
+        assert!(render(Dialect::SparkSql, 18).ends_with("o_orderdate\nlimit 100;"));
+        assert!(render(Dialect::DuckDb, 1).ends_with("l_linestatus;"));
+        assert!(render(Dialect::ClickHouse, 21).ends_with(
+            "s_name\nlimit 100\nsettings allow_experimental_correlated_subqueries = 1;"
+        ));
+    }
+
+    #[test]
+    fn test_clickhouse() {
+        let query = render(Dialect::ClickHouse, 1);
+        assert!(query.contains("l_shipdate <= toDate('1998-12-01') - interval 90 day\n"));
+        let query = render(Dialect::ClickHouse, 4);
+        assert!(query.contains("o_orderdate < toDate('1993-07-01') + interval 3 month\n"));
+        let query = render(Dialect::ClickHouse, 13);
+        assert!(query.contains("count(o_orderkey) as c_count\n"));
+        assert!(query.contains(") as c_orders\n"));
+        assert!(query.ends_with("c_count desc\nsettings join_use_nulls = 1;"));
+        let query = render(Dialect::ClickHouse, 22);
+        assert!(query.contains("substring(c_phone, 1, 2) as cntrycode"));
+        for query_number in 1..=22 {
+            let query = render(Dialect::ClickHouse, query_number);
+            assert!(!query.contains("date '"), "Q{query_number}: {query}");
+            assert!(!query.contains("interval '"), "Q{query_number}: {query}");
+        }
+    }
+
+    #[test]
+    fn test_q15() {
+        let parameters = QueryParameters::validation(15).unwrap().with_stream(3);
+        let view = QueryRenderer::new(Dialect::PostgreSql).render(&parameters);
+        assert!(view.starts_with("\ncreate view revenue3 (supplier_no, total_revenue) as\n"));
+        assert!(view.ends_with("drop view revenue3;"));
+
+        let spark = QueryRenderer::new(Dialect::SparkSql).render(&parameters);
+        assert!(
+            spark.starts_with("\ncreate temporary view revenue3 (supplier_no, total_revenue) as\n")
+        );
+
+        let renderer = QueryRenderer::new(Dialect::ClickHouse).with_q15_variant(Q15Variant::Cte);
+        let cte = renderer.render(&parameters);
+        assert!(cte.starts_with("\nwith revenue3 as (\n\tselect\n\t\tl_suppkey as supplier_no,\n"));
+        assert!(cte.contains("sum(l_extendedprice * (1 - l_discount)) as total_revenue\n"));
+        assert!(cte.contains("l_shipdate >= toDate('1996-01-01')\n"));
+        assert_eq!(cte.matches(';').count(), 1);
+
+        // the view and CTE variants have the same body and select
+        let view = QueryRenderer::new(Dialect::Ansi).render(&parameters);
+        let cte = QueryRenderer::new(Dialect::Ansi)
+            .with_q15_variant(Q15Variant::Cte)
+            .render(&parameters);
+        let body = |query: &str, start: &str, end: &str| {
+            let start = query.find(start).unwrap();
+            query[start..start + query[start..].find(end).unwrap()].to_string()
+        };
+        assert_eq!(
+            body(&view, "\tselect", "\tgroup by"),
+            body(&cte, "\tselect", "\tgroup by")
+        );
+        assert_eq!(
+            body(&view, "\nselect", "s_suppkey;"),
+            body(&cte, "\nselect", "s_suppkey;")
+        );
+    }
+
+    #[test]
+    fn test_rewrite_literals() {
+        let rewrite = |query: &str| rewrite_literals(query, "date '", |d| format!("toDate('{d}')"));
+        assert_eq!(
+            rewrite("x < date '1995-01-01' and y = my_date 'a'"),
+            "x < toDate('1995-01-01') and y = my_date 'a'"
+        );
+        assert_eq!(rewrite("date '1995-01-01"), "date '1995-01-01");
+    }
+}
@@ -0,0 +1,1922 @@
+//! Reference answers of the TPC-H queries for any scale factor.
+//!
+//! [`answers_sf1`] only contains the answers for scale factor 1. The
+//! [`QueryEvaluator`] computes the answer of any of the 22 queries, for any
+//! scale factor, seed and date range, by streaming the rows of the generators through a
+//! hand written plan of the query. The tables are never materialized: only
+//! the per key values needed by the joins (e.g. the nation of each customer)
+//! are kept in memory.
+//!
+//! The answers use the format of [`answers_sf1`]: a line with the column
+//! names followed by one line per row, with the values separated by `|`.
+//! Decimal values are computed exactly and rounded (half away from zero) to
+//! 2 decimal places, so a few values differ from [`answers_sf1`] in the last
+//! decimal place (e.g. in Q9 and Q17), within the precision required by the
+//! specification.
+//!
+//! # Example
+//! ```
+//! # use tpchgen::q_and_a::evaluator::QueryEvaluator;
+//! let evaluator = QueryEvaluator::new(0.01);
+//! // Q6 with the validation parameters of the specification
+//! let answer = evaluator.validation_answer(6).unwrap();
+//! let mut lines = answer.lines().skip(1);
+//! assert_eq!(lines.next(), Some("revenue"));
+//! assert!(lines.next().unwrap().parse::<f64>().unwrap() > 0.0);
+//! ```
+//!
+//! [`answers_sf1`]: crate::q_and_a::answers_sf1
+
+use crate::dates::DateRange;
+use crate::distribution::Distributions;
+use crate::generators::{
+    Customer, CustomerGenerator, CustomerName, LineItem, LineItemGenerator, OrderGenerator,
+    OrderLineItemGenerator, OrderStatus, Part, PartGenerator, PartManufacturerName, PartSupp,
+    PartSuppGenerator, Supplier, SupplierGenerator,
+};
+use crate::q_and_a::parameters::QueryParameters;
+use crate::skew::Skew;
+use crate::text::TextPool;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU32, Ordering};
+use std::thread;
+
+/// Computes the answers of the TPC-H queries from the generated data
+///
+/// The rows are generated in parallel on [`Self::with_threads`] threads.
+#[derive(Debug, Clone)]
+pub struct QueryEvaluator<'a> {
+    scale_factor: f64,
+    seed: u64,
+    skew: Skew,
+    date_range: DateRange,
+    threads: usize,
+    distributions: &'a Distributions,
+    text_pool: &'a TextPool,
+}
+
+impl<'a> QueryEvaluator<'a> {
+    /// Creates a new QueryEvaluator for the data of the given scale factor
+    ///
+    /// Note the evaluator's lifetime is `&'static`. See [`NationGenerator`]
+    /// for more details.
+    ///
+    /// [`NationGenerator`]: crate::generators::NationGenerator
+    pub fn new(scale_factor: f64) -> QueryEvaluator<'static> {
+        QueryEvaluator::new_with_distributions_and_text_pool(
+            scale_factor,
+            Distributions::static_default(),
+            TextPool::get_or_init_default(),
+        )
+    }
+
+    /// Creates a QueryEvaluator with the specified distributions and text pool
+    ///
+    /// These must be the ones the data was generated with.
+    pub fn new_with_distributions_and_text_pool<'b>(
+        scale_factor: f64,
+        distributions: &'b Distributions,
+        text_pool: &'b TextPool,
+    ) -> QueryEvaluator<'b> {
+        QueryEvaluator {
+            scale_factor,
+            seed: 0,
+            skew: Skew::default(),
+            date_range: DateRange::default(),
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            distributions,
+            text_pool,
+        }
+    }
+
+    /// Evaluates the queries on the data generated with `seed`, see
+    /// [`OrderGenerator::with_seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Evaluates the queries on the data generated with `skew`, see the
+    /// [`skew`](crate::skew) module
+    pub fn with_skew(mut self, skew: Skew) -> Self {
+        self.skew = skew;
+        self
+    }
+
+    /// Evaluates the queries on the data generated with `date_range`, see
+    /// [`OrderGenerator::with_date_range`]
+    ///
+    /// The parameters of [`Self::answer`] must be moved to the same date
+    /// range, see [`QueryParameters::with_date_range`].
+    pub fn with_date_range(mut self, date_range: DateRange) -> Self {
+        self.date_range = date_range;
+        self
+    }
+
+    /// Sets the number of threads generating the rows (the number of CPUs
+    /// by default)
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Returns the answer of a query with the validation parameters of the
+    /// specification, see [`QueryParameters::validation_for_scale_factor`],
+    /// moved to the date range of the data
+    pub fn validation_answer(&self, query_number: i32) -> Result<String, String> {
+        self.answer(
+            &QueryParameters::validation_for_scale_factor(query_number, self.scale_factor)?
+                .with_date_range(self.date_range),
+        )
+    }
+
+    /// Returns the answer of the query with the given parameters
+    pub fn answer(&self, parameters: &QueryParameters) -> Result<String, String> {
+        let values = Values {
+            query_number: parameters.query_number(),
+            values: parameters.values(),
+            year_shift: parameters.date_range().year_shift(),
+        };
+        match parameters.query_number() {
+            1 => self.q1(&values),
+            2 => self.q2(&values),
+            3 => self.q3(&values),
+            4 => self.q4(&values),
+            5 => self.q5(&values),
+            6 => self.q6(&values),
+            7 => self.q7(&values),
+            8 => self.q8(&values),
+            9 => self.q9(&values),
+            10 => self.q10(&values),
+            11 => self.q11(&values),
+            12 => self.q12(&values),
+            13 => self.q13(&values),
+            14 => self.q14(&values),
+            15 => self.q15(&values),
+            16 => self.q16(&values),
+            17 => self.q17(&values),
+            18 => self.q18(&values),
+            19 => self.q19(&values),
+            20 => self.q20(&values),
+            21 => self.q21(&values),
+            22 => self.q22(&values),
+            n => Err(format!("Invalid TPC-H query number: {n}, expected 1 to 22")),
+        }
+    }
+
+    /// Pricing Summary Report Query (Q1)
+    fn q1(&self, values: &Values) -> Result<String, String> {
+        let max_ship_date = values.literal(1998, 12, 1).index() - values.parse::<i32>(1)?;
+
+        #[derive(Default)]
+        struct Sums {
+            quantity: i128,
+            base_price: i128,
+            disc_price: i128,
+            charge: i128,
+            discount: i128,
+            count: i128,
+        }
+        let groups = merge_maps(
+            self.scan(
+                |part, part_count| self.line_items(part, part_count),
+                BTreeMap::new,
+                |groups, line| {
+                    if line.l_shipdate.into_inner() > max_ship_date {
+                        return;
+                    }
+                    let sums: &mut Sums = groups
+                        .entry((line.l_returnflag, line.l_linestatus))
+                        .or_default();
+                    let price = line.l_extendedprice.0 as i128;
+                    let disc_price = price * (100 - line.l_discount.0 as i128);
+                    sums.quantity += line.l_quantity as i128;
+                    sums.base_price += price;
+                    sums.disc_price += disc_price;
+                    sums.charge += disc_price * (100 + line.l_tax.0 as i128);
+                    sums.discount += line.l_discount.0 as i128;
+                    sums.count += 1;
+                },
+            ),
+            |total, sums| {
+                total.quantity += sums.quantity;
+                total.base_price += sums.base_price;
+                total.disc_price += sums.disc_price;
+                total.charge += sums.charge;
+                total.discount += sums.discount;
+                total.count += sums.count;
+            },
+        );
+
+        let mut answer = AnswerBuilder::new(&[
+            "l_returnflag",
+            "l_linestatus",
+            "sum_qty",
+            "sum_base_price",
+            "sum_disc_price",
+            "sum_charge",
+            "avg_qty",
+            "avg_price",
+            "avg_disc",
+            "count_order",
+        ]);
+        for ((return_flag, line_status), sums) in groups {
+            answer.row(&[
+                &return_flag,
+                &line_status,
+                &Hundredths(sums.quantity * 100),
+                &Hundredths(sums.base_price),
+                &Hundredths(round_div(sums.disc_price, 100)),
+                &Hundredths(round_div(sums.charge, 10_000)),
+                &Hundredths(round_div(sums.quantity * 100, sums.count)),
+                &Hundredths(round_div(sums.base_price, sums.count)),
+                &Hundredths(round_div(sums.discount, sums.count)),
+                &sums.count,
+            ]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Minimum Cost Supplier Query (Q2)
+    fn q2(&self, values: &Values) -> Result<String, String> {
+        let size = values.parse::<i32>(1)?;
+        let type_suffix = values.get(2)?;
+        let region = self.region_key(values.get(3)?)?;
+
+        let supplier_info = self.column(
+            |part, part_count| self.suppliers(part, part_count),
+            |supplier| (supplier.s_nationkey, supplier.s_acctbal.0),
+        );
+        let in_region =
+            |suppkey: i64| self.nation_region(supplier_info[suppkey as usize - 1].0) == region;
+
+        // (s_acctbal desc, n_name, s_suppkey, p_partkey) and p_mfgr of each
+        // part supplier with the minimum cost in the region
+        let mut top = TopK::new(100);
+        for candidates in self.scan(
+            |part, part_count| self.parts_with_part_supps(part, part_count),
+            Vec::new,
+            |candidates, (part, part_supps)| {
+                if part.p_size != size || !part.p_type.ends_with(type_suffix) {
+                    return;
+                }
+                let Some(min_cost) = part_supps
+                    .iter()
+                    .filter(|ps| in_region(ps.ps_suppkey))
+                    .map(|ps| ps.ps_supplycost.0)
+                    .min()
+                else {
+                    return;
+                };
+                for part_supp in &part_supps {
+                    if part_supp.ps_supplycost.0 == min_cost && in_region(part_supp.ps_suppkey) {
+                        let (nation, acctbal) = supplier_info[part_supp.ps_suppkey as usize - 1];
+                        candidates.push((
+                            (
+                                Reverse(acctbal),
+                                self.nation_name(nation),
+                                part_supp.ps_suppkey,
+                                part.p_partkey,
+                            ),
+                            part.p_mfgr.value(),
+                        ));
+                    }
+                }
+            },
+        ) {
+            top.extend(candidates);
+        }
+
+        let mut answer = AnswerBuilder::new(&[
+            "s_acctbal",
+            "s_name",
+            "n_name",
+            "p_partkey",
+            "p_mfgr",
+            "s_address",
+            "s_phone",
+            "s_comment",
+        ]);
+        for ((_, nation, suppkey, partkey), mfgr) in top.into_sorted_vec() {
+            let supplier = self.supplier(suppkey);
+            answer.row(&[
+                &supplier.s_acctbal,
+                &supplier.s_name,
+                &nation,
+                &partkey,
+                &PartManufacturerName::new(mfgr),
+                &supplier.s_address,
+                &supplier.s_phone,
+                &supplier.s_comment,
+            ]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Shipping Priority Query (Q3)
+    fn q3(&self, values: &Values) -> Result<String, String> {
+        let segment = values.get(1)?;
+        let date = values.date(2)?.index();
+
+        let in_segment = self.column(
+            |part, part_count| self.customers(part, part_count),
+            |customer| customer.c_mktsegment == segment,
+        );
+
+        let mut top = TopK::new(10);
+        for orders in self.scan(
+            |part, part_count| self.order_line_items(part, part_count),
+            || TopK::new(10),
+            |top, item| {
+                let order = &item.order;
+                if order.o_orderdate.into_inner() >= date
+                    || !in_segment[order.o_custkey as usize - 1]
+                {
+                    return;
+                }
+                let mut revenue = None;
+                for line in &item.line_items {
+                    if line.l_shipdate.into_inner() > date {
+                        *revenue.get_or_insert(0) += disc_price(line);
+                    }
+                }
+                if let Some(revenue) = revenue {
+                    top.push((
+                        Reverse(revenue),
+                        order.o_orderdate,
+                        order.o_orderkey,
+                        order.o_shippriority,
+                    ));
+                }
+            },
+        ) {
+            top.extend(orders.into_sorted_vec());
+        }
+
+        let mut answer =
+            AnswerBuilder::new(&["l_orderkey", "revenue", "o_orderdat", "o_shippriority"]);
+        for (Reverse(revenue), order_date, orderkey, ship_priority) in top.into_sorted_vec() {
+            answer.row(&[
+                &orderkey,
+                &Hundredths(round_div(revenue, 100)),
+                &order_date,
+                &ship_priority,
+            ]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Order Priority Checking Query (Q4)
+    fn q4(&self, values: &Values) -> Result<String, String> {
+        let start = values.date(1)?;
+        let (start, end) = (start.index(), start.add_months(3).index());
+
+        let counts = merge_maps(
+            self.scan(
+                |part, part_count| self.order_line_items(part, part_count),
+                BTreeMap::new,
+                |counts, item| {
+                    let date = item.order.o_orderdate.into_inner();
+                    if (start..end).contains(&date)
+                        && item
+                            .line_items
+                            .iter()
+                            .any(|line| line.l_commitdate < line.l_receiptdate)
+                    {
+                        *counts.entry(item.order.o_orderpriority).or_default() += 1;
+                    }
+                },
+            ),
+            |total, count: i64| *total += count,
+        );
+
+        let mut answer = AnswerBuilder::new(&["o_orderpriority", "order_count"]);
+        for (priority, count) in counts {
+            answer.row(&[&priority, &count]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Local Supplier Volume Query (Q5)
+    fn q5(&self, values: &Values) -> Result<String, String> {
+        let region = self.region_key(values.get(1)?)?;
+        let start = values.date(2)?;
+        let (start, end) = (start.index(), start.add_months(12).index());
+
+        let customer_nations = self.customer_nations();
+        let supplier_nations = self.supplier_nations();
+
+        let revenues = merge_maps(
+            self.scan(
+                |part, part_count| self.order_line_items(part, part_count),
+                BTreeMap::new,
+                |revenues, item| {
+                    let nation = customer_nations[item.order.o_custkey as usize - 1];
+                    if !(start..end).contains(&item.order.o_orderdate.into_inner())
+                        || self.nation_region(nation) != region
+                    {
+                        return;
+                    }
+                    for line in &item.line_items {
+                        if supplier_nations[line.l_suppkey as usize - 1] == nation {
+                            *revenues.entry(nation).or_default() += disc_price(line);
+                        }
+                    }
+                },
+            ),
+            |total, revenue: i128| *total += revenue,
+        );
+
+        let mut revenues: Vec<_> = revenues
+            .into_iter()
+            .map(|(nation, revenue)| (Reverse(revenue), self.nation_name(nation)))
+            .collect();
+        revenues.sort();
+
+        let mut answer = AnswerBuilder::new(&["n_name", "revenue"]);
+        for (Reverse(revenue), nation) in revenues {
+            answer.row(&[&nation, &Hundredths(round_div(revenue, 100))]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Forecasting Revenue Change Query (Q6)
+    fn q6(&self, values: &Values) -> Result<String, String> {
+        let start = values.date(1)?;
+        let (start, end) = (start.index(), start.add_months(12).index());
+        let discount = values.hundredths(2)?;
+        let quantity = values.parse::<i64>(3)?;
+
+        let revenue = self
+            .scan(
+                |part, part_count| self.line_items(part, part_count),
+                || None,
+                |revenue: &mut Option<i128>, line| {
+                    if (start..end).contains(&line.l_shipdate.into_inner())
+                        && (discount - 1..=discount + 1).contains(&line.l_discount.0)
+                        && line.l_quantity < quantity
+                    {
+                        *revenue.get_or_insert(0) +=
+                            line.l_extendedprice.0 as i128 * line.l_discount.0 as i128;
+                    }
+                },
+            )
+            .into_iter()
+            .flatten()
+            .reduce(|a, b| a + b);
+
+        let mut answer = AnswerBuilder::new(&["revenue"]);
+        answer.row(&[&Nullable(revenue.map(|r| Hundredths(round_div(r, 100))))]);
+        Ok(answer.finish())
+    }
+
+    /// Volume Shipping Query (Q7)
+    fn q7(&self, values: &Values) -> Result<String, String> {
+        let nation1 = self.nation_key(values.get(1)?)?;
+        let nation2 = self.nation_key(values.get(2)?)?;
+        let start = values.literal(1995, 1, 1).index();
+        let end = values.literal(1996, 12, 31).index();
+
+        let customer_nations = self.customer_nations();
+        let supplier_nations = self.supplier_nations();
+
+        let revenues = merge_maps(
+            self.scan(
+                |part, part_count| self.order_line_items(part, part_count),
+                BTreeMap::new,
+                |revenues, item| {
+                    let customer_nation = customer_nations[item.order.o_custkey as usize - 1];
+                    if customer_nation != nation1 && customer_nation != nation2 {
+                        return;
+                    }
+                    for line in &item.line_items {
+                        let supplier_nation = supplier_nations[line.l_suppkey as usize - 1];
+                        let nations = (supplier_nation, customer_nation);
+                        if (start..=end).contains(&line.l_shipdate.into_inner())
+                            && (nations == (nation1, nation2) || nations == (nation2, nation1))
+                        {
+                            let key = (
+                                self.nation_name(supplier_nation),
+                                self.nation_name(customer_nation),
+                                year(line.l_shipdate.to_ymd()),
+                            );
+                            *revenues.entry(key).or_default() += disc_price(line);
+                        }
+                    }
+                },
+            ),
+            |total, revenue: i128| *total += revenue,
+        );
+
+        let mut answer = AnswerBuilder::new(&["supp_nation", "cust_nation", "l_year", "revenue"]);
+        for ((supplier_nation, customer_nation, year), revenue) in revenues {
+            answer.row(&[
+                &supplier_nation,
+                &customer_nation,
+                &year,
+                &Hundredths(round_div(revenue, 100)),
+            ]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// National Market Share Query (Q8)
+    fn q8(&self, values: &Values) -> Result<String, String> {
+        let nation = self.nation_key(values.get(1)?)?;
+        let region = self.region_key(values.get(2)?)?;
+        let part_type = values.get(3)?;
+        let start = values.literal(1995, 1, 1).index();
+        let end = values.literal(1996, 12, 31).index();
+
+        let customer_nations = self.customer_nations();
+        let supplier_nations = self.supplier_nations();
+        let has_type = self.column(
+            |part, part_count| self.parts(part, part_count),
+            |part| part.p_type == part_type,
+        );
+
+        // (volume of the nation, total volume) by year
+        let volumes = merge_maps(
+            self.scan(
+                |part, part_count| self.order_line_items(part, part_count),
+                BTreeMap::new,
+                |volumes, item| {
+                    let order = &item.order;
+                    if !(start..=end).contains(&order.o_orderdate.into_inner())
+                        || self.nation_region(customer_nations[order.o_custkey as usize - 1])
+                            != region
+                    {
+                        return;
+                    }
+                    for line in &item.line_items {
+                        if has_type[line.l_partkey as usize - 1] {
+                            let volume = disc_price(line);
+                            let (nation_volume, total_volume): &mut (i128, i128) =
+                                volumes.entry(year(order.o_orderdate.to_ymd())).or_default();
+                            if supplier_nations[line.l_suppkey as usize - 1] == nation {
+                                *nation_volume += volume;
+                            }
+                            *total_volume += volume;
+                        }
+                    }
+                },
+            ),
+            |total, (nation_volume, total_volume)| {
+                total.0 += nation_volume;
+                total.1 += total_volume;
+            },
+        );
+
+        let mut answer = AnswerBuilder::new(&["o_year", "mkt_share"]);
+        for (year, (nation_volume, total_volume)) in volumes {
+            answer.row(&[
+                &year,
+                &Hundredths(round_div(nation_volume * 100, total_volume)),
+            ]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Product Type Profit Measure Query (Q9)
+    fn q9(&self, values: &Values) -> Result<String, String> {
+        let color = values.get(1)?;
+
+        let supplier_nations = self.supplier_nations();
+        // ps_supplycost of the parts with the color in their name
+        let supply_costs: HashMap<(i64, i64), i64> = self
+            .scan(
+                |part, part_count| self.parts_with_part_supps(part, part_count),
+                Vec::new,
+                |costs, (part, part_supps)| {
+                    if part.p_name.to_string().contains(color) {
+                        costs.extend(
+                            part_supps
+                                .iter()
+                                .map(|ps| ((ps.ps_partkey, ps.ps_suppkey), ps.ps_supplycost.0)),
+                        );
+                    }
+                },
+            )
+            .into_iter()
+            .flatten()
+            .collect();
+
+        let profits = merge_maps(
+            self.scan(
+                |part, part_count| self.order_line_items(part, part_count),
+                BTreeMap::new,
+                |profits, item| {
+                    for line in &item.line_items {
+                        let Some(&cost) = supply_costs.get(&(line.l_partkey, line.l_suppkey))
+                        else {
+                            continue;
+                        };
+                        let amount =
+                            disc_price(line) - cost as i128 * line.l_quantity as i128 * 100;
+                        let key = (
+                            self.nation_name(supplier_nations[line.l_suppkey as usize - 1]),
+                            Reverse(year(item.order.o_orderdate.to_ymd())),
+                        );
+                        *profits.entry(key).or_default() += amount;
+                    }
+                },
+            ),
+            |total, profit: i128| *total += profit,
+        );
+
+        let mut answer = AnswerBuilder::new(&["nation", "o_year", "sum_profit"]);
+        for ((nation, Reverse(year)), profit) in profits {
+            answer.row(&[&nation, &year, &Hundredths(round_div(profit, 100))]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Returned Item Reporting Query (Q10)
+    fn q10(&self, values: &Values) -> Result<String, String> {
+        let start = values.date(1)?;
+        let (start, end) = (start.index(), start.add_months(3).index());
+
+        let revenues = merge_hash_maps(
+            self.scan(
+                |part, part_count| self.order_line_items(part, part_count),
+                HashMap::new,
+                |revenues, item| {
+                    if !(start..end).contains(&item.order.o_orderdate.into_inner()) {
+                        return;
+                    }
+                    for line in &item.line_items {
+                        if line.l_returnflag == "R" {
+                            *revenues.entry(item.order.o_custkey).or_default() += disc_price(line);
+                        }
+                    }
+                },
+            ),
+            |total, revenue: i128| *total += revenue,
+        );
+
+        let mut top = TopK::new(20);
+        top.extend(
+            revenues
+                .into_iter()
+                .map(|(custkey, revenue)| (Reverse(revenue), custkey)),
+        );
+
+        let mut answer = AnswerBuilder::new(&[
+            "c_custkey",
+            "c_name",
+            "revenue",
+            "c_acctbal",
+            "n_name",
+            "c_address",
+            "c_phone",
+            "c_comment",
+        ]);
+        for (Reverse(revenue), custkey) in top.into_sorted_vec() {
+            let customer = self.customer(custkey);
+            answer.row(&[
+                &custkey,
+                &customer.c_name,
+                &Hundredths(round_div(revenue, 100)),
+                &customer.c_acctbal,
+                &self.nation_name(customer.c_nationkey),
+                &customer.c_address,
+                &customer.c_phone,
+                &customer.c_comment,
+            ]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Important Stock Identification Query (Q11)
+    fn q11(&self, values: &Values) -> Result<String, String> {
+        let nation = self.nation_key(values.get(1)?)?;
+        let (fraction, fraction_scale) = values.decimal(2)?;
+
+        let supplier_nations = self.supplier_nations();
+        // the part supplier rows of a part are consecutive
+        let part_values: Vec<(i64, i128)> = self
+            .scan(
+                |part, part_count| self.part_supps(part, part_count),
+                Vec::new,
+                |part_values: &mut Vec<(i64, i128)>, part_supp| {
+                    if supplier_nations[part_supp.ps_suppkey as usize - 1] != nation {
+                        return;
+                    }
+                    let value = part_supp.ps_supplycost.0 as i128 * part_supp.ps_availqty as i128;
+                    match part_values.last_mut() {
+                        Some((partkey, total)) if *partkey == part_supp.ps_partkey => {
+                            *total += value
+                        }
+                        _ => part_values.push((part_supp.ps_partkey, value)),
+                    }
+                },
+            )
+            .into_iter()
+            .flatten()
+            .collect();
+
+        // value > total * fraction, with fraction = fraction / 10^fraction_scale
+        let total: i128 = part_values.iter().map(|(_, value)| value).sum();
+        let threshold = total * fraction;
+        let mut part_values: Vec<_> = part_values
+            .into_iter()
+            .filter(|(_, value)| value * 10i128.pow(fraction_scale) > threshold)
+            .map(|(partkey, value)| (Reverse(value), partkey))
+            .collect();
+        part_values.sort();
+
+        let mut answer = AnswerBuilder::new(&["ps_partkey", "value"]);
+        for (Reverse(value), partkey) in part_values {
+            answer.row(&[&partkey, &Hundredths(value)]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Shipping Modes and Order Priority Query (Q12)
+    fn q12(&self, values: &Values) -> Result<String, String> {
+        let ship_modes = [values.get(1)?, values.get(2)?];
+        let start = values.date(3)?;
+        let (start, end) = (start.index(), start.add_months(12).index());
+
+        // (high_line_count, low_line_count) by ship mode
+        let counts = merge_maps(
+            self.scan(
+                |part, part_count| self.order_line_items(part, part_count),
+                BTreeMap::new,
+                |counts, item| {
+                    let high = matches!(item.order.o_orderpriority, "1-URGENT" | "2-HIGH");
+                    for line in &item.line_items {
+                        if ship_modes.contains(&line.l_shipmode)
+                            && line.l_commitdate < line.l_receiptdate
+                            && line.l_shipdate < line.l_commitdate
+                            && (start..end).contains(&line.l_receiptdate.into_inner())
+                        {
+                            let (high_count, low_count): &mut (i64, i64) =
+                                counts.entry(line.l_shipmode).or_default();
+                            if high {
+                                *high_count += 1;
+                            } else {
+                                *low_count += 1;
+                            }
+                        }
+                    }
+                },
+            ),
+            |total, (high_count, low_count)| {
+                total.0 += high_count;
+                total.1 += low_count;
+            },
+        );
+
+        let mut answer = AnswerBuilder::new(&["l_shipmode", "high_line_count", "low_line_count"]);
+        for (ship_mode, (high_count, low_count)) in counts {
+            answer.row(&[&ship_mode, &high_count, &low_count]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Customer Distribution Query (Q13)
+    fn q13(&self, values: &Values) -> Result<String, String> {
+        let (word1, word2) = (values.get(1)?, values.get(2)?);
+
+        let customer_count = CustomerGenerator::calculate_row_count(self.scale_factor, 1, 1);
+        let order_counts: Vec<AtomicU32> = (0..customer_count).map(|_| AtomicU32::new(0)).collect();
+        self.scan(
+            |part, part_count| self.orders(part, part_count),
+            || (),
+            |_, order| {
+                if !like_words(order.o_comment, word1, word2) {
+                    order_counts[order.o_custkey as usize - 1].fetch_add(1, Ordering::Relaxed);
+                }
+            },
+        );
+
+        let mut distribution: BTreeMap<u32, i64> = BTreeMap::new();
+        for count in order_counts {
+            *distribution.entry(count.into_inner()).or_default() += 1;
+        }
+        let mut distribution: Vec<_> = distribution
+            .into_iter()
+            .map(|(count, customers)| (Reverse(customers), Reverse(count)))
+            .collect();
+        distribution.sort();
+
+        let mut answer = AnswerBuilder::new(&["c_count", "custdist"]);
+        for (Reverse(customers), Reverse(count)) in distribution {
+            answer.row(&[&count, &customers]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Promotion Effect Query (Q14)
+    fn q14(&self, values: &Values) -> Result<String, String> {
+        let start = values.date(1)?;
+        let (start, end) = (start.index(), start.add_months(1).index());
+
+        let is_promo = self.column(
+            |part, part_count| self.parts(part, part_count),
+            |part| part.p_type.starts_with("PROMO"),
+        );
+
+        // (promo revenue, total revenue)
+        let revenues = self
+            .scan(
+                |part, part_count| self.line_items(part, part_count),
+                || None,
+                |revenues: &mut Option<(i128, i128)>, line| {
+                    if (start..end).contains(&line.l_shipdate.into_inner()) {
+                        let (promo, total) = revenues.get_or_insert((0, 0));
+                        let revenue = disc_price(&line);
+                        if is_promo[line.l_partkey as usize - 1] {
+                            *promo += revenue;
+                        }
+                        *total += revenue;
+                    }
+                },
+            )
+            .into_iter()
+            .flatten()
+            .reduce(|a, b| (a.0 + b.0, a.1 + b.1));
+
+        let mut answer = AnswerBuilder::new(&["promo_revenue"]);
+        answer.row(&[&Nullable(revenues.map(|(promo, total)| {
+            Hundredths(round_div(promo * 100 * 100, total))
+        }))]);
+        Ok(answer.finish())
+    }
+
+    /// Top Supplier Query (Q15)
+    fn q15(&self, values: &Values) -> Result<String, String> {
+        let start = values.date(1)?;
+        let (start, end) = (start.index(), start.add_months(3).index());
+
+        // the revenue of a supplier (in 10^-4) fits in an i64 at any scale
+        // factor, as the number of suppliers grows with the line items
+        let supplier_count = SupplierGenerator::calculate_row_count(self.scale_factor, 1, 1);
+        let revenues: Vec<AtomicI64> = (0..supplier_count).map(|_| AtomicI64::new(-1)).collect();
+        self.scan(
+            |part, part_count| self.line_items(part, part_count),
+            || (),
+            |_, line| {
+                if (start..end).contains(&line.l_shipdate.into_inner()) {
+                    let revenue = &revenues[line.l_suppkey as usize - 1];
+                    // -1 marks the suppliers without line items
+                    let _ = revenue.compare_exchange(-1, 0, Ordering::Relaxed, Ordering::Relaxed);
+                    revenue.fetch_add(disc_price(&line) as i64, Ordering::Relaxed);
+                }
+            },
+        );
+        let revenues: Vec<i64> = revenues.into_iter().map(AtomicI64::into_inner).collect();
+        let max_revenue = revenues.iter().copied().max().unwrap_or(-1);
+
+        let mut answer = AnswerBuilder::new(&[
+            "s_suppkey",
+            "s_name",
+            "s_address",
+            "s_phone",
+            "total_revenue",
+        ]);
+        if max_revenue >= 0 {
+            for (index, _) in revenues
+                .iter()
+                .enumerate()
+                .filter(|(_, r)| **r == max_revenue)
+            {
+                let supplier = self.supplier(index as i64 + 1);
+                answer.row(&[
+                    &supplier.s_suppkey,
+                    &supplier.s_name,
+                    &supplier.s_address,
+                    &supplier.s_phone,
+                    &Hundredths(round_div(max_revenue as i128, 100)),
+                ]);
+            }
+        }
+        Ok(answer.finish())
+    }
+
+    /// Parts/Supplier Relationship Query (Q16)
+    fn q16(&self, values: &Values) -> Result<String, String> {
+        let brand = values.get(1)?;
+        let type_prefix = values.get(2)?;
+        let sizes = (3..=10)
+            .map(|n| values.parse::<i32>(n))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let has_complaints = self.column(
+            |part, part_count| self.suppliers(part, part_count),
+            |supplier| like_words(&supplier.s_comment, "Customer", "Complaints"),
+        );
+
+        let mut suppliers = merge_hash_maps(
+            self.scan(
+                |part, part_count| self.parts_with_part_supps(part, part_count),
+                HashMap::new,
+                |suppliers, (part, part_supps)| {
+                    if part.p_type.starts_with(type_prefix)
+                        || !sizes.contains(&part.p_size)
+                        || part.p_brand.to_string() == brand
+                    {
+                        return;
+                    }
+                    let group: &mut Vec<i64> = suppliers
+                        .entry((part.p_brand.value(), part.p_type, part.p_size))
+                        .or_default();
+                    group.extend(
+                        part_supps
+                            .iter()
+                            .map(|ps| ps.ps_suppkey)
+                            .filter(|suppkey| !has_complaints[*suppkey as usize - 1]),
+                    );
+                },
+            ),
+            |total, suppliers| total.extend(suppliers),
+        );
+
+        let mut groups: Vec<_> = suppliers
+            .iter_mut()
+            .map(|((brand, part_type, size), suppliers)| {
+                suppliers.sort_unstable();
+                suppliers.dedup();
+                (
+                    Reverse(suppliers.len()),
+                    format!("Brand#{brand}"),
+                    *part_type,
+                    *size,
+                )
+            })
+            .filter(|(Reverse(count), ..)| *count > 0)
+            .collect();
+        groups.sort();
+
+        let mut answer = AnswerBuilder::new(&["p_brand", "p_type", "p_size", "supplier_cnt"]);
+        for (Reverse(count), brand, part_type, size) in groups {
+            answer.row(&[&brand, &part_type, &size, &count]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Small-Quantity-Order Revenue Query (Q17)
+    fn q17(&self, values: &Values) -> Result<String, String> {
+        let brand = values.get(1)?;
+        let container = values.get(2)?;
+
+        let selected = self.column(
+            |part, part_count| self.parts(part, part_count),
+            |part| part.p_container == container && part.p_brand.to_string() == brand,
+        );
+
+        // (l_quantity, l_extendedprice) of the line items of each part
+        let lines = merge_hash_maps(
+            self.scan(
+                |part, part_count| self.line_items(part, part_count),
+                HashMap::new,
+                |lines, line| {
+                    if selected[line.l_partkey as usize - 1] {
+                        let part_lines: &mut Vec<(i64, i64)> =
+                            lines.entry(line.l_partkey).or_default();
+                        part_lines.push((line.l_quantity, line.l_extendedprice.0));
+                    }
+                },
+            ),
+            |total, lines| total.extend(lines),
+        );
+
+        // l_quantity < 0.2 * sum / count  <=>  5 * l_quantity * count < sum
+        let mut total_price = None;
+        for part_lines in lines.values() {
+            let count = part_lines.len() as i64;
+            let sum: i64 = part_lines.iter().map(|(quantity, _)| quantity).sum();
+            for (quantity, price) in part_lines {
+                if 5 * quantity * count < sum {
+                    *total_price.get_or_insert(0) += *price as i128;
+                }
+            }
+        }
+
+        let mut answer = AnswerBuilder::new(&["avg_yearly"]);
+        answer.row(&[&Nullable(
+            total_price.map(|price| Hundredths(round_div(price, 7))),
+        )]);
+        Ok(answer.finish())
+    }
+
+    /// Large Volume Customer Query (Q18)
+    fn q18(&self, values: &Values) -> Result<String, String> {
+        let quantity = values.parse::<i64>(1)?;
+
+        let mut top = TopK::new(100);
+        for orders in self.scan(
+            |part, part_count| self.order_line_items(part, part_count),
+            || TopK::new(100),
+            |top, item| {
+                let total_quantity: i64 = item.line_items.iter().map(|l| l.l_quantity).sum();
+                if total_quantity > quantity {
+                    let order = &item.order;
+                    top.push((
+                        Reverse(order.o_totalprice.0),
+                        order.o_orderdate,
+                        order.o_orderkey,
+                        order.o_custkey,
+                        total_quantity,
+                    ));
+                }
+            },
+        ) {
+            top.extend(orders.into_sorted_vec());
+        }
+
+        let mut answer = AnswerBuilder::new(&[
+            "c_name",
+            "c_custkey",
+            "o_orderkey",
+            "o_orderdat",
+            "o_totalprice",
+            "col6",
+        ]);
+        for (Reverse(total_price), order_date, orderkey, custkey, total_quantity) in
+            top.into_sorted_vec()
+        {
+            answer.row(&[
+                &CustomerName::new(custkey),
+                &custkey,
+                &orderkey,
+                &order_date,
+                &Hundredths(total_price as i128),
+                &Hundredths(total_quantity as i128 * 100),
+            ]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Discounted Revenue Query (Q19)
+    fn q19(&self, values: &Values) -> Result<String, String> {
+        const CONTAINERS: [[&str; 4]; 3] = [
+            ["SM CASE", "SM BOX", "SM PACK", "SM PKG"],
+            ["MED BAG", "MED BOX", "MED PKG", "MED PACK"],
+            ["LG CASE", "LG BOX", "LG PACK", "LG PKG"],
+        ];
+        const MAX_SIZES: [i32; 3] = [5, 10, 15];
+        let brands = [values.get(1)?, values.get(2)?, values.get(3)?];
+        let quantities = [
+            values.parse::<i64>(4)?,
+            values.parse::<i64>(5)?,
+            values.parse::<i64>(6)?,
+        ];
+
+        // the index of the condition the part satisfies + 1, or 0. The
+        // containers of the conditions are disjoint
+        let conditions = self.column(
+            |part, part_count| self.parts(part, part_count),
+            |part| {
+                let brand = part.p_brand.to_string();
+                (0..3)
+                    .find(|&i| {
+                        brands[i] == brand
+                            && CONTAINERS[i].contains(&part.p_container)
+                            && (1..=MAX_SIZES[i]).contains(&part.p_size)
+                    })
+                    .map_or(0, |i| i as u8 + 1)
+            },
+        );
+
+        let revenue = self
+            .scan(
+                |part, part_count| self.line_items(part, part_count),
+                || None,
+                |revenue: &mut Option<i128>, line| {
+                    let condition = conditions[line.l_partkey as usize - 1];
+                    if condition == 0
+                        || !matches!(line.l_shipmode, "AIR" | "AIR REG")
+                        || line.l_shipinstruct != "DELIVER IN PERSON"
+                    {
+                        return;
+                    }
+                    let min_quantity = quantities[condition as usize - 1];
+                    if (min_quantity..=min_quantity + 10).contains(&line.l_quantity) {
+                        *revenue.get_or_insert(0) += disc_price(&line);
+                    }
+                },
+            )
+            .into_iter()
+            .flatten()
+            .reduce(|a, b| a + b);
+
+        let mut answer = AnswerBuilder::new(&["revenue"]);
+        answer.row(&[&Nullable(revenue.map(|r| Hundredths(round_div(r, 100))))]);
+        Ok(answer.finish())
+    }
+
+    /// Potential Part Promotion Query (Q20)
+    fn q20(&self, values: &Values) -> Result<String, String> {
+        let color = values.get(1)?;
+        let start = values.date(2)?;
+        let (start, end) = (start.index(), start.add_months(12).index());
+        let nation = self.nation_key(values.get(3)?)?;
+
+        let supplier_nations = self.supplier_nations();
+        // ps_availqty of the candidate part suppliers
+        let available: HashMap<(i64, i64), i32> = self
+            .scan(
+                |part, part_count| self.parts_with_part_supps(part, part_count),
+                Vec::new,
+                |available, (part, part_supps)| {
+                    if part.p_name.to_string().starts_with(color) {
+                        available.extend(
+                            part_supps
+                                .iter()
+                                .filter(|ps| supplier_nations[ps.ps_suppkey as usize - 1] == nation)
+                                .map(|ps| ((ps.ps_partkey, ps.ps_suppkey), ps.ps_availqty)),
+                        );
+                    }
+                },
+            )
+            .into_iter()
+            .flatten()
+            .collect();
+
+        let shipped = merge_hash_maps(
+            self.scan(
+                |part, part_count| self.line_items(part, part_count),
+                HashMap::new,
+                |shipped, line| {
+                    let key = (line.l_partkey, line.l_suppkey);
+                    if (start..end).contains(&line.l_shipdate.into_inner())
+                        && available.contains_key(&key)
+                    {
+                        *shipped.entry(key).or_default() += line.l_quantity;
+                    }
+                },
+            ),
+            |total, quantity: i64| *total += quantity,
+        );
+
+        // ps_availqty > 0.5 * sum(l_quantity), which is null without line items
+        let suppliers: BTreeSet<i64> = shipped
+            .iter()
+            .filter(|(key, quantity)| 2 * available[key] as i64 > **quantity)
+            .map(|((_, suppkey), _)| *suppkey)
+            .collect();
+
+        let mut answer = AnswerBuilder::new(&["s_name", "s_address"]);
+        // the names are the zero padded keys
+        for suppkey in suppliers {
+            let supplier = self.supplier(suppkey);
+            answer.row(&[&supplier.s_name, &supplier.s_address]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Suppliers Who Kept Orders Waiting Query (Q21)
+    fn q21(&self, values: &Values) -> Result<String, String> {
+        let nation = self.nation_key(values.get(1)?)?;
+
+        let supplier_nations = self.supplier_nations();
+        let waits = merge_hash_maps(
+            self.scan(
+                |part, part_count| self.order_line_items(part, part_count),
+                HashMap::new,
+                |waits, item| {
+                    if item.order.o_orderstatus != OrderStatus::Fulfilled {
+                        return;
+                    }
+                    let lines = &item.line_items;
+                    for line in lines {
+                        if !is_late(line) || supplier_nations[line.l_suppkey as usize - 1] != nation
+                        {
+                            continue;
+                        }
+                        // another supplier of the order, and none of them was late
+                        let mut others = lines.iter().filter(|l| l.l_suppkey != line.l_suppkey);
+                        if others.clone().next().is_some() && !others.any(is_late) {
+                            *waits.entry(line.l_suppkey).or_default() += 1;
+                        }
+                    }
+                },
+            ),
+            |total, count: i64| *total += count,
+        );
+
+        let mut top = TopK::new(100);
+        // the names are the zero padded keys
+        top.extend(
+            waits
+                .into_iter()
+                .map(|(suppkey, count)| (Reverse(count), suppkey)),
+        );
+
+        let mut answer = AnswerBuilder::new(&["s_name", "numwait"]);
+        for (Reverse(count), suppkey) in top.into_sorted_vec() {
+            answer.row(&[&self.supplier(suppkey).s_name, &count]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Global Sales Opportunity Query (Q22)
+    fn q22(&self, values: &Values) -> Result<String, String> {
+        let codes = (1..=7)
+            .map(|n| values.get(n))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // (c_custkey, index of the country code, c_acctbal) of the customers
+        // with a positive balance in the countries
+        let candidates: Vec<(i64, usize, i64)> = self
+            .scan(
+                |part, part_count| self.customers(part, part_count),
+                Vec::new,
+                |candidates, customer| {
+                    if customer.c_acctbal.0 <= 0 {
+                        return;
+                    }
+                    let phone = customer.c_phone.to_string();
+                    if let Some(code) = codes.iter().position(|code| phone.starts_with(code)) {
+                        candidates.push((customer.c_custkey, code, customer.c_acctbal.0));
+                    }
+                },
+            )
+            .into_iter()
+            .flatten()
+            .collect();
+
+        let customer_count = CustomerGenerator::calculate_row_count(self.scale_factor, 1, 1);
+        let has_orders: Vec<AtomicBool> = (0..customer_count)
+            .map(|_| AtomicBool::new(false))
+            .collect();
+        self.scan(
+            |part, part_count| self.orders(part, part_count),
+            || (),
+            |_, order| has_orders[order.o_custkey as usize - 1].store(true, Ordering::Relaxed),
+        );
+
+        // c_acctbal > avg(c_acctbal)  <=>  c_acctbal * count > sum
+        let count = candidates.len() as i128;
+        let sum: i128 = candidates
+            .iter()
+            .map(|(_, _, balance)| *balance as i128)
+            .sum();
+        let mut groups: BTreeMap<&str, (i64, i128)> = BTreeMap::new();
+        for (custkey, code, balance) in candidates {
+            if balance as i128 * count > sum
+                && !has_orders[custkey as usize - 1].load(Ordering::Relaxed)
+            {
+                let (customers, total_balance) = groups.entry(codes[code]).or_default();
+                *customers += 1;
+                *total_balance += balance as i128;
+            }
+        }
+
+        let mut answer = AnswerBuilder::new(&["cntrycode", "numcust", "totacctbal"]);
+        for (code, (customers, total_balance)) in groups {
+            answer.row(&[&code, &customers, &Hundredths(total_balance)]);
+        }
+        Ok(answer.finish())
+    }
+
+    /// Generates the rows of `generator` on [`Self::threads`] threads, each
+    /// folding the rows of one part of the table into its own accumulator
+    ///
+    /// Returns the accumulators in the order of the parts.
+    fn scan<G, A>(
+        &self,
+        generator: impl Fn(i32, i32) -> G + Sync,
+        init: impl Fn() -> A + Sync,
+        fold: impl Fn(&mut A, G::Item) + Sync,
+    ) -> Vec<A>
+    where
+        G: IntoIterator,
+        A: Send,
+    {
+        let part_count = self.threads as i32;
+        let (generator, init, fold) = (&generator, &init, &fold);
+        thread::scope(|scope| {
+            let handles: Vec<_> = (1..=part_count)
+                .map(|part| {
+                    scope.spawn(move || {
+                        let mut accumulator = init();
+                        for row in generator(part, part_count) {
+                            fold(&mut accumulator, row);
+                        }
+                        accumulator
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("query evaluation thread panicked"))
+                .collect()
+        })
+    }
+
+    /// Returns `value` of every row of `generator`, indexed by the key - 1
+    fn column<G, T>(
+        &self,
+        generator: impl Fn(i32, i32) -> G + Sync,
+        value: impl Fn(G::Item) -> T + Sync,
+    ) -> Vec<T>
+    where
+        G: IntoIterator,
+        T: Send,
+    {
+        self.scan(generator, Vec::new, |values, row| values.push(value(row)))
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    fn customer_nations(&self) -> Vec<i64> {
+        self.column(
+            |part, part_count| self.customers(part, part_count),
+            |customer| customer.c_nationkey,
+        )
+    }
+
+    fn supplier_nations(&self) -> Vec<i64> {
+        self.column(
+            |part, part_count| self.suppliers(part, part_count),
+            |supplier| supplier.s_nationkey,
+        )
+    }
+
+    fn parts(&self, part: i32, part_count: i32) -> PartGenerator<'a> {
+        PartGenerator::new_with_distributions_and_text_pool(
+            self.scale_factor,
+            part,
+            part_count,
+            self.distributions,
+            self.text_pool,
+        )
+        .with_seed(self.seed)
+        .with_skew(self.skew)
+    }
+
+    fn part_supps(&self, part: i32, part_count: i32) -> PartSuppGenerator<'a> {
+        PartSuppGenerator::new_with_text_pool(self.scale_factor, part, part_count, self.text_pool)
+            .with_seed(self.seed)
+    }
+
+    /// Returns the parts with their [`PartSuppGenerator::SUPPLIERS_PER_PART`]
+    /// part supplier rows
+    fn parts_with_part_supps(
+        &self,
+        part: i32,
+        part_count: i32,
+    ) -> impl Iterator<Item = (Part<'a>, Vec<PartSupp<'a>>)> {
+        let mut part_supps = self.part_supps(part, part_count).into_iter();
+        self.parts(part, part_count).into_iter().map(move |part| {
+            let suppliers = part_supps
+                .by_ref()
+                .take(PartSuppGenerator::SUPPLIERS_PER_PART as usize)
+                .collect();
+            (part, suppliers)
+        })
+    }
+
+    fn suppliers(&self, part: i32, part_count: i32) -> SupplierGenerator<'a> {
+        SupplierGenerator::new_with_distributions_and_text_pool(
+            self.scale_factor,
+            part,
+            part_count,
+            self.distributions,
+            self.text_pool,
+        )
+        .with_seed(self.seed)
+    }
+
+    fn supplier(&self, suppkey: i64) -> Supplier {
+        self.suppliers(1, 1)
+            .generate_row(suppkey - 1)
+            .expect("supplier key out of range")
+    }
+
+    fn customers(&self, part: i32, part_count: i32) -> CustomerGenerator<'a> {
+        CustomerGenerator::new_with_distributions_and_text_pool(
+            self.scale_factor,
+            part,
+            part_count,
+            self.distributions,
+            self.text_pool,
+        )
+        .with_seed(self.seed)
+        .with_skew(self.skew)
+    }
+
+    fn customer(&self, custkey: i64) -> Customer<'a> {
+        self.customers(1, 1)
+            .generate_row(custkey - 1)
+            .expect("customer key out of range")
+    }
+
+    fn orders(&self, part: i32, part_count: i32) -> OrderGenerator<'a> {
+        OrderGenerator::new_with_distributions_and_text_pool(
+            self.scale_factor,
+            part,
+            part_count,
+            self.distributions,
+            self.text_pool,
+        )
+        .with_seed(self.seed)
+        .with_skew(self.skew)
+        .with_date_range(self.date_range)
+    }
+
+    fn line_items(&self, part: i32, part_count: i32) -> LineItemGenerator<'a> {
+        LineItemGenerator::new_with_distributions_and_text_pool(
+            self.scale_factor,
+            part,
+            part_count,
+            self.distributions,
+            self.text_pool,
+        )
+        .with_seed(self.seed)
+        .with_skew(self.skew)
+        .with_date_range(self.date_range)
+    }
+
+    fn order_line_items(&self, part: i32, part_count: i32) -> OrderLineItemGenerator<'a> {
+        OrderLineItemGenerator::new_with_distributions_and_text_pool(
+            self.scale_factor,
+            part,
+            part_count,
+            self.distributions,
+            self.text_pool,
+        )
+        .with_seed(self.seed)
+        .with_skew(self.skew)
+        .with_date_range(self.date_range)
+    }
+
+    fn nation_key(&self, name: &str) -> Result<i64, String> {
+        self.distributions
+            .nations()
+            .get_values()
+            .iter()
+            .position(|nation| *nation == name)
+            .map(|index| index as i64)
+            .ok_or_else(|| format!("Unknown nation: {name}"))
+    }
+
+    fn nation_name(&self, nationkey: i64) -> &'a str {
+        self.distributions.nations().get_value(nationkey as usize)
+    }
+
+    fn nation_region(&self, nationkey: i64) -> i64 {
+        self.distributions.nations().get_weight(nationkey as usize) as i64
+    }
+
+    fn region_key(&self, name: &str) -> Result<i64, String> {
+        self.distributions
+            .regions()
+            .get_values()
+            .iter()
+            .position(|region| *region == name)
+            .map(|index| index as i64)
+            .ok_or_else(|| format!("Unknown region: {name}"))
+    }
+}
+
+/// The parameter values of a query, `:1` is `get(1)`
+struct Values<'p> {
+    query_number: i32,
+    values: &'p [String],
+    /// See [`DateRange::year_shift`]
+    year_shift: i32,
+}
+
+impl<'p> Values<'p> {
+    fn get(&self, n: usize) -> Result<&'p str, String> {
+        self.values
+            .get(n - 1)
+            .map(String::as_str)
+            .ok_or_else(|| format!("Missing parameter :{n} of Q{}", self.query_number))
+    }
+
+    fn invalid(&self, n: usize) -> String {
+        format!(
+            "Invalid parameter :{n} of Q{}: {}",
+            self.query_number,
+            self.values[n - 1]
+        )
+    }
+
+    fn parse<T: FromStr>(&self, n: usize) -> Result<T, String> {
+        self.get(n)?.parse().map_err(|_| self.invalid(n))
+    }
+
+    fn date(&self, n: usize) -> Result<Date, String> {
+        Date::parse(self.get(n)?).ok_or_else(|| self.invalid(n))
+    }
+
+    /// Returns a date of the query text, moved like the parameters (see
+    /// [`QueryParameters::with_date_range`])
+    fn literal(&self, year: i32, month: i32, day: i32) -> Date {
+        Date::new(year + self.year_shift, month, day)
+    }
+
+    /// Returns the decimal as `(value, scale)`, its value is `value / 10^scale`
+    fn decimal(&self, n: usize) -> Result<(i128, u32), String> {
+        parse_decimal(self.get(n)?).ok_or_else(|| self.invalid(n))
+    }
+
+    /// Returns a decimal with at most 2 decimal places in hundredths, like
+    /// [`TPCHDecimal`](crate::decimal::TPCHDecimal)
+    fn hundredths(&self, n: usize) -> Result<i64, String> {
+        match self.decimal(n)? {
+            (value, scale) if scale <= 2 => Ok((value * 10i128.pow(2 - scale)) as i64),
+            _ => Err(self.invalid(n)),
+        }
+    }
+}
+
+/// A date parameter of a query
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Date {
+    year: i32,
+    month: i32,
+    day: i32,
+}
+
+impl Date {
+    fn new(year: i32, month: i32, day: i32) -> Self {
+        Self { year, month, day }
+    }
+
+    /// Parses a `YYYY-MM-DD` date
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let mut fields = value.splitn(3, '-').map(|field| field.parse::<i32>().ok());
+        let date = Self::new(fields.next()??, fields.next()??, fields.next()??);
+        ((1..=12).contains(&date.month) && (1..=date.days_in_month()).contains(&date.day))
+            .then_some(date)
+    }
+
+    /// Adds `months` months, like `date + interval 'months' month` in SQL:
+    /// the day is clamped to the last day of the month
+    fn add_months(self, months: i32) -> Self {
+        let mut date = self.first_of_month(months);
+        date.day = self.day.min(date.days_in_month());
+        date
+    }
+
+    /// Returns the first day of the month `months` months after this one
+    fn first_of_month(self, months: i32) -> Self {
+        let months = self.year * 12 + (self.month - 1) + months;
+        Self::new(months.div_euclid(12), months.rem_euclid(12) + 1, 1)
+    }
+
+    fn days_in_month(self) -> i32 {
+        self.first_of_month(1).days_since_epoch() - self.first_of_month(0).days_since_epoch()
+    }
+
+    /// Returns the number of days since 1970-01-01
+    ///
+    /// See <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+    fn days_since_epoch(self) -> i32 {
+        let year = if self.month <= 2 {
+            self.year - 1
+        } else {
+            self.year
+        };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((self.month + 9) % 12) + 2) / 5 + self.day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Returns the index of the date, comparable with
+    /// [`TPCHDate::into_inner`](crate::dates::TPCHDate::into_inner)
+    fn index(self) -> i32 {
+        self.days_since_epoch() - crate::dates::TPCHDate::UNIX_EPOCH_OFFSET
+    }
+}
+
+/// Parses a decimal like `0.0001` as `(1, 4)`
+fn parse_decimal(value: &str) -> Option<(i128, u32)> {
+    let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+    let digits = integer.trim_start_matches('-');
+    if digits.is_empty() && fraction.is_empty()
+        || !digits
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let scale = fraction.len() as u32;
+    let magnitude: i128 = format!("{digits}{fraction}").parse().ok()?;
+    let value = if integer.starts_with('-') {
+        -magnitude
+    } else {
+        magnitude
+    };
+    Some((value, scale))
+}
+
+/// Returns the year of [`TPCHDate::to_ymd`](crate::dates::TPCHDate::to_ymd)
+fn year((year, _, _): (i32, i32, i32)) -> i32 {
+    1900 + year
+}
+
+/// Returns `l_extendedprice * (1 - l_discount)` in 10^-4
+fn disc_price(line: &LineItem) -> i128 {
+    line.l_extendedprice.0 as i128 * (100 - line.l_discount.0 as i128)
+}
+
+/// Returns true if the line item was received after its commit date
+fn is_late(line: &LineItem) -> bool {
+    line.l_receiptdate > line.l_commitdate
+}
+
+/// Returns true if `text` is `like '%first%second%'`
+fn like_words(text: &str, first: &str, second: &str) -> bool {
+    text.find(first)
+        .is_some_and(|start| text[start + first.len()..].contains(second))
+}
+
+/// Divides, rounding half away from zero
+fn round_div(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    if 2 * remainder.abs() >= denominator.abs() {
+        quotient + numerator.signum() * denominator.signum()
+    } else {
+        quotient
+    }
+}
+
+/// Merges the accumulators of [`QueryEvaluator::scan`]
+fn merge_maps<K: Ord, V: Default>(
+    maps: Vec<BTreeMap<K, V>>,
+    add: impl Fn(&mut V, V),
+) -> BTreeMap<K, V> {
+    let mut merged = BTreeMap::new();
+    for map in maps {
+        for (key, value) in map {
+            add(merged.entry(key).or_default(), value);
+        }
+    }
+    merged
+}
+
+/// Merges the accumulators of [`QueryEvaluator::scan`]
+fn merge_hash_maps<K: Eq + std::hash::Hash, V: Default>(
+    maps: Vec<HashMap<K, V>>,
+    add: impl Fn(&mut V, V),
+) -> HashMap<K, V> {
+    let mut merged = HashMap::new();
+    for map in maps {
+        for (key, value) in map {
+            add(merged.entry(key).or_default(), value);
+        }
+    }
+    merged
+}
+
+/// Keeps the `limit` smallest rows, for `order by ... limit`
+struct TopK<T: Ord> {
+    limit: usize,
+    heap: BinaryHeap<T>,
+}
+
+impl<T: Ord> TopK<T> {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            heap: BinaryHeap::with_capacity(limit + 1),
+        }
+    }
+
+    fn push(&mut self, row: T) {
+        self.heap.push(row);
+        if self.heap.len() > self.limit {
+            self.heap.pop();
+        }
+    }
+
+    fn extend(&mut self, rows: impl IntoIterator<Item = T>) {
+        for row in rows {
+            self.push(row);
+        }
+    }
+
+    fn into_sorted_vec(self) -> Vec<T> {
+        self.heap.into_sorted_vec()
+    }
+}
+
+/// Formats the answer like [`answers_sf1`](crate::q_and_a::answers_sf1)
+struct AnswerBuilder(String);
+
+impl AnswerBuilder {
+    fn new(columns: &[&str]) -> Self {
+        Self(format!("\n{}\n", columns.join("|")))
+    }
+
+    fn row(&mut self, values: &[&dyn Display]) {
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                self.0.push('|');
+            }
+            self.0.push_str(&value.to_string());
+        }
+        self.0.push('\n');
+    }
+
+    fn finish(self) -> String {
+        self.0
+    }
+}
+
+/// A value in hundredths, formatted with 2 decimal places
+struct Hundredths(i128);
+
+impl Display for Hundredths {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let value = self.0.unsigned_abs();
+        write!(f, "{sign}{}.{:02}", value / 100, value % 100)
+    }
+}
+
+/// A value that is empty when null
+struct Nullable<T>(Option<T>);
+
+impl<T: Display> Display for Nullable<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(value) => value.fmt(f),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::q_and_a::answers_sf1;
+    use crate::q_and_a::validation::Answer;
+
+    /// Trims the padding of the values in the answers of [`answers_sf1`]
+    ///
+    /// A null value in a single column answer is an empty line.
+    fn normalize(answer: &str) -> Vec<Vec<String>> {
+        answer
+            .lines()
+            .skip(1)
+            .map(|line| line.split('|').map(|v| v.trim().to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_answers_sf1() {
+        // the queries that do not scan the line items, which are slow in
+        // debug builds
+        let evaluator = QueryEvaluator::new(1.0);
+        for query_number in [2, 16, 22] {
+            assert_eq!(
+                normalize(&evaluator.validation_answer(query_number).unwrap()),
+                normalize(answers_sf1::answer(query_number).unwrap()),
+                "Q{query_number}"
+            );
+        }
+
+        check_q11_answer_sf1(&evaluator.validation_answer(11).unwrap());
+    }
+
+    /// Compares the Q11 answer with [`answers_sf1`]
+    ///
+    /// The part keys of the bundled answer lost their last 2 digits (e.g.
+    /// `1297` for `129760`), so the keys are compared by prefix.
+    fn check_q11_answer_sf1(answer: &str) {
+        let answer = normalize(answer);
+        let expected = normalize(answers_sf1::answer(11).unwrap());
+        assert_eq!(answer.len(), expected.len());
+        for (row, expected_row) in answer.iter().zip(&expected).skip(1) {
+            assert!(
+                row[0].starts_with(&expected_row[0]),
+                "{row:?} {expected_row:?}"
+            );
+            assert_eq!(row[1], expected_row[1]);
+        }
+    }
+
+    /// Compares the answers of all the queries with [`answers_sf1`]
+    ///
+    /// Too slow for debug builds, CI runs it with
+    /// `cargo test --release -p tpchgen --lib -- --ignored test_all_answers_sf1`
+    #[test]
+    #[ignore]
+    fn test_all_answers_sf1() {
+        let evaluator = QueryEvaluator::new(1.0);
+        for query_number in 1..=22 {
+            let answer = evaluator.validation_answer(query_number).unwrap();
+            if query_number == 11 {
+                check_q11_answer_sf1(&answer);
+                continue;
+            }
+            let expected = Answer::parse(
+                query_number,
+                answers_sf1::answer(query_number).unwrap(),
+                '|',
+            )
+            .unwrap();
+            let actual = Answer::parse(query_number, &answer, '|').unwrap();
+            let differences = expected.compare(&actual);
+            assert!(
+                differences.is_empty(),
+                "Q{query_number}: {}",
+                differences[0]
+            );
+        }
+    }
+
+    #[test]
+    fn test_answers_small_scale_factor() {
+        let evaluator = QueryEvaluator::new(0.01);
+        for query_number in 1..=22 {
+            let answer = evaluator.validation_answer(query_number).unwrap();
+            let expected_columns = normalize(answers_sf1::answer(query_number).unwrap())
+                .remove(0)
+                .len();
+            let rows = normalize(&answer);
+            assert!(rows.len() > 1, "Q{query_number} has no rows: {answer}");
+            assert!(
+                rows.iter().all(|row| row.len() == expected_columns),
+                "Q{query_number} has the wrong number of columns: {answer}"
+            );
+        }
+
+        // the answers do not depend on the number of threads
+        let single_threaded = QueryEvaluator::new(0.01).with_threads(1);
+        for query_number in [1, 3, 13, 18] {
+            assert_eq!(
+                single_threaded.validation_answer(query_number),
+                evaluator.validation_answer(query_number)
+            );
+        }
+    }
+
+    #[test]
+    fn test_date_range() {
+        // the data of a range of 7 years has the dates of the default range
+        // moved by the same number of days, so the answers without dates are
+        // the same
+        let evaluator = QueryEvaluator::new(0.01);
+        let shifted =
+            QueryEvaluator::new(0.01).with_date_range(DateRange::new(2019, 2025).unwrap());
+        for query_number in [1, 4, 6, 12] {
+            assert_eq!(
+                shifted.validation_answer(query_number),
+                evaluator.validation_answer(query_number),
+                "Q{query_number}"
+            );
+        }
+        let answer = shifted.validation_answer(7).unwrap();
+        assert!(answer.contains("|2022|") && answer.contains("|2023|"));
+        assert!(!answer.contains("|1995|"));
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let evaluator = QueryEvaluator::new(0.01);
+        let parameters = QueryParameters::new(3, vec!["BUILDING".into(), "1995-13-01".into()]);
+        assert_eq!(
+            evaluator.answer(&parameters.unwrap()),
+            Err("Invalid parameter :2 of Q3: 1995-13-01".to_string())
+        );
+        let parameters = QueryParameters::new(21, vec!["ATLANTIS".into()]).unwrap();
+        assert_eq!(
+            evaluator.answer(&parameters),
+            Err("Unknown nation: ATLANTIS".to_string())
+        );
+        let parameters = QueryParameters::new(6, vec!["1994-01-01".into()]).unwrap();
+        assert_eq!(
+            evaluator.answer(&parameters),
+            Err("Missing parameter :2 of Q6".to_string())
+        );
+    }
+
+    #[test]
+    fn test_date() {
+        assert_eq!(Date::new(1992, 1, 1).index(), 0);
+        assert_eq!(Date::new(1992, 2, 11).index(), 41);
+        assert_eq!(Date::parse("1995-03-15"), Some(Date::new(1995, 3, 15)));
+        assert_eq!(Date::parse("1995-02-29"), None);
+        assert_eq!(Date::new(1996, 1, 31).add_months(1), Date::new(1996, 2, 29));
+        assert_eq!(Date::new(1995, 11, 1).add_months(3), Date::new(1996, 2, 1));
+    }
+
+    #[test]
+    fn test_rounding() {
+        assert_eq!(round_div(5, 10), 1);
+        assert_eq!(round_div(-5, 10), -1);
+        assert_eq!(round_div(14, 10), 1);
+        assert_eq!(Hundredths(-5).to_string(), "-0.05");
+        assert_eq!(Hundredths(12345).to_string(), "123.45");
+        assert_eq!(parse_decimal("0.0001"), Some((1, 4)));
+        assert_eq!(parse_decimal("-1.5"), Some((-15, 1)));
+        assert_eq!(parse_decimal("1e-5"), None);
+    }
+}
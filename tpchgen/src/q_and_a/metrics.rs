@@ -0,0 +1,400 @@
+//! TPC-H query streams and performance metrics.
+//!
+//! The performance test of the TPC-H specification (section 5.3) runs the 22
+//! queries in the order of a *query stream*: stream 0 in the power test, and
+//! streams 1 to S concurrently in the throughput test, in the orders listed in
+//! Appendix A of the specification (see [`stream_queries`]). Both tests also
+//! run the refresh functions RF1 (new sales) and RF2 (old sales).
+//!
+//! The metrics of section 5.4 are computed from the timing intervals of these
+//! tests, in seconds:
+//!
+//! * `Power@Size` ([`PowerTest::power`]): `3600 * SF` divided by the
+//!   geometric mean of the 22 query and 2 refresh function intervals of the
+//!   power test
+//! * `Throughput@Size` ([`ThroughputTest::throughput`]): `S * 22 * 3600 * SF`
+//!   divided by the measurement interval of the throughput test
+//! * `QphH@Size` ([`Metrics`]): the geometric mean of both
+//!
+//! # Example
+//! ```
+//! # use tpchgen::q_and_a::metrics::{stream_queries, Metrics, PowerTest, ThroughputTest};
+//! assert_eq!(stream_queries(0)[..4], [14, 2, 9, 20]);
+//!
+//! // every query and refresh function of the power test takes 10 seconds
+//! let power_test = PowerTest::new([10.0; 22], [10.0; 2]);
+//! // 2 query streams run in 400 seconds
+//! let throughput_test = ThroughputTest::new(2, 400.0);
+//!
+//! let metrics = Metrics::compute(1.0, &power_test, &throughput_test).unwrap();
+//! assert_eq!(metrics.power.round(), 360.0);
+//! assert_eq!(metrics.throughput, 396.0);
+//! assert_eq!(metrics.qphh.round(), 378.0);
+//! ```
+
+/// The order of the queries in each stream (Appendix A of the specification)
+const PERMUTATIONS: [[i32; 22]; 41] = [
+    [
+        14, 2, 9, 20, 6, 17, 18, 8, 21, 13, 3, 22, 16, 4, 11, 15, 1, 10, 19, 5, 7, 12,
+    ],
+    [
+        21, 3, 18, 5, 11, 7, 6, 20, 17, 12, 16, 15, 13, 10, 2, 8, 14, 19, 9, 22, 1, 4,
+    ],
+    [
+        6, 17, 14, 16, 19, 10, 9, 2, 15, 8, 5, 22, 12, 7, 13, 18, 1, 4, 20, 3, 11, 21,
+    ],
+    [
+        8, 5, 4, 6, 17, 7, 1, 18, 22, 14, 9, 10, 15, 11, 20, 2, 21, 19, 13, 16, 12, 3,
+    ],
+    [
+        5, 21, 14, 19, 15, 17, 12, 6, 4, 9, 8, 16, 11, 2, 10, 18, 1, 13, 7, 22, 3, 20,
+    ],
+    [
+        21, 15, 4, 6, 7, 16, 19, 18, 14, 22, 11, 13, 3, 1, 2, 5, 8, 20, 12, 17, 10, 9,
+    ],
+    [
+        10, 3, 15, 13, 6, 8, 9, 7, 4, 11, 22, 18, 12, 1, 5, 16, 2, 14, 19, 20, 17, 21,
+    ],
+    [
+        18, 8, 20, 21, 2, 4, 22, 17, 1, 11, 9, 19, 3, 13, 5, 7, 10, 16, 6, 14, 15, 12,
+    ],
+    [
+        19, 1, 15, 17, 5, 8, 9, 12, 14, 7, 4, 3, 20, 16, 6, 22, 10, 13, 2, 21, 18, 11,
+    ],
+    [
+        8, 13, 2, 20, 17, 3, 6, 21, 18, 11, 19, 10, 15, 4, 22, 1, 7, 12, 9, 14, 5, 16,
+    ],
+    [
+        6, 15, 18, 17, 12, 1, 7, 2, 22, 13, 21, 10, 14, 9, 3, 16, 20, 19, 11, 4, 8, 5,
+    ],
+    [
+        15, 14, 18, 17, 10, 20, 16, 11, 1, 8, 4, 22, 5, 12, 3, 9, 21, 2, 13, 6, 19, 7,
+    ],
+    [
+        1, 7, 16, 17, 18, 22, 12, 6, 8, 9, 11, 4, 2, 5, 20, 21, 13, 10, 19, 3, 14, 15,
+    ],
+    [
+        21, 17, 7, 3, 1, 10, 12, 22, 9, 16, 6, 11, 2, 4, 5, 14, 8, 20, 13, 18, 15, 19,
+    ],
+    [
+        2, 9, 5, 4, 18, 1, 20, 15, 16, 17, 7, 21, 13, 14, 19, 8, 22, 11, 10, 3, 12, 6,
+    ],
+    [
+        16, 9, 17, 8, 14, 11, 10, 12, 6, 21, 7, 3, 15, 5, 22, 20, 1, 13, 19, 2, 4, 18,
+    ],
+    [
+        1, 3, 6, 5, 2, 16, 14, 22, 17, 20, 4, 9, 10, 11, 15, 8, 12, 19, 18, 13, 7, 21,
+    ],
+    [
+        3, 16, 5, 11, 21, 9, 2, 15, 10, 18, 17, 7, 8, 19, 14, 13, 1, 4, 22, 20, 6, 12,
+    ],
+    [
+        14, 4, 13, 5, 21, 11, 8, 6, 3, 17, 2, 20, 1, 19, 10, 9, 12, 18, 15, 7, 22, 16,
+    ],
+    [
+        4, 12, 22, 14, 5, 15, 16, 2, 8, 10, 17, 9, 21, 7, 3, 6, 13, 18, 11, 20, 19, 1,
+    ],
+    [
+        16, 15, 14, 13, 4, 22, 18, 19, 7, 1, 12, 17, 5, 10, 20, 3, 9, 21, 11, 2, 6, 8,
+    ],
+    [
+        20, 14, 21, 12, 15, 17, 4, 19, 13, 10, 11, 1, 16, 5, 18, 7, 8, 22, 9, 6, 3, 2,
+    ],
+    [
+        16, 14, 13, 2, 21, 10, 11, 4, 1, 22, 18, 12, 19, 5, 7, 8, 6, 3, 15, 20, 9, 17,
+    ],
+    [
+        18, 15, 9, 14, 12, 2, 8, 11, 22, 21, 16, 1, 6, 17, 5, 10, 19, 4, 20, 13, 3, 7,
+    ],
+    [
+        7, 3, 10, 14, 13, 21, 18, 6, 20, 4, 9, 8, 22, 15, 2, 1, 5, 12, 19, 17, 11, 16,
+    ],
+    [
+        18, 1, 13, 7, 16, 10, 14, 2, 19, 5, 21, 11, 22, 15, 8, 17, 20, 3, 4, 12, 6, 9,
+    ],
+    [
+        13, 2, 22, 5, 11, 21, 20, 14, 7, 10, 4, 9, 19, 18, 6, 3, 1, 8, 15, 12, 17, 16,
+    ],
+    [
+        14, 17, 21, 8, 2, 9, 6, 4, 5, 13, 22, 7, 15, 3, 1, 18, 16, 11, 10, 12, 20, 19,
+    ],
+    [
+        10, 22, 1, 12, 13, 18, 21, 20, 2, 14, 16, 7, 15, 3, 4, 17, 5, 19, 6, 8, 9, 11,
+    ],
+    [
+        10, 8, 9, 18, 12, 6, 1, 5, 20, 11, 17, 22, 16, 3, 13, 2, 15, 21, 14, 19, 7, 4,
+    ],
+    [
+        7, 17, 22, 5, 3, 10, 13, 18, 9, 1, 14, 15, 21, 19, 16, 12, 8, 6, 11, 20, 4, 2,
+    ],
+    [
+        2, 9, 21, 3, 4, 7, 1, 11, 16, 5, 20, 19, 18, 8, 17, 13, 10, 12, 15, 6, 14, 22,
+    ],
+    [
+        15, 12, 8, 4, 22, 13, 16, 17, 18, 3, 7, 5, 6, 1, 9, 11, 21, 10, 14, 20, 19, 2,
+    ],
+    [
+        15, 16, 2, 11, 17, 7, 5, 14, 20, 4, 21, 3, 10, 9, 12, 8, 13, 6, 18, 19, 22, 1,
+    ],
+    [
+        1, 13, 11, 3, 4, 21, 6, 14, 15, 22, 18, 9, 7, 5, 10, 20, 12, 16, 17, 8, 19, 2,
+    ],
+    [
+        14, 17, 22, 20, 8, 16, 5, 10, 1, 13, 2, 21, 12, 9, 4, 18, 3, 7, 6, 19, 15, 11,
+    ],
+    [
+        9, 17, 7, 4, 5, 13, 21, 18, 11, 3, 22, 1, 6, 16, 20, 14, 15, 10, 8, 2, 12, 19,
+    ],
+    [
+        13, 14, 5, 22, 19, 11, 9, 6, 18, 15, 8, 10, 7, 4, 17, 16, 3, 1, 12, 2, 21, 20,
+    ],
+    [
+        20, 5, 4, 14, 11, 1, 6, 16, 8, 22, 7, 3, 2, 12, 21, 19, 17, 13, 10, 15, 18, 9,
+    ],
+    [
+        3, 7, 14, 15, 6, 5, 21, 20, 18, 10, 4, 16, 19, 1, 13, 9, 8, 17, 11, 12, 22, 2,
+    ],
+    [
+        13, 15, 17, 1, 22, 11, 3, 4, 7, 20, 14, 21, 9, 8, 2, 18, 16, 6, 10, 12, 5, 19,
+    ],
+];
+
+/// Returns the query numbers of query stream `stream` in execution order
+///
+/// Stream 0 is the power test and streams 1 to S the throughput test. The
+/// specification lists 41 orders, like `qgen` the orders are reused for
+/// streams 41 and above (stream 41 has the order of stream 0).
+pub fn stream_queries(stream: usize) -> [i32; 22] {
+    PERMUTATIONS[stream % PERMUTATIONS.len()]
+}
+
+/// Returns the minimum number of query streams of the throughput test for
+/// `scale_factor` (section 5.3.4)
+pub fn minimum_streams(scale_factor: f64) -> usize {
+    const STREAMS: [(f64, usize); 10] = [
+        (1.0, 2),
+        (10.0, 3),
+        (30.0, 4),
+        (100.0, 5),
+        (300.0, 6),
+        (1000.0, 7),
+        (3000.0, 8),
+        (10000.0, 9),
+        (30000.0, 10),
+        (100000.0, 11),
+    ];
+    STREAMS
+        .iter()
+        .take_while(|(sf, _)| *sf <= scale_factor)
+        .last()
+        .map_or(STREAMS[0].1, |(_, streams)| *streams)
+}
+
+/// The timing intervals of the power test, in seconds
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowerTest {
+    /// Timing interval of each query, `query_seconds[0]` is Q1
+    pub query_seconds: [f64; 22],
+    /// Timing interval of RF1 and RF2
+    pub refresh_seconds: [f64; 2],
+}
+
+impl PowerTest {
+    /// Creates a power test from the timing intervals of each query (in query
+    /// number order, not stream order) and of RF1 and RF2
+    pub fn new(query_seconds: [f64; 22], refresh_seconds: [f64; 2]) -> Self {
+        Self {
+            query_seconds,
+            refresh_seconds,
+        }
+    }
+
+    /// Returns `Power@Size` for `scale_factor` (section 5.4.1)
+    ///
+    /// If the longest query interval is more than 1000 times the shortest,
+    /// the query intervals smaller than a thousandth of the longest are
+    /// increased to it, as required by section 5.4.1.4.
+    pub fn power(&self, scale_factor: f64) -> Result<f64, String> {
+        check_intervals(&self.query_seconds, "query")?;
+        check_intervals(&self.refresh_seconds, "refresh function")?;
+        let longest = self.query_seconds.iter().copied().fold(0.0, f64::max);
+        let log_sum: f64 = self
+            .query_seconds
+            .iter()
+            .map(|seconds| seconds.max(longest / 1000.0))
+            .chain(self.refresh_seconds.iter().copied())
+            .map(f64::ln)
+            .sum();
+        let intervals = (self.query_seconds.len() + self.refresh_seconds.len()) as f64;
+        Ok(3600.0 * scale_factor / (log_sum / intervals).exp())
+    }
+}
+
+/// The measurement interval of the throughput test
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThroughputTest {
+    /// Number of query streams (S)
+    pub streams: usize,
+    /// Measurement interval (Ts) in seconds, from the start of the first
+    /// query stream to the end of the last query or refresh stream,
+    /// whichever is later
+    pub seconds: f64,
+}
+
+impl ThroughputTest {
+    /// Creates a throughput test of `streams` query streams that ran in
+    /// `seconds`
+    pub fn new(streams: usize, seconds: f64) -> Self {
+        Self { streams, seconds }
+    }
+
+    /// Returns the measurement interval of a throughput test from the start
+    /// and end times (in seconds from any origin) of each query stream and of
+    /// the refresh stream
+    pub fn from_streams(query_streams: &[(f64, f64)], refresh_stream: (f64, f64)) -> Self {
+        let start = query_streams
+            .iter()
+            .map(|(start, _)| *start)
+            .fold(f64::INFINITY, f64::min);
+        let end = query_streams
+            .iter()
+            .map(|(_, end)| *end)
+            .fold(refresh_stream.1, f64::max);
+        Self::new(query_streams.len(), end - start)
+    }
+
+    /// Returns `Throughput@Size` for `scale_factor` (section 5.4.2)
+    pub fn throughput(&self, scale_factor: f64) -> Result<f64, String> {
+        if self.streams == 0 {
+            return Err("Invalid throughput test: no query streams".to_string());
+        }
+        check_intervals(&[self.seconds], "throughput test")?;
+        Ok(self.streams as f64 * 22.0 * 3600.0 / self.seconds * scale_factor)
+    }
+}
+
+/// The TPC-H performance metrics of a scale factor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Metrics {
+    /// `Power@Size`
+    pub power: f64,
+    /// `Throughput@Size`
+    pub throughput: f64,
+    /// `QphH@Size`, the composite query-per-hour metric
+    pub qphh: f64,
+}
+
+impl Metrics {
+    /// Computes the metrics of the power and throughput tests at
+    /// `scale_factor` (section 5.4)
+    pub fn compute(
+        scale_factor: f64,
+        power_test: &PowerTest,
+        throughput_test: &ThroughputTest,
+    ) -> Result<Self, String> {
+        let power = power_test.power(scale_factor)?;
+        let throughput = throughput_test.throughput(scale_factor)?;
+        Ok(Self {
+            power,
+            throughput,
+            qphh: (power * throughput).sqrt(),
+        })
+    }
+}
+
+/// Checks that the timing intervals are positive numbers of seconds
+fn check_intervals(seconds: &[f64], name: &str) -> Result<(), String> {
+    match seconds
+        .iter()
+        .position(|seconds| !(seconds.is_finite() && *seconds > 0.0))
+    {
+        Some(index) if seconds.len() > 1 => Err(format!(
+            "Invalid {name} {} timing interval: {}",
+            index + 1,
+            seconds[index]
+        )),
+        Some(index) => Err(format!(
+            "Invalid {name} timing interval: {}",
+            seconds[index]
+        )),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_queries() {
+        for stream in 0..PERMUTATIONS.len() {
+            let mut queries = stream_queries(stream);
+            queries.sort();
+            assert_eq!(queries.to_vec(), (1..=22).collect::<Vec<_>>(), "{stream}");
+        }
+        assert_eq!(
+            stream_queries(1),
+            [21, 3, 18, 5, 11, 7, 6, 20, 17, 12, 16, 15, 13, 10, 2, 8, 14, 19, 9, 22, 1, 4]
+        );
+        assert_eq!(stream_queries(41), stream_queries(0));
+        assert_eq!(stream_queries(45), stream_queries(4));
+    }
+
+    #[test]
+    fn test_minimum_streams() {
+        assert_eq!(minimum_streams(0.01), 2);
+        assert_eq!(minimum_streams(1.0), 2);
+        assert_eq!(minimum_streams(10.0), 3);
+        assert_eq!(minimum_streams(50.0), 4);
+        assert_eq!(minimum_streams(1000.0), 7);
+        assert_eq!(minimum_streams(1000000.0), 11);
+    }
+
+    #[test]
+    fn test_power() {
+        let mut power_test = PowerTest::new([2.0; 22], [2.0; 2]);
+        assert!((power_test.power(10.0).unwrap() - 18000.0).abs() < 1e-6);
+
+        // geometric mean of 23 intervals of 1 second and one of 2^24 seconds
+        power_test.query_seconds = [1.0; 22];
+        power_test.refresh_seconds = [1.0, 16777216.0];
+        assert!((power_test.power(1.0).unwrap() - 1800.0).abs() < 1e-6);
+
+        // query intervals shorter than a thousandth of the longest are increased
+        power_test.query_seconds = [0.001; 22];
+        power_test.query_seconds[0] = 1000.0;
+        power_test.refresh_seconds = [1.0; 2];
+        let expected = 3600.0 / 1000f64.powf(1.0 / 24.0);
+        assert!((power_test.power(1.0).unwrap() - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_throughput() {
+        assert_eq!(ThroughputTest::new(4, 3600.0).throughput(100.0), Ok(8800.0));
+        let throughput_test =
+            ThroughputTest::from_streams(&[(10.0, 50.0), (12.0, 70.0)], (10.0, 90.0));
+        assert_eq!(throughput_test, ThroughputTest::new(2, 80.0));
+    }
+
+    #[test]
+    fn test_invalid_timings() {
+        let mut query_seconds = [1.0; 22];
+        query_seconds[4] = 0.0;
+        assert_eq!(
+            PowerTest::new(query_seconds, [1.0; 2]).power(1.0),
+            Err("Invalid query 5 timing interval: 0".to_string())
+        );
+        assert_eq!(
+            PowerTest::new([1.0; 22], [1.0, f64::NAN]).power(1.0),
+            Err("Invalid refresh function 2 timing interval: NaN".to_string())
+        );
+        assert_eq!(
+            ThroughputTest::new(0, 1.0).throughput(1.0),
+            Err("Invalid throughput test: no query streams".to_string())
+        );
+        assert_eq!(
+            ThroughputTest::new(2, -1.0).throughput(1.0),
+            Err("Invalid throughput test timing interval: -1".to_string())
+        );
+    }
+}
@@ -3,33 +3,61 @@
 //! This module exposes a bundled query and answer tuple that makes it
 //! easier to work with them in benchmark contexts.
 pub mod answers_sf1;
+pub mod dialect;
+pub mod evaluator;
+pub mod metrics;
+pub mod parameters;
 pub mod queries;
+pub mod validation;
+
+use evaluator::QueryEvaluator;
+use parameters::QueryParameters;
+use std::borrow::Cow;
 
 /// QueryAndAnswer is a struct that contains a TPC-H query and its expected answer.
+///
+/// The query is substituted with the validation parameters of the TPC-H
+/// specification (see [`QueryParameters::validation`]), which were used to
+/// produce the answers.
 pub struct QueryAndAnswer(
-    &'static str, // The TPC-H query as a string
-    &'static str, // The expected answer as a string
+    String,            // The TPC-H query as a string
+    Cow<'static, str>, // The expected answer as a string
 );
 
 impl QueryAndAnswer {
     /// Creates a new QueryAndAnswer instance.
+    ///
+    /// See [`Self::compute`] for the answers at other scale factors.
     pub fn new(num: i32, scale_factor: f64) -> Result<Self, String> {
         match (num, scale_factor) {
             (1..=22, 1.) => Ok(QueryAndAnswer(
-                queries::query(num).unwrap(),
-                answers_sf1::answer(num).unwrap(),
+                QueryParameters::validation(num)?.query(),
+                Cow::Borrowed(answers_sf1::answer(num).unwrap()),
             )),
             _ => Err(format!("Invalid TPC-H query number: {} the answers are only available for queries (1 to 22) and a scale factor of 1.0", num)),
         }
     }
 
+    /// Creates a new QueryAndAnswer instance for any scale factor, computing
+    /// the answer from the generated data with a [`QueryEvaluator`]
+    ///
+    /// The query is substituted with the validation parameters for the scale
+    /// factor (see [`QueryParameters::validation_for_scale_factor`]). This
+    /// generates the tables the query reads, which takes about as long as
+    /// generating them with `tpchgen-cli`.
+    pub fn compute(num: i32, scale_factor: f64) -> Result<Self, String> {
+        let parameters = QueryParameters::validation_for_scale_factor(num, scale_factor)?;
+        let answer = QueryEvaluator::new(scale_factor).answer(&parameters)?;
+        Ok(QueryAndAnswer(parameters.query(), Cow::Owned(answer)))
+    }
+
     /// Returns the query string.
     pub fn query(&self) -> &str {
-        self.0
+        &self.0
     }
 
     /// Returns the expected answer string.
     pub fn answer(&self) -> &str {
-        self.1
+        &self.1
     }
 }
@@ -0,0 +1,646 @@
+//! TPC-H query parameter substitution.
+//!
+//! The queries in [`queries`] contain `:1`, `:2`, ... placeholders for their
+//! substitution parameters. This module generates values for these parameters
+//! following the rules of the TPC-H specification (section 2.4), similarly to
+//! the `qgen` tool of the TPC-H Tools distribution.
+//!
+//! # Example
+//! ```
+//! # use tpchgen::q_and_a::parameters::{QueryParameterGenerator, QueryParameters};
+//! // The validation parameters of the specification
+//! let parameters = QueryParameters::validation(13).unwrap();
+//! assert_eq!(parameters.values(), &["special", "requests"]);
+//! assert!(parameters.query().contains("o_comment not like '%special%requests%'"));
+//!
+//! // Random parameters for a given seed
+//! let generator = QueryParameterGenerator::new(1.0, 42);
+//! let parameters = generator.generate(13).unwrap();
+//! assert_eq!(parameters.values().len(), 2);
+//! assert!(!parameters.query().contains(":1"));
+//! ```
+//!
+//! [`queries`]: crate::q_and_a::queries
+
+use crate::dates::{DateRange, TPCHDate};
+use crate::distribution::{Distribution, Distributions};
+use crate::q_and_a::queries;
+use crate::random::RowRandomInt;
+
+/// The values substituted in the placeholders of a TPC-H query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryParameters {
+    query_number: i32,
+    stream: i32,
+    values: Vec<String>,
+    date_range: DateRange,
+}
+
+impl QueryParameters {
+    /// Creates the parameters for query `query_number` from the given values
+    ///
+    /// `values[0]` is substituted for `:1`, `values[1]` for `:2`, and so on.
+    pub fn new(query_number: i32, values: Vec<String>) -> Result<Self, String> {
+        if !(1..=22).contains(&query_number) {
+            return Err(format!(
+                "Invalid TPC-H query number: {query_number}, expected 1 to 22"
+            ));
+        }
+        Ok(Self {
+            query_number,
+            stream: 0,
+            values,
+            date_range: DateRange::default(),
+        })
+    }
+
+    /// Returns the validation parameters of the TPC-H specification
+    ///
+    /// The answers in [`answers_sf1`] were produced with these parameters.
+    ///
+    /// [`answers_sf1`]: crate::q_and_a::answers_sf1
+    pub fn validation(query_number: i32) -> Result<Self, String> {
+        let values: &[&str] = match query_number {
+            1 => &["90"],
+            2 => &["15", "BRASS", "EUROPE"],
+            3 => &["BUILDING", "1995-03-15"],
+            4 => &["1993-07-01"],
+            5 => &["ASIA", "1994-01-01"],
+            6 => &["1994-01-01", "0.06", "24"],
+            7 => &["FRANCE", "GERMANY"],
+            8 => &["BRAZIL", "AMERICA", "ECONOMY ANODIZED STEEL"],
+            9 => &["green"],
+            10 => &["1993-10-01"],
+            11 => &["GERMANY", "0.0001"],
+            12 => &["MAIL", "SHIP", "1994-01-01"],
+            13 => &["special", "requests"],
+            14 => &["1995-09-01"],
+            15 => &["1996-01-01"],
+            16 => &[
+                "Brand#45",
+                "MEDIUM POLISHED",
+                "49",
+                "14",
+                "23",
+                "45",
+                "19",
+                "3",
+                "36",
+                "9",
+            ],
+            17 => &["Brand#23", "MED BOX"],
+            18 => &["300"],
+            19 => &["Brand#12", "Brand#23", "Brand#34", "1", "10", "20"],
+            20 => &["forest", "1994-01-01", "CANADA"],
+            21 => &["SAUDI ARABIA"],
+            22 => &["13", "31", "23", "29", "30", "18", "17"],
+            _ => {
+                return Err(format!(
+                    "Invalid TPC-H query number: {query_number}, expected 1 to 22"
+                ))
+            }
+        };
+        Self::new(
+            query_number,
+            values.iter().map(|value| value.to_string()).collect(),
+        )
+    }
+
+    /// Returns the validation parameters of the TPC-H specification for the
+    /// data of `scale_factor`
+    ///
+    /// These are the parameters of [`Self::validation`], except for the
+    /// `FRACTION` of Q11 which is `0.0001 / SF` (section 2.4.11.3).
+    pub fn validation_for_scale_factor(
+        query_number: i32,
+        scale_factor: f64,
+    ) -> Result<Self, String> {
+        let mut parameters = Self::validation(query_number)?;
+        if query_number == 11 {
+            parameters.values[1] = (0.0001 / scale_factor).to_string();
+        }
+        Ok(parameters)
+    }
+
+    /// Sets the stream number substituted for `:s` (the view name suffix in Q15)
+    pub fn with_stream(mut self, stream: i32) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    /// Moves the dates of the query to the data generated with `date_range`
+    /// (see [`OrderGenerator::with_date_range`])
+    ///
+    /// The date values, and the dates of the query text (e.g. `1998-12-01`
+    /// in Q1), are moved by [`DateRange::year_shift`] years, so that they
+    /// have the same position relative to the current date of the range.
+    /// The current date is in the middle year of the range, so with a range
+    /// longer than the default 7 years, the dates near the end of the default
+    /// range are moved before the end of the range (with 2000-2009, the Q1
+    /// cutoff is `2007-12-01`, not `2009-12-01`).
+    ///
+    /// ```
+    /// # use tpchgen::dates::DateRange;
+    /// # use tpchgen::q_and_a::parameters::QueryParameters;
+    /// let date_range = DateRange::new(2019, 2025).unwrap();
+    /// let parameters = QueryParameters::validation(1).unwrap().with_date_range(date_range);
+    /// assert!(parameters.query().contains("date '2025-12-01' - interval '90' day"));
+    /// let parameters = QueryParameters::validation(3).unwrap().with_date_range(date_range);
+    /// assert_eq!(parameters.values(), &["BUILDING", "2022-03-15"]);
+    ///
+    /// let date_range = DateRange::new(2000, 2009).unwrap();
+    /// let parameters = QueryParameters::validation(1).unwrap().with_date_range(date_range);
+    /// assert!(parameters.query().contains("date '2007-12-01' - interval '90' day"));
+    /// ```
+    ///
+    /// [`OrderGenerator::with_date_range`]: crate::generators::OrderGenerator::with_date_range
+    pub fn with_date_range(mut self, date_range: DateRange) -> Self {
+        let years = date_range.year_shift() - self.date_range.year_shift();
+        for value in &mut self.values {
+            if let Ok(date) = value.parse::<TPCHDate>() {
+                *value = date.add_years(years).to_string();
+            }
+        }
+        self.date_range = date_range;
+        self
+    }
+
+    /// Returns the query number (1 to 22)
+    pub fn query_number(&self) -> i32 {
+        self.query_number
+    }
+
+    /// Returns the stream number substituted for `:s`
+    pub fn stream(&self) -> i32 {
+        self.stream
+    }
+
+    /// Returns the substituted values, `values()[0]` is the value of `:1`
+    pub fn values(&self) -> &[String] {
+        &self.values
+    }
+
+    /// Returns the date range of the data queried, see [`Self::with_date_range`]
+    pub fn date_range(&self) -> DateRange {
+        self.date_range
+    }
+
+    /// Returns the query text with all placeholders substituted
+    pub fn query(&self) -> String {
+        let template = queries::query(self.query_number).expect("query number is validated");
+        self.substitute_template(template)
+    }
+
+    /// Substitutes the placeholders of `template` with the values, after
+    /// moving its literal dates to the date range
+    pub(crate) fn substitute_template(&self, template: &str) -> String {
+        let years = self.date_range.year_shift();
+        if years == 0 {
+            return substitute(template, &self.values, self.stream);
+        }
+        let template =
+            rewrite_literals(template, "date '", |date| match date.parse::<TPCHDate>() {
+                Ok(date) => format!("date '{}'", date.add_years(years)),
+                // a placeholder
+                Err(_) => format!("date '{date}'"),
+            });
+        substitute(&template, &self.values, self.stream)
+    }
+}
+
+/// Substitutes the `:1`, `:2`, ... placeholders of `template` with `values`
+/// and `:s` with `stream`
+///
+/// Placeholders without a corresponding value are left unchanged.
+///
+/// ```
+/// # use tpchgen::q_and_a::parameters::substitute;
+/// let values = vec!["a".to_string(), "b".to_string()];
+/// assert_eq!(substitute("x = ':1' or y = :2 or z = :3", &values, 0), "x = 'a' or y = b or z = :3");
+/// assert_eq!(substitute("create view revenue:s", &values, 1), "create view revenue1");
+/// ```
+pub fn substitute(template: &str, values: &[String], stream: i32) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(pos) = rest.find(':') {
+        result.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        let digits = after.bytes().take_while(u8::is_ascii_digit).count();
+        if digits > 0 {
+            let value = after[..digits]
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .and_then(|index| values.get(index));
+            match value {
+                Some(value) => result.push_str(value),
+                None => result.push_str(&rest[pos..pos + 1 + digits]),
+            }
+            rest = &after[digits..];
+        } else if let Some(after_stream) = after.strip_prefix('s') {
+            result.push_str(&stream.to_string());
+            rest = after_stream;
+        } else {
+            result.push(':');
+            rest = after;
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Replaces the `<prefix>value'` literals of `query`, where `prefix` starts
+/// with a keyword, with `rewrite(value)`
+pub(crate) fn rewrite_literals(
+    query: &str,
+    prefix: &str,
+    rewrite: impl Fn(&str) -> String,
+) -> String {
+    let mut result = String::with_capacity(query.len());
+    let mut rest = query;
+    while let Some(pos) = rest.find(prefix) {
+        let keyword = !rest[..pos]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_');
+        let after = &rest[pos + prefix.len()..];
+        match after.find('\'') {
+            Some(end) if keyword => {
+                result.push_str(&rest[..pos]);
+                result.push_str(&rewrite(&after[..end]));
+                rest = &after[end + 1..];
+            }
+            _ => {
+                result.push_str(&rest[..pos + prefix.len()]);
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Generates random query parameters following the TPC-H specification rules
+///
+/// The parameters of each query only depend on the seed and scale factor, so
+/// the same seed always produces the same queries.
+#[derive(Debug, Clone)]
+pub struct QueryParameterGenerator<'a> {
+    scale_factor: f64,
+    seed: i64,
+    distributions: &'a Distributions,
+    date_range: DateRange,
+}
+
+impl<'a> QueryParameterGenerator<'a> {
+    /// The syllables of the part types substituted in Q2
+    const Q2_TYPE_SYLLABLES: [&'static str; 5] = ["TIN", "NICKEL", "BRASS", "STEEL", "COPPER"];
+
+    /// Creates a new QueryParameterGenerator with the given scale factor and seed
+    pub fn new(scale_factor: f64, seed: i64) -> QueryParameterGenerator<'static> {
+        QueryParameterGenerator::new_with_distributions(
+            scale_factor,
+            seed,
+            Distributions::static_default(),
+        )
+    }
+
+    /// Creates a QueryParameterGenerator with the specified distributions
+    pub fn new_with_distributions<'b>(
+        scale_factor: f64,
+        seed: i64,
+        distributions: &'b Distributions,
+    ) -> QueryParameterGenerator<'b> {
+        QueryParameterGenerator {
+            scale_factor,
+            seed,
+            distributions,
+            date_range: DateRange::default(),
+        }
+    }
+
+    /// Generates the parameters for the data generated with `date_range`,
+    /// see [`QueryParameters::with_date_range`]
+    pub fn with_date_range(mut self, date_range: DateRange) -> Self {
+        self.date_range = date_range;
+        self
+    }
+
+    /// Generates random parameters for query `query_number` (1 to 22)
+    pub fn generate(&self, query_number: i32) -> Result<QueryParameters, String> {
+        if !(1..=22).contains(&query_number) {
+            return Err(format!(
+                "Invalid TPC-H query number: {query_number}, expected 1 to 22"
+            ));
+        }
+
+        let mut random = RowRandomInt::new(self.query_seed(query_number), 1);
+        let random = &mut random;
+        let distributions = self.distributions;
+
+        let values = match query_number {
+            1 => vec![random.next_int(60, 120).to_string()],
+            2 => vec![
+                random.next_int(1, 50).to_string(),
+                Self::pick(random, &Self::Q2_TYPE_SYLLABLES).to_string(),
+                distributions.regions().random_value(random).to_string(),
+            ],
+            3 => vec![
+                distributions
+                    .market_segments()
+                    .random_value(random)
+                    .to_string(),
+                format!("1995-03-{:02}", random.next_int(1, 31)),
+            ],
+            4 => vec![Self::first_of_month(random, 1993, 1, 58)],
+            5 => vec![
+                distributions.regions().random_value(random).to_string(),
+                Self::first_of_year(random),
+            ],
+            6 => vec![
+                Self::first_of_year(random),
+                format!("0.{:02}", random.next_int(2, 9)),
+                random.next_int(24, 25).to_string(),
+            ],
+            7 => {
+                let (nation1, nation2) = self.distinct_nations(random);
+                vec![nation1, nation2]
+            }
+            8 => {
+                let nations = distributions.nations();
+                let index = random.next_int(0, nations.size() as i32 - 1) as usize;
+                let region_key = nations.get_weight(index) as usize;
+                vec![
+                    nations.get_value(index).to_string(),
+                    distributions.regions().get_value(region_key).to_string(),
+                    distributions.part_types().random_value(random).to_string(),
+                ]
+            }
+            9 => vec![distributions.part_colors().random_value(random).to_string()],
+            10 => vec![Self::first_of_month(random, 1993, 2, 24)],
+            11 => vec![
+                self.random_nation(random),
+                (0.0001 / self.scale_factor).to_string(),
+            ],
+            12 => {
+                let ship_modes = distributions.ship_modes();
+                let mode1 = ship_modes.random_value(random);
+                let mut mode2 = ship_modes.random_value(random);
+                while mode2 == mode1 {
+                    mode2 = ship_modes.random_value(random);
+                }
+                vec![
+                    mode1.to_string(),
+                    mode2.to_string(),
+                    Self::first_of_year(random),
+                ]
+            }
+            13 => vec![
+                distributions.q13a().random_value(random).to_string(),
+                distributions.q13b().random_value(random).to_string(),
+            ],
+            14 => vec![Self::first_of_month(random, 1993, 1, 60)],
+            15 => vec![Self::first_of_month(random, 1993, 1, 58)],
+            16 => {
+                let part_type = distributions.part_types().random_value(random);
+                // only the first two syllables of the type are used
+                let syllables: Vec<_> = part_type.split(' ').take(2).collect();
+                let mut values = vec![Self::random_brand(random), syllables.join(" ")];
+                let mut sizes: Vec<i32> = Vec::with_capacity(8);
+                while sizes.len() < 8 {
+                    let size = random.next_int(1, 50);
+                    if !sizes.contains(&size) {
+                        sizes.push(size);
+                    }
+                }
+                values.extend(sizes.iter().map(|size| size.to_string()));
+                values
+            }
+            17 => vec![
+                Self::random_brand(random),
+                distributions
+                    .part_containers()
+                    .random_value(random)
+                    .to_string(),
+            ],
+            18 => vec![random.next_int(312, 315).to_string()],
+            19 => vec![
+                Self::random_brand(random),
+                Self::random_brand(random),
+                Self::random_brand(random),
+                random.next_int(1, 10).to_string(),
+                random.next_int(10, 20).to_string(),
+                random.next_int(20, 30).to_string(),
+            ],
+            20 => vec![
+                distributions.part_colors().random_value(random).to_string(),
+                Self::first_of_year(random),
+                self.random_nation(random),
+            ],
+            21 => vec![self.random_nation(random)],
+            22 => {
+                // country codes are the nation keys + 10
+                let mut codes: Vec<i32> = Vec::with_capacity(7);
+                let nation_count = distributions.nations().size() as i32;
+                while codes.len() < 7 {
+                    let code = random.next_int(10, 10 + nation_count - 1);
+                    if !codes.contains(&code) {
+                        codes.push(code);
+                    }
+                }
+                codes.iter().map(|code| code.to_string()).collect()
+            }
+            _ => unreachable!("query number is validated"),
+        };
+
+        Ok(QueryParameters::new(query_number, values)?.with_date_range(self.date_range))
+    }
+
+    /// Returns the seed of the random stream for `query_number`
+    ///
+    /// The user provided seed is mixed so that nearby seeds produce unrelated
+    /// parameters, then each query is given its own stream.
+    fn query_seed(&self, query_number: i32) -> i64 {
+        const MODULUS: i64 = 2147483647;
+        let mut mixed = (self.seed as u64).wrapping_add(0x9E3779B97F4A7C15);
+        mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94D049BB133111EB);
+        mixed ^= mixed >> 31;
+        let seed = (mixed % (MODULUS as u64 - 1)) as i64 + 1;
+        // seeds must be in the range [1, MODULUS - 1]
+        (seed + query_number as i64 * (MODULUS / 799)) % (MODULUS - 1) + 1
+    }
+
+    /// Picks a uniformly distributed value from `values`
+    fn pick<'b>(random: &mut RowRandomInt, values: &'b [impl AsRef<str>]) -> &'b str {
+        values[random.next_int(0, values.len() as i32 - 1) as usize].as_ref()
+    }
+
+    /// Returns a random nation name
+    fn random_nation(&self, random: &mut RowRandomInt) -> String {
+        let nations: &Distribution = self.distributions.nations();
+        Self::pick(random, nations.get_values()).to_string()
+    }
+
+    /// Returns two different random nation names
+    fn distinct_nations(&self, random: &mut RowRandomInt) -> (String, String) {
+        let nation1 = self.random_nation(random);
+        let mut nation2 = self.random_nation(random);
+        while nation2 == nation1 {
+            nation2 = self.random_nation(random);
+        }
+        (nation1, nation2)
+    }
+
+    /// Returns a random `Brand#MN` with `M` and `N` between 1 and 5
+    fn random_brand(random: &mut RowRandomInt) -> String {
+        format!("Brand#{}{}", random.next_int(1, 5), random.next_int(1, 5))
+    }
+
+    /// Returns January 1st of a random year between 1993 and 1997
+    fn first_of_year(random: &mut RowRandomInt) -> String {
+        format!("{}-01-01", random.next_int(1993, 1997))
+    }
+
+    /// Returns the first day of one of the `month_count` months starting
+    /// at `year`-`month`
+    fn first_of_month(
+        random: &mut RowRandomInt,
+        year: i32,
+        month: i32,
+        month_count: i32,
+    ) -> String {
+        let months = (month - 1) + random.next_int(0, month_count - 1);
+        format!("{}-{:02}-01", year + months / 12, months % 12 + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_parameters() {
+        for query_number in 1..=22 {
+            let parameters = QueryParameters::validation(query_number).unwrap();
+            let query = parameters.query();
+            assert!(
+                !query.contains(":1") && !query.contains(":s"),
+                "unsubstituted placeholder in Q{query_number}: {query}"
+            );
+        }
+        assert!(QueryParameters::validation(0).is_err());
+        assert!(QueryParameters::validation(23).is_err());
+
+        // `:10` is not substituted as `:1` followed by `0`
+        let query = QueryParameters::validation(16).unwrap().query();
+        assert!(query.contains("p_size in (49, 14, 23, 45, 19, 3, 36, 9)"));
+
+        // Q11's FRACTION depends on the scale factor
+        let q11 = QueryParameters::validation_for_scale_factor(11, 10.0).unwrap();
+        assert_eq!(q11.values(), &["GERMANY", "0.00001"]);
+        let q1 = QueryParameters::validation_for_scale_factor(1, 10.0).unwrap();
+        assert_eq!(q1, QueryParameters::validation(1).unwrap());
+    }
+
+    #[test]
+    fn test_date_range() {
+        let date_range = DateRange::new(2019, 2025).unwrap();
+        for query_number in 1..=22 {
+            let parameters = QueryParameters::validation(query_number).unwrap();
+            let shifted = parameters.clone().with_date_range(date_range);
+            assert!(
+                !shifted.query().contains("'199"),
+                "date not moved in Q{query_number}: {}",
+                shifted.query()
+            );
+            assert_eq!(shifted.with_date_range(DateRange::default()), parameters);
+        }
+        let q7 = QueryParameters::validation(7)
+            .unwrap()
+            .with_date_range(date_range);
+        assert!(q7
+            .query()
+            .contains("l_shipdate between date '2022-01-01' and date '2023-12-31'"));
+
+        // the generated dates are moved by 27 years
+        let generator = QueryParameterGenerator::new(1.0, 7);
+        let shifted = generator.clone().with_date_range(date_range);
+        for query_number in [4, 5, 10, 14] {
+            let date = |parameters: QueryParameters| {
+                parameters
+                    .values()
+                    .iter()
+                    .find_map(|value| value.parse::<TPCHDate>().ok())
+                    .unwrap()
+            };
+            assert_eq!(
+                date(shifted.generate(query_number).unwrap()),
+                date(generator.generate(query_number).unwrap()).add_years(27)
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_is_deterministic() {
+        let generator = QueryParameterGenerator::new(1.0, 7);
+        for query_number in 1..=22 {
+            let parameters = generator.generate(query_number).unwrap();
+            assert_eq!(parameters, generator.generate(query_number).unwrap());
+            assert_eq!(
+                parameters.values().len(),
+                QueryParameters::validation(query_number)
+                    .unwrap()
+                    .values()
+                    .len(),
+                "wrong number of parameters for Q{query_number}"
+            );
+        }
+        assert!(generator.generate(23).is_err());
+
+        // Different seeds produce different parameters
+        let other = QueryParameterGenerator::new(1.0, 8);
+        assert!((1..=22).any(|q| generator.generate(q) != other.generate(q)));
+    }
+
+    #[test]
+    fn test_generate_ranges() {
+        for seed in 0..50 {
+            let generator = QueryParameterGenerator::new(10.0, seed);
+
+            let delta: i32 = generator.generate(1).unwrap().values()[0].parse().unwrap();
+            assert!((60..=120).contains(&delta));
+
+            let q4 = generator.generate(4).unwrap();
+            assert!(q4.values()[0].as_str() >= "1993-01-01");
+            assert!(q4.values()[0].as_str() <= "1997-10-01");
+
+            let q8 = generator.generate(8).unwrap();
+            let nations = Distributions::static_default().nations();
+            let index = nations
+                .get_values()
+                .iter()
+                .position(|n| *n == q8.values()[0])
+                .unwrap();
+            let region = Distributions::static_default()
+                .regions()
+                .get_value(nations.get_weight(index) as usize);
+            assert_eq!(q8.values()[1], region);
+
+            assert_eq!(generator.generate(11).unwrap().values()[1], "0.00001");
+
+            let q13 = generator.generate(13).unwrap();
+            assert!(
+                ["special", "pending", "unusual", "express"].contains(&q13.values()[0].as_str())
+            );
+            assert!(["packages", "requests", "accounts", "deposits"]
+                .contains(&q13.values()[1].as_str()));
+
+            let q22 = generator.generate(22).unwrap();
+            let mut codes = q22.values().to_vec();
+            codes.sort();
+            codes.dedup();
+            assert_eq!(codes.len(), 7);
+        }
+    }
+}
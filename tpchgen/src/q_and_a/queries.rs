@@ -444,6 +444,41 @@ order by
 
 drop view revenue:s;"#;
 
+/// TPC-H Top Supplier Query (Q15), approved variant with a common table
+/// expression instead of a view.
+pub const Q15_CTE: &str = r#"
+with revenue:s (supplier_no, total_revenue) as (
+	select
+		l_suppkey,
+		sum(l_extendedprice * (1 - l_discount))
+	from
+		lineitem
+	where
+		l_shipdate >= date ':1'
+		and l_shipdate < date ':1' + interval '3' month
+	group by
+		l_suppkey
+)
+select
+	s_suppkey,
+	s_name,
+	s_address,
+	s_phone,
+	total_revenue
+from
+	supplier,
+	revenue:s
+where
+	s_suppkey = supplier_no
+	and total_revenue = (
+		select
+			max(total_revenue)
+		from
+			revenue:s
+	)
+order by
+	s_suppkey;"#;
+
 /// TPC-H Parts/Supplier Relationship Query (Q16).
 pub const Q16: &str = r#"
 select
@@ -720,3 +755,16 @@ pub fn query(num: i32) -> Option<&'static str> {
         _ => None,
     }
 }
+
+/// Returns the maximum number of rows output by the TPC-H query (the `:n`
+/// directive of the `qgen` query templates), if any.
+pub fn row_limit(num: i32) -> Option<usize> {
+    match num {
+        2 => Some(100),
+        3 => Some(10),
+        10 => Some(20),
+        18 => Some(100),
+        21 => Some(100),
+        _ => None,
+    }
+}
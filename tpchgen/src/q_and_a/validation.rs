@@ -0,0 +1,794 @@
+//! Typed answers of the TPC-H queries and their validation.
+//!
+//! [`Answer::parse`] parses the result of a query, e.g. a CSV file exported
+//! from a query engine or one of the [`answers_sf1`], into typed values using
+//! the [`AnswerSchema`] of the query. [`Answer::compare`] then checks a result
+//! against the expected answer with the precision rules of the TPC-H
+//! specification for the query validation:
+//!
+//! * identifiers, counts, strings, dates and singleton values (e.g.
+//!   `s_acctbal`) must match exactly
+//! * results of `SUM` aggregates must be within 100 of the expected value
+//! * results of `AVG` aggregates and ratios must be within 1% of the expected
+//!   value
+//!
+//! Decimal values are rounded to the nearest 1/100th before being compared.
+//! The rows must be in the order of the `order by` clause of the query, but
+//! rows with equal `order by` values (ties) can be in any order. With a
+//! `limit`, the tied rows at the end of the answer can be any of the tied rows.
+//!
+//! # Example
+//! ```
+//! # use tpchgen::q_and_a::answers_sf1;
+//! # use tpchgen::q_and_a::validation::{Answer, Difference};
+//! let expected = Answer::parse(6, answers_sf1::Q6_ANSWER, '|').unwrap();
+//!
+//! // a SUM may be off by up to 100
+//! let actual = Answer::parse(6, "revenue\n123141078.2283\n", ',').unwrap();
+//! assert!(expected.compare(&actual).is_empty());
+//!
+//! let actual = Answer::parse(6, "revenue\n123141278.23\n", ',').unwrap();
+//! let differences = expected.compare(&actual);
+//! assert!(matches!(differences[0], Difference::Value { row: 0, column: "revenue", .. }));
+//! ```
+//!
+//! [`answers_sf1`]: crate::q_and_a::answers_sf1
+
+use crate::q_and_a::evaluator::Date;
+use std::fmt::{Display, Formatter};
+
+/// The type of the values of a column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Integer,
+    Decimal,
+    /// `YYYY-MM-DD`
+    Date,
+    Text,
+}
+
+/// How close a value must be to the expected value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    /// Identifiers, counts, strings, dates and singleton values
+    Exact,
+    /// Results of `SUM` aggregates: within 100
+    Sum,
+    /// Results of `AVG` aggregates and ratios: within 1%
+    Relative,
+}
+
+/// A column of the answer of a query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+    /// The name of the column in [`answers_sf1`](crate::q_and_a::answers_sf1)
+    pub name: &'static str,
+    pub column_type: ColumnType,
+    pub precision: Precision,
+}
+
+impl Column {
+    const fn new(name: &'static str, column_type: ColumnType, precision: Precision) -> Self {
+        Self {
+            name,
+            column_type,
+            precision,
+        }
+    }
+
+    const fn integer(name: &'static str) -> Self {
+        Self::new(name, ColumnType::Integer, Precision::Exact)
+    }
+
+    const fn decimal(name: &'static str, precision: Precision) -> Self {
+        Self::new(name, ColumnType::Decimal, precision)
+    }
+
+    const fn date(name: &'static str) -> Self {
+        Self::new(name, ColumnType::Date, Precision::Exact)
+    }
+
+    const fn text(name: &'static str) -> Self {
+        Self::new(name, ColumnType::Text, Precision::Exact)
+    }
+}
+
+/// The columns, order and limit of the answer of a query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnswerSchema {
+    columns: &'static [Column],
+    order_by: &'static [usize],
+    limit: Option<usize>,
+}
+
+impl AnswerSchema {
+    /// Returns the schema of the answer of query `query_number` (1 to 22)
+    pub fn new(query_number: i32) -> Result<&'static Self, String> {
+        usize::try_from(query_number - 1)
+            .ok()
+            .and_then(|index| SCHEMAS.get(index))
+            .ok_or_else(|| format!("Invalid TPC-H query number: {query_number}, expected 1 to 22"))
+    }
+
+    /// Returns the columns of the answer
+    pub fn columns(&self) -> &'static [Column] {
+        self.columns
+    }
+
+    /// Returns the indexes of the columns of the `order by` clause
+    pub fn order_by(&self) -> &'static [usize] {
+        self.order_by
+    }
+
+    /// Returns the maximum number of rows of the answer (`limit`), if any
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+}
+
+const fn schema(
+    columns: &'static [Column],
+    order_by: &'static [usize],
+    limit: Option<usize>,
+) -> AnswerSchema {
+    AnswerSchema {
+        columns,
+        order_by,
+        limit,
+    }
+}
+
+use Precision::{Exact, Relative, Sum};
+
+static SCHEMAS: [AnswerSchema; 22] = [
+    // Q1
+    schema(
+        &[
+            Column::text("l_returnflag"),
+            Column::text("l_linestatus"),
+            Column::decimal("sum_qty", Sum),
+            Column::decimal("sum_base_price", Sum),
+            Column::decimal("sum_disc_price", Sum),
+            Column::decimal("sum_charge", Sum),
+            Column::decimal("avg_qty", Relative),
+            Column::decimal("avg_price", Relative),
+            Column::decimal("avg_disc", Relative),
+            Column::integer("count_order"),
+        ],
+        &[0, 1],
+        None,
+    ),
+    // Q2
+    schema(
+        &[
+            Column::decimal("s_acctbal", Exact),
+            Column::text("s_name"),
+            Column::text("n_name"),
+            Column::integer("p_partkey"),
+            Column::text("p_mfgr"),
+            Column::text("s_address"),
+            Column::text("s_phone"),
+            Column::text("s_comment"),
+        ],
+        &[0, 2, 1, 3],
+        Some(100),
+    ),
+    // Q3
+    schema(
+        &[
+            Column::integer("l_orderkey"),
+            Column::decimal("revenue", Sum),
+            Column::date("o_orderdat"),
+            Column::integer("o_shippriority"),
+        ],
+        &[1, 2],
+        Some(10),
+    ),
+    // Q4
+    schema(
+        &[
+            Column::text("o_orderpriority"),
+            Column::integer("order_count"),
+        ],
+        &[0],
+        None,
+    ),
+    // Q5
+    schema(
+        &[Column::text("n_name"), Column::decimal("revenue", Sum)],
+        &[1],
+        None,
+    ),
+    // Q6
+    schema(&[Column::decimal("revenue", Sum)], &[], None),
+    // Q7
+    schema(
+        &[
+            Column::text("supp_nation"),
+            Column::text("cust_nation"),
+            Column::integer("l_year"),
+            Column::decimal("revenue", Sum),
+        ],
+        &[0, 1, 2],
+        None,
+    ),
+    // Q8
+    schema(
+        &[
+            Column::integer("o_year"),
+            Column::decimal("mkt_share", Relative),
+        ],
+        &[0],
+        None,
+    ),
+    // Q9
+    schema(
+        &[
+            Column::text("nation"),
+            Column::integer("o_year"),
+            Column::decimal("sum_profit", Sum),
+        ],
+        &[0, 1],
+        None,
+    ),
+    // Q10
+    schema(
+        &[
+            Column::integer("c_custkey"),
+            Column::text("c_name"),
+            Column::decimal("revenue", Sum),
+            Column::decimal("c_acctbal", Exact),
+            Column::text("n_name"),
+            Column::text("c_address"),
+            Column::text("c_phone"),
+            Column::text("c_comment"),
+        ],
+        &[2],
+        Some(20),
+    ),
+    // Q11
+    schema(
+        &[Column::integer("ps_partkey"), Column::decimal("value", Sum)],
+        &[1],
+        None,
+    ),
+    // Q12
+    schema(
+        &[
+            Column::text("l_shipmode"),
+            Column::integer("high_line_count"),
+            Column::integer("low_line_count"),
+        ],
+        &[0],
+        None,
+    ),
+    // Q13
+    schema(
+        &[Column::integer("c_count"), Column::integer("custdist")],
+        &[1, 0],
+        None,
+    ),
+    // Q14
+    schema(&[Column::decimal("promo_revenue", Relative)], &[], None),
+    // Q15
+    schema(
+        &[
+            Column::integer("s_suppkey"),
+            Column::text("s_name"),
+            Column::text("s_address"),
+            Column::text("s_phone"),
+            Column::decimal("total_revenue", Sum),
+        ],
+        &[0],
+        None,
+    ),
+    // Q16
+    schema(
+        &[
+            Column::text("p_brand"),
+            Column::text("p_type"),
+            Column::integer("p_size"),
+            Column::integer("supplier_cnt"),
+        ],
+        &[3, 0, 1, 2],
+        None,
+    ),
+    // Q17
+    schema(&[Column::decimal("avg_yearly", Sum)], &[], None),
+    // Q18
+    schema(
+        &[
+            Column::text("c_name"),
+            Column::integer("c_custkey"),
+            Column::integer("o_orderkey"),
+            Column::date("o_orderdat"),
+            Column::decimal("o_totalprice", Exact),
+            Column::decimal("col6", Sum),
+        ],
+        &[4, 3],
+        Some(100),
+    ),
+    // Q19
+    schema(&[Column::decimal("revenue", Sum)], &[], None),
+    // Q20
+    schema(
+        &[Column::text("s_name"), Column::text("s_address")],
+        &[0],
+        None,
+    ),
+    // Q21
+    schema(
+        &[Column::text("s_name"), Column::integer("numwait")],
+        &[1, 0],
+        Some(100),
+    ),
+    // Q22
+    schema(
+        &[
+            Column::text("cntrycode"),
+            Column::integer("numcust"),
+            Column::decimal("totacctbal", Sum),
+        ],
+        &[0],
+        None,
+    ),
+];
+
+/// A value of an answer
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Integer(i64),
+    Decimal(f64),
+    /// `YYYY-MM-DD`
+    Date(String),
+    Text(String),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Null => write!(f, "NULL"),
+            Value::Integer(value) => write!(f, "{value}"),
+            Value::Decimal(value) => write!(f, "{value}"),
+            Value::Date(value) | Value::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl Value {
+    /// Parses a value of `column_type`, empty values are null
+    fn parse(value: &str, column_type: ColumnType) -> Option<Self> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Some(Value::Null);
+        }
+        match column_type {
+            // some engines print integer results (e.g. `extract(year ...)`)
+            // as decimals
+            ColumnType::Integer => value.parse().ok().map(Value::Integer).or_else(|| {
+                let value: f64 = value.parse().ok()?;
+                (value.fract() == 0.0).then_some(Value::Integer(value as i64))
+            }),
+            ColumnType::Decimal => value
+                .parse()
+                .ok()
+                .filter(|value: &f64| value.is_finite())
+                .map(Value::Decimal),
+            ColumnType::Date => Date::parse(value).map(|_| Value::Date(value.to_string())),
+            ColumnType::Text => Some(Value::Text(value.to_string())),
+        }
+    }
+
+    /// Returns true if `actual` matches this expected value in `column`
+    fn matches(&self, actual: &Value, column: &Column) -> bool {
+        match (self, actual) {
+            (Value::Decimal(expected), Value::Decimal(actual)) => {
+                // rounded to the nearest 1/100th
+                let expected = (expected * 100.0).round();
+                let actual = (actual * 100.0).round();
+                match column.precision {
+                    Precision::Exact => expected == actual,
+                    Precision::Sum => (expected - actual).abs() <= 100.0 * 100.0,
+                    Precision::Relative => (expected - actual).abs() <= expected.abs() * 0.01,
+                }
+            }
+            (expected, actual) => expected == actual,
+        }
+    }
+}
+
+/// The typed rows of the answer of a query
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answer {
+    schema: &'static AnswerSchema,
+    rows: Vec<Vec<Value>>,
+}
+
+impl Answer {
+    /// Parses the answer of query `query_number`
+    ///
+    /// The first non empty line is the header, whose column names are not
+    /// checked (engines name the expressions differently), followed by one
+    /// line per row with the values separated by `delimiter`. Values can be
+    /// quoted with `"` like in CSV files, are trimmed and are null when empty.
+    pub fn parse(query_number: i32, text: &str, delimiter: char) -> Result<Self, String> {
+        let schema = AnswerSchema::new(query_number)?;
+        let columns = schema.columns();
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .skip_while(|(_, line)| line.trim().is_empty());
+
+        let Some((_, header)) = lines.next() else {
+            return Err(format!("The answer of Q{query_number} has no header"));
+        };
+        split_fields(header, delimiter, columns.len()).map_err(|count| {
+            format!(
+                "The answer of Q{query_number} has {count} columns, expected {}",
+                columns.len()
+            )
+        })?;
+
+        let mut rows = Vec::new();
+        for (index, line) in lines {
+            let line_number = index + 1;
+            // an empty line is a null value in a single column answer
+            if line.trim().is_empty() && columns.len() > 1 {
+                continue;
+            }
+            let fields = split_fields(line, delimiter, columns.len()).map_err(|count| {
+                format!(
+                    "Line {line_number}: {count} values, expected {}",
+                    columns.len()
+                )
+            })?;
+            let row = fields
+                .iter()
+                .zip(columns)
+                .map(|(field, column)| {
+                    Value::parse(field, column.column_type).ok_or_else(|| {
+                        format!(
+                            "Line {line_number}: invalid {:?} value for {}: {field}",
+                            column.column_type, column.name
+                        )
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+            rows.push(row);
+        }
+        Ok(Self { schema, rows })
+    }
+
+    /// Returns the schema of the answer
+    pub fn schema(&self) -> &'static AnswerSchema {
+        self.schema
+    }
+
+    /// Returns the rows of the answer
+    pub fn rows(&self) -> &[Vec<Value>] {
+        &self.rows
+    }
+
+    /// Compares `actual` with this expected answer of the same query
+    ///
+    /// Returns the differences, which are empty if `actual` is a valid
+    /// answer.
+    pub fn compare(&self, actual: &Answer) -> Vec<Difference> {
+        let columns = self.schema.columns();
+        let order_by = self.schema.order_by();
+        let mut differences = Vec::new();
+        if actual.rows.len() != self.rows.len() {
+            differences.push(Difference::RowCount {
+                expected: self.rows.len(),
+                actual: actual.rows.len(),
+            });
+        }
+        // with a limit, the last tied rows are any of the tied rows
+        let truncated = self.schema.limit() == Some(self.rows.len());
+
+        let mut start = 0;
+        while start < self.rows.len().min(actual.rows.len()) {
+            // the rows tied with the first one
+            let key = &self.rows[start];
+            let end = start
+                + self.rows[start..]
+                    .iter()
+                    .take_while(|row| order_by.iter().all(|&i| row[i] == key[i]))
+                    .count();
+            let expected_rows = &self.rows[start..end];
+            let actual_rows = &actual.rows[start..end.min(actual.rows.len())];
+
+            if let ([expected], [actual]) = (expected_rows, actual_rows) {
+                for (column, (expected, actual)) in columns.iter().zip(expected.iter().zip(actual))
+                {
+                    if !expected.matches(actual, column) {
+                        differences.push(Difference::Value {
+                            row: start,
+                            column: column.name,
+                            expected: expected.clone(),
+                            actual: actual.clone(),
+                        });
+                    }
+                }
+            } else {
+                let tied_columns: Vec<usize> = if truncated && end == self.rows.len() {
+                    order_by.to_vec()
+                } else {
+                    (0..columns.len()).collect()
+                };
+                let row_matches = |expected: &[Value], actual: &[Value]| {
+                    tied_columns
+                        .iter()
+                        .all(|&i| expected[i].matches(&actual[i], &columns[i]))
+                };
+                // pair the rows in any order
+                let mut unmatched: Vec<&Vec<Value>> = actual_rows.iter().collect();
+                let mut missing = Vec::new();
+                for (index, expected) in expected_rows.iter().enumerate() {
+                    match unmatched
+                        .iter()
+                        .position(|actual| row_matches(expected, actual))
+                    {
+                        Some(position) => {
+                            unmatched.remove(position);
+                        }
+                        None => missing.push((start + index, expected)),
+                    }
+                }
+                for ((row, expected), actual) in missing.into_iter().zip(unmatched) {
+                    differences.push(Difference::Row {
+                        row,
+                        expected: expected.clone(),
+                        actual: actual.clone(),
+                    });
+                }
+            }
+            start = end;
+        }
+        differences
+    }
+}
+
+/// A difference between an answer and the expected answer
+#[derive(Debug, Clone, PartialEq)]
+pub enum Difference {
+    /// The answers have a different number of rows
+    RowCount { expected: usize, actual: usize },
+    /// A value of a row differs from the expected value
+    Value {
+        /// 0 based index of the row
+        row: usize,
+        column: &'static str,
+        expected: Value,
+        actual: Value,
+    },
+    /// No row matches the expected row among the rows tied with it
+    Row {
+        /// 0 based index of the row
+        row: usize,
+        expected: Vec<Value>,
+        /// One of the tied rows that do not match any expected row
+        actual: Vec<Value>,
+    },
+}
+
+impl Display for Difference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let join = |values: &[Value]| {
+            values
+                .iter()
+                .map(Value::to_string)
+                .collect::<Vec<_>>()
+                .join("|")
+        };
+        match self {
+            Difference::RowCount { expected, actual } => {
+                write!(f, "expected {expected} rows, got {actual}")
+            }
+            Difference::Value {
+                row,
+                column,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "row {}, column {column}: expected {expected}, got {actual}",
+                row + 1
+            ),
+            Difference::Row {
+                row,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "row {}: expected {}, got {}",
+                row + 1,
+                join(expected),
+                join(actual)
+            ),
+        }
+    }
+}
+
+/// Splits a line into `count` fields separated by `delimiter`, with quoted
+/// fields like in CSV files
+///
+/// Returns the number of fields if it is not `count`. An extra empty field
+/// (a trailing delimiter, like in TBL files) is ignored.
+fn split_fields(line: &str, delimiter: char, count: usize) -> Result<Vec<String>, usize> {
+    let mut fields = Vec::with_capacity(count);
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    if fields.len() == count + 1 && fields[count].is_empty() {
+        fields.pop();
+    }
+    if fields.len() == count {
+        Ok(fields)
+    } else {
+        Err(fields.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::q_and_a::answers_sf1;
+    use crate::q_and_a::evaluator::QueryEvaluator;
+
+    #[test]
+    fn test_parse_answers_sf1() {
+        for query_number in 1..=22 {
+            let answer = Answer::parse(
+                query_number,
+                answers_sf1::answer(query_number).unwrap(),
+                '|',
+            )
+            .unwrap();
+            assert!(!answer.rows().is_empty(), "Q{query_number}");
+            assert!(answer.compare(&answer).is_empty(), "Q{query_number}");
+        }
+
+        let q3 = Answer::parse(3, answers_sf1::Q3_ANSWER, '|').unwrap();
+        assert_eq!(
+            q3.rows()[0],
+            vec![
+                Value::Integer(2456423),
+                Value::Decimal(406181.01),
+                Value::Date("1995-03-05".to_string()),
+                Value::Integer(0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Answer::parse(4, "o_orderpriority,order_count\n1-URGENT,many\n", ','),
+            Err("Line 2: invalid Integer value for order_count: many".to_string())
+        );
+        assert_eq!(
+            Answer::parse(4, "o_orderpriority\n", ','),
+            Err("The answer of Q4 has 1 columns, expected 2".to_string())
+        );
+        assert_eq!(
+            Answer::parse(4, "a,b\n1-URGENT,1,2\n", ','),
+            Err("Line 2: 3 values, expected 2".to_string())
+        );
+        assert!(Answer::parse(3, "a,b,c,d\n1,2.0,1995-02-30,0\n", ',').is_err());
+        assert!(Answer::parse(23, "", ',').is_err());
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let answer = Answer::parse(
+            20,
+            "s_name,s_address\n\"Supplier#000000020\",\"a \"\"quoted\"\", address\"\n",
+            ',',
+        )
+        .unwrap();
+        assert_eq!(
+            answer.rows()[0][1],
+            Value::Text("a \"quoted\", address".to_string())
+        );
+
+        // integers printed as decimals, and a null single value
+        let answer = Answer::parse(8, "o_year,mkt_share\n1995.0,0.03\n", ',').unwrap();
+        assert_eq!(answer.rows()[0][0], Value::Integer(1995));
+        let answer = Answer::parse(17, "\navg_yearly\n\n", '|').unwrap();
+        assert_eq!(answer.rows(), &[vec![Value::Null]]);
+    }
+
+    #[test]
+    fn test_precision() {
+        let expected = Answer::parse(1, answers_sf1::Q1_ANSWER, '|').unwrap();
+        let with_first_row = |row: &str| {
+            let mut lines: Vec<&str> = answers_sf1::Q1_ANSWER.lines().collect();
+            lines[2] = row;
+            Answer::parse(1, &lines.join("\n"), '|').unwrap()
+        };
+
+        // sums within 100, averages within 1%, unrounded values
+        let actual = with_first_row(
+            "A|F|37734107.00|56586554400.73|53758257134.87|55909065322.83|25.7|38273.125|0.0502|1478493",
+        );
+        assert_eq!(expected.compare(&actual), vec![]);
+
+        let actual = with_first_row(
+            "A|F|37734107.00|56586554400.73|53758257134.87|55909065322.84|25.52|38273.13|0.06|1478492",
+        );
+        let columns: Vec<_> = expected
+            .compare(&actual)
+            .into_iter()
+            .map(|difference| match difference {
+                Difference::Value { row: 0, column, .. } => column,
+                difference => panic!("unexpected difference {difference}"),
+            })
+            .collect();
+        assert_eq!(columns, vec!["sum_charge", "avg_disc", "count_order"]);
+
+        // singleton values must match exactly
+        let expected = Answer::parse(18, answers_sf1::Q18_ANSWER, '|').unwrap();
+        let mut actual = expected.clone();
+        actual.rows[0][4] = Value::Decimal(544089.1);
+        assert_eq!(expected.compare(&actual).len(), 1);
+    }
+
+    #[test]
+    fn test_ties() {
+        let evaluator = QueryEvaluator::new(0.01);
+        // Q13 is ordered by custdist desc, c_count desc, without ties
+        let expected = Answer::parse(13, &evaluator.validation_answer(13).unwrap(), '|').unwrap();
+        let mut actual = expected.clone();
+        actual.rows.swap(0, 1);
+        assert_eq!(expected.compare(&actual).len(), 4);
+
+        // Q10 is ordered by revenue desc only
+        let mut expected = Answer::parse(10, answers_sf1::Q10_ANSWER, '|').unwrap();
+        expected.rows[6][2] = expected.rows[5][2].clone();
+        let mut actual = expected.clone();
+        actual.rows.swap(5, 6);
+        assert_eq!(expected.compare(&actual), vec![]);
+        actual.rows[5][0] = Value::Integer(1);
+        assert_eq!(
+            expected.compare(&actual),
+            vec![Difference::Row {
+                row: 6,
+                expected: expected.rows[6].clone(),
+                actual: actual.rows[5].clone(),
+            }]
+        );
+
+        // the last tied rows of a limited answer can be any of the tied rows
+        let mut actual = expected.clone();
+        actual.rows[19][0] = Value::Integer(1);
+        assert_eq!(expected.compare(&actual).len(), 1);
+        expected.rows[19][2] = expected.rows[18][2].clone();
+        actual.rows[19][2] = expected.rows[18][2].clone();
+        assert_eq!(expected.compare(&actual), vec![]);
+
+        // missing rows
+        actual.rows.truncate(18);
+        assert_eq!(
+            expected.compare(&actual),
+            vec![Difference::RowCount {
+                expected: 20,
+                actual: 18
+            }]
+        );
+    }
+}
@@ -3,6 +3,51 @@
 use crate::{distribution::Distribution, text::TextPool};
 use std::fmt::Display;
 
+/// Returns the seed of a random number stream for a dataset generated with
+/// `dataset_seed`
+///
+/// Each column uses its own random number stream, started from a hard coded
+/// `seed`. For `dataset_seed` `0` the seed is returned unchanged, which
+/// generates the standard TPC-H data. For other values the seed is replaced
+/// by a pseudo random value derived from both, so each stream starts at a
+/// different, but still valid, point of the TPC-H random number sequence.
+pub fn stream_seed(seed: i64, dataset_seed: u64) -> i64 {
+    if dataset_seed == 0 {
+        return seed;
+    }
+    // SplitMix64 finalizer
+    let mix = |mut z: u64| {
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    };
+    let mixed = mix(mix(dataset_seed) ^ seed as u64);
+    // valid seeds are in `1..MODULUS`
+    1 + (mixed % (RowRandomInt::MODULUS as u64 - 1)) as i64
+}
+
+/// Maps a `uniform` value in `[0, 1]` to a rank in `0..count` that follows a
+/// Zipf distribution with exponent `factor`
+///
+/// Rank `0` is the most frequent, rank `1` the second most frequent and so
+/// on, with the frequency of rank `k` proportional to `1 / (k + 1)^factor`.
+/// A `factor` of `0` is the uniform distribution. The rank is computed by
+/// inverting the cumulative distribution function of the continuous
+/// approximation of the Zipf distribution, so each value uses exactly one
+/// random number, like the uniform generators.
+pub fn zipf_rank(uniform: f64, count: i64, factor: f64) -> i64 {
+    debug_assert!(count > 0, "count must be positive: {count}");
+    debug_assert!(factor >= 0.0, "factor must not be negative: {factor}");
+    let n = count as f64 + 1.0;
+    let x = if (factor - 1.0).abs() < 1e-9 {
+        n.powf(uniform)
+    } else {
+        let exponent = 1.0 - factor;
+        (1.0 + uniform * (n.powf(exponent) - 1.0)).powf(1.0 / exponent)
+    };
+    (x as i64 - 1).clamp(0, count - 1)
+}
+
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct RowRandomInt {
     seed: i64,
@@ -57,6 +102,15 @@ impl RowRandomInt {
         lower_bound + value
     }
 
+    /// Returns a random value between lower and upper bounds (both inclusive)
+    /// following a Zipf distribution with exponent `factor`, where
+    /// `lower_bound` is the most frequent value. See [`zipf_rank`].
+    pub fn next_int_skewed(&mut self, lower_bound: i32, upper_bound: i32, factor: f64) -> i32 {
+        let uniform = self.next_rand() as f64 / Self::MODULUS as f64;
+        let count = upper_bound as i64 - lower_bound as i64 + 1;
+        (lower_bound as i64 + zipf_rank(uniform, count, factor)) as i32
+    }
+
     /// Instantiates a new seed for the next random value.
     pub fn next_rand(&mut self) -> i64 {
         self.seed = (self.seed * Self::MULTIPLIER) % Self::MODULUS;
@@ -134,6 +188,16 @@ impl RowRandomLong {
         lower_bound + value_in_range
     }
 
+    /// Returns a random value between `lower_bound` and `upper_bound` (both
+    /// inclusive) following a Zipf distribution with exponent `factor`, where
+    /// `lower_bound` is the most frequent value. See [`zipf_rank`].
+    pub fn next_long_skewed(&mut self, lower_bound: i64, upper_bound: i64, factor: f64) -> i64 {
+        self.next_rand();
+
+        let uniform = self.seed.unsigned_abs() as f64 / (1u64 << 63) as f64;
+        lower_bound + zipf_rank(uniform, upper_bound - lower_bound + 1, factor)
+    }
+
     /// Instantiates a new seed for the next random value.
     fn next_rand(&mut self) -> i64 {
         self.seed = (self.seed.wrapping_mul(Self::MULTIPLIER)) + Self::INCREMENT;
@@ -182,6 +246,8 @@ pub struct RandomBoundedInt {
     lower_bound: i32,
     upper_bound: i32,
     random_int: RowRandomInt,
+    /// Zipf exponent of the values, `0.0` for uniformly distributed values
+    skew: f64,
 }
 
 impl RandomBoundedInt {
@@ -191,6 +257,7 @@ impl RandomBoundedInt {
             lower_bound,
             upper_bound,
             random_int: RowRandomInt::new(seed, 1),
+            skew: 0.0,
         }
     }
 
@@ -206,12 +273,26 @@ impl RandomBoundedInt {
             lower_bound,
             upper_bound,
             random_int: RowRandomInt::new(seed, seeds_per_row),
+            skew: 0.0,
         }
     }
 
+    /// Skews the values with a Zipf distribution with exponent `factor`, so
+    /// that the lower bound is the most frequent value. A `factor` of `0.0`
+    /// keeps the uniform distribution.
+    pub fn with_skew(mut self, factor: f64) -> Self {
+        self.skew = factor;
+        self
+    }
+
     /// Returns a random value between the lower and upper bounds (both inclusive).
     pub fn next_value(&mut self) -> i32 {
-        self.random_int.next_int(self.lower_bound, self.upper_bound)
+        if self.skew == 0.0 {
+            self.random_int.next_int(self.lower_bound, self.upper_bound)
+        } else {
+            self.random_int
+                .next_int_skewed(self.lower_bound, self.upper_bound, self.skew)
+        }
     }
 
     /// Advance the inner random number generator by the specified number of rows.
@@ -234,6 +315,8 @@ pub struct RandomBoundedLong {
     random_long: RowRandomLong,
     // 32-bit values.
     random_int: RowRandomInt,
+    /// Zipf exponent of the values, `0.0` for uniformly distributed values
+    skew: f64,
 }
 
 impl RandomBoundedLong {
@@ -245,6 +328,7 @@ impl RandomBoundedLong {
             upper_bound,
             random_long: RowRandomLong::new(seed, 1),
             random_int: RowRandomInt::new(seed, 1),
+            skew: 0.0,
         }
     }
 
@@ -263,17 +347,36 @@ impl RandomBoundedLong {
             upper_bound,
             random_long: RowRandomLong::new(seed, seeds_per_row),
             random_int: RowRandomInt::new(seed, seeds_per_row),
+            skew: 0.0,
         }
     }
 
+    /// Skews the values with a Zipf distribution with exponent `factor`. See
+    /// [`RandomBoundedInt::with_skew`].
+    pub fn with_skew(mut self, factor: f64) -> Self {
+        self.skew = factor;
+        self
+    }
+
     /// Returns a random value between the lower and upper bounds (both inclusive).
     pub fn next_value(&mut self) -> i64 {
-        if self.use_64bits {
-            self.random_long
-                .next_long(self.lower_bound, self.upper_bound)
-        } else {
-            self.random_int
-                .next_int(self.lower_bound as i32, self.upper_bound as i32) as i64
+        match (self.use_64bits, self.skew == 0.0) {
+            (true, true) => self
+                .random_long
+                .next_long(self.lower_bound, self.upper_bound),
+            (true, false) => {
+                self.random_long
+                    .next_long_skewed(self.lower_bound, self.upper_bound, self.skew)
+            }
+            (false, true) => self
+                .random_int
+                .next_int(self.lower_bound as i32, self.upper_bound as i32)
+                as i64,
+            (false, false) => self.random_int.next_int_skewed(
+                self.lower_bound as i32,
+                self.upper_bound as i32,
+                self.skew,
+            ) as i64,
         }
     }
 
@@ -301,6 +404,7 @@ pub struct RandomAlphaNumeric {
     inner: RowRandomInt,
     min_length: i32,
     max_length: i32,
+    wrap_char_range: bool,
 }
 
 impl RandomAlphaNumeric {
@@ -327,9 +431,17 @@ impl RandomAlphaNumeric {
             inner: RowRandomInt::new(seed, Self::USAGE_PER_ROW * seeds_per_row),
             min_length,
             max_length,
+            wrap_char_range: true,
         }
     }
 
+    /// Draws the characters like `ssb-dbgen`, without the 32-bit overflow of
+    /// the character range of TPC-H `dbgen` (see [`RowRandomInt::next_int`])
+    pub fn without_overflow(mut self) -> Self {
+        self.wrap_char_range = false;
+        self
+    }
+
     /// Returns the next string as a [`RandomAlphaNumericInstance`], which can
     /// generate the string on demand.
     pub fn next_value(&mut self) -> RandomAlphaNumericInstance {
@@ -338,6 +450,7 @@ impl RandomAlphaNumeric {
         RandomAlphaNumericInstance {
             length,
             snapshot: self.inner,
+            wrap_char_range: self.wrap_char_range,
         }
     }
 
@@ -358,6 +471,8 @@ pub struct RandomAlphaNumericInstance {
     length: usize,
     /// snapshot of the random number generator
     snapshot: RowRandomInt,
+    /// see [`RandomAlphaNumeric::without_overflow`]
+    wrap_char_range: bool,
 }
 
 impl Display for RandomAlphaNumericInstance {
@@ -381,7 +496,12 @@ impl Display for RandomAlphaNumericInstance {
         #[allow(clippy::needless_range_loop)]
         for i in 0..self.length {
             if i % 5 == 0 {
-                char_index = generator.next_int(0, i32::MAX) as i64;
+                char_index = if self.wrap_char_range {
+                    generator.next_int(0, i32::MAX) as i64
+                } else {
+                    let seed = generator.next_rand() as f64;
+                    (seed / RowRandomInt::MODULUS as f64 * (i32::MAX as f64 + 1.0)) as i64
+                };
             }
 
             let char_pos = (char_index & 0x3f) as usize;
@@ -463,6 +583,8 @@ impl Display for PhoneNumberInstance {
 pub struct RandomString<'a> {
     inner: RowRandomInt,
     distribution: &'a Distribution,
+    /// Zipf exponent of the values, `0.0` for the weights of the distribution
+    skew: f64,
 }
 
 impl<'a> RandomString<'a> {
@@ -478,11 +600,25 @@ impl<'a> RandomString<'a> {
         Self {
             inner: RowRandomInt::new(seed, seeds_per_row),
             distribution,
+            skew: 0.0,
         }
     }
 
+    /// Skews the values with a Zipf distribution with exponent `factor`
+    /// instead of using the weights of the distribution. See
+    /// [`Distribution::random_skewed_value`].
+    pub fn with_skew(mut self, factor: f64) -> Self {
+        self.skew = factor;
+        self
+    }
+
     pub fn next_value(&mut self) -> &'a str {
-        self.distribution.random_value(&mut self.inner)
+        if self.skew == 0.0 {
+            self.distribution.random_value(&mut self.inner)
+        } else {
+            self.distribution
+                .random_skewed_value(&mut self.inner, self.skew)
+        }
     }
 
     /// Advance the inner random number generator by the given number of rows.
@@ -501,6 +637,7 @@ pub struct RandomStringSequence<'a> {
     inner: RowRandomInt,
     count: i32,
     distribution: &'a Distribution,
+    full_permutation: bool,
 }
 
 impl<'a> RandomStringSequence<'a> {
@@ -518,12 +655,39 @@ impl<'a> RandomStringSequence<'a> {
             inner: RowRandomInt::new(seed, distribution.size() as i32 * seeds_per_row),
             count,
             distribution,
+            full_permutation: false,
         }
     }
 
+    /// Picks the values from a permutation of the whole distribution, like
+    /// `dbgen` 1.x and `ssb-dbgen`
+    ///
+    /// Every position is swapped with a random position of the whole
+    /// distribution, using one random number per value of the distribution,
+    /// before keeping the first `count` values.
+    pub fn with_full_permutation(mut self) -> Self {
+        self.full_permutation = true;
+        self
+    }
+
     pub fn next_value(&mut self) -> StringSequenceInstance<'a> {
         // Get all values from the distribution
-        let mut values: Vec<&str> = self.distribution.get_values().to_vec();
+        let mut values: Vec<&str> = self
+            .distribution
+            .get_values()
+            .iter()
+            .map(String::as_str)
+            .collect();
+
+        if self.full_permutation {
+            let last_position = values.len() as i32 - 1;
+            for current_position in 0..values.len() {
+                let swap_position = self.inner.next_int(0, last_position) as usize;
+                values.swap(current_position, swap_position);
+            }
+            values.truncate(self.count as usize);
+            return StringSequenceInstance { values };
+        }
 
         // Randomize first 'count' elements
         for current_position in 0..self.count {
@@ -564,6 +728,13 @@ pub struct StringSequenceInstance<'a> {
     values: Vec<&'a str>,
 }
 
+impl<'a> StringSequenceInstance<'a> {
+    /// Removes and returns the first string of the sequence
+    pub fn remove_first(&mut self) -> Option<&'a str> {
+        (!self.values.is_empty()).then(|| self.values.remove(0))
+    }
+}
+
 impl Display for StringSequenceInstance<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut iter = self.values.iter();
@@ -630,6 +801,51 @@ impl<'a> RandomText<'a> {
 mod test {
     use super::*;
     use std::collections::HashSet;
+
+    #[test]
+    fn test_stream_seed() {
+        assert_eq!(stream_seed(1066728069, 0), 1066728069);
+        assert_eq!(stream_seed(1, 0), 1);
+
+        let seeds: HashSet<_> = (1..1000)
+            .flat_map(|dataset_seed| {
+                [1, 1066728069, 1434868289]
+                    .map(|seed| stream_seed(seed, dataset_seed))
+                    .into_iter()
+            })
+            .collect();
+        assert_eq!(seeds.len(), 3 * 999);
+        assert!(seeds
+            .iter()
+            .all(|seed| (1..RowRandomInt::MODULUS).contains(seed)));
+    }
+
+    #[test]
+    fn test_zipf_rank() {
+        // factor 0 is uniform
+        assert_eq!(zipf_rank(0.0, 10, 0.0), 0);
+        assert_eq!(zipf_rank(0.55, 10, 0.0), 5);
+        assert_eq!(zipf_rank(1.0, 10, 0.0), 9);
+
+        for factor in [0.5, 1.0, 2.0] {
+            let mut random = RowRandomInt::new(1, 1);
+            let mut counts = [0; 100];
+            for _ in 0..100_000 {
+                counts[random.next_int_skewed(1, 100, factor) as usize - 1] += 1;
+            }
+            // the lower ranks are more frequent
+            assert!(counts[0] > counts[1], "{factor}: {counts:?}");
+            assert!(counts[1] > counts[9], "{factor}: {counts:?}");
+            assert!(counts[9] > counts[99], "{factor}: {counts:?}");
+        }
+
+        let mut random = RowRandomLong::new(1, 1);
+        let values: Vec<_> = (0..1000)
+            .map(|_| random.next_long_skewed(10, 20, 1.0))
+            .collect();
+        assert!(values.iter().all(|value| (10..=20).contains(value)));
+    }
+
     #[test]
     fn test_small_random_alpha_numeric() {
         RandomAlphaNumericTest {
@@ -0,0 +1,117 @@
+//! Implementations of the [`serde`] traits, enabled by the `serde` feature.
+//!
+//! The rows of the [`generators`] implement `Serialize`, and the [`owned`]
+//! rows implement `Serialize` and `Deserialize`. The values that the
+//! generators format lazily (names, addresses, phone numbers, ...) are
+//! serialized as strings, as are [`TPCHDate`] (`"1996-01-02"`),
+//! [`TPCHDecimal`] (`"21168.23"`, to keep the exact value) and
+//! [`OrderStatus`] (`"O"`), so a row of a generator and the owned row created
+//! from it serialize the same way.
+//!
+//! [`generators`]: crate::generators
+//! [`owned`]: crate::owned
+
+use crate::dates::TPCHDate;
+use crate::decimal::TPCHDecimal;
+use crate::generators::{
+    ClerkName, CustomerName, OrderStatus, PartBrandName, PartManufacturerName, SupplierName,
+};
+use crate::random::{PhoneNumberInstance, RandomAlphaNumericInstance, StringSequenceInstance};
+use crate::ssb::generators::{BrandName, CategoryName, City, ManufacturerName};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Implements `Serialize` as the `Display` string of the value
+macro_rules! serialize_display {
+    ($($TYPE:ty),* $(,)?) => {
+        $(
+            impl Serialize for $TYPE {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `Deserialize` by parsing a string with `FromStr`
+macro_rules! deserialize_from_str {
+    ($($TYPE:ty),* $(,)?) => {
+        $(
+            impl<'de> Deserialize<'de> for $TYPE {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    String::deserialize(deserializer)?
+                        .parse()
+                        .map_err(D::Error::custom)
+                }
+            }
+        )*
+    };
+}
+
+serialize_display!(
+    TPCHDate,
+    TPCHDecimal,
+    OrderStatus,
+    PartManufacturerName,
+    PartBrandName,
+    SupplierName,
+    CustomerName,
+    ClerkName,
+    PhoneNumberInstance,
+    RandomAlphaNumericInstance,
+    StringSequenceInstance<'_>,
+    City<'_>,
+    ManufacturerName,
+    CategoryName,
+    BrandName,
+);
+
+deserialize_from_str!(TPCHDate, TPCHDecimal, OrderStatus);
+
+#[cfg(test)]
+mod tests {
+    use crate::generators::{OrderGenerator, SupplierGenerator};
+    use crate::owned;
+    use crate::ssb::generators::PartGenerator;
+
+    #[test]
+    fn test_serialize_rows() {
+        let order = OrderGenerator::new(0.01, 1, 1).iter().next().unwrap();
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(
+            json,
+            r#"{"o_orderkey":1,"o_custkey":370,"o_orderstatus":"O","o_totalprice":"172799.49","o_orderdate":"1996-01-02","o_orderpriority":"5-LOW","o_clerk":"Clerk#000000951","o_shippriority":0,"o_comment":"nstructions sleep furiously among "}"#
+        );
+        // the owned row serializes the same way, and deserializes back
+        let owned = owned::Order::from(&order);
+        assert_eq!(serde_json::to_string(&owned).unwrap(), json);
+        assert_eq!(serde_json::from_str::<owned::Order>(&json).unwrap(), owned);
+
+        let supplier = SupplierGenerator::new(0.01, 1, 1).iter().next().unwrap();
+        let json = serde_json::to_string(&supplier).unwrap();
+        assert!(json.contains(r#""s_name":"Supplier#000000001""#), "{json}");
+        assert!(json.contains(r#""s_phone":"27-918-335-1736""#), "{json}");
+        let owned = owned::Supplier::from(supplier);
+        assert_eq!(
+            serde_json::from_str::<owned::Supplier>(&json).unwrap(),
+            owned
+        );
+
+        let part = PartGenerator::new(0.01, 1, 1).iter().next().unwrap();
+        let json = serde_json::to_string(&part).unwrap();
+        assert!(json.contains(r#""p_brand1":"MFGR#1121""#), "{json}");
+    }
+
+    #[test]
+    fn test_deserialize_errors() {
+        let json = r#"{"o_orderkey":1,"o_custkey":370,"o_orderstatus":"O","o_totalprice":"172799.49","o_orderdate":"1996-02-30","o_orderpriority":"5-LOW","o_clerk":"Clerk#000000951","o_shippriority":0,"o_comment":""}"#;
+        let error = serde_json::from_str::<owned::Order>(json).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("Invalid date '1996-02-30', expected yyyy-mm-dd"),
+            "{error}"
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Skewed (Zipfian) data generation
+//!
+//! The TPC-H data is uniformly distributed: every customer places about the
+//! same number of orders and every part is ordered about the same number of
+//! times. A [`Skew`] replaces the uniform distribution of some columns with a
+//! Zipf distribution, similar to the "skewed TPC-D" generator and JCC-H, to
+//! create hot keys and skewed joins.
+//!
+//! Each [`SkewedColumn`] has its own Zipf exponent (factor), where `0.0` is the
+//! standard uniform distribution and larger values are more skewed (`1.0` is
+//! the classic Zipf distribution). The smallest key of a skewed key column and
+//! the first value of a skewed categorical column are the most frequent.
+//!
+//! Skewing a column does not change any other column, the row counts, or the
+//! foreign key relationships, and a skewed dataset can be generated in parts
+//! like the standard one.
+//!
+//! # Example
+//! ```
+//! # use tpchgen::generators::OrderGenerator;
+//! # use tpchgen::skew::{Skew, SkewedColumn};
+//! let skew: Skew = "o_custkey=1.5".parse().unwrap();
+//! assert_eq!(skew.factor(SkewedColumn::OCustkey), 1.5);
+//! assert_eq!(skew.factor(SkewedColumn::LPartkey), 0.0);
+//!
+//! // The first customer places many of the orders
+//! let orders = OrderGenerator::new(0.01, 1, 1).with_skew(skew);
+//! let hot = orders.iter().filter(|o| o.o_custkey == 1).count();
+//! assert!(hot > 1000, "{hot}");
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A column whose values can be skewed, see the [module docs](self)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkewedColumn {
+    /// Customer of an order (`o_custkey`)
+    OCustkey,
+    /// Part of a line item (`l_partkey`), which also skews `l_suppkey` as the
+    /// supplier is one of the suppliers of the part
+    LPartkey,
+    /// Which of the 4 suppliers of the part supplies a line item (`l_suppkey`)
+    LSuppkey,
+    /// Part type (`p_type`)
+    PType,
+    /// Part container (`p_container`)
+    PContainer,
+    /// Customer market segment (`c_mktsegment`)
+    CMktsegment,
+    /// Order priority (`o_orderpriority`)
+    OOrderpriority,
+    /// Return flag of a line item (`l_returnflag`) that was received
+    LReturnflag,
+    /// Shipping instructions (`l_shipinstruct`)
+    LShipinstruct,
+    /// Shipping mode (`l_shipmode`)
+    LShipmode,
+}
+
+impl SkewedColumn {
+    /// All columns that can be skewed
+    pub const ALL: [SkewedColumn; 10] = [
+        SkewedColumn::OCustkey,
+        SkewedColumn::LPartkey,
+        SkewedColumn::LSuppkey,
+        SkewedColumn::PType,
+        SkewedColumn::PContainer,
+        SkewedColumn::CMktsegment,
+        SkewedColumn::OOrderpriority,
+        SkewedColumn::LReturnflag,
+        SkewedColumn::LShipinstruct,
+        SkewedColumn::LShipmode,
+    ];
+
+    /// Returns the name of the column, e.g. `o_custkey`
+    pub const fn name(&self) -> &'static str {
+        match self {
+            SkewedColumn::OCustkey => "o_custkey",
+            SkewedColumn::LPartkey => "l_partkey",
+            SkewedColumn::LSuppkey => "l_suppkey",
+            SkewedColumn::PType => "p_type",
+            SkewedColumn::PContainer => "p_container",
+            SkewedColumn::CMktsegment => "c_mktsegment",
+            SkewedColumn::OOrderpriority => "o_orderpriority",
+            SkewedColumn::LReturnflag => "l_returnflag",
+            SkewedColumn::LShipinstruct => "l_shipinstruct",
+            SkewedColumn::LShipmode => "l_shipmode",
+        }
+    }
+}
+
+impl Display for SkewedColumn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for SkewedColumn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SkewedColumn::ALL
+            .into_iter()
+            .find(|column| column.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<_> = SkewedColumn::ALL.iter().map(|c| c.name()).collect();
+                format!(
+                    "Unknown skewed column '{s}', expected one of: {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// The Zipf exponent (factor) of each [`SkewedColumn`]
+///
+/// The default has no skew, which generates the standard TPC-H data.
+///
+/// A `Skew` can be parsed from a comma separated list of `column=factor`
+/// entries, where a factor without a column applies to all columns, e.g.
+/// `0.5,o_custkey=1.2` skews all columns with factor `0.5` except `o_custkey`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Skew {
+    factors: [f64; SkewedColumn::ALL.len()],
+}
+
+impl Skew {
+    /// Returns a `Skew` with the same `factor` for all columns
+    pub fn all(factor: f64) -> Self {
+        Self::default().with_all(factor)
+    }
+
+    /// Sets the factor of all columns
+    ///
+    /// # Panics
+    /// If `factor` is negative or not finite
+    pub fn with_all(self, factor: f64) -> Self {
+        SkewedColumn::ALL
+            .into_iter()
+            .fold(self, |skew, column| skew.with_factor(column, factor))
+    }
+
+    /// Sets the factor of `column`
+    ///
+    /// # Panics
+    /// If `factor` is negative or not finite
+    pub fn with_factor(mut self, column: SkewedColumn, factor: f64) -> Self {
+        assert!(
+            factor.is_finite() && factor >= 0.0,
+            "Invalid skew factor {factor} for {column}, must be a non negative number"
+        );
+        self.factors[column as usize] = factor;
+        self
+    }
+
+    /// Returns the factor of `column`, `0.0` if it is not skewed
+    pub fn factor(&self, column: SkewedColumn) -> f64 {
+        self.factors[column as usize]
+    }
+
+    /// Returns true if no column is skewed
+    pub fn is_uniform(&self) -> bool {
+        self.factors.iter().all(|factor| *factor == 0.0)
+    }
+}
+
+/// Displays the skewed columns as `column=factor` entries that can be parsed
+/// again, or `none` if no column is skewed
+impl Display for Skew {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_uniform() {
+            return write!(f, "none");
+        }
+        let entries: Vec<_> = SkewedColumn::ALL
+            .into_iter()
+            .filter(|column| self.factor(*column) != 0.0)
+            .map(|column| format!("{column}={}", self.factor(column)))
+            .collect();
+        write!(f, "{}", entries.join(","))
+    }
+}
+
+impl FromStr for Skew {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_factor = |factor: &str| {
+            factor
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|factor| factor.is_finite() && *factor >= 0.0)
+                .ok_or_else(|| {
+                    format!("Invalid skew factor '{factor}', must be a non negative number")
+                })
+        };
+
+        let mut skew = Skew::default();
+        if s.trim().eq_ignore_ascii_case("none") {
+            return Ok(skew);
+        }
+        for entry in s.split(',') {
+            skew = match entry.split_once('=') {
+                Some((column, factor)) => {
+                    skew.with_factor(column.trim().parse()?, parse_factor(factor)?)
+                }
+                None => skew.with_all(parse_factor(entry)?),
+            };
+        }
+        Ok(skew)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_skew() {
+        assert_eq!("none".parse::<Skew>().unwrap(), Skew::default());
+        assert_eq!("0.5".parse::<Skew>().unwrap(), Skew::all(0.5));
+
+        let skew: Skew = "0.5, o_custkey=1.2,L_SHIPMODE=0".parse().unwrap();
+        assert_eq!(skew.factor(SkewedColumn::OCustkey), 1.2);
+        assert_eq!(skew.factor(SkewedColumn::LShipmode), 0.0);
+        assert_eq!(skew.factor(SkewedColumn::LPartkey), 0.5);
+        assert_eq!(skew.to_string().parse::<Skew>().unwrap(), skew);
+
+        assert!("o_custkey=-1".parse::<Skew>().is_err());
+        assert!("o_custkey=x".parse::<Skew>().is_err());
+        assert!("o_orderkey=1".parse::<Skew>().is_err());
+    }
+}
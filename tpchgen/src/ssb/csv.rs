@@ -0,0 +1,296 @@
+//! CSV formatting support for the SSB row structs, see [`crate::csv`]
+
+use crate::ssb::generators::{Customer, Date, LineOrder, Part, Supplier};
+use core::fmt;
+use std::fmt::Display;
+
+/// Write SSB [`Customer`]s in CSV format.
+///
+/// # Example
+/// ```
+/// # use tpchgen::ssb::generators::CustomerGenerator;
+/// # use tpchgen::ssb::csv::CustomerCsv;
+/// # use std::fmt::Write;
+/// // Output the first 2 rows in CSV format
+/// let generator = CustomerGenerator::new(1.0, 1, 1);
+/// let mut csv = String::new();
+/// writeln!(&mut csv, "{}", CustomerCsv::header()).unwrap(); // write header
+/// for line in generator.iter().take(2) {
+///   // write line using CSV formatter
+///   writeln!(&mut csv, "{}", CustomerCsv::new(line)).unwrap();
+/// }
+/// assert_eq!(
+///   csv,
+///   "c_custkey,c_name,c_address,c_city,c_nation,c_region,c_phone,c_mktsegment\n\
+///    1,Customer#000000001,\"j5JsirBM9P\",MOROCCO  0,MOROCCO,AFRICA,25-989-741-2988,BUILDING\n\
+///    2,Customer#000000002,\"487LW1dovn6Q4dMVym\",JORDAN   1,JORDAN,MIDDLE EAST,23-768-687-3665,AUTOMOBILE\n"
+/// );
+/// ```
+pub struct CustomerCsv<'a> {
+    inner: Customer<'a>,
+}
+
+impl<'a> CustomerCsv<'a> {
+    pub fn new(inner: Customer<'a>) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the CSV header for the Customer table
+    pub fn header() -> &'static str {
+        "c_custkey,c_name,c_address,c_city,c_nation,c_region,c_phone,c_mktsegment"
+    }
+}
+
+impl Display for CustomerCsv<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            // note must quote the address field as it may contain commas
+            "{},{},\"{}\",{},{},{},{},{}",
+            self.inner.c_custkey,
+            self.inner.c_name,
+            self.inner.c_address,
+            self.inner.c_city,
+            self.inner.c_nation,
+            self.inner.c_region,
+            self.inner.c_phone,
+            self.inner.c_mktsegment
+        )
+    }
+}
+
+/// Write SSB [`Supplier`]s in CSV format.
+///
+/// # Example
+/// ```
+/// # use tpchgen::ssb::generators::SupplierGenerator;
+/// # use tpchgen::ssb::csv::SupplierCsv;
+/// # use std::fmt::Write;
+/// // Output the first 2 rows in CSV format
+/// let generator = SupplierGenerator::new(1.0, 1, 1);
+/// let mut csv = String::new();
+/// writeln!(&mut csv, "{}", SupplierCsv::header()).unwrap(); // write header
+/// for line in generator.iter().take(2) {
+///   // write line using CSV formatter
+///   writeln!(&mut csv, "{}", SupplierCsv::new(line)).unwrap();
+/// }
+/// assert_eq!(
+///   csv,
+///   "s_suppkey,s_name,s_address,s_city,s_nation,s_region,s_phone\n\
+///    1,Supplier#000000001,\"sdrGnXCDRcfriBvY0KL,i\",PERU     0,PERU,AMERICA,27-989-741-2988\n\
+///    2,Supplier#000000002,\"TRMhVHz3XiFu\",ETHIOPIA 1,ETHIOPIA,AFRICA,15-768-687-3665\n"
+/// );
+/// ```
+pub struct SupplierCsv<'a> {
+    inner: Supplier<'a>,
+}
+
+impl<'a> SupplierCsv<'a> {
+    pub fn new(inner: Supplier<'a>) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the CSV header for the Supplier table
+    pub fn header() -> &'static str {
+        "s_suppkey,s_name,s_address,s_city,s_nation,s_region,s_phone"
+    }
+}
+
+impl Display for SupplierCsv<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            // note must quote the address field as it may contain commas
+            "{},{},\"{}\",{},{},{},{}",
+            self.inner.s_suppkey,
+            self.inner.s_name,
+            self.inner.s_address,
+            self.inner.s_city,
+            self.inner.s_nation,
+            self.inner.s_region,
+            self.inner.s_phone
+        )
+    }
+}
+
+/// Write SSB [`Part`]s in CSV format.
+///
+/// # Example
+/// ```
+/// # use tpchgen::ssb::generators::PartGenerator;
+/// # use tpchgen::ssb::csv::PartCsv;
+/// # use std::fmt::Write;
+/// // Output the first 2 rows in CSV format
+/// let generator = PartGenerator::new(1.0, 1, 1);
+/// let mut csv = String::new();
+/// writeln!(&mut csv, "{}", PartCsv::header()).unwrap(); // write header
+/// for line in generator.iter().take(2) {
+///   // write line using CSV formatter
+///   writeln!(&mut csv, "{}", PartCsv::new(line)).unwrap();
+/// }
+/// assert_eq!(
+///   csv,
+///   "p_partkey,p_name,p_mfgr,p_category,p_brand1,p_color,p_type,p_size,p_container\n\
+///    1,lace spring,MFGR#1,MFGR#11,MFGR#1121,goldenrod,PROMO BURNISHED COPPER,7,JUMBO PKG\n\
+///    2,rosy metallic,MFGR#4,MFGR#43,MFGR#4318,blush,LARGE BRUSHED BRASS,1,LG CASE\n"
+/// );
+/// ```
+pub struct PartCsv<'a> {
+    inner: Part<'a>,
+}
+
+impl<'a> PartCsv<'a> {
+    pub fn new(inner: Part<'a>) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the CSV header for the Part table
+    pub fn header() -> &'static str {
+        "p_partkey,p_name,p_mfgr,p_category,p_brand1,p_color,p_type,p_size,p_container"
+    }
+}
+
+impl Display for PartCsv<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{},{},{},{},{},{}",
+            self.inner.p_partkey,
+            self.inner.p_name,
+            self.inner.p_mfgr,
+            self.inner.p_category,
+            self.inner.p_brand1,
+            self.inner.p_color,
+            self.inner.p_type,
+            self.inner.p_size,
+            self.inner.p_container
+        )
+    }
+}
+
+/// Write SSB [`Date`]s in CSV format.
+///
+/// # Example
+/// ```
+/// # use tpchgen::ssb::generators::DateGenerator;
+/// # use tpchgen::ssb::csv::DateCsv;
+/// # use std::fmt::Write;
+/// // Output the first 2 rows in CSV format
+/// let generator = DateGenerator::default();
+/// let mut csv = String::new();
+/// writeln!(&mut csv, "{}", DateCsv::header()).unwrap(); // write header
+/// for line in generator.iter().take(2) {
+///   // write line using CSV formatter
+///   writeln!(&mut csv, "{}", DateCsv::new(line)).unwrap();
+/// }
+/// assert_eq!(
+///   csv,
+///   "d_datekey,d_date,d_dayofweek,d_month,d_year,d_yearmonthnum,d_yearmonth,d_daynuminweek,d_daynuminmonth,d_daynuminyear,d_monthnuminyear,d_weeknuminyear,d_sellingseason,d_lastdayinweekfl,d_lastdayinmonthfl,d_holidayfl,d_weekdayfl\n\
+///    19920101,\"January 1, 1992\",Wednesday,January,1992,199201,Jan1992,4,1,1,1,1,Winter,0,0,1,1\n\
+///    19920102,\"January 2, 1992\",Thursday,January,1992,199201,Jan1992,5,2,2,1,1,Winter,0,0,0,1\n"
+/// );
+/// ```
+pub struct DateCsv {
+    inner: Date,
+}
+
+impl DateCsv {
+    pub fn new(inner: Date) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the CSV header for the Date table
+    pub fn header() -> &'static str {
+        "d_datekey,d_date,d_dayofweek,d_month,d_year,d_yearmonthnum,d_yearmonth,d_daynuminweek,d_daynuminmonth,d_daynuminyear,d_monthnuminyear,d_weeknuminyear,d_sellingseason,d_lastdayinweekfl,d_lastdayinmonthfl,d_holidayfl,d_weekdayfl"
+    }
+}
+
+impl Display for DateCsv {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            // note must quote the date field as it contains a comma
+            "{},\"{}\",{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.inner.d_datekey,
+            self.inner.d_date,
+            self.inner.d_dayofweek,
+            self.inner.d_month,
+            self.inner.d_year,
+            self.inner.d_yearmonthnum,
+            self.inner.d_yearmonth,
+            self.inner.d_daynuminweek,
+            self.inner.d_daynuminmonth,
+            self.inner.d_daynuminyear,
+            self.inner.d_monthnuminyear,
+            self.inner.d_weeknuminyear,
+            self.inner.d_sellingseason,
+            u8::from(self.inner.d_lastdayinweekfl),
+            u8::from(self.inner.d_lastdayinmonthfl),
+            u8::from(self.inner.d_holidayfl),
+            u8::from(self.inner.d_weekdayfl)
+        )
+    }
+}
+
+/// Write [`LineOrder`]s in CSV format.
+///
+/// # Example
+/// ```
+/// # use tpchgen::ssb::generators::LineOrderGenerator;
+/// # use tpchgen::ssb::csv::LineOrderCsv;
+/// # use std::fmt::Write;
+/// // Output the first 2 rows in CSV format
+/// let generator = LineOrderGenerator::new(1.0, 1, 1);
+/// let mut csv = String::new();
+/// writeln!(&mut csv, "{}", LineOrderCsv::header()).unwrap(); // write header
+/// for line in generator.iter().take(2) {
+///   // write line using CSV formatter
+///   writeln!(&mut csv, "{}", LineOrderCsv::new(line)).unwrap();
+/// }
+/// assert_eq!(
+///   csv,
+///   "lo_orderkey,lo_linenumber,lo_custkey,lo_partkey,lo_suppkey,lo_orderdate,lo_orderpriority,lo_shippriority,lo_quantity,lo_extendedprice,lo_ordtotalprice,lo_discount,lo_revenue,lo_supplycost,lo_tax,lo_commitdate,lo_shipmode\n\
+///    1,1,7381,155190,828,19960102,5-LOW,0,17,2116823,17366547,4,2032150,74711,2,19960212,TRUCK\n\
+///    1,2,7381,67310,163,19960102,5-LOW,0,36,4598316,17366547,9,4184467,76638,6,19960228,MAIL\n"
+/// );
+/// ```
+pub struct LineOrderCsv<'a> {
+    inner: LineOrder<'a>,
+}
+
+impl<'a> LineOrderCsv<'a> {
+    pub fn new(inner: LineOrder<'a>) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the CSV header for the LineOrder table
+    pub fn header() -> &'static str {
+        "lo_orderkey,lo_linenumber,lo_custkey,lo_partkey,lo_suppkey,lo_orderdate,lo_orderpriority,lo_shippriority,lo_quantity,lo_extendedprice,lo_ordtotalprice,lo_discount,lo_revenue,lo_supplycost,lo_tax,lo_commitdate,lo_shipmode"
+    }
+}
+
+impl Display for LineOrderCsv<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.inner.lo_orderkey,
+            self.inner.lo_linenumber,
+            self.inner.lo_custkey,
+            self.inner.lo_partkey,
+            self.inner.lo_suppkey,
+            self.inner.lo_orderdate,
+            self.inner.lo_orderpriority,
+            self.inner.lo_shippriority,
+            self.inner.lo_quantity,
+            self.inner.lo_extendedprice,
+            self.inner.lo_ordtotalprice,
+            self.inner.lo_discount,
+            self.inner.lo_revenue,
+            self.inner.lo_supplycost,
+            self.inner.lo_tax,
+            self.inner.lo_commitdate,
+            self.inner.lo_shipmode
+        )
+    }
+}
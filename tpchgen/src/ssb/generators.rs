@@ -0,0 +1,1532 @@
+//! Generators for each SSB table, see the [`ssb`](crate::ssb) module
+use crate::dates::{DateRange, GenerateUtils, TPCHDate, MIN_GENERATE_DATE, TOTAL_DATE_RANGE};
+use crate::distribution::Distributions;
+use crate::generators::{
+    self as tpch, CustomerName, LineItemGenerator, OrderGenerator, SupplierName,
+};
+use crate::random::{
+    stream_seed, PhoneNumberInstance, RandomAlphaNumeric, RandomAlphaNumericInstance,
+    RandomBoundedInt, RandomBoundedLong, RandomPhoneNumber, RandomString, RandomStringSequence,
+    RowRandomInt, StringSequenceInstance,
+};
+use crate::text::TextPool;
+use core::fmt;
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+/// A city, formatted as the first 9 characters of the nation name, padded
+/// with spaces, followed by a digit, e.g. `"UNITED KI1"` or `"PERU     0"`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct City<'a> {
+    nation: &'a str,
+    number: i32,
+}
+
+impl<'a> City<'a> {
+    /// Creates the city `number` (`0..=9`) of `nation`
+    pub fn new(nation: &'a str, number: i32) -> Self {
+        City { nation, number }
+    }
+}
+
+impl fmt::Display for City<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:<9.9}{}", self.nation, self.number)
+    }
+}
+
+/// Average length of the customer and supplier addresses
+const ADDRESS_AVERAGE_LENGTH: i32 = 15;
+
+/// Returns the nation and region names of a TPC-H nation key
+fn nation_and_region(distributions: &Distributions, nation_key: i64) -> (&str, &str) {
+    let nations = distributions.nations();
+    let region_key = nations.get_weight(nation_key as usize);
+    (
+        nations.get_value(nation_key as usize),
+        distributions.regions().get_value(region_key as usize),
+    )
+}
+
+/// The CUSTOMER table
+///
+/// The Display trait is implemented to format the customer data as a string
+/// in the `ssb-dbgen` 'tbl' format.
+///
+/// ```text
+/// 1|Customer#000000001|j5JsirBM9P|MOROCCO  0|MOROCCO|AFRICA|25-989-741-2988|BUILDING|
+/// 2|Customer#000000002|487LW1dovn6Q4dMVym|JORDAN   1|JORDAN|MIDDLE EAST|23-768-687-3665|AUTOMOBILE|
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Customer<'a> {
+    /// Primary key
+    pub c_custkey: i64,
+    /// Customer name
+    pub c_name: CustomerName,
+    /// Customer address
+    pub c_address: RandomAlphaNumericInstance,
+    /// Customer city
+    pub c_city: City<'a>,
+    /// Customer nation name
+    pub c_nation: &'a str,
+    /// Customer region name
+    pub c_region: &'a str,
+    /// Customer phone number
+    pub c_phone: PhoneNumberInstance,
+    /// Customer market segment
+    pub c_mktsegment: &'a str,
+}
+
+impl fmt::Display for Customer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}|{}|{}|",
+            self.c_custkey,
+            self.c_name,
+            self.c_address,
+            self.c_city,
+            self.c_nation,
+            self.c_region,
+            self.c_phone,
+            self.c_mktsegment
+        )
+    }
+}
+
+/// Generator for the SSB Customer table data
+#[derive(Debug, Clone)]
+pub struct CustomerGenerator<'a> {
+    scale_factor: f64,
+    row_range: Range<i64>,
+    distributions: &'a Distributions,
+    text_pool: &'a TextPool,
+    seed: u64,
+}
+
+impl<'a> CustomerGenerator<'a> {
+    /// Base scale for customer generation
+    const SCALE_BASE: i32 = 30_000;
+
+    /// Creates a new CustomerGenerator with the given scale factor
+    ///
+    /// Note the generator's lifetime is `&'static`. See
+    /// [`NationGenerator`](tpch::NationGenerator) for more details.
+    pub fn new(scale_factor: f64, part: i32, part_count: i32) -> CustomerGenerator<'static> {
+        // Note: use explicit lifetime to ensure this remains `&'static`
+        Self::new_with_distributions_and_text_pool(
+            scale_factor,
+            part,
+            part_count,
+            Distributions::static_default(),
+            TextPool::get_or_init_default(),
+        )
+    }
+
+    /// Creates a CustomerGenerator with specified distributions and text pool
+    pub fn new_with_distributions_and_text_pool<'b>(
+        scale_factor: f64,
+        part: i32,
+        part_count: i32,
+        distributions: &'b Distributions,
+        text_pool: &'b TextPool,
+    ) -> CustomerGenerator<'b> {
+        CustomerGenerator {
+            scale_factor,
+            row_range: GenerateUtils::calculate_range(
+                Self::SCALE_BASE,
+                scale_factor,
+                part,
+                part_count,
+            ),
+            distributions,
+            text_pool,
+            seed: 0,
+        }
+    }
+
+    /// Return the row count for the given scale factor and generator part count
+    pub fn calculate_row_count(scale_factor: f64, part: i32, part_count: i32) -> i64 {
+        GenerateUtils::calculate_row_count(Self::SCALE_BASE, scale_factor, part, part_count)
+    }
+
+    /// Restricts the generator to the rows with indexes in `range`, instead
+    /// of its part. See [`tpch::CustomerGenerator::with_row_range`]
+    pub fn with_row_range(mut self, range: Range<i64>) -> Self {
+        self.row_range =
+            GenerateUtils::clamp_range(range, Self::calculate_row_count(self.scale_factor, 1, 1));
+        self
+    }
+
+    /// Generates a different dataset by using `seed` for the random number
+    /// generators. See [`tpch::NationGenerator::with_seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns an iterator over the customer rows
+    pub fn iter(&self) -> CustomerGeneratorIterator<'a> {
+        CustomerGeneratorIterator::new(
+            self.scale_factor,
+            self.distributions,
+            self.text_pool,
+            self.row_range.clone(),
+            self.seed,
+        )
+    }
+}
+
+impl<'a> IntoIterator for CustomerGenerator<'a> {
+    type Item = Customer<'a>;
+    type IntoIter = CustomerGeneratorIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator that generates SSB Customer rows
+///
+/// The name, nation, phone and market segment come from the TPC-H customer
+/// with the same key. The address is shorter than in TPC-H.
+#[derive(Debug)]
+pub struct CustomerGeneratorIterator<'a> {
+    inner: tpch::CustomerGeneratorIterator<'a>,
+    address_random: RandomAlphaNumeric,
+    city_random: RandomBoundedInt,
+    distributions: &'a Distributions,
+}
+
+impl<'a> CustomerGeneratorIterator<'a> {
+    fn new(
+        scale_factor: f64,
+        distributions: &'a Distributions,
+        text_pool: &'a TextPool,
+        row_range: Range<i64>,
+        seed: u64,
+    ) -> Self {
+        let mut address_random =
+            RandomAlphaNumeric::new(stream_seed(881155353, seed), ADDRESS_AVERAGE_LENGTH)
+                .without_overflow();
+        let mut city_random = RandomBoundedInt::new(stream_seed(1, seed), 0, 9);
+
+        address_random.advance_rows(row_range.start);
+        city_random.advance_rows(row_range.start);
+
+        let inner = tpch::CustomerGenerator::new_with_distributions_and_text_pool(
+            scale_factor,
+            1,
+            1,
+            distributions,
+            text_pool,
+        )
+        .with_seed(seed)
+        .generate_range(row_range);
+
+        CustomerGeneratorIterator {
+            inner,
+            address_random,
+            city_random,
+            distributions,
+        }
+    }
+}
+
+impl<'a> Iterator for CustomerGeneratorIterator<'a> {
+    type Item = Customer<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let customer = self.inner.next()?;
+        let (nation, region) = nation_and_region(self.distributions, customer.c_nationkey);
+
+        let customer = Customer {
+            c_custkey: customer.c_custkey,
+            c_name: customer.c_name,
+            c_address: self.address_random.next_value(),
+            c_city: City::new(nation, self.city_random.next_value()),
+            c_nation: nation,
+            c_region: region,
+            c_phone: customer.c_phone,
+            c_mktsegment: customer.c_mktsegment,
+        };
+
+        self.address_random.row_finished();
+        self.city_random.row_finished();
+
+        Some(customer)
+    }
+}
+
+/// The SUPPLIER table
+///
+/// The Display trait is implemented to format the supplier data as a string
+/// in the `ssb-dbgen` 'tbl' format.
+///
+/// ```text
+/// 1|Supplier#000000001|sdrGnXCDRcfriBvY0KL,i|PERU     0|PERU|AMERICA|27-989-741-2988|
+/// 2|Supplier#000000002|TRMhVHz3XiFu|ETHIOPIA 1|ETHIOPIA|AFRICA|15-768-687-3665|
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Supplier<'a> {
+    /// Primary key
+    pub s_suppkey: i64,
+    /// Supplier name
+    pub s_name: SupplierName,
+    /// Supplier address
+    pub s_address: RandomAlphaNumericInstance,
+    /// Supplier city
+    pub s_city: City<'a>,
+    /// Supplier nation name
+    pub s_nation: &'a str,
+    /// Supplier region name
+    pub s_region: &'a str,
+    /// Supplier phone number
+    pub s_phone: PhoneNumberInstance,
+}
+
+impl fmt::Display for Supplier<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}|{}|",
+            self.s_suppkey,
+            self.s_name,
+            self.s_address,
+            self.s_city,
+            self.s_nation,
+            self.s_region,
+            self.s_phone
+        )
+    }
+}
+
+/// Generator for the SSB Supplier table data
+#[derive(Debug, Clone)]
+pub struct SupplierGenerator<'a> {
+    scale_factor: f64,
+    row_range: Range<i64>,
+    distributions: &'a Distributions,
+    text_pool: &'a TextPool,
+    seed: u64,
+}
+
+impl<'a> SupplierGenerator<'a> {
+    /// Base scale for supplier generation
+    const SCALE_BASE: i32 = 2_000;
+
+    /// Creates a new SupplierGenerator with the given scale factor
+    ///
+    /// Note the generator's lifetime is `&'static`. See
+    /// [`NationGenerator`](tpch::NationGenerator) for more details.
+    pub fn new(scale_factor: f64, part: i32, part_count: i32) -> SupplierGenerator<'static> {
+        // Note: use explicit lifetime to ensure this remains `&'static`
+        Self::new_with_distributions_and_text_pool(
+            scale_factor,
+            part,
+            part_count,
+            Distributions::static_default(),
+            TextPool::get_or_init_default(),
+        )
+    }
+
+    /// Creates a SupplierGenerator with specified distributions and text pool
+    pub fn new_with_distributions_and_text_pool<'b>(
+        scale_factor: f64,
+        part: i32,
+        part_count: i32,
+        distributions: &'b Distributions,
+        text_pool: &'b TextPool,
+    ) -> SupplierGenerator<'b> {
+        SupplierGenerator {
+            scale_factor,
+            row_range: GenerateUtils::calculate_range(
+                Self::SCALE_BASE,
+                scale_factor,
+                part,
+                part_count,
+            ),
+            distributions,
+            text_pool,
+            seed: 0,
+        }
+    }
+
+    /// Return the row count for the given scale factor and generator part count
+    pub fn calculate_row_count(scale_factor: f64, part: i32, part_count: i32) -> i64 {
+        GenerateUtils::calculate_row_count(Self::SCALE_BASE, scale_factor, part, part_count)
+    }
+
+    /// Restricts the generator to the rows with indexes in `range`, instead
+    /// of its part. See [`tpch::SupplierGenerator::with_row_range`]
+    pub fn with_row_range(mut self, range: Range<i64>) -> Self {
+        self.row_range =
+            GenerateUtils::clamp_range(range, Self::calculate_row_count(self.scale_factor, 1, 1));
+        self
+    }
+
+    /// Generates a different dataset by using `seed` for the random number
+    /// generators. See [`tpch::NationGenerator::with_seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns an iterator over the supplier rows
+    pub fn iter(&self) -> SupplierGeneratorIterator<'a> {
+        SupplierGeneratorIterator::new(
+            self.scale_factor,
+            self.distributions,
+            self.text_pool,
+            self.row_range.clone(),
+            self.seed,
+        )
+    }
+}
+
+impl<'a> IntoIterator for SupplierGenerator<'a> {
+    type Item = Supplier<'a>;
+    type IntoIter = SupplierGeneratorIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator that generates SSB Supplier rows
+///
+/// The name and nation come from the TPC-H supplier with the same key. Like
+/// in `ssb-dbgen`, the address is shorter than in TPC-H and the phone number
+/// uses the random number stream of the TPC-H customer phone numbers.
+#[derive(Debug)]
+pub struct SupplierGeneratorIterator<'a> {
+    inner: tpch::SupplierGeneratorIterator<'a>,
+    address_random: RandomAlphaNumeric,
+    city_random: RandomBoundedInt,
+    phone_random: RandomPhoneNumber,
+    distributions: &'a Distributions,
+}
+
+impl<'a> SupplierGeneratorIterator<'a> {
+    fn new(
+        scale_factor: f64,
+        distributions: &'a Distributions,
+        text_pool: &'a TextPool,
+        row_range: Range<i64>,
+        seed: u64,
+    ) -> Self {
+        let mut address_random =
+            RandomAlphaNumeric::new(stream_seed(706178559, seed), ADDRESS_AVERAGE_LENGTH)
+                .without_overflow();
+        let mut city_random = RandomBoundedInt::new(stream_seed(1, seed), 0, 9);
+        let mut phone_random = RandomPhoneNumber::new(stream_seed(1521138112, seed));
+
+        address_random.advance_rows(row_range.start);
+        city_random.advance_rows(row_range.start);
+        phone_random.advance_rows(row_range.start);
+
+        let inner = tpch::SupplierGenerator::new_with_distributions_and_text_pool(
+            scale_factor,
+            1,
+            1,
+            distributions,
+            text_pool,
+        )
+        .with_seed(seed)
+        .generate_range(row_range);
+
+        SupplierGeneratorIterator {
+            inner,
+            address_random,
+            city_random,
+            phone_random,
+            distributions,
+        }
+    }
+}
+
+impl<'a> Iterator for SupplierGeneratorIterator<'a> {
+    type Item = Supplier<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let supplier = self.inner.next()?;
+        let (nation, region) = nation_and_region(self.distributions, supplier.s_nationkey);
+
+        let supplier = Supplier {
+            s_suppkey: supplier.s_suppkey,
+            s_name: supplier.s_name,
+            s_address: self.address_random.next_value(),
+            s_city: City::new(nation, self.city_random.next_value()),
+            s_nation: nation,
+            s_region: region,
+            s_phone: self.phone_random.next_value(supplier.s_nationkey),
+        };
+
+        self.address_random.row_finished();
+        self.city_random.row_finished();
+        self.phone_random.row_finished();
+
+        Some(supplier)
+    }
+}
+
+/// A part manufacturer, formatted as `"MFGR#<n>"`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ManufacturerName(i32);
+
+impl ManufacturerName {
+    pub fn new(value: i32) -> Self {
+        ManufacturerName(value)
+    }
+}
+
+impl fmt::Display for ManufacturerName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MFGR#{}", self.0)
+    }
+}
+
+/// A part category, formatted as `"MFGR#<n>"` where `n` is the manufacturer
+/// number followed by a digit, e.g. `"MFGR#13"`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CategoryName(i32);
+
+impl CategoryName {
+    pub fn new(value: i32) -> Self {
+        CategoryName(value)
+    }
+}
+
+impl fmt::Display for CategoryName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MFGR#{}", self.0)
+    }
+}
+
+/// A part brand, formatted as the category followed by the brand number,
+/// e.g. `"MFGR#1310"`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BrandName {
+    category: i32,
+    brand: i32,
+}
+
+impl BrandName {
+    /// Creates the brand `brand` (`1..=40`) of the category `category`
+    pub fn new(category: i32, brand: i32) -> Self {
+        BrandName { category, brand }
+    }
+}
+
+impl fmt::Display for BrandName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MFGR#{}{}", self.category, self.brand)
+    }
+}
+
+/// The PART table
+///
+/// The Display trait is implemented to format the part data as a string
+/// in the `ssb-dbgen` 'tbl' format.
+///
+/// ```text
+/// 1|lace spring|MFGR#1|MFGR#11|MFGR#1121|goldenrod|PROMO BURNISHED COPPER|7|JUMBO PKG|
+/// 2|rosy metallic|MFGR#4|MFGR#43|MFGR#4318|blush|LARGE BRUSHED BRASS|1|LG CASE|
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Part<'a> {
+    /// Primary key
+    pub p_partkey: i64,
+    /// Part name
+    pub p_name: StringSequenceInstance<'a>,
+    /// Part manufacturer
+    pub p_mfgr: ManufacturerName,
+    /// Part category
+    pub p_category: CategoryName,
+    /// Part brand
+    pub p_brand1: BrandName,
+    /// Part color
+    pub p_color: &'a str,
+    /// Part type
+    pub p_type: &'a str,
+    /// Part size
+    pub p_size: i32,
+    /// Part container
+    pub p_container: &'a str,
+}
+
+impl fmt::Display for Part<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}|{}|{}|{}|",
+            self.p_partkey,
+            self.p_name,
+            self.p_mfgr,
+            self.p_category,
+            self.p_brand1,
+            self.p_color,
+            self.p_type,
+            self.p_size,
+            self.p_container
+        )
+    }
+}
+
+/// Generator for the SSB Part table data
+#[derive(Debug, Clone)]
+pub struct PartGenerator<'a> {
+    scale_factor: f64,
+    row_range: Range<i64>,
+    distributions: &'a Distributions,
+    text_pool: &'a TextPool,
+    seed: u64,
+}
+
+impl<'a> PartGenerator<'a> {
+    /// Base scale for part generation
+    const SCALE_BASE: i64 = 200_000;
+
+    // Constants for part generation
+    const NAME_WORDS: i32 = 2;
+    const MANUFACTURER_MIN: i32 = 1;
+    const MANUFACTURER_MAX: i32 = 5;
+    const CATEGORY_MIN: i32 = 1;
+    const CATEGORY_MAX: i32 = 5;
+    const BRAND_MIN: i32 = 1;
+    const BRAND_MAX: i32 = 40;
+
+    /// Creates a new PartGenerator with the given scale factor
+    ///
+    /// Note the generator's lifetime is `&'static`. See
+    /// [`NationGenerator`](tpch::NationGenerator) for more details.
+    pub fn new(scale_factor: f64, part: i32, part_count: i32) -> PartGenerator<'static> {
+        // Note: use explicit lifetime to ensure this remains `&'static`
+        Self::new_with_distributions_and_text_pool(
+            scale_factor,
+            part,
+            part_count,
+            Distributions::static_default(),
+            TextPool::get_or_init_default(),
+        )
+    }
+
+    /// Creates a PartGenerator with specified distributions and text pool
+    pub fn new_with_distributions_and_text_pool<'b>(
+        scale_factor: f64,
+        part: i32,
+        part_count: i32,
+        distributions: &'b Distributions,
+        text_pool: &'b TextPool,
+    ) -> PartGenerator<'b> {
+        PartGenerator {
+            scale_factor,
+            row_range: GenerateUtils::split_range(
+                0..Self::total_row_count(scale_factor),
+                part as i64,
+                part_count as i64,
+            ),
+            distributions,
+            text_pool,
+            seed: 0,
+        }
+    }
+
+    /// Returns the number of parts at the given scale factor
+    ///
+    /// Unlike TPC-H, the number of parts grows logarithmically with the scale
+    /// factor: `200,000 * floor(1 + log2(SF))` for scale factors of at least 1.
+    fn total_row_count(scale_factor: f64) -> i64 {
+        if scale_factor < 1.0 {
+            (Self::SCALE_BASE as f64 * scale_factor) as i64
+        } else {
+            Self::SCALE_BASE * (1.0 + scale_factor.log2()).floor() as i64
+        }
+    }
+
+    /// Return the row count for the given scale factor and generator part count
+    ///
+    /// ```
+    /// # use tpchgen::ssb::generators::PartGenerator;
+    /// assert_eq!(PartGenerator::calculate_row_count(1.0, 1, 1), 200_000);
+    /// assert_eq!(PartGenerator::calculate_row_count(10.0, 1, 1), 800_000);
+    /// ```
+    pub fn calculate_row_count(scale_factor: f64, part: i32, part_count: i32) -> i64 {
+        let range = GenerateUtils::split_range(
+            0..Self::total_row_count(scale_factor),
+            part as i64,
+            part_count as i64,
+        );
+        range.end - range.start
+    }
+
+    /// Restricts the generator to the rows with indexes in `range`, instead
+    /// of its part. See [`tpch::PartGenerator::with_row_range`]
+    pub fn with_row_range(mut self, range: Range<i64>) -> Self {
+        self.row_range =
+            GenerateUtils::clamp_range(range, Self::total_row_count(self.scale_factor));
+        self
+    }
+
+    /// Generates a different dataset by using `seed` for the random number
+    /// generators. See [`tpch::NationGenerator::with_seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns an iterator over the part rows
+    pub fn iter(&self) -> PartGeneratorIterator<'a> {
+        PartGeneratorIterator::new(
+            self.scale_factor,
+            self.distributions,
+            self.text_pool,
+            self.row_range.clone(),
+            self.seed,
+        )
+    }
+}
+
+impl<'a> IntoIterator for PartGenerator<'a> {
+    type Item = Part<'a>;
+    type IntoIter = PartGeneratorIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator that generates SSB Part rows
+///
+/// The type, size and container come from the TPC-H part with the same key.
+/// Like in `ssb-dbgen`, the color and the two words of the name are the
+/// first words of a permutation of the TPC-H part colors.
+#[derive(Debug)]
+pub struct PartGeneratorIterator<'a> {
+    inner: tpch::PartGeneratorIterator<'a>,
+    name_random: RandomStringSequence<'a>,
+    manufacturer_random: RowRandomInt,
+    brand_random: RandomBoundedInt,
+}
+
+impl<'a> PartGeneratorIterator<'a> {
+    fn new(
+        scale_factor: f64,
+        distributions: &'a Distributions,
+        text_pool: &'a TextPool,
+        row_range: Range<i64>,
+        seed: u64,
+    ) -> Self {
+        // same stream as the TPC-H part name
+        let mut name_random = RandomStringSequence::new(
+            stream_seed(709314158, seed),
+            PartGenerator::NAME_WORDS + 1,
+            distributions.part_colors(),
+        )
+        .with_full_permutation();
+        // the manufacturer and the category
+        let mut manufacturer_random = RowRandomInt::new(stream_seed(1, seed), 2);
+        let mut brand_random = RandomBoundedInt::new(
+            stream_seed(46831694, seed),
+            PartGenerator::BRAND_MIN,
+            PartGenerator::BRAND_MAX,
+        );
+
+        name_random.advance_rows(row_range.start);
+        manufacturer_random.advance_rows(row_range.start);
+        brand_random.advance_rows(row_range.start);
+
+        // The TPC-H table has at least as many parts at every scale factor
+        let inner = tpch::PartGenerator::new_with_distributions_and_text_pool(
+            scale_factor,
+            1,
+            1,
+            distributions,
+            text_pool,
+        )
+        .with_seed(seed)
+        .generate_range(row_range);
+
+        PartGeneratorIterator {
+            inner,
+            name_random,
+            manufacturer_random,
+            brand_random,
+        }
+    }
+}
+
+impl<'a> Iterator for PartGeneratorIterator<'a> {
+    type Item = Part<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let part = self.inner.next()?;
+
+        let mut name = self.name_random.next_value();
+        let color = name.remove_first().expect("the name has a color");
+        let manufacturer = self.manufacturer_random.next_int(
+            PartGenerator::MANUFACTURER_MIN,
+            PartGenerator::MANUFACTURER_MAX,
+        );
+        let category = manufacturer * 10
+            + self
+                .manufacturer_random
+                .next_int(PartGenerator::CATEGORY_MIN, PartGenerator::CATEGORY_MAX);
+
+        let part = Part {
+            p_partkey: part.p_partkey,
+            p_name: name,
+            p_mfgr: ManufacturerName::new(manufacturer),
+            p_category: CategoryName::new(category),
+            p_brand1: BrandName::new(category, self.brand_random.next_value()),
+            p_color: color,
+            p_type: part.p_type,
+            p_size: part.p_size,
+            p_container: part.p_container,
+        };
+
+        self.name_random.row_finished();
+        self.manufacturer_random.row_finished();
+        self.brand_random.row_finished();
+
+        Some(part)
+    }
+}
+
+/// Names of the days of the week, starting on Sunday
+const DAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// Names of the months, starting in January
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// The DATE table
+///
+/// The Display trait is implemented to format the date data as a string
+/// in the `ssb-dbgen` 'tbl' format, with the flags formatted as `0` or `1`.
+///
+/// ```text
+/// 19920101|January 1, 1992|Wednesday|January|1992|199201|Jan1992|4|1|1|1|1|Winter|0|0|1|1|
+/// 19920102|January 2, 1992|Thursday|January|1992|199201|Jan1992|5|2|2|1|1|Winter|0|0|0|1|
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Date {
+    /// Primary key, the date as `yyyymmdd`
+    pub d_datekey: i32,
+    /// The date, e.g. `January 1, 1992`
+    pub d_date: String,
+    /// Name of the day of the week, e.g. `Wednesday`
+    pub d_dayofweek: &'static str,
+    /// Name of the month, e.g. `January`
+    pub d_month: &'static str,
+    /// Year, e.g. `1992`
+    pub d_year: i32,
+    /// Year and month as `yyyymm`
+    pub d_yearmonthnum: i32,
+    /// Abbreviated month and year, e.g. `Jan1992`
+    pub d_yearmonth: String,
+    /// Day of the week, from `1` (Sunday) to `7` (Saturday)
+    pub d_daynuminweek: i32,
+    /// Day of the month, from `1`
+    pub d_daynuminmonth: i32,
+    /// Day of the year, from `1`
+    pub d_daynuminyear: i32,
+    /// Month, from `1` to `12`
+    pub d_monthnuminyear: i32,
+    /// Week of the year, from `1`, where the first week starts on January 1st
+    pub d_weeknuminyear: i32,
+    /// Selling season: `Christmas`, `Winter`, `Spring`, `Summer` or `Fall`
+    pub d_sellingseason: &'static str,
+    /// True for the last day of the week (Saturday)
+    pub d_lastdayinweekfl: bool,
+    /// True for the last day of the month
+    pub d_lastdayinmonthfl: bool,
+    /// True for New Year's Day, Independence Day, Thanksgiving and Christmas
+    pub d_holidayfl: bool,
+    /// True from Monday to Friday
+    pub d_weekdayfl: bool,
+}
+
+impl Date {
+    /// Creates the row of `date`
+    fn new(date: TPCHDate) -> Self {
+        let (year, month, day) = date.to_ymd();
+        let year = 1900 + year;
+        let month_name = MONTH_NAMES[month as usize - 1];
+        let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+
+        // 1970-01-01 was a Thursday
+        let day_of_week = (date.to_unix_epoch() + 4) % 7;
+        let day_of_year = days_before_month(month, is_leap_year) + day;
+        let days_in_month =
+            days_before_month(month + 1, is_leap_year) - days_before_month(month, is_leap_year);
+
+        let holiday = matches!((month, day), (1, 1) | (7, 4) | (12, 25))
+            // Thanksgiving, the fourth Thursday of November
+            || (month == 11 && day_of_week == 4 && (22..=28).contains(&day));
+
+        Date {
+            d_datekey: year * 10000 + month * 100 + day,
+            d_date: format!("{month_name} {day}, {year}"),
+            d_dayofweek: DAY_NAMES[day_of_week as usize],
+            d_month: month_name,
+            d_year: year,
+            d_yearmonthnum: year * 100 + month,
+            d_yearmonth: format!("{}{year}", &month_name[..3]),
+            d_daynuminweek: day_of_week + 1,
+            d_daynuminmonth: day,
+            d_daynuminyear: day_of_year,
+            d_monthnuminyear: month,
+            d_weeknuminyear: (day_of_year - 1) / 7 + 1,
+            d_sellingseason: match month {
+                12 => "Christmas",
+                1 | 2 => "Winter",
+                3..=5 => "Spring",
+                6..=8 => "Summer",
+                _ => "Fall",
+            },
+            d_lastdayinweekfl: day_of_week == 6,
+            d_lastdayinmonthfl: day == days_in_month,
+            d_holidayfl: holiday,
+            d_weekdayfl: (1..=5).contains(&day_of_week),
+        }
+    }
+}
+
+/// Returns the number of days of the year before the first day of `month`
+/// (`1..=13`)
+fn days_before_month(month: i32, is_leap_year: bool) -> i32 {
+    const DAYS_BEFORE_MONTH: [i32; 13] =
+        [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334, 365];
+    DAYS_BEFORE_MONTH[month as usize - 1] + i32::from(is_leap_year && month > 2)
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|",
+            self.d_datekey,
+            self.d_date,
+            self.d_dayofweek,
+            self.d_month,
+            self.d_year,
+            self.d_yearmonthnum,
+            self.d_yearmonth,
+            self.d_daynuminweek,
+            self.d_daynuminmonth,
+            self.d_daynuminyear,
+            self.d_monthnuminyear,
+            self.d_weeknuminyear,
+            self.d_sellingseason,
+            u8::from(self.d_lastdayinweekfl),
+            u8::from(self.d_lastdayinmonthfl),
+            u8::from(self.d_holidayfl),
+            u8::from(self.d_weekdayfl)
+        )
+    }
+}
+
+/// Generator for the SSB Date table data
+///
+/// The table has one row for each day from 1992-01-01 to 1998-12-31, for all
+/// scale factors.
+#[derive(Debug, Clone)]
+pub struct DateGenerator {
+    row_range: Range<i64>,
+}
+
+impl Default for DateGenerator {
+    fn default() -> Self {
+        // arguments are ignored
+        Self::new(1.0, 1, 1)
+    }
+}
+
+impl DateGenerator {
+    /// Creates a new DateGenerator
+    ///
+    /// The table has the same rows at every scale factor and is not split in
+    /// parts, so the arguments are ignored.
+    pub fn new(_scale_factor: f64, _part: i32, _part_count: i32) -> Self {
+        DateGenerator {
+            row_range: 0..TOTAL_DATE_RANGE as i64,
+        }
+    }
+
+    /// Return the row count, which is the same for all scale factors
+    pub fn calculate_row_count(_scale_factor: f64, _part: i32, _part_count: i32) -> i64 {
+        TOTAL_DATE_RANGE as i64
+    }
+
+    /// Restricts the generator to the rows with indexes in `range`
+    pub fn with_row_range(mut self, range: Range<i64>) -> Self {
+        self.row_range = GenerateUtils::clamp_range(range, TOTAL_DATE_RANGE as i64);
+        self
+    }
+
+    /// Returns an iterator over the date rows
+    pub fn iter(&self) -> DateGeneratorIterator {
+        DateGeneratorIterator {
+            row_range: self.row_range.clone(),
+        }
+    }
+}
+
+impl IntoIterator for DateGenerator {
+    type Item = Date;
+    type IntoIter = DateGeneratorIterator;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator that generates SSB Date rows
+#[derive(Debug)]
+pub struct DateGeneratorIterator {
+    row_range: Range<i64>,
+}
+
+impl Iterator for DateGeneratorIterator {
+    type Item = Date;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.row_range.next()?;
+        Some(Date::new(TPCHDate::new(MIN_GENERATE_DATE + index as i32)))
+    }
+}
+
+/// Lookup table of the `yyyymmdd` keys of the generated dates
+///
+/// index: dates generated by the data generator
+/// value: the date key, e.g. 19920101
+static DATE_KEYS: LazyLock<Vec<i32>> = LazyLock::new(|| {
+    (0..TOTAL_DATE_RANGE)
+        .map(|index| {
+            let (year, month, day) = TPCHDate::new(MIN_GENERATE_DATE + index).to_ymd();
+            (1900 + year) * 10000 + month * 100 + day
+        })
+        .collect()
+});
+
+/// Returns the `yyyymmdd` key of a generated date
+fn date_key(generated_date: i32) -> i32 {
+    DATE_KEYS[(generated_date - MIN_GENERATE_DATE) as usize]
+}
+
+/// The LINEORDER table
+///
+/// The Display trait is implemented to format the line order data as a string
+/// in the `ssb-dbgen` 'tbl' format. Prices are in cents, and dates are
+/// `yyyymmdd` keys of the DATE table.
+///
+/// ```text
+/// 1|1|7381|155190|828|19960102|5-LOW|0|17|2116823|17366547|4|2032150|74711|2|19960212|TRUCK|
+/// 1|2|7381|67310|163|19960102|5-LOW|0|36|4598316|17366547|9|4184467|76638|6|19960228|MAIL|
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LineOrder<'a> {
+    /// Order key
+    pub lo_orderkey: i64,
+    /// Line number within the order
+    pub lo_linenumber: i32,
+    /// Foreign key to CUSTOMER
+    pub lo_custkey: i64,
+    /// Foreign key to PART
+    pub lo_partkey: i64,
+    /// Foreign key to SUPPLIER
+    pub lo_suppkey: i64,
+    /// Order date, foreign key to DATE
+    pub lo_orderdate: i32,
+    /// Order priority
+    pub lo_orderpriority: &'a str,
+    /// Ship priority
+    pub lo_shippriority: i32,
+    /// Quantity
+    pub lo_quantity: i32,
+    /// Extended price (quantity times the part retail price), in cents
+    pub lo_extendedprice: i64,
+    /// Total price of the order, in cents
+    pub lo_ordtotalprice: i64,
+    /// Discount, in percent
+    pub lo_discount: i32,
+    /// Revenue (discounted extended price), in cents
+    pub lo_revenue: i64,
+    /// Supply cost, in cents
+    pub lo_supplycost: i64,
+    /// Tax, in percent
+    pub lo_tax: i32,
+    /// Commit date, foreign key to DATE
+    pub lo_commitdate: i32,
+    /// Ship mode
+    pub lo_shipmode: &'a str,
+}
+
+impl fmt::Display for LineOrder<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|",
+            self.lo_orderkey,
+            self.lo_linenumber,
+            self.lo_custkey,
+            self.lo_partkey,
+            self.lo_suppkey,
+            self.lo_orderdate,
+            self.lo_orderpriority,
+            self.lo_shippriority,
+            self.lo_quantity,
+            self.lo_extendedprice,
+            self.lo_ordtotalprice,
+            self.lo_discount,
+            self.lo_revenue,
+            self.lo_supplycost,
+            self.lo_tax,
+            self.lo_commitdate,
+            self.lo_shipmode
+        )
+    }
+}
+
+/// Generator for the SSB LineOrder table data
+#[derive(Debug, Clone)]
+pub struct LineOrderGenerator<'a> {
+    scale_factor: f64,
+    row_range: Range<i64>,
+    distributions: &'a Distributions,
+    seed: u64,
+}
+
+impl<'a> LineOrderGenerator<'a> {
+    /// Base scale for order generation
+    const ORDER_SCALE_BASE: i32 = 1_500_000;
+
+    // Constants for line order generation, the same as for TPC-H
+    const LINE_COUNT_MAX: i32 = 7;
+    const CUSTOMER_MORTALITY: i64 = 3; // portion with no orders
+    const COMMIT_DATE_MIN: i32 = 30;
+    const COMMIT_DATE_MAX: i32 = 90;
+
+    /// Creates a new LineOrderGenerator with the given scale factor
+    ///
+    /// Note the generator's lifetime is `&'static`. See
+    /// [`NationGenerator`](tpch::NationGenerator) for more details.
+    pub fn new(scale_factor: f64, part: i32, part_count: i32) -> LineOrderGenerator<'static> {
+        Self::new_with_distributions(
+            scale_factor,
+            part,
+            part_count,
+            Distributions::static_default(),
+        )
+    }
+
+    /// Creates a LineOrderGenerator with specified distributions
+    pub fn new_with_distributions(
+        scale_factor: f64,
+        part: i32,
+        part_count: i32,
+        distributions: &Distributions,
+    ) -> LineOrderGenerator<'_> {
+        LineOrderGenerator {
+            scale_factor,
+            row_range: GenerateUtils::calculate_range(
+                Self::ORDER_SCALE_BASE,
+                scale_factor,
+                part,
+                part_count,
+            ),
+            distributions,
+            seed: 0,
+        }
+    }
+
+    /// Returns the total number of orders at the given scale factor
+    pub fn order_count(scale_factor: f64) -> i64 {
+        GenerateUtils::calculate_row_count(Self::ORDER_SCALE_BASE, scale_factor, 1, 1)
+    }
+
+    /// Return the exact row count for the given scale factor and generator
+    /// part count
+    ///
+    /// The orders have the same number of lines as in TPC-H, see
+    /// [`LineItemGenerator::calculate_row_count`].
+    pub fn calculate_row_count(scale_factor: f64, part: i32, part_count: i32) -> i64 {
+        LineItemGenerator::calculate_row_count(scale_factor, part, part_count)
+    }
+
+    /// Restricts the generator to the orders with indexes in `range`, instead
+    /// of its part. See [`LineItemGenerator::with_row_range`]
+    pub fn with_row_range(mut self, range: Range<i64>) -> Self {
+        self.row_range = GenerateUtils::clamp_range(range, Self::order_count(self.scale_factor));
+        self
+    }
+
+    /// Generates a different dataset by using `seed` for the random number
+    /// generators. See [`tpch::NationGenerator::with_seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns an iterator over the line order rows
+    pub fn iter(&self) -> LineOrderGeneratorIterator<'a> {
+        LineOrderGeneratorIterator::new(
+            self.distributions,
+            self.scale_factor,
+            self.row_range.start,
+            self.row_range.end - self.row_range.start,
+            self.seed,
+        )
+    }
+}
+
+impl<'a> IntoIterator for LineOrderGenerator<'a> {
+    type Item = LineOrder<'a>;
+    type IntoIter = LineOrderGeneratorIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator that generates LineOrder rows
+///
+/// Uses the random streams of the TPC-H orders and line items, except for the
+/// customer, part and supplier keys which are drawn from the smaller SSB
+/// tables.
+#[derive(Debug)]
+pub struct LineOrderGeneratorIterator<'a> {
+    order_date_random: RandomBoundedInt,
+    line_count_random: RandomBoundedInt,
+    customer_key_random: RandomBoundedLong,
+    order_priority_random: RandomString<'a>,
+
+    quantity_random: RandomBoundedInt,
+    discount_random: RandomBoundedInt,
+    tax_random: RandomBoundedInt,
+    part_key_random: RandomBoundedLong,
+    supplier_key_random: RandomBoundedLong,
+    commit_date_random: RandomBoundedInt,
+    ship_mode_random: RandomString<'a>,
+
+    start_index: i64,
+    row_count: i64,
+    max_customer_key: i64,
+
+    index: i64,
+    /// The remaining line orders of the current order
+    line_orders: VecDeque<LineOrder<'a>>,
+}
+
+impl<'a> LineOrderGeneratorIterator<'a> {
+    fn new(
+        distributions: &'a Distributions,
+        scale_factor: f64,
+        start_index: i64,
+        row_count: i64,
+        seed: u64,
+    ) -> Self {
+        let use_64bits = scale_factor >= 30000.0;
+        let max_customer_key = CustomerGenerator::calculate_row_count(scale_factor, 1, 1);
+
+        let mut order_date_random =
+            OrderGenerator::create_order_date_random(seed, DateRange::default());
+        let mut line_count_random = OrderGenerator::create_line_count_random();
+        let mut customer_key_random = RandomBoundedLong::new(
+            stream_seed(851767375, seed),
+            use_64bits,
+            1,
+            max_customer_key,
+        );
+        let mut order_priority_random =
+            RandomString::new(stream_seed(591449447, seed), distributions.order_priority());
+
+        let mut quantity_random = LineItemGenerator::create_quantity_random(seed);
+        let mut discount_random = LineItemGenerator::create_discount_random(seed);
+        let mut tax_random = LineItemGenerator::create_tax_random(seed);
+        let mut part_key_random = RandomBoundedLong::new_with_seeds_per_row(
+            stream_seed(1808217256, seed),
+            use_64bits,
+            1,
+            PartGenerator::total_row_count(scale_factor),
+            LineOrderGenerator::LINE_COUNT_MAX,
+        );
+        let mut supplier_key_random = RandomBoundedLong::new_with_seeds_per_row(
+            stream_seed(2095021727, seed),
+            use_64bits,
+            1,
+            SupplierGenerator::calculate_row_count(scale_factor, 1, 1),
+            LineOrderGenerator::LINE_COUNT_MAX,
+        );
+        let mut commit_date_random = RandomBoundedInt::new_with_seeds_per_row(
+            stream_seed(904914315, seed),
+            LineOrderGenerator::COMMIT_DATE_MIN,
+            LineOrderGenerator::COMMIT_DATE_MAX,
+            LineOrderGenerator::LINE_COUNT_MAX,
+        );
+        let mut ship_mode_random = RandomString::new_with_expected_row_count(
+            stream_seed(675466456, seed),
+            distributions.ship_modes(),
+            LineOrderGenerator::LINE_COUNT_MAX,
+        );
+
+        // Advance all generators to the starting position
+        order_date_random.advance_rows(start_index);
+        line_count_random.advance_rows(start_index);
+        customer_key_random.advance_rows(start_index);
+        order_priority_random.advance_rows(start_index);
+
+        quantity_random.advance_rows(start_index);
+        discount_random.advance_rows(start_index);
+        tax_random.advance_rows(start_index);
+        part_key_random.advance_rows(start_index);
+        supplier_key_random.advance_rows(start_index);
+        commit_date_random.advance_rows(start_index);
+        ship_mode_random.advance_rows(start_index);
+
+        LineOrderGeneratorIterator {
+            order_date_random,
+            line_count_random,
+            customer_key_random,
+            order_priority_random,
+            quantity_random,
+            discount_random,
+            tax_random,
+            part_key_random,
+            supplier_key_random,
+            commit_date_random,
+            ship_mode_random,
+            start_index,
+            row_count,
+            max_customer_key,
+            index: 0,
+            line_orders: VecDeque::with_capacity(LineOrderGenerator::LINE_COUNT_MAX as usize),
+        }
+    }
+
+    /// Generates the line orders of the order with the given index
+    fn make_order(&mut self, index: i64) {
+        let order_key = OrderGenerator::make_order_key(index);
+        let order_date = self.order_date_random.next_value();
+
+        // generate customer key, taking into account customer mortality rate
+        let mut customer_key = self.customer_key_random.next_value();
+        let mut delta = 1;
+        while customer_key % LineOrderGenerator::CUSTOMER_MORTALITY == 0 {
+            customer_key += delta;
+            customer_key = customer_key.min(self.max_customer_key);
+            delta *= -1;
+        }
+
+        let order_priority = self.order_priority_random.next_value();
+
+        let mut total_price = 0;
+        let line_count = self.line_count_random.next_value();
+        for line_number in 1..=line_count {
+            let quantity = self.quantity_random.next_value();
+            let discount = self.discount_random.next_value();
+            let tax = self.tax_random.next_value();
+            let part_key = self.part_key_random.next_value();
+
+            let part_price = tpch::PartGeneratorIterator::calculate_part_price(part_key);
+            let extended_price = part_price * quantity as i64;
+            let discounted_price = extended_price * (100 - discount as i64);
+            total_price += ((discounted_price / 100) * (100 + tax as i64)) / 100;
+
+            self.line_orders.push_back(LineOrder {
+                lo_orderkey: order_key,
+                lo_linenumber: line_number,
+                lo_custkey: customer_key,
+                lo_partkey: part_key,
+                lo_suppkey: self.supplier_key_random.next_value(),
+                lo_orderdate: date_key(order_date),
+                lo_orderpriority: order_priority,
+                lo_shippriority: 0,
+                lo_quantity: quantity,
+                lo_extendedprice: extended_price,
+                lo_ordtotalprice: 0, // set once all the lines are generated
+                lo_discount: discount,
+                lo_revenue: discounted_price / 100,
+                lo_supplycost: 6 * part_price / 10,
+                lo_tax: tax,
+                lo_commitdate: date_key(order_date + self.commit_date_random.next_value()),
+                lo_shipmode: self.ship_mode_random.next_value(),
+            });
+        }
+
+        for line_order in &mut self.line_orders {
+            line_order.lo_ordtotalprice = total_price;
+        }
+    }
+
+    /// Advances all the random streams to the next order
+    fn finish_row(&mut self) {
+        self.order_date_random.row_finished();
+        self.line_count_random.row_finished();
+        self.customer_key_random.row_finished();
+        self.order_priority_random.row_finished();
+
+        self.quantity_random.row_finished();
+        self.discount_random.row_finished();
+        self.tax_random.row_finished();
+        self.part_key_random.row_finished();
+        self.supplier_key_random.row_finished();
+        self.commit_date_random.row_finished();
+        self.ship_mode_random.row_finished();
+
+        self.index += 1;
+    }
+}
+
+impl<'a> Iterator for LineOrderGeneratorIterator<'a> {
+    type Item = LineOrder<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.line_orders.is_empty() {
+            if self.index >= self.row_count {
+                return None;
+            }
+            self.make_order(self.start_index + self.index + 1);
+            self.finish_row();
+        }
+        self.line_orders.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_order_matches_line_items() {
+        // the shared columns are the same as TPC-H at SF 1
+        let line_orders = LineOrderGenerator::new(1.0, 1, 1).iter().take(100);
+        let line_items = LineItemGenerator::new(1.0, 1, 1).iter().take(100);
+        for (line_order, line_item) in line_orders.zip(line_items) {
+            assert_eq!(line_order.lo_orderkey, line_item.l_orderkey);
+            assert_eq!(
+                line_order.lo_linenumber as i64,
+                line_item.l_linenumber as i64
+            );
+            assert_eq!(line_order.lo_partkey, line_item.l_partkey);
+            assert_eq!(line_order.lo_extendedprice, line_item.l_extendedprice.0);
+            assert_eq!(line_order.lo_shipmode, line_item.l_shipmode);
+            assert_eq!(
+                line_order.lo_commitdate,
+                date_key(line_item.l_commitdate.into_inner() + MIN_GENERATE_DATE)
+            );
+        }
+    }
+
+    #[test]
+    fn test_line_order_row_ranges() {
+        let generator = LineOrderGenerator::new(0.01, 1, 1);
+        let all: Vec<_> = generator.iter().map(|row| row.to_string()).collect();
+        assert_eq!(
+            all.len() as i64,
+            LineOrderGenerator::calculate_row_count(0.01, 1, 1)
+        );
+
+        let parts: Vec<_> = (1..=3)
+            .flat_map(|part| LineOrderGenerator::new(0.01, part, 3).iter())
+            .map(|row| row.to_string())
+            .collect();
+        assert_eq!(parts, all);
+
+        let ranged: Vec<_> = [0..100, 100..15000]
+            .into_iter()
+            .flat_map(|range| generator.clone().with_row_range(range).iter())
+            .map(|row| row.to_string())
+            .collect();
+        assert_eq!(ranged, all);
+    }
+
+    #[test]
+    fn test_dimension_tables() {
+        let customer = CustomerGenerator::new(1.0, 1, 1).iter().next().unwrap();
+        let tpch_customer = tpch::CustomerGenerator::new(1.0, 1, 1)
+            .iter()
+            .next()
+            .unwrap();
+        assert_eq!(customer.c_name, tpch_customer.c_name);
+        assert_eq!(customer.c_phone, tpch_customer.c_phone);
+        assert_eq!(customer.c_nation, "MOROCCO");
+        assert_eq!(customer.c_region, "AFRICA");
+        assert!(customer.c_city.to_string().starts_with("MOROCCO  "));
+
+        // parts of the range generate the same rows
+        let suppliers: Vec<_> = SupplierGenerator::new(1.0, 1, 1).iter().collect();
+        assert_eq!(suppliers.len(), 2000);
+        let tail: Vec<_> = SupplierGenerator::new(1.0, 2, 2).iter().collect();
+        assert_eq!(&suppliers[1000..], &tail[..]);
+        assert_eq!(suppliers[1].s_city.to_string().len(), 10);
+
+        let part = PartGenerator::new(1.0, 1, 1).iter().next().unwrap();
+        assert_eq!(part.p_name.to_string(), "lace spring");
+        assert_eq!(part.p_mfgr.to_string(), "MFGR#1");
+        assert_eq!(part.p_category.to_string(), "MFGR#11");
+        assert_eq!(part.p_brand1.to_string(), "MFGR#1121");
+        assert_eq!(part.p_color, "goldenrod");
+
+        // SF 4 has 3 times as many parts as SF 1, with the same first rows
+        assert_eq!(PartGenerator::calculate_row_count(4.0, 1, 1), 600_000);
+        let last = PartGenerator::new(4.0, 1, 1)
+            .with_row_range(599_999..700_000)
+            .iter()
+            .collect::<Vec<_>>();
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].p_partkey, 600_000);
+    }
+
+    #[test]
+    fn test_dates() {
+        let dates: Vec<_> = DateGenerator::default().iter().collect();
+        assert_eq!(dates.len(), 2557);
+        assert_eq!(
+            dates[0].to_string(),
+            "19920101|January 1, 1992|Wednesday|January|1992|199201|Jan1992|4|1|1|1|1|Winter|0|0|1|1|"
+        );
+        assert_eq!(dates[2556].d_datekey, 19981231);
+        assert_eq!(dates[2556].d_daynuminyear, 365);
+        assert!(dates[2556].d_lastdayinmonthfl);
+
+        // leap day and Thanksgiving 1992
+        let leap_day = &dates[59];
+        assert_eq!(
+            (leap_day.d_datekey, leap_day.d_dayofweek),
+            (19920229, "Saturday")
+        );
+        assert!(leap_day.d_lastdayinweekfl && leap_day.d_lastdayinmonthfl);
+        let thanksgiving = dates.iter().find(|d| d.d_datekey == 19921126).unwrap();
+        assert!(thanksgiving.d_holidayfl);
+        assert_eq!(thanksgiving.d_dayofweek, "Thursday");
+        assert_eq!(thanksgiving.d_sellingseason, "Fall");
+    }
+}
@@ -0,0 +1,123 @@
+//! Star Schema Benchmark (SSB) data generation
+//!
+//! The [Star Schema Benchmark] is derived from TPC-H: its LINEORDER fact table
+//! combines the TPC-H ORDERS and LINEITEM tables, and its CUSTOMER, SUPPLIER
+//! and PART dimensions are denormalized versions of the TPC-H tables with the
+//! nation and region names inlined. The DATE dimension has one row per day of
+//! the TPC-H date range.
+//!
+//! The generators in the [`generators`] module are built on the TPC-H
+//! generators and random number streams, the same way the reference
+//! `ssb-dbgen` is built on TPC-H `dbgen`: the dimension tables wrap the TPC-H
+//! row iterators, and LINEORDER uses the random streams of the TPC-H orders and
+//! line items, so for example the prices and dates of the first line orders are
+//! the same as the ones of the first TPC-H line items.
+//!
+//! The columns that differ from TPC-H (`c_address`, `c_city`, `p_color`,
+//! `p_brand1`...) use the random streams and distributions of `ssb-dbgen`, so
+//! with the default seed all the tables match the `ssb-dbgen` output.
+//!
+//! # Example
+//! ```
+//! # use tpchgen::ssb::generators::LineOrderGenerator;
+//! let generator = LineOrderGenerator::new(1.0, 1, 1);
+//! let first = generator.iter().next().unwrap();
+//! assert_eq!(
+//!     first.to_string(),
+//!     "1|1|7381|155190|828|19960102|5-LOW|0|17|2116823|17366547|4|2032150|74711|2|19960212|TRUCK|"
+//! );
+//! ```
+//!
+//! [Star Schema Benchmark]: https://www.cs.umb.edu/~poneil/StarSchemaB.PDF
+
+pub mod csv;
+pub mod generators;
+
+use crate::ssb::generators::{
+    CustomerGenerator, DateGenerator, LineOrderGenerator, PartGenerator, SupplierGenerator,
+};
+use std::fmt;
+use std::str::FromStr;
+
+/// The SSB tables
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SsbTable {
+    Customer,
+    Supplier,
+    Part,
+    Date,
+    Lineorder,
+}
+
+impl SsbTable {
+    /// All the tables, ordered from the smallest to the largest
+    pub const ALL: [SsbTable; 5] = [
+        SsbTable::Date,
+        SsbTable::Supplier,
+        SsbTable::Customer,
+        SsbTable::Part,
+        SsbTable::Lineorder,
+    ];
+
+    /// Returns the table name, e.g. `lineorder`
+    pub fn name(&self) -> &'static str {
+        match self {
+            SsbTable::Customer => "customer",
+            SsbTable::Supplier => "supplier",
+            SsbTable::Part => "part",
+            SsbTable::Date => "date",
+            SsbTable::Lineorder => "lineorder",
+        }
+    }
+
+    /// Returns the exact number of rows in part `part` of `part_count` of the
+    /// table
+    pub fn row_count(&self, scale_factor: f64, part: i32, part_count: i32) -> i64 {
+        match self {
+            SsbTable::Customer => {
+                CustomerGenerator::calculate_row_count(scale_factor, part, part_count)
+            }
+            SsbTable::Supplier => {
+                SupplierGenerator::calculate_row_count(scale_factor, part, part_count)
+            }
+            SsbTable::Part => PartGenerator::calculate_row_count(scale_factor, part, part_count),
+            SsbTable::Date => DateGenerator::calculate_row_count(scale_factor, part, part_count),
+            SsbTable::Lineorder => {
+                LineOrderGenerator::calculate_row_count(scale_factor, part, part_count)
+            }
+        }
+    }
+
+    /// Returns the total number of rows used to partition the table
+    ///
+    /// This is the number of rows of the table, except for LINEORDER which is
+    /// partitioned by order.
+    pub fn partition_row_count(&self, scale_factor: f64) -> i64 {
+        match self {
+            SsbTable::Lineorder => LineOrderGenerator::order_count(scale_factor),
+            table => table.row_count(scale_factor, 1, 1),
+        }
+    }
+}
+
+impl fmt::Display for SsbTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for SsbTable {
+    type Err = String;
+
+    /// Returns the table from its full name or `ssb-dbgen` abbreviation
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "c" | "customer" => Ok(SsbTable::Customer),
+            "s" | "supplier" => Ok(SsbTable::Supplier),
+            "p" | "part" => Ok(SsbTable::Part),
+            "d" | "date" => Ok(SsbTable::Date),
+            "l" | "lineorder" => Ok(SsbTable::Lineorder),
+            _ => Err(format!("Invalid SSB table name {s}")),
+        }
+    }
+}
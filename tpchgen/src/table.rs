@@ -0,0 +1,668 @@
+//! [`Table`] and [`TableGenerator`] for writing code generic over the TPC-H
+//! tables.
+//!
+//! # Example
+//! ```
+//! # use tpchgen::table::{Table, TableGenerator};
+//! # use tpchgen::generators::{LineItemGenerator, NationGenerator};
+//! /// Returns the first `n` rows of any table in TBL format
+//! fn head<'a, G: TableGenerator<'a>>(scale_factor: f64, n: usize) -> Vec<String> {
+//!     G::new(scale_factor, 1, 1)
+//!         .into_iter()
+//!         .take(n)
+//!         .map(|row| row.to_string())
+//!         .collect()
+//! }
+//!
+//! assert_eq!(head::<NationGenerator>(1.0, 1), vec![
+//!    "0|ALGERIA|0| haggle. carefully final deposits detect slyly agai|"
+//! ]);
+//! assert_eq!(LineItemGenerator::TABLE, Table::Lineitem);
+//! assert_eq!(LineItemGenerator::columns()[0].name(), "l_orderkey");
+//! ```
+
+use crate::dates::DateRange;
+use crate::distribution::Distributions;
+use crate::generators::{
+    Customer, CustomerGenerator, LineItem, LineItemGenerator, Nation, NationGenerator, Order,
+    OrderGenerator, Part, PartGenerator, PartSupp, PartSuppGenerator, Region, RegionGenerator,
+    Supplier, SupplierGenerator,
+};
+use crate::skew::Skew;
+use crate::text::TextPool;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// The TPC-H tables
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Table {
+    Nation,
+    Region,
+    Part,
+    Supplier,
+    Partsupp,
+    Customer,
+    Orders,
+    Lineitem,
+}
+
+impl Table {
+    /// All the tables, ordered from the smallest to the largest
+    pub const ALL: [Table; 8] = [
+        Table::Nation,
+        Table::Region,
+        Table::Part,
+        Table::Supplier,
+        Table::Partsupp,
+        Table::Customer,
+        Table::Orders,
+        Table::Lineitem,
+    ];
+
+    /// Returns the table name, e.g. `lineitem`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Table::Nation => "nation",
+            Table::Region => "region",
+            Table::Part => "part",
+            Table::Supplier => "supplier",
+            Table::Partsupp => "partsupp",
+            Table::Customer => "customer",
+            Table::Orders => "orders",
+            Table::Lineitem => "lineitem",
+        }
+    }
+
+    /// Returns the columns of the table
+    pub fn columns(&self) -> &'static [Column] {
+        match self {
+            Table::Nation => NationGenerator::columns(),
+            Table::Region => RegionGenerator::columns(),
+            Table::Part => PartGenerator::columns(),
+            Table::Supplier => SupplierGenerator::columns(),
+            Table::Partsupp => PartSuppGenerator::columns(),
+            Table::Customer => CustomerGenerator::columns(),
+            Table::Orders => OrderGenerator::columns(),
+            Table::Lineitem => LineItemGenerator::columns(),
+        }
+    }
+
+    /// Returns the exact number of rows in part `part` of `part_count` of the
+    /// table
+    ///
+    /// See [`TableGenerator::row_count`] for more details.
+    pub fn row_count(&self, scale_factor: f64, part: i32, part_count: i32) -> i64 {
+        match self {
+            Table::Nation => NationGenerator::row_count(scale_factor, part, part_count),
+            Table::Region => RegionGenerator::row_count(scale_factor, part, part_count),
+            Table::Part => PartGenerator::row_count(scale_factor, part, part_count),
+            Table::Supplier => SupplierGenerator::row_count(scale_factor, part, part_count),
+            Table::Partsupp => PartSuppGenerator::row_count(scale_factor, part, part_count),
+            Table::Customer => CustomerGenerator::row_count(scale_factor, part, part_count),
+            Table::Orders => OrderGenerator::row_count(scale_factor, part, part_count),
+            Table::Lineitem => LineItemGenerator::row_count(scale_factor, part, part_count),
+        }
+    }
+
+    /// Returns the total number of rows used to partition the table
+    ///
+    /// See [`TableGenerator::partition_row_count`] for more details.
+    pub fn partition_row_count(&self, scale_factor: f64) -> i64 {
+        match self {
+            Table::Nation => NationGenerator::partition_row_count(scale_factor),
+            Table::Region => RegionGenerator::partition_row_count(scale_factor),
+            Table::Part => PartGenerator::partition_row_count(scale_factor),
+            Table::Supplier => SupplierGenerator::partition_row_count(scale_factor),
+            Table::Partsupp => PartSuppGenerator::partition_row_count(scale_factor),
+            Table::Customer => CustomerGenerator::partition_row_count(scale_factor),
+            Table::Orders => OrderGenerator::partition_row_count(scale_factor),
+            Table::Lineitem => LineItemGenerator::partition_row_count(scale_factor),
+        }
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Table {
+    type Err = String;
+
+    /// Returns the table from its full name or `dbgen` abbreviation
+    ///
+    /// The original dbgen tool allows some abbreviations to mean two different tables
+    /// like 'p' which aliases to both 'part' and 'partsupp'. This implementation does
+    /// not support this since it just adds unnecessary complexity and confusion so we
+    /// only support the exclusive abbreviations.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "n" | "nation" => Ok(Table::Nation),
+            "r" | "region" => Ok(Table::Region),
+            "s" | "supplier" => Ok(Table::Supplier),
+            "P" | "part" => Ok(Table::Part),
+            "S" | "partsupp" => Ok(Table::Partsupp),
+            "c" | "customer" => Ok(Table::Customer),
+            "O" | "orders" => Ok(Table::Orders),
+            "L" | "lineitem" => Ok(Table::Lineitem),
+            _ => Err(format!("Invalid table name {s}")),
+        }
+    }
+}
+
+/// The data type of a [`Column`], as defined in the TPC-H specification
+/// (section 1.3.1)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColumnType {
+    /// A key, able to represent all the rows of the table at any scale factor
+    Identifier,
+    /// A 32-bit integer
+    Integer,
+    /// A decimal with 2 digits after the decimal point
+    Decimal,
+    /// A date between 1992-01-01 and 1998-12-31, or in the [`DateRange`] of
+    /// the generator
+    Date,
+    /// A string of exactly this many characters
+    FixedText(usize),
+    /// A string of at most this many characters
+    VariableText(usize),
+}
+
+/// Metadata of a column of a TPC-H [`Table`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Column {
+    name: &'static str,
+    column_type: ColumnType,
+}
+
+impl Column {
+    const fn new(name: &'static str, column_type: ColumnType) -> Self {
+        Self { name, column_type }
+    }
+
+    /// Returns the column name, e.g. `l_orderkey`
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the column data type
+    pub fn column_type(&self) -> ColumnType {
+        self.column_type
+    }
+}
+
+/// A generator for one of the TPC-H tables
+///
+/// All the table generators are created from a scale factor and a part of
+/// the table, and produce rows whose `Display` impl writes the TBL format.
+///
+/// # Row indexes
+///
+/// [`Self::with_row_range`], [`Self::generate_row`] and
+/// [`Self::generate_range`] all use the same row indexes, numbered from `0`
+/// across the whole table, up to [`Self::partition_row_count`]. Each index
+/// generates a single row, except for:
+///
+/// * PARTSUPP, indexed by part: each index generates the
+///   [`PartSuppGenerator::SUPPLIERS_PER_PART`] suppliers of the part.
+/// * LINEITEM, indexed by order: each index generates the line items of the
+///   order, so that ORDERS and LINEITEM can be split with the same ranges.
+pub trait TableGenerator<'a>: IntoIterator<Item = Self::Row> + Sized {
+    /// The type of the generated rows
+    type Row: fmt::Display + 'a;
+
+    /// The generated table
+    const TABLE: Table;
+
+    /// Creates a generator for part `part` of `part_count` of the table at
+    /// the given scale factor, using the default distributions and text pool
+    fn new(scale_factor: f64, part: i32, part_count: i32) -> Self;
+
+    /// Creates a generator with the specified distributions and text pool
+    fn new_with_distributions_and_text_pool(
+        scale_factor: f64,
+        part: i32,
+        part_count: i32,
+        distributions: &'a Distributions,
+        text_pool: &'a TextPool,
+    ) -> Self;
+
+    /// Returns the exact number of rows the generator for part `part` of
+    /// `part_count` produces at the given scale factor
+    fn row_count(scale_factor: f64, part: i32, part_count: i32) -> i64;
+
+    /// Returns the total number of row indexes of the table at the given
+    /// scale factor, see [Row indexes]
+    ///
+    /// This is the number of rows of the table, except for PARTSUPP and
+    /// LINEITEM which are indexed by part and by order respectively.
+    ///
+    /// [Row indexes]: Self#row-indexes
+    fn partition_row_count(scale_factor: f64) -> i64;
+
+    /// Restricts the generator to the rows with indexes in `range` instead
+    /// of its part, see [Row indexes]
+    ///
+    /// Generators for adjacent ranges produce the same rows as a single
+    /// generator for the whole table.
+    ///
+    /// [Row indexes]: Self#row-indexes
+    fn with_row_range(self, range: Range<i64>) -> Self;
+
+    /// Generates a different dataset by using `seed` for the random number
+    /// generators, see [`NationGenerator::with_seed`]
+    fn with_seed(self, seed: u64) -> Self;
+
+    /// Skews the columns of this table with `skew`, see the [`skew`] module
+    ///
+    /// NATION, REGION, SUPPLIER and PARTSUPP have no skewed columns, and
+    /// their generators are returned unchanged.
+    ///
+    /// [`skew`]: crate::skew
+    fn with_skew(self, skew: Skew) -> Self {
+        let _ = skew;
+        self
+    }
+
+    /// Generates the dates of this table in `date_range`, see [`DateRange`]
+    ///
+    /// Only ORDERS and LINEITEM have dates, the other generators are returned
+    /// unchanged.
+    fn with_date_range(self, date_range: DateRange) -> Self {
+        let _ = date_range;
+        self
+    }
+
+    /// Returns the rows with the given index, see [Row indexes]
+    ///
+    /// The iterator is empty if there is no such index, and has a single row
+    /// except for PARTSUPP and LINEITEM.
+    ///
+    /// [Row indexes]: Self#row-indexes
+    fn generate_row(&self, index: i64) -> impl Iterator<Item = Self::Row>;
+
+    /// Returns an iterator over the rows with indexes in `range`, see
+    /// [Row indexes]
+    ///
+    /// This produces the same rows as the generator returned by
+    /// [`Self::with_row_range`], without consuming this generator.
+    ///
+    /// [Row indexes]: Self#row-indexes
+    fn generate_range(&self, range: Range<i64>) -> impl Iterator<Item = Self::Row>;
+
+    /// Returns the columns of the generated rows, in TBL order
+    fn columns() -> &'static [Column];
+}
+
+use ColumnType::*;
+
+/// Implements [`TableGenerator`] for a generator
+macro_rules! impl_table_generator {
+    ($GENERATOR:ident, $ROW:ty, $TABLE:expr, $COLUMNS:expr, |$SF:ident, $PART:ident, $PART_COUNT:ident| $ROW_COUNT:expr, |$P_SF:ident| $PARTITION_ROW_COUNT:expr, |$D_SF:ident, $D_PART:ident, $D_PART_COUNT:ident, $DISTRIBUTIONS:ident, $TEXT_POOL:ident| $WITH_DISTRIBUTIONS:expr $(, |$K_GENERATOR:ident, $SKEW:ident| $WITH_SKEW:expr $(, |$R_GENERATOR:ident, $DATE_RANGE:ident| $WITH_DATE_RANGE:expr)?)?) => {
+        impl<'a> TableGenerator<'a> for $GENERATOR<'a> {
+            type Row = $ROW;
+            const TABLE: Table = $TABLE;
+
+            fn new(scale_factor: f64, part: i32, part_count: i32) -> Self {
+                $GENERATOR::new(scale_factor, part, part_count)
+            }
+
+            fn new_with_distributions_and_text_pool(
+                $D_SF: f64,
+                $D_PART: i32,
+                $D_PART_COUNT: i32,
+                $DISTRIBUTIONS: &'a Distributions,
+                $TEXT_POOL: &'a TextPool,
+            ) -> Self {
+                $WITH_DISTRIBUTIONS
+            }
+
+            fn row_count($SF: f64, $PART: i32, $PART_COUNT: i32) -> i64 {
+                $ROW_COUNT
+            }
+
+            fn partition_row_count($P_SF: f64) -> i64 {
+                $PARTITION_ROW_COUNT
+            }
+
+            fn with_row_range(self, range: Range<i64>) -> Self {
+                $GENERATOR::with_row_range(self, range)
+            }
+
+            fn with_seed(self, seed: u64) -> Self {
+                $GENERATOR::with_seed(self, seed)
+            }
+
+            $(
+            fn with_skew(self, $SKEW: Skew) -> Self {
+                let $K_GENERATOR = self;
+                $WITH_SKEW
+            }
+
+            $(
+            fn with_date_range(self, $DATE_RANGE: DateRange) -> Self {
+                let $R_GENERATOR = self;
+                $WITH_DATE_RANGE
+            }
+            )?
+            )?
+
+            fn generate_row(&self, index: i64) -> impl Iterator<Item = Self::Row> {
+                $GENERATOR::generate_row(self, index).into_iter()
+            }
+
+            fn generate_range(&self, range: Range<i64>) -> impl Iterator<Item = Self::Row> {
+                $GENERATOR::generate_range(self, range)
+            }
+
+            fn columns() -> &'static [Column] {
+                const COLUMNS: &[Column] = $COLUMNS;
+                COLUMNS
+            }
+        }
+    };
+}
+
+impl_table_generator!(
+    NationGenerator,
+    Nation<'a>,
+    Table::Nation,
+    &[
+        Column::new("n_nationkey", Identifier),
+        Column::new("n_name", FixedText(25)),
+        Column::new("n_regionkey", Identifier),
+        Column::new("n_comment", VariableText(152)),
+    ],
+    |_sf, _part, _part_count| Distributions::static_default().nations().size() as i64,
+    |_sf| Distributions::static_default().nations().size() as i64,
+    |_sf, _part, _part_count, distributions, text_pool| {
+        NationGenerator::new_with_distributions_and_text_pool(distributions, text_pool)
+    }
+);
+
+impl_table_generator!(
+    RegionGenerator,
+    Region<'a>,
+    Table::Region,
+    &[
+        Column::new("r_regionkey", Identifier),
+        Column::new("r_name", FixedText(25)),
+        Column::new("r_comment", VariableText(152)),
+    ],
+    |_sf, _part, _part_count| Distributions::static_default().regions().size() as i64,
+    |_sf| Distributions::static_default().regions().size() as i64,
+    |_sf, _part, _part_count, distributions, text_pool| {
+        RegionGenerator::new_with_distributions_and_text_pool(distributions, text_pool)
+    }
+);
+
+impl_table_generator!(
+    PartGenerator,
+    Part<'a>,
+    Table::Part,
+    &[
+        Column::new("p_partkey", Identifier),
+        Column::new("p_name", VariableText(55)),
+        Column::new("p_mfgr", FixedText(25)),
+        Column::new("p_brand", FixedText(10)),
+        Column::new("p_type", VariableText(25)),
+        Column::new("p_size", Integer),
+        Column::new("p_container", FixedText(10)),
+        Column::new("p_retailprice", Decimal),
+        Column::new("p_comment", VariableText(23)),
+    ],
+    |sf, part, part_count| PartGenerator::calculate_row_count(sf, part, part_count),
+    |sf| PartGenerator::calculate_row_count(sf, 1, 1),
+    |sf, part, part_count, distributions, text_pool| {
+        PartGenerator::new_with_distributions_and_text_pool(
+            sf,
+            part,
+            part_count,
+            distributions,
+            text_pool,
+        )
+    },
+    |generator, skew| PartGenerator::with_skew(generator, skew)
+);
+
+impl_table_generator!(
+    SupplierGenerator,
+    Supplier,
+    Table::Supplier,
+    &[
+        Column::new("s_suppkey", Identifier),
+        Column::new("s_name", FixedText(25)),
+        Column::new("s_address", VariableText(40)),
+        Column::new("s_nationkey", Identifier),
+        Column::new("s_phone", FixedText(15)),
+        Column::new("s_acctbal", Decimal),
+        Column::new("s_comment", VariableText(101)),
+    ],
+    |sf, part, part_count| SupplierGenerator::calculate_row_count(sf, part, part_count),
+    |sf| SupplierGenerator::calculate_row_count(sf, 1, 1),
+    |sf, part, part_count, distributions, text_pool| {
+        SupplierGenerator::new_with_distributions_and_text_pool(
+            sf,
+            part,
+            part_count,
+            distributions,
+            text_pool,
+        )
+    }
+);
+
+impl_table_generator!(
+    PartSuppGenerator,
+    PartSupp<'a>,
+    Table::Partsupp,
+    &[
+        Column::new("ps_partkey", Identifier),
+        Column::new("ps_suppkey", Identifier),
+        Column::new("ps_availqty", Integer),
+        Column::new("ps_supplycost", Decimal),
+        Column::new("ps_comment", VariableText(199)),
+    ],
+    // the generator is partitioned by part, with several suppliers per part
+    |sf, part, part_count| PartSuppGenerator::calculate_row_count(sf, part, part_count)
+        * PartSuppGenerator::SUPPLIERS_PER_PART as i64,
+    |sf| PartSuppGenerator::calculate_row_count(sf, 1, 1),
+    |sf, part, part_count, _distributions, text_pool| {
+        PartSuppGenerator::new_with_text_pool(sf, part, part_count, text_pool)
+    }
+);
+
+impl_table_generator!(
+    CustomerGenerator,
+    Customer<'a>,
+    Table::Customer,
+    &[
+        Column::new("c_custkey", Identifier),
+        Column::new("c_name", VariableText(25)),
+        Column::new("c_address", VariableText(40)),
+        Column::new("c_nationkey", Identifier),
+        Column::new("c_phone", FixedText(15)),
+        Column::new("c_acctbal", Decimal),
+        Column::new("c_mktsegment", FixedText(10)),
+        Column::new("c_comment", VariableText(117)),
+    ],
+    |sf, part, part_count| CustomerGenerator::calculate_row_count(sf, part, part_count),
+    |sf| CustomerGenerator::calculate_row_count(sf, 1, 1),
+    |sf, part, part_count, distributions, text_pool| {
+        CustomerGenerator::new_with_distributions_and_text_pool(
+            sf,
+            part,
+            part_count,
+            distributions,
+            text_pool,
+        )
+    },
+    |generator, skew| CustomerGenerator::with_skew(generator, skew)
+);
+
+impl_table_generator!(
+    OrderGenerator,
+    Order<'a>,
+    Table::Orders,
+    &[
+        Column::new("o_orderkey", Identifier),
+        Column::new("o_custkey", Identifier),
+        Column::new("o_orderstatus", FixedText(1)),
+        Column::new("o_totalprice", Decimal),
+        Column::new("o_orderdate", Date),
+        Column::new("o_orderpriority", FixedText(15)),
+        Column::new("o_clerk", FixedText(15)),
+        Column::new("o_shippriority", Integer),
+        Column::new("o_comment", VariableText(79)),
+    ],
+    |sf, part, part_count| OrderGenerator::calculate_row_count(sf, part, part_count),
+    |sf| OrderGenerator::calculate_row_count(sf, 1, 1),
+    |sf, part, part_count, distributions, text_pool| {
+        OrderGenerator::new_with_distributions_and_text_pool(
+            sf,
+            part,
+            part_count,
+            distributions,
+            text_pool,
+        )
+    },
+    |generator, skew| OrderGenerator::with_skew(generator, skew),
+    |generator, date_range| OrderGenerator::with_date_range(generator, date_range)
+);
+
+impl_table_generator!(
+    LineItemGenerator,
+    LineItem<'a>,
+    Table::Lineitem,
+    &[
+        Column::new("l_orderkey", Identifier),
+        Column::new("l_partkey", Identifier),
+        Column::new("l_suppkey", Identifier),
+        Column::new("l_linenumber", Integer),
+        Column::new("l_quantity", Decimal),
+        Column::new("l_extendedprice", Decimal),
+        Column::new("l_discount", Decimal),
+        Column::new("l_tax", Decimal),
+        Column::new("l_returnflag", FixedText(1)),
+        Column::new("l_linestatus", FixedText(1)),
+        Column::new("l_shipdate", Date),
+        Column::new("l_commitdate", Date),
+        Column::new("l_receiptdate", Date),
+        Column::new("l_shipinstruct", FixedText(25)),
+        Column::new("l_shipmode", FixedText(10)),
+        Column::new("l_comment", VariableText(44)),
+    ],
+    |sf, part, part_count| LineItemGenerator::calculate_row_count(sf, part, part_count),
+    |sf| OrderGenerator::calculate_row_count(sf, 1, 1),
+    |sf, part, part_count, distributions, text_pool| {
+        LineItemGenerator::new_with_distributions_and_text_pool(
+            sf,
+            part,
+            part_count,
+            distributions,
+            text_pool,
+        )
+    },
+    |generator, skew| LineItemGenerator::with_skew(generator, skew),
+    |generator, date_range| LineItemGenerator::with_date_range(generator, date_range)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dates::GenerateUtils;
+
+    #[test]
+    fn test_table_names() {
+        for table in Table::ALL {
+            assert_eq!(table.name().parse::<Table>().unwrap(), table);
+            assert_eq!(table.to_string(), table.name());
+        }
+        assert_eq!("L".parse::<Table>().unwrap(), Table::Lineitem);
+        assert!("p".parse::<Table>().is_err());
+    }
+
+    /// The row counts and columns match the generated rows
+    #[test]
+    fn test_row_count_and_columns() {
+        fn check<'a, G: TableGenerator<'a>>() {
+            for part in 1..=2 {
+                let rows: Vec<String> = G::new(0.01, part, 2)
+                    .into_iter()
+                    .map(|row| row.to_string())
+                    .collect();
+                assert_eq!(
+                    rows.len() as i64,
+                    G::row_count(0.01, part, 2),
+                    "{}",
+                    G::TABLE
+                );
+                for row in rows {
+                    // TBL rows end with a trailing `|`
+                    assert_eq!(row.matches('|').count(), G::columns().len(), "{row}");
+                }
+            }
+            assert_eq!(G::columns(), G::TABLE.columns());
+        }
+
+        check::<NationGenerator>();
+        check::<RegionGenerator>();
+        check::<PartGenerator>();
+        check::<SupplierGenerator>();
+        check::<PartSuppGenerator>();
+        check::<CustomerGenerator>();
+        check::<OrderGenerator>();
+        check::<LineItemGenerator>();
+    }
+
+    /// Uneven row ranges produce the same rows as the whole table
+    #[test]
+    fn test_row_ranges() {
+        fn check<'a, G: TableGenerator<'a>>() {
+            let scale_factor = 0.01;
+            let expected: Vec<String> = G::new(scale_factor, 1, 1)
+                .into_iter()
+                .map(|row| row.to_string())
+                .collect();
+
+            let partition_row_count = G::partition_row_count(scale_factor);
+            let boundaries = [
+                0,
+                1,
+                2,
+                (partition_row_count / 3).max(2),
+                partition_row_count,
+            ];
+            let mut rows = vec![];
+            for range in boundaries.windows(2) {
+                let generator = G::new(scale_factor, 1, 1).with_row_range(range[0]..range[1]);
+                rows.extend(generator.into_iter().map(|row| row.to_string()));
+            }
+            assert_eq!(rows, expected, "{}", G::TABLE);
+
+            // parts are row ranges
+            let part = G::new(scale_factor, 2, 3)
+                .into_iter()
+                .map(|row| row.to_string());
+            let range = GenerateUtils::split_range(0..partition_row_count, 2, 3);
+            let range = G::new(scale_factor, 1, 1)
+                .with_row_range(range)
+                .into_iter()
+                .map(|row| row.to_string());
+            if G::TABLE != Table::Nation && G::TABLE != Table::Region {
+                assert!(part.eq(range), "{}", G::TABLE);
+            }
+        }
+
+        check::<NationGenerator>();
+        check::<RegionGenerator>();
+        check::<PartGenerator>();
+        check::<SupplierGenerator>();
+        check::<PartSuppGenerator>();
+        check::<CustomerGenerator>();
+        check::<OrderGenerator>();
+        check::<LineItemGenerator>();
+    }
+}
@@ -5,23 +5,115 @@
 //!
 //! <https://github.com/trinodb/tpch/blob/master/src/main/java/io/trino/tpch/TextPool.java>
 
-use crate::{distribution::Distributions, random::RowRandomInt};
+use crate::{
+    distribution::Distributions,
+    random::{stream_seed, RowRandomInt},
+};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::ops::Deref;
+use std::path::Path;
 use std::sync::OnceLock;
 
 /// Pool of random text that follows TPC-H grammar.
+///
+/// Creating the default text pool takes a few seconds, so it can be written
+/// to a cache file with [`write_cache`](Self::write_cache) and loaded back
+/// with [`read_cache`](Self::read_cache) (or memory-mapped with `map_cache`,
+/// with the `mmap` feature) by other processes. The cache file records the
+/// options the text was generated with, so a stale cache is rejected instead
+/// of changing the generated data, and a checksum of the text, so a corrupted
+/// cache is rejected by `read_cache` (and by `map_cache` when it verifies the
+/// text).
+///
+/// # Low-memory mode
+///
+/// The TPC-H specification requires a pool of
+/// [`DEFAULT_TEXT_POOL_SIZE`](Self::DEFAULT_TEXT_POOL_SIZE) bytes (300 MB).
+/// A smaller pool, down to [`MIN_TEXT_POOL_SIZE`](Self::MIN_TEXT_POOL_SIZE),
+/// can be created with [`TextPool::new`] and passed to the
+/// `new_with_distributions_and_text_pool` constructors of the generators to
+/// use less memory. **The data generated with another size is not TPC-H
+/// conformant**: the comments are taken from less text, so they repeat more
+/// often and differ from the comments of `dbgen`. The other columns are the
+/// same.
+///
+/// ```
+/// # use tpchgen::distribution::Distributions;
+/// # use tpchgen::generators::OrderGenerator;
+/// # use tpchgen::text::TextPool;
+/// let distributions = Distributions::static_default();
+/// let text_pool = TextPool::new(TextPool::MIN_TEXT_POOL_SIZE, distributions);
+/// let generator =
+///     OrderGenerator::new_with_distributions_and_text_pool(0.01, 1, 1, distributions, &text_pool);
+/// let order = generator.iter().next().unwrap();
+/// let default_order = OrderGenerator::new(0.01, 1, 1).iter().next().unwrap();
+/// assert_eq!(order.o_totalprice, default_order.o_totalprice);
+/// assert_ne!(order.o_comment, default_order.o_comment);
+/// ```
+///
+/// # Multilingual text
+///
+/// The TPC-H text is ASCII, but the words of the distributions can be any
+/// UTF-8 text, e.g. the multilingual words of
+/// [`DistributionsBuilder::with_multilingual_text`]. The text of such a pool
+/// is sliced at character boundaries: [`text`](Self::text) moves the begin
+/// and end of the text to the next character boundary, so the length of the
+/// text in bytes is the same on average.
+///
+/// [`DistributionsBuilder::with_multilingual_text`]: crate::distribution::DistributionsBuilder::with_multilingual_text
 #[derive(Debug, Clone)]
 pub struct TextPool {
     /// Bytes making up the text pool, exact size.
-    text: Vec<u8>,
+    text: TextBytes,
+    /// Whether the text is ASCII, so any range of the text is valid UTF-8
+    ascii: bool,
+    /// Identifies the size, distributions and seed of the text, see
+    /// [`TextPool::cache_key`]
+    key: u64,
 }
 
+/// The bytes of a [`TextPool`], generated or loaded from a cache file
+#[derive(Debug, Clone)]
+enum TextBytes {
+    Owned(Vec<u8>),
+    /// A memory-mapped cache file, with the text after the header
+    #[cfg(feature = "mmap")]
+    Mapped(std::sync::Arc<memmap2::Mmap>),
+}
+
+impl Deref for TextBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            TextBytes::Owned(bytes) => bytes,
+            #[cfg(feature = "mmap")]
+            TextBytes::Mapped(map) => &map[CACHE_HEADER_LEN..],
+        }
+    }
+}
+
+/// Identifies the format of text pool cache files
+const CACHE_MAGIC: &[u8; 8] = b"TPCHTXT2";
+
+/// Length of the header of a cache file: the magic bytes, the key, the length,
+/// the checksum and the flags of the text
+const CACHE_HEADER_LEN: usize = 40;
+
+/// Flag of the header of a cache file set when the text is ASCII
+const CACHE_FLAG_ASCII: u64 = 1;
+
 /// The default global text pool is lazily initialized once and shared across
 /// all the table generators.
 static DEFAULT_TEXT_POOL: OnceLock<TextPool> = OnceLock::new();
 
 impl TextPool {
-    /// Default text pool size.
-    const DEFAULT_TEXT_POOL_SIZE: i32 = 300 * 1024 * 1024;
+    /// Default text pool size, required by the TPC-H specification.
+    pub const DEFAULT_TEXT_POOL_SIZE: i32 = 300 * 1024 * 1024;
+    /// Smallest supported text pool size, see the [low-memory
+    /// mode](Self#low-memory-mode).
+    pub const MIN_TEXT_POOL_SIZE: i32 = 1024 * 1024;
     /// Maximum length of a sentence in the text.
     const MAX_SENTENCE_LENGTH: i32 = 256;
 
@@ -36,17 +128,231 @@ impl TextPool {
         })
     }
 
+    /// Initializes the default text pool with the text generated using `seed`,
+    /// see [`Self::new_with_seed`]
+    ///
+    /// Returns an error if the default text pool was already initialized.
+    pub fn init_default_with_seed(seed: u64) -> io::Result<()> {
+        Self::init_default(Self::new_with_seed(
+            Self::DEFAULT_TEXT_POOL_SIZE,
+            Distributions::static_default(),
+            seed,
+        ))
+    }
+
     /// Returns a new text pool with a predefined size and set of distributions.
+    ///
+    /// Sizes other than [`Self::DEFAULT_TEXT_POOL_SIZE`] generate data that
+    /// is not TPC-H conformant, see the [low-memory mode](Self#low-memory-mode).
+    ///
+    /// # Panics
+    /// If `size` is smaller than [`Self::MIN_TEXT_POOL_SIZE`]
     pub fn new(size: i32, distributions: &Distributions) -> Self {
-        let mut rng = RowRandomInt::new(933588178, i32::MAX);
+        Self::new_with_seed(size, distributions, 0)
+    }
+
+    /// Returns a new text pool with a predefined size and set of
+    /// distributions, generating the text using `seed`
+    ///
+    /// Seed `0` generates the standard TPC-H text pool. See
+    /// [`stream_seed`].
+    ///
+    /// # Panics
+    /// If `size` is smaller than [`Self::MIN_TEXT_POOL_SIZE`]
+    pub fn new_with_seed(size: i32, distributions: &Distributions, seed: u64) -> Self {
+        assert!(
+            size >= Self::MIN_TEXT_POOL_SIZE,
+            "Invalid text pool size {size}, must be at least {}",
+            Self::MIN_TEXT_POOL_SIZE
+        );
+        let mut rng = RowRandomInt::new(stream_seed(933588178, seed), i32::MAX);
         let mut text_bytes = Vec::with_capacity(size as usize + Self::MAX_SENTENCE_LENGTH as usize);
 
         while text_bytes.len() < size as usize {
             Self::generate_sentence(distributions, &mut text_bytes, &mut rng);
         }
-        text_bytes.truncate(size as usize);
+        // end the text at a character boundary, and pad it to `size` bytes
+        text_bytes.truncate(floor_char_boundary(&text_bytes, size as usize));
+        text_bytes.resize(size as usize, b' ');
+
+        Self {
+            ascii: text_bytes.is_ascii(),
+            text: TextBytes::Owned(text_bytes),
+            key: Self::cache_key(size, distributions, seed),
+        }
+    }
 
-        Self { text: text_bytes }
+    /// Sets the default text pool, e.g. to a text pool loaded from a cache
+    /// file
+    ///
+    /// Returns an error if the default text pool was already initialized.
+    pub fn init_default(text_pool: TextPool) -> io::Result<()> {
+        DEFAULT_TEXT_POOL.set(text_pool).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "Text pool already initialized",
+            )
+        })
+    }
+
+    /// Writes the text pool to a cache file at `path`
+    ///
+    /// The file is written to a temporary file first and then renamed, so
+    /// other processes never read (or map) a partially written file.
+    pub fn write_cache(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(format!(".{}.tmp", std::process::id()));
+
+        let mut file = io::BufWriter::new(File::create(&temp_path)?);
+        file.write_all(CACHE_MAGIC)?;
+        file.write_all(&self.key.to_le_bytes())?;
+        file.write_all(&(self.text.len() as u64).to_le_bytes())?;
+        file.write_all(&checksum(&self.text).to_le_bytes())?;
+        let flags = if self.ascii { CACHE_FLAG_ASCII } else { 0 };
+        file.write_all(&flags.to_le_bytes())?;
+        file.write_all(&self.text)?;
+        file.into_inner()?.sync_all()?;
+        fs::rename(&temp_path, path)
+    }
+
+    /// Reads a text pool of `size` bytes generated with `distributions` and
+    /// `seed` from a cache file written by [`Self::write_cache`]
+    ///
+    /// The whole file is read, and its text is checked against the checksum.
+    ///
+    /// Returns an `InvalidData` error if the file was created with other
+    /// options, or its text does not match its checksum.
+    pub fn read_cache(
+        path: impl AsRef<Path>,
+        size: i32,
+        distributions: &Distributions,
+        seed: u64,
+    ) -> io::Result<Self> {
+        let mut bytes = vec![];
+        File::open(path.as_ref())?.read_to_end(&mut bytes)?;
+        let ascii = Self::check_cache(path.as_ref(), &bytes, size, distributions, seed, true)?;
+        bytes.drain(..CACHE_HEADER_LEN);
+        Ok(Self {
+            ascii,
+            text: TextBytes::Owned(bytes),
+            key: Self::cache_key(size, distributions, seed),
+        })
+    }
+
+    /// Memory-maps a cache file written by [`Self::write_cache`], like
+    /// [`Self::read_cache`]
+    ///
+    /// Only the header of the file is checked, unless `verify` is true: the
+    /// text of the pool is then read from the file as needed, and shared with
+    /// the other processes that map the same file. With `verify`, the whole
+    /// text is read up front and checked against the checksum, like
+    /// [`Self::read_cache`].
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified while the text pool is used, see
+    /// [`memmap2::Mmap`]. [`Self::write_cache`] replaces the file instead of
+    /// modifying it, so it can be used to update the cache file.
+    ///
+    /// Unless `verify` is true, the text of the file must also not have been
+    /// corrupted since it was written by [`Self::write_cache`]: the text is
+    /// assumed to be valid UTF-8.
+    #[cfg(feature = "mmap")]
+    pub unsafe fn map_cache(
+        path: impl AsRef<Path>,
+        size: i32,
+        distributions: &Distributions,
+        seed: u64,
+        verify: bool,
+    ) -> io::Result<Self> {
+        let file = File::open(path.as_ref())?;
+        // Safety: see the safety section of this method
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        let ascii = Self::check_cache(path.as_ref(), &map, size, distributions, seed, verify)?;
+        Ok(Self {
+            ascii,
+            text: TextBytes::Mapped(std::sync::Arc::new(map)),
+            key: Self::cache_key(size, distributions, seed),
+        })
+    }
+
+    /// Checks that `bytes` is the content of a valid cache file for a text
+    /// pool of `size` bytes generated with `distributions` and `seed`, and
+    /// returns whether its text is ASCII
+    ///
+    /// Only the header is checked unless `verify` is true, so the text is not
+    /// read.
+    fn check_cache(
+        path: &Path,
+        bytes: &[u8],
+        size: i32,
+        distributions: &Distributions,
+        seed: u64,
+        verify: bool,
+    ) -> io::Result<bool> {
+        let invalid = |message: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid text pool cache {}: {message}", path.display()),
+            )
+        };
+        if bytes.len() < CACHE_HEADER_LEN || &bytes[..8] != CACHE_MAGIC {
+            return Err(invalid("not a text pool cache file"));
+        }
+        let header_value = |index: usize| {
+            let offset = 8 * index;
+            u64::from_le_bytes(bytes[offset..offset + 8].try_into().expect("8 bytes"))
+        };
+        if header_value(1) != Self::cache_key(size, distributions, seed) {
+            return Err(invalid("created with another size, seed or distributions"));
+        }
+        let text = &bytes[CACHE_HEADER_LEN..];
+        if header_value(2) != text.len() as u64 {
+            return Err(invalid("the file is truncated"));
+        }
+        let ascii = header_value(4) & CACHE_FLAG_ASCII != 0;
+        if verify {
+            if header_value(3) != checksum(text) {
+                return Err(invalid("the text does not match the checksum"));
+            }
+            // the text must be valid UTF-8, and ASCII if flagged so, see `text`
+            if std::str::from_utf8(text).is_err() || (ascii && !text.is_ascii()) {
+                return Err(invalid("the text is not valid UTF-8"));
+            }
+        }
+        Ok(ascii)
+    }
+
+    /// Returns a hash of the options that determine the text of a pool: its
+    /// size, the seed and the distributions of the grammar
+    fn cache_key(size: i32, distributions: &Distributions, seed: u64) -> u64 {
+        let mut options = vec![];
+        options.extend_from_slice(&size.to_le_bytes());
+        options.extend_from_slice(&seed.to_le_bytes());
+        let grammar = [
+            "grammar",
+            "np",
+            "vp",
+            "articles",
+            "adjectives",
+            "adverbs",
+            "auxillaries",
+            "nouns",
+            "prepositions",
+            "terminators",
+            "verbs",
+        ];
+        for name in grammar {
+            let distribution = distributions.get(name).expect("known distribution");
+            for (index, value) in distribution.get_values().iter().enumerate() {
+                options.extend_from_slice(value.as_bytes());
+                options.push(b'|');
+                options.extend_from_slice(&distribution.get_weight(index).to_le_bytes());
+            }
+            options.push(b'\n');
+        }
+        checksum(&options)
     }
 
     /// Returns the text pool size.
@@ -59,10 +365,21 @@ impl TextPool {
     /// Returns a chunk of text from the pool
     ///
     /// Returns the text from the pool between the given begin and end indices.
+    /// If the text is not ASCII, the begin and end indices are moved to the
+    /// next character boundary.
     pub fn text(&self, begin: i32, end: i32) -> &str {
+        let (begin, end) = if self.ascii {
+            (begin as usize, end as usize)
+        } else {
+            (
+                ceil_char_boundary(&self.text, begin as usize),
+                ceil_char_boundary(&self.text, end as usize),
+            )
+        };
         // get slice of bytes (note this also does bounds checks)
-        let result: &[u8] = &self.text[begin as usize..end as usize];
-        // Safety: text pool contains only ASCII
+        let result: &[u8] = &self.text[begin..end];
+        // Safety: text pool is valid UTF-8, and the slice is ASCII or starts
+        // and ends at character boundaries
         unsafe { std::str::from_utf8_unchecked(result) }
     }
 
@@ -154,3 +471,167 @@ impl TextPool {
         }
     }
 }
+
+/// Returns the first character boundary of the UTF-8 text `bytes` at or after
+/// `index`, or `index` if it is after the end of the text
+pub(crate) fn ceil_char_boundary(bytes: &[u8], mut index: usize) -> usize {
+    while index < bytes.len() && is_continuation_byte(bytes[index]) {
+        index += 1;
+    }
+    index
+}
+
+/// Returns the last character boundary of the UTF-8 text `bytes` at or before
+/// `index`
+fn floor_char_boundary(bytes: &[u8], index: usize) -> usize {
+    let mut index = index.min(bytes.len());
+    while index < bytes.len() && is_continuation_byte(bytes[index]) {
+        index -= 1;
+    }
+    index
+}
+
+/// Returns true for the bytes of a UTF-8 character after the first byte
+fn is_continuation_byte(byte: u8) -> bool {
+    byte & 0b1100_0000 == 0b1000_0000
+}
+
+/// Fast 64-bit checksum of `bytes`, a multiplicative hash of each 8 bytes
+fn checksum(bytes: &[u8]) -> u64 {
+    const PRIME: u64 = 0x100000001b3;
+    let mut hash = 0xcbf29ce484222325 ^ bytes.len() as u64;
+    let mut chunks = bytes.chunks_exact(8);
+    for chunk in &mut chunks {
+        let word = u64::from_le_bytes(chunk.try_into().expect("8 bytes"));
+        hash = (hash ^ word).wrapping_mul(PRIME).rotate_left(29);
+    }
+    for &byte in chunks.remainder() {
+        hash = (hash ^ byte as u64).wrapping_mul(PRIME);
+    }
+    // finalizer of MurmurHash3
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+    hash ^ (hash >> 33)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Distribution;
+    use crate::generators::SupplierGenerator;
+
+    #[test]
+    fn test_cache() {
+        let distributions = Distributions::static_default();
+        let size = TextPool::MIN_TEXT_POOL_SIZE;
+        let path = std::env::temp_dir().join(format!("tpchgen-text-pool-{}", std::process::id()));
+        let text_pool = TextPool::new_with_seed(size, distributions, 7);
+        text_pool.write_cache(&path).unwrap();
+
+        let cached = TextPool::read_cache(&path, size, distributions, 7).unwrap();
+        assert_eq!(cached.text(0, size), text_pool.text(0, size));
+        #[cfg(feature = "mmap")]
+        {
+            for verify in [false, true] {
+                let mapped =
+                    unsafe { TextPool::map_cache(&path, size, distributions, 7, verify) }.unwrap();
+                assert!(mapped.ascii);
+                assert_eq!(mapped.text(0, size), text_pool.text(0, size));
+            }
+        }
+
+        // a cache of other options is rejected
+        let error = TextPool::read_cache(&path, size, distributions, 0).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error
+            .to_string()
+            .ends_with("created with another size, seed or distributions"));
+        let other_distributions = Distributions::builder()
+            .with_distribution(Distribution::new("nouns", vec![("packages", 1)]))
+            .build()
+            .unwrap();
+        assert!(TextPool::read_cache(&path, size, &other_distributions, 7).is_err());
+
+        // and so is a corrupted cache, when the text is verified
+        let mut bytes = fs::read(&path).unwrap();
+        bytes[CACHE_HEADER_LEN + 10] ^= 1;
+        fs::write(&path, &bytes).unwrap();
+        let error = TextPool::read_cache(&path, size, distributions, 7).unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("the text does not match the checksum"));
+        #[cfg(feature = "mmap")]
+        {
+            let error =
+                unsafe { TextPool::map_cache(&path, size, distributions, 7, true) }.unwrap_err();
+            assert!(error
+                .to_string()
+                .ends_with("the text does not match the checksum"));
+        }
+
+        // a truncated cache is rejected from its header
+        fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+        let error = TextPool::read_cache(&path, size, distributions, 7).unwrap_err();
+        assert!(error.to_string().ends_with("the file is truncated"));
+        #[cfg(feature = "mmap")]
+        {
+            let error =
+                unsafe { TextPool::map_cache(&path, size, distributions, 7, false) }.unwrap_err();
+            assert!(error.to_string().ends_with("the file is truncated"));
+        }
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_multilingual_text() {
+        let distributions = Distributions::builder()
+            .with_multilingual_text()
+            .build()
+            .unwrap();
+        let size = TextPool::MIN_TEXT_POOL_SIZE;
+        let text_pool = TextPool::new(size, &distributions);
+        assert_eq!(text_pool.size(), size);
+        assert!(!text_pool.ascii);
+        assert!(std::str::from_utf8(&text_pool.text).is_ok());
+
+        // the cache file records that the text is not ASCII
+        let path =
+            std::env::temp_dir().join(format!("tpchgen-text-pool-utf8-{}", std::process::id()));
+        text_pool.write_cache(&path).unwrap();
+        let cached = TextPool::read_cache(&path, size, &distributions, 0).unwrap();
+        assert!(!cached.ascii);
+        #[cfg(feature = "mmap")]
+        {
+            let mapped =
+                unsafe { TextPool::map_cache(&path, size, &distributions, 0, false) }.unwrap();
+            assert!(!mapped.ascii);
+        }
+        fs::remove_file(&path).unwrap();
+
+        // the text is sliced at character boundaries, with the same length
+        // on average
+        let mut total_length = 0;
+        for begin in 0..10_000 {
+            let text = text_pool.text(begin, begin + 50);
+            assert!((47..=53).contains(&text.len()), "{text}");
+            total_length += text.len();
+        }
+        assert!((49.8..=50.2).contains(&(total_length as f64 / 10_000.0)));
+
+        // the supplier comments with complaints are also valid text
+        let suppliers = SupplierGenerator::new_with_distributions_and_text_pool(
+            1.0,
+            1,
+            1,
+            &distributions,
+            &text_pool,
+        );
+        let complaints = suppliers
+            .iter()
+            .filter(|supplier| supplier.s_comment.contains("Customer "))
+            .count();
+        assert!(complaints > 0);
+    }
+}
@@ -0,0 +1,805 @@
+//! Checks of the rules of the TPC-H data over the rows of the tables.
+//!
+//! The [`Validator`] checks the [`owned`] rows of the tables against the
+//! rules of the data of the specification (clause 4.2.3), for example:
+//!
+//! * `o_totalprice` is the sum of the charges of the line items of the order
+//! * `o_orderstatus` is consistent with the `l_linestatus` of the line items
+//! * `l_shipdate` is after `o_orderdate`, and `l_receiptdate` after `l_shipdate`
+//! * each (`l_partkey`, `l_suppkey`) pair exists in PARTSUPP
+//! * customers with a `c_custkey` multiple of 3 have no orders
+//!
+//! See [`Rule`] for all the rules. The rows can come from the generators
+//! (converted with `From`) or from the files of any other generator, read
+//! with the parsers of the [`parse`] module.
+//!
+//! The rules between tables (such as the foreign keys) are only checked when
+//! the referenced table was added before, so the tables must be added in the
+//! order of their references: REGION, NATION, SUPPLIER, PART, PARTSUPP,
+//! CUSTOMER, then the ORDERS with their LINEITEMs. Only the keys of the
+//! tables are kept in memory, not the rows.
+//!
+//! # Example
+//! ```
+//! # use tpchgen::generators::{OrderLineItemGenerator, PartSuppGenerator};
+//! # use tpchgen::owned;
+//! # use tpchgen::validate::{Rule, Validator};
+//! let mut validator = Validator::new();
+//! for part_supp in PartSuppGenerator::new(0.001, 1, 1).iter() {
+//!     validator.add_part_supp(&owned::PartSupp::from(part_supp));
+//! }
+//! for order_line_items in OrderLineItemGenerator::new(0.001, 1, 1).iter().take(100) {
+//!     let order = owned::Order::from(&order_line_items.order);
+//!     let line_items: Vec<_> = order_line_items.line_items.iter().map(owned::LineItem::from).collect();
+//!     validator.add_order(&order, &line_items);
+//! }
+//! assert_eq!(validator.violation_count(), 0);
+//!
+//! // an order whose total price is not the total of its line items
+//! let order_line_items = OrderLineItemGenerator::new(0.001, 1, 1).iter().next().unwrap();
+//! let mut order = owned::Order::from(&order_line_items.order);
+//! order.o_totalprice.0 += 1;
+//! let line_items: Vec<_> = order_line_items.line_items.iter().map(owned::LineItem::from).collect();
+//! validator.add_order(&order, &line_items);
+//! assert_eq!(validator.violations()[0].rule, Rule::OrderTotalPrice);
+//! ```
+//!
+//! [`owned`]: crate::owned
+//! [`parse`]: crate::parse
+
+use crate::dates::{DateRange, TPCHDate, MIN_GENERATE_DATE};
+use crate::decimal::TPCHDecimal;
+use crate::generators::{LineItemGenerator, OrderGenerator, OrderStatus, PartGeneratorIterator};
+use crate::owned::{Customer, LineItem, Nation, Order, Part, PartSupp, Region, Supplier};
+use crate::table::Table;
+use std::collections::HashSet;
+use std::fmt;
+use std::iter::Peekable;
+
+/// A rule of the TPC-H data checked by the [`Validator`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// The foreign keys reference existing rows (`n_regionkey`,
+    /// `s_nationkey`, `ps_partkey`, `ps_suppkey`, `c_nationkey`, `o_custkey`
+    /// and `l_orderkey`)
+    ForeignKey,
+    /// The values are in the ranges of the specification, e.g. `l_quantity`
+    /// from 1 to 50
+    ValueRange,
+    /// `p_retailprice` is computed from `p_partkey`
+    PartRetailPrice,
+    /// Customers with a `c_custkey` multiple of 3 have no orders
+    CustomerWithoutOrders,
+    /// An order has 1 to 7 line items, numbered from 1
+    LineNumber,
+    /// `o_totalprice` is the sum of
+    /// `l_extendedprice * (1 + l_tax) * (1 - l_discount)` of the line items
+    OrderTotalPrice,
+    /// `o_orderstatus` is `F` if all the line items have the `l_linestatus`
+    /// `F`, `O` if they all have the status `O`, and `P` otherwise
+    OrderStatus,
+    /// `l_extendedprice` is `l_quantity * p_retailprice`
+    ExtendedPrice,
+    /// `l_shipdate` is 1 to 121 days after `o_orderdate`
+    ShipDate,
+    /// `l_commitdate` is 30 to 90 days after `o_orderdate`
+    CommitDate,
+    /// `l_receiptdate` is 1 to 30 days after `l_shipdate`
+    ReceiptDate,
+    /// `l_linestatus` is `O` if `l_shipdate` is after the current date
+    /// (1995-06-17, see [`DateRange::current_date`]), and `F` otherwise
+    LineStatus,
+    /// `l_returnflag` is `R` or `A` if `l_receiptdate` is not after the
+    /// current date (1995-06-17, see [`DateRange::current_date`]), and `N`
+    /// otherwise
+    ReturnFlag,
+    /// The (`l_partkey`, `l_suppkey`) pair of a line item exists in PARTSUPP
+    PartSupplier,
+}
+
+impl Rule {
+    /// All the rules
+    pub const ALL: [Rule; 14] = [
+        Rule::ForeignKey,
+        Rule::ValueRange,
+        Rule::PartRetailPrice,
+        Rule::CustomerWithoutOrders,
+        Rule::LineNumber,
+        Rule::OrderTotalPrice,
+        Rule::OrderStatus,
+        Rule::ExtendedPrice,
+        Rule::ShipDate,
+        Rule::CommitDate,
+        Rule::ReceiptDate,
+        Rule::LineStatus,
+        Rule::ReturnFlag,
+        Rule::PartSupplier,
+    ];
+
+    /// Returns the name of the rule, e.g. `order_total_price`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::ForeignKey => "foreign_key",
+            Rule::ValueRange => "value_range",
+            Rule::PartRetailPrice => "part_retail_price",
+            Rule::CustomerWithoutOrders => "customer_without_orders",
+            Rule::LineNumber => "line_number",
+            Rule::OrderTotalPrice => "order_total_price",
+            Rule::OrderStatus => "order_status",
+            Rule::ExtendedPrice => "extended_price",
+            Rule::ShipDate => "ship_date",
+            Rule::CommitDate => "commit_date",
+            Rule::ReceiptDate => "receipt_date",
+            Rule::LineStatus => "line_status",
+            Rule::ReturnFlag => "return_flag",
+            Rule::PartSupplier => "part_supplier",
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A row that breaks a [`Rule`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// The rule
+    pub rule: Rule,
+    /// The table of the row
+    pub table: Table,
+    /// The key of the row: the primary key, the part key for PARTSUPP and
+    /// the order key for LINEITEM
+    pub key: i64,
+    /// Description of the violation
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: {} ({})",
+            self.table, self.key, self.message, self.rule
+        )
+    }
+}
+
+/// Checks the rows of the TPC-H tables against the [`Rule`]s, see the
+/// [module docs](self)
+#[derive(Debug)]
+pub struct Validator {
+    violations: Violations,
+    date_range: DateRange,
+    /// The keys of the tables added so far, `None` for the tables not added
+    region_keys: Option<HashSet<i64>>,
+    nation_keys: Option<HashSet<i64>>,
+    supplier_keys: Option<HashSet<i64>>,
+    part_keys: Option<HashSet<i64>>,
+    part_supplier_keys: Option<HashSet<(i64, i64)>>,
+    customer_keys: Option<HashSet<i64>>,
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Validator {
+    /// The default maximum number of violations kept, see
+    /// [`Self::with_max_violations`]
+    pub const DEFAULT_MAX_VIOLATIONS: usize = 100;
+
+    /// Creates a validator without any rows
+    pub fn new() -> Self {
+        Self {
+            violations: Violations {
+                max: Self::DEFAULT_MAX_VIOLATIONS,
+                list: vec![],
+                count: 0,
+            },
+            date_range: DateRange::default(),
+            region_keys: None,
+            nation_keys: None,
+            supplier_keys: None,
+            part_keys: None,
+            part_supplier_keys: None,
+            customer_keys: None,
+        }
+    }
+
+    /// Keeps only the first `max_violations` violations (all the violations
+    /// are still counted)
+    pub fn with_max_violations(mut self, max_violations: usize) -> Self {
+        self.violations.max = max_violations;
+        self
+    }
+
+    /// Checks the dates of rows generated with `date_range` (see
+    /// [`OrderGenerator::with_date_range`]) instead of 1992 to 1998
+    pub fn with_date_range(mut self, date_range: DateRange) -> Self {
+        self.date_range = date_range;
+        self
+    }
+
+    /// Returns the first violations found, see [`Self::with_max_violations`]
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations.list
+    }
+
+    /// Returns the number of violations found
+    pub fn violation_count(&self) -> usize {
+        self.violations.count
+    }
+
+    /// Checks a row of the REGION table
+    pub fn add_region(&mut self, region: &Region) {
+        self.region_keys
+            .get_or_insert_with(HashSet::new)
+            .insert(region.r_regionkey);
+    }
+
+    /// Checks a row of the NATION table
+    pub fn add_nation(&mut self, nation: &Nation) {
+        let mut check = self.violations.check(Table::Nation, nation.n_nationkey);
+        check.foreign_key(&self.region_keys, "n_regionkey", nation.n_regionkey);
+        self.nation_keys
+            .get_or_insert_with(HashSet::new)
+            .insert(nation.n_nationkey);
+    }
+
+    /// Checks a row of the SUPPLIER table
+    pub fn add_supplier(&mut self, supplier: &Supplier) {
+        let mut check = self.violations.check(Table::Supplier, supplier.s_suppkey);
+        check.foreign_key(&self.nation_keys, "s_nationkey", supplier.s_nationkey);
+        check.account_balance("s_acctbal", supplier.s_acctbal);
+        self.supplier_keys
+            .get_or_insert_with(HashSet::new)
+            .insert(supplier.s_suppkey);
+    }
+
+    /// Checks a row of the PART table
+    pub fn add_part(&mut self, part: &Part) {
+        let mut check = self.violations.check(Table::Part, part.p_partkey);
+        check.range("p_size", part.p_size as i64, 1, 50);
+        let retail_price = PartGeneratorIterator::calculate_part_price(part.p_partkey);
+        if part.p_retailprice.0 != retail_price {
+            check.violation(
+                Rule::PartRetailPrice,
+                format!(
+                    "p_retailprice {} is not {}",
+                    part.p_retailprice,
+                    TPCHDecimal(retail_price)
+                ),
+            );
+        }
+        self.part_keys
+            .get_or_insert_with(HashSet::new)
+            .insert(part.p_partkey);
+    }
+
+    /// Checks a row of the PARTSUPP table
+    pub fn add_part_supp(&mut self, part_supp: &PartSupp) {
+        let mut check = self.violations.check(Table::Partsupp, part_supp.ps_partkey);
+        check.foreign_key(&self.part_keys, "ps_partkey", part_supp.ps_partkey);
+        check.foreign_key(&self.supplier_keys, "ps_suppkey", part_supp.ps_suppkey);
+        check.range("ps_availqty", part_supp.ps_availqty as i64, 1, 9999);
+        check.range("ps_supplycost", part_supp.ps_supplycost.0, 100, 100000);
+        self.part_supplier_keys
+            .get_or_insert_with(HashSet::new)
+            .insert((part_supp.ps_partkey, part_supp.ps_suppkey));
+    }
+
+    /// Checks a row of the CUSTOMER table
+    pub fn add_customer(&mut self, customer: &Customer) {
+        let mut check = self.violations.check(Table::Customer, customer.c_custkey);
+        check.foreign_key(&self.nation_keys, "c_nationkey", customer.c_nationkey);
+        check.account_balance("c_acctbal", customer.c_acctbal);
+        self.customer_keys
+            .get_or_insert_with(HashSet::new)
+            .insert(customer.c_custkey);
+    }
+
+    /// Checks a row of the ORDERS table and its rows of the LINEITEM table
+    pub fn add_order(&mut self, order: &Order, line_items: &[LineItem]) {
+        let mut check = self.violations.check(Table::Orders, order.o_orderkey);
+        check.foreign_key(&self.customer_keys, "o_custkey", order.o_custkey);
+        if order.o_custkey % 3 == 0 {
+            check.violation(
+                Rule::CustomerWithoutOrders,
+                format!("o_custkey {} is a multiple of 3", order.o_custkey),
+            );
+        }
+        if order.o_orderdate < self.date_range.start_date() {
+            check.violation(
+                Rule::ValueRange,
+                format!(
+                    "o_orderdate {} is before the start date {}",
+                    order.o_orderdate,
+                    self.date_range.start_date()
+                ),
+            );
+        }
+        if order.o_orderdate.into_inner()
+            > self.date_range.end_date().into_inner() - LineItemGenerator::ITEM_SHIP_DAYS
+        {
+            check.violation(
+                Rule::ValueRange,
+                format!(
+                    "o_orderdate {} is less than {} days before the end date",
+                    order.o_orderdate,
+                    LineItemGenerator::ITEM_SHIP_DAYS + 1
+                ),
+            );
+        }
+
+        let line_numbers: Vec<_> = line_items.iter().map(|l| l.l_linenumber).collect();
+        if line_numbers.is_empty()
+            || line_numbers.len() > OrderGenerator::LINE_COUNT_MAX as usize
+            || line_numbers.iter().zip(1..).any(|(&n, i)| n != i)
+        {
+            check.violation(
+                Rule::LineNumber,
+                format!("line numbers {line_numbers:?} are not 1 to n, with n from 1 to 7"),
+            );
+        }
+
+        let mut total_price = 0;
+        let mut shipped_count = 0;
+        let mut check = self.violations.check(Table::Lineitem, order.o_orderkey);
+        for line_item in line_items {
+            let discounted_price = line_item.l_extendedprice.0 * (100 - line_item.l_discount.0);
+            total_price += ((discounted_price / 100) * (100 + line_item.l_tax.0)) / 100;
+            if line_item.l_linestatus == "F" {
+                shipped_count += 1;
+            }
+            check.line_item(order, line_item, &self.part_supplier_keys, &self.date_range);
+        }
+
+        let order_status = if shipped_count == line_items.len() {
+            OrderStatus::Fulfilled
+        } else if shipped_count > 0 {
+            OrderStatus::Pending
+        } else {
+            OrderStatus::Open
+        };
+        let mut check = self.violations.check(Table::Orders, order.o_orderkey);
+        if order.o_totalprice.0 != total_price {
+            check.violation(
+                Rule::OrderTotalPrice,
+                format!(
+                    "o_totalprice {} is not the total of the line items {}",
+                    order.o_totalprice,
+                    TPCHDecimal(total_price)
+                ),
+            );
+        }
+        if !line_items.is_empty() && order.o_orderstatus != order_status {
+            check.violation(
+                Rule::OrderStatus,
+                format!(
+                    "o_orderstatus {} is not {order_status} ({shipped_count} of {} line items shipped)",
+                    order.o_orderstatus,
+                    line_items.len()
+                ),
+            );
+        }
+    }
+
+    /// Checks the rows of the ORDERS and LINEITEM tables
+    ///
+    /// The line items must be sorted like the orders (as in the files of
+    /// `dbgen`), so that the line items of each order follow the line items
+    /// of the previous order. Line items without an order break the
+    /// [`Rule::ForeignKey`] rule.
+    pub fn add_orders(
+        &mut self,
+        orders: impl IntoIterator<Item = Order>,
+        line_items: impl IntoIterator<Item = LineItem>,
+    ) {
+        let mut line_items = line_items.into_iter().peekable();
+        let mut order_line_items = vec![];
+        for order in orders {
+            self.skip_line_items_before(order.o_orderkey, &mut line_items);
+            order_line_items.clear();
+            while let Some(line_item) = line_items.next_if(|l| l.l_orderkey == order.o_orderkey) {
+                order_line_items.push(line_item);
+            }
+            self.add_order(&order, &order_line_items);
+        }
+        self.skip_line_items_before(i64::MAX, &mut line_items);
+        for line_item in line_items {
+            self.orphan_line_item(&line_item);
+        }
+    }
+
+    /// Reports the line items of orders before `order_key`, which have no
+    /// order
+    fn skip_line_items_before<I: Iterator<Item = LineItem>>(
+        &mut self,
+        order_key: i64,
+        line_items: &mut Peekable<I>,
+    ) {
+        while let Some(line_item) = line_items.next_if(|l| l.l_orderkey < order_key) {
+            self.orphan_line_item(&line_item);
+        }
+    }
+
+    fn orphan_line_item(&mut self, line_item: &LineItem) {
+        self.violations
+            .check(Table::Lineitem, line_item.l_orderkey)
+            .violation(
+                Rule::ForeignKey,
+                format!(
+                    "l_orderkey {} (line {}) has no order",
+                    line_item.l_orderkey, line_item.l_linenumber
+                ),
+            );
+    }
+}
+
+/// The violations found by a [`Validator`]
+#[derive(Debug)]
+struct Violations {
+    /// The maximum number of violations kept in `list`
+    max: usize,
+    list: Vec<Violation>,
+    count: usize,
+}
+
+impl Violations {
+    /// Returns a [`Check`] of the row `key` of `table`
+    fn check(&mut self, table: Table, key: i64) -> Check<'_> {
+        Check {
+            table,
+            key,
+            violations: self,
+        }
+    }
+}
+
+/// The checks of a row, recording its violations
+struct Check<'a> {
+    table: Table,
+    key: i64,
+    violations: &'a mut Violations,
+}
+
+impl Check<'_> {
+    fn violation(&mut self, rule: Rule, message: String) {
+        self.violations.count += 1;
+        if self.violations.list.len() < self.violations.max {
+            self.violations.list.push(Violation {
+                rule,
+                table: self.table,
+                key: self.key,
+                message,
+            });
+        }
+    }
+
+    /// Checks that `key` is in `keys`, if the referenced table was added
+    fn foreign_key<K: Eq + std::hash::Hash + fmt::Debug>(
+        &mut self,
+        keys: &Option<HashSet<K>>,
+        column: &str,
+        key: K,
+    ) {
+        if keys.as_ref().is_some_and(|keys| !keys.contains(&key)) {
+            self.violation(Rule::ForeignKey, format!("{column} {key:?} does not exist"));
+        }
+    }
+
+    /// Checks that `value` is from `min` to `max` (inclusive)
+    fn range(&mut self, column: &str, value: i64, min: i64, max: i64) {
+        if !(min..=max).contains(&value) {
+            self.violation(
+                Rule::ValueRange,
+                format!("{column} {value} is not from {min} to {max}"),
+            );
+        }
+    }
+
+    fn account_balance(&mut self, column: &str, balance: TPCHDecimal) {
+        if !(-99999..=999999).contains(&balance.0) {
+            self.violation(
+                Rule::ValueRange,
+                format!("{column} {balance} is not from -999.99 to 9999.99"),
+            );
+        }
+    }
+
+    /// Checks the rules of a line item of `order`
+    fn line_item(
+        &mut self,
+        order: &Order,
+        line_item: &LineItem,
+        part_supplier_keys: &Option<HashSet<(i64, i64)>>,
+        date_range: &DateRange,
+    ) {
+        let line = line_item.l_linenumber;
+        if line_item.l_orderkey != order.o_orderkey {
+            self.violation(
+                Rule::ForeignKey,
+                format!(
+                    "l_orderkey {} (line {line}) is not the order key",
+                    line_item.l_orderkey
+                ),
+            );
+        }
+        self.range("l_quantity", line_item.l_quantity, 1, 50);
+        self.range("l_discount", line_item.l_discount.0, 0, 10);
+        self.range("l_tax", line_item.l_tax.0, 0, 8);
+
+        let extended_price =
+            line_item.l_quantity * PartGeneratorIterator::calculate_part_price(line_item.l_partkey);
+        if line_item.l_extendedprice.0 != extended_price {
+            self.violation(
+                Rule::ExtendedPrice,
+                format!(
+                    "l_extendedprice {} (line {line}) is not {}",
+                    line_item.l_extendedprice,
+                    TPCHDecimal(extended_price)
+                ),
+            );
+        }
+        if part_supplier_keys
+            .as_ref()
+            .is_some_and(|keys| !keys.contains(&(line_item.l_partkey, line_item.l_suppkey)))
+        {
+            self.violation(
+                Rule::PartSupplier,
+                format!(
+                    "l_partkey {} and l_suppkey {} (line {line}) are not in PARTSUPP",
+                    line_item.l_partkey, line_item.l_suppkey
+                ),
+            );
+        }
+
+        let order_date = order.o_orderdate.into_inner();
+        let ship_date = line_item.l_shipdate.into_inner();
+        let days = ship_date - order_date;
+        if !(1..=121).contains(&days) {
+            self.violation(
+                Rule::ShipDate,
+                format!(
+                    "l_shipdate {} (line {line}) is {days} days after o_orderdate {}",
+                    line_item.l_shipdate, order.o_orderdate
+                ),
+            );
+        }
+        let days = line_item.l_commitdate.into_inner() - order_date;
+        if !(30..=90).contains(&days) {
+            self.violation(
+                Rule::CommitDate,
+                format!(
+                    "l_commitdate {} (line {line}) is {days} days after o_orderdate {}",
+                    line_item.l_commitdate, order.o_orderdate
+                ),
+            );
+        }
+        let days = line_item.l_receiptdate.into_inner() - ship_date;
+        if !(1..=30).contains(&days) {
+            self.violation(
+                Rule::ReceiptDate,
+                format!(
+                    "l_receiptdate {} (line {line}) is {days} days after l_shipdate {}",
+                    line_item.l_receiptdate, line_item.l_shipdate
+                ),
+            );
+        }
+
+        let is_in_past =
+            |date: TPCHDate| date_range.is_in_past(date.into_inner() + MIN_GENERATE_DATE);
+        let line_status = if is_in_past(line_item.l_shipdate) {
+            "F"
+        } else {
+            "O"
+        };
+        if line_item.l_linestatus != line_status {
+            self.violation(
+                Rule::LineStatus,
+                format!(
+                    "l_linestatus {} (line {line}) is not {line_status} for l_shipdate {}",
+                    line_item.l_linestatus, line_item.l_shipdate
+                ),
+            );
+        }
+        let return_flags: &[&str] = if is_in_past(line_item.l_receiptdate) {
+            &["R", "A"]
+        } else {
+            &["N"]
+        };
+        if !return_flags.contains(&line_item.l_returnflag.as_str()) {
+            self.violation(
+                Rule::ReturnFlag,
+                format!(
+                    "l_returnflag {} (line {line}) is not {} for l_receiptdate {}",
+                    line_item.l_returnflag,
+                    return_flags.join(" or "),
+                    line_item.l_receiptdate
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::{
+        CustomerGenerator, LineItemGenerator, NationGenerator, OrderLineItemGenerator,
+        PartGenerator, PartSuppGenerator, RegionGenerator, SupplierGenerator,
+    };
+
+    /// Returns a validator with all the tables but ORDERS and LINEITEM of SF 0.001
+    fn reference_validator() -> Validator {
+        let mut validator = Validator::new();
+        RegionGenerator::default()
+            .iter()
+            .for_each(|row| validator.add_region(&row.into()));
+        NationGenerator::default()
+            .iter()
+            .for_each(|row| validator.add_nation(&row.into()));
+        SupplierGenerator::new(0.001, 1, 1)
+            .iter()
+            .for_each(|row| validator.add_supplier(&row.into()));
+        PartGenerator::new(0.001, 1, 1)
+            .iter()
+            .for_each(|row| validator.add_part(&row.into()));
+        PartSuppGenerator::new(0.001, 1, 1)
+            .iter()
+            .for_each(|row| validator.add_part_supp(&row.into()));
+        CustomerGenerator::new(0.001, 1, 1)
+            .iter()
+            .for_each(|row| validator.add_customer(&row.into()));
+        validator
+    }
+
+    /// Returns the first order of SF 0.001 with its line items
+    fn first_order() -> (Order, Vec<LineItem>) {
+        let order = OrderLineItemGenerator::new(0.001, 1, 1)
+            .iter()
+            .next()
+            .unwrap();
+        let line_items = order.line_items.iter().map(LineItem::from).collect();
+        (Order::from(order.order), line_items)
+    }
+
+    fn rules(validator: &Validator) -> Vec<Rule> {
+        validator.violations().iter().map(|v| v.rule).collect()
+    }
+
+    #[test]
+    fn test_generated_data() {
+        let mut validator = reference_validator();
+        validator.add_orders(
+            OrderGenerator::new(0.001, 1, 1).iter().map(Order::from),
+            LineItemGenerator::new(0.001, 1, 1)
+                .iter()
+                .map(LineItem::from),
+        );
+        assert_eq!(validator.violations(), &[]);
+    }
+
+    #[test]
+    fn test_date_range() {
+        let date_range = DateRange::new(2020, 2029).unwrap();
+        let orders = OrderGenerator::new(0.001, 1, 1).with_date_range(date_range);
+        let line_items = LineItemGenerator::new(0.001, 1, 1).with_date_range(date_range);
+
+        let mut validator = reference_validator().with_date_range(date_range);
+        validator.add_orders(
+            orders.iter().map(Order::from),
+            line_items.iter().map(LineItem::from),
+        );
+        assert_eq!(validator.violations(), &[]);
+
+        // the dates are not in the default range
+        let mut validator = reference_validator();
+        validator.add_orders(
+            orders.iter().map(Order::from),
+            line_items.iter().map(LineItem::from),
+        );
+        assert!(rules(&validator).contains(&Rule::ValueRange));
+        assert!(rules(&validator).contains(&Rule::LineStatus));
+    }
+
+    #[test]
+    fn test_order_violations() {
+        let (mut order, line_items) = first_order();
+        order.o_custkey = 3;
+        order.o_orderstatus = OrderStatus::Fulfilled;
+        order.o_totalprice.0 += 1;
+        let mut validator = reference_validator();
+        validator.add_order(&order, &line_items);
+        assert_eq!(
+            rules(&validator),
+            [
+                Rule::CustomerWithoutOrders,
+                Rule::OrderTotalPrice,
+                Rule::OrderStatus
+            ]
+        );
+        assert_eq!(
+            validator.violations()[2].to_string(),
+            "orders 1: o_orderstatus F is not O (0 of 6 line items shipped) (order_status)"
+        );
+
+        let mut validator = reference_validator();
+        validator.add_order(&order, &[]);
+        assert_eq!(rules(&validator)[1], Rule::LineNumber);
+    }
+
+    #[test]
+    fn test_line_item_violations() {
+        let (order, mut line_items) = first_order();
+        let line_item = &mut line_items[1];
+        line_item.l_suppkey += 1;
+        line_item.l_shipdate = order.o_orderdate;
+        line_item.l_linestatus = "F".to_string();
+        line_items[2].l_linenumber = 4;
+        line_items[3].l_quantity = 51;
+
+        let mut validator = reference_validator();
+        validator.add_order(&order, &line_items);
+        assert_eq!(
+            rules(&validator),
+            [
+                Rule::LineNumber,
+                Rule::PartSupplier,
+                Rule::ShipDate,
+                Rule::ReceiptDate,
+                Rule::LineStatus,
+                Rule::ValueRange,
+                Rule::ExtendedPrice,
+                Rule::OrderStatus,
+            ]
+        );
+        assert_eq!(
+            validator.violations()[2].to_string(),
+            "lineitem 1: l_shipdate 1996-01-02 (line 2) is 0 days after o_orderdate 1996-01-02 (ship_date)"
+        );
+    }
+
+    #[test]
+    fn test_foreign_keys() {
+        let mut validator = reference_validator().with_max_violations(2);
+        let (mut order, line_items) = first_order();
+        order.o_custkey = 100_000;
+        // 6 line items without order, then the order with an unknown customer,
+        // no line items and a total price that is not 0
+        validator.add_orders(
+            [order],
+            line_items.into_iter().map(|mut line_item| {
+                line_item.l_orderkey = 0;
+                line_item
+            }),
+        );
+        assert_eq!(validator.violation_count(), 9);
+        assert_eq!(
+            validator
+                .violations()
+                .iter()
+                .map(Violation::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "lineitem 0: l_orderkey 0 (line 1) has no order (foreign_key)",
+                "lineitem 0: l_orderkey 0 (line 2) has no order (foreign_key)",
+            ]
+        );
+
+        // the checks of the tables that were not added are skipped
+        let mut validator = Validator::new();
+        validator.add_nation(&Nation::from(
+            NationGenerator::default().iter().next().unwrap(),
+        ));
+        let (mut order, line_items) = first_order();
+        order.o_custkey = 100_000;
+        validator.add_order(&order, &line_items);
+        assert_eq!(validator.violation_count(), 0);
+    }
+}
@@ -4,10 +4,16 @@ use flate2::read::GzDecoder;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
+use tpchgen::digest::TableDigest;
 use tpchgen::generators::{
     CustomerGenerator, LineItemGenerator, NationGenerator, OrderGenerator, PartGenerator,
     PartSuppGenerator, RegionGenerator, SupplierGenerator,
 };
+use tpchgen::owned;
+use tpchgen::parse::{ParseRow, TblReader};
+use tpchgen::ssb::generators as ssb;
+use tpchgen::table::Table;
+use tpchgen::validate::Validator;
 
 fn read_tbl_gz<P: AsRef<Path>>(path: P) -> Vec<String> {
     let file = File::open(path).expect("Failed to open file");
@@ -205,6 +211,130 @@ fn test_lineitem_sf_0_01() {
     })
 }
 
+/// Checks that the first rows of `iter` are the rows of an uncompressed
+/// reference file, which holds a prefix of the table
+fn test_generator_prefix<T: ToString>(iter: impl Iterator<Item = T>, reference_path: &str) {
+    let mut path =
+        PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set"));
+    path.push(reference_path);
+    let reference_data = std::fs::read_to_string(path).expect("Failed to read file");
+
+    let reference_rows: Vec<_> = reference_data.lines().collect();
+    let generated_rows: Vec<_> = iter
+        .take(reference_rows.len())
+        .map(|row| row.to_string())
+        .collect();
+    assert_eq!(generated_rows, reference_rows, "{reference_path}");
+}
+
+/// The SSB tables match the first rows of the reference files, see
+/// `data/README.md`
+#[test]
+fn test_ssb_sf_0_01() {
+    test_generator_prefix(
+        ssb::CustomerGenerator::new(0.01, 1, 1).iter(),
+        "data/ssb-sf-0.01/customer.tbl",
+    );
+    test_generator_prefix(
+        ssb::SupplierGenerator::new(0.01, 1, 1).iter(),
+        "data/ssb-sf-0.01/supplier.tbl",
+    );
+    test_generator_prefix(
+        ssb::PartGenerator::new(0.01, 1, 1).iter(),
+        "data/ssb-sf-0.01/part.tbl",
+    );
+    test_generator_prefix(
+        ssb::LineOrderGenerator::new(0.01, 1, 1).iter(),
+        "data/ssb-sf-0.01/lineorder.tbl",
+    );
+    test_generator_prefix(
+        ssb::DateGenerator::new(0.01, 1, 1).iter(),
+        "data/ssb-sf-0.01/date.tbl",
+    );
+
+    // the first row of `ssb-dbgen -s 1`
+    assert_eq!(
+        ssb::LineOrderGenerator::new(1.0, 1, 1)
+            .iter()
+            .next()
+            .unwrap()
+            .to_string(),
+        "1|1|7381|155190|828|19960102|5-LOW|0|17|2116823|17366547|4|2032150|74711|2|19960212|TRUCK|"
+    );
+}
+
+/// Parses the reference data with [`TblReader`] and checks that the parsed
+/// rows are written back unchanged
+fn test_parse_reference<T>(reference_path: &str)
+where
+    T: ParseRow + std::fmt::Display,
+{
+    let mut dir =
+        PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set"));
+    dir.push(reference_path);
+
+    let reference_data = read_tbl_gz(&dir);
+    let file = File::open(dir).expect("Failed to open file");
+    let parsed_data = TblReader::<T, _>::new(BufReader::new(GzDecoder::new(file)))
+        .map(|row| row.expect("Failed to parse row").to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        reference_data, parsed_data,
+        "Rows don't match for {reference_path}"
+    );
+}
+
+#[test]
+fn test_parse_sf_0_01() {
+    test_parse_reference::<owned::Nation>("data/sf-0.01/nation.tbl.gz");
+    test_parse_reference::<owned::Region>("data/sf-0.01/region.tbl.gz");
+    test_parse_reference::<owned::Part>("data/sf-0.01/part.tbl.gz");
+    test_parse_reference::<owned::Supplier>("data/sf-0.01/supplier.tbl.gz");
+    test_parse_reference::<owned::PartSupp>("data/sf-0.01/partsupp.tbl.gz");
+    test_parse_reference::<owned::Customer>("data/sf-0.01/customer.tbl.gz");
+    test_parse_reference::<owned::Order>("data/sf-0.01/orders.tbl.gz");
+    test_parse_reference::<owned::LineItem>("data/sf-0.01/lineitem.tbl.gz");
+}
+
+/// Returns the rows of a reference file
+fn read_reference<T: ParseRow>(reference_path: &str) -> impl Iterator<Item = T> {
+    let mut dir =
+        PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set"));
+    dir.push(reference_path);
+    let file = File::open(dir).expect("Failed to open file");
+    TblReader::<T, _>::new(BufReader::new(GzDecoder::new(file)))
+        .map(|row| row.expect("Failed to parse row"))
+}
+
+#[test]
+fn test_validate_sf_0_01() {
+    let mut validator = Validator::new();
+    read_reference("data/sf-0.01/region.tbl.gz").for_each(|row| validator.add_region(&row));
+    read_reference("data/sf-0.01/nation.tbl.gz").for_each(|row| validator.add_nation(&row));
+    read_reference("data/sf-0.01/supplier.tbl.gz").for_each(|row| validator.add_supplier(&row));
+    read_reference("data/sf-0.01/part.tbl.gz").for_each(|row| validator.add_part(&row));
+    read_reference("data/sf-0.01/partsupp.tbl.gz").for_each(|row| validator.add_part_supp(&row));
+    read_reference("data/sf-0.01/customer.tbl.gz").for_each(|row| validator.add_customer(&row));
+    validator.add_orders(
+        read_reference("data/sf-0.01/orders.tbl.gz"),
+        read_reference("data/sf-0.01/lineitem.tbl.gz"),
+    );
+    assert_eq!(validator.violations(), &[]);
+}
+
+#[test]
+fn test_digest_sf_0_01() {
+    let dir =
+        PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set"));
+    for table in Table::ALL {
+        let mut digest = TableDigest::new();
+        for line in read_tbl_gz(dir.join(format!("data/sf-0.01/{table}.tbl.gz"))) {
+            digest.add_line(&line);
+        }
+        assert_eq!(Some(digest), TableDigest::known(table, 0.01), "{table}");
+    }
+}
+
 struct TestIntoIterator<G>
 where
     G: IntoIterator,